@@ -53,6 +53,8 @@ pub enum KernelError {
     ChangeDataFeedIncompatibleSchema,
     InvalidCheckpoint,
     LiteralExpressionTransformError,
+    IncompatibleWriteSchemaError,
+    UnsupportedFeatureError,
 }
 
 impl From<Error> for KernelError {
@@ -105,6 +107,7 @@ impl From<Error> for KernelError {
             Error::FileAlreadyExists(_) => KernelError::FileAlreadyExists,
             Error::MissingCommitInfo => KernelError::MissingCommitInfo,
             Error::Unsupported(_) => KernelError::UnsupportedError,
+            Error::UnsupportedFeature { .. } => KernelError::UnsupportedFeatureError,
             Error::ParseIntervalError(_) => KernelError::ParseIntervalError,
             Error::ChangeDataFeedUnsupported(_) => KernelError::ChangeDataFeedUnsupported,
             Error::ChangeDataFeedIncompatibleSchema(_, _) => {
@@ -114,6 +117,7 @@ impl From<Error> for KernelError {
             Error::LiteralExpressionTransformError(_) => {
                 KernelError::LiteralExpressionTransformError
             }
+            Error::IncompatibleWriteSchema(_) => KernelError::IncompatibleWriteSchemaError,
         }
     }
 }
@@ -12,7 +12,9 @@ use delta_kernel::engine::arrow_data::ArrowEngineData;
 use delta_kernel::engine::default::executor::tokio::TokioBackgroundExecutor;
 use delta_kernel::engine::default::DefaultEngine;
 use delta_kernel::engine::sync::SyncEngine;
-use delta_kernel::scan::state::{transform_to_logical, DvInfo, GlobalScanState, Stats};
+use delta_kernel::scan::state::{
+    resolve_file_path, transform_to_logical, DvInfo, GlobalScanState, Stats,
+};
 use delta_kernel::schema::Schema;
 use delta_kernel::{DeltaResult, Engine, EngineData, ExpressionRef, FileMeta, Table};
 
@@ -264,7 +266,7 @@ fn do_work(
             .unwrap();
 
         // build the required metadata for our parquet handler to read this file
-        let location = root_url.join(&scan_file.path).unwrap();
+        let location = resolve_file_path(&root_url, &scan_file.path).unwrap();
         let meta = FileMeta {
             last_modified: 0,
             size: scan_file.size.try_into().unwrap(),
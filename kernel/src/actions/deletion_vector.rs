@@ -10,7 +10,7 @@ use url::Url;
 use delta_kernel_derive::Schema;
 
 use crate::utils::require;
-use crate::{DeltaResult, Error, StorageHandler};
+use crate::{DeltaResult, Engine, Error, StorageHandler};
 
 #[derive(Debug, Clone, PartialEq, Eq, Schema)]
 #[cfg_attr(test, derive(serde::Serialize), serde(rename_all = "camelCase"))]
@@ -46,6 +46,13 @@ pub struct DeletionVectorDescriptor {
 }
 
 impl DeletionVectorDescriptor {
+    /// The number of rows this deletion vector removes from its data file. This is the
+    /// `cardinality` recorded in the descriptor, which the protocol requires to be exact, so it can
+    /// be used without reading the deletion vector itself.
+    pub fn cardinality(&self) -> u64 {
+        self.cardinality.try_into().unwrap_or_default()
+    }
+
     pub fn unique_id(&self) -> String {
         Self::unique_id_from_parts(&self.storage_type, &self.path_or_inline_dv, self.offset)
     }
@@ -109,15 +116,26 @@ impl DeletionVectorDescriptor {
     ) -> DeltaResult<RoaringTreemap> {
         match self.absolute_path(parent)? {
             None => {
-                let byte_slice = z85::decode(&self.path_or_inline_dv)
+                let mut byte_slice = z85::decode(&self.path_or_inline_dv)
                     .map_err(|_| Error::deletion_vector("Failed to decode DV"))?;
+                // base85 encodes 4 byte words, so the encoded DV may be padded with trailing zeros
+                // that are not part of the `sizeInBytes` bytes of the bitmap
+                let size_in_bytes = self.size_in_bytes;
+                require!(
+                    size_in_bytes >= 4 && size_in_bytes as usize <= byte_slice.len(),
+                    Error::DeletionVector(format!(
+                        "DV size mismatch. Log indicates {size_in_bytes}, inline DV has {} bytes",
+                        byte_slice.len()
+                    ))
+                );
+                byte_slice.truncate(size_in_bytes as usize);
                 let magic = slice_to_u32(&byte_slice[0..4], Endian::Little)?;
                 match magic {
                     1681511377 => RoaringTreemap::deserialize_from(&byte_slice[4..])
                         .map_err(|err| Error::DeletionVector(err.to_string())),
-                    1681511376 => {
-                        todo!("Don't support native serialization in inline bitmaps yet");
-                    }
+                    1681511376 => Err(Error::deletion_vector(
+                        "Native serialization of inline DVs is not supported",
+                    )),
                     _ => Err(Error::DeletionVector(format!("Invalid magic {magic}"))),
                 }
             }
@@ -183,6 +201,17 @@ impl DeletionVectorDescriptor {
     ) -> DeltaResult<Vec<u64>> {
         Ok(self.read(storage, parent)?.into_iter().collect())
     }
+
+    /// Load the deletion vector, whether stored inline or in a file under `table_root`, and return
+    /// the positions of the deleted rows in ascending order. This is intended for engines that
+    /// read parquet themselves and filter out deleted rows after reading.
+    pub fn read_deleted_row_indices(
+        &self,
+        engine: &dyn Engine,
+        table_root: &Url,
+    ) -> DeltaResult<Vec<u64>> {
+        self.row_indexes(engine.storage_handler(), table_root)
+    }
 }
 
 enum Endian {
@@ -375,6 +404,55 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_inline_read_padded() {
+        // an odd number of 2 byte array container values makes the DV size not a multiple of 4
+        let tree_map = RoaringTreemap::from_iter([5, 100, 200]);
+        let mut bytes = 1681511377u32.to_le_bytes().to_vec();
+        tree_map.serialize_into(&mut bytes).unwrap();
+        let size_in_bytes = bytes.len();
+        assert_ne!(size_in_bytes % 4, 0);
+        bytes.resize(size_in_bytes.next_multiple_of(4), 0);
+        let mut inline = DeletionVectorDescriptor {
+            storage_type: "i".to_string(),
+            path_or_inline_dv: z85::encode(&bytes),
+            offset: None,
+            size_in_bytes: size_in_bytes as i32,
+            cardinality: 3,
+        };
+
+        let engine = SyncEngine::new();
+        let parent = Url::parse("http://not.used").unwrap();
+        let deleted = inline.read_deleted_row_indices(&engine, &parent).unwrap();
+        assert_eq!(deleted, [5, 100, 200]);
+
+        // a size larger than the inline DV is rejected rather than read past the end
+        inline.size_in_bytes = bytes.len() as i32 + 1;
+        let err = inline
+            .read_deleted_row_indices(&engine, &parent)
+            .unwrap_err();
+        assert!(matches!(err, Error::DeletionVector(_)), "{err}");
+    }
+
+    #[test]
+    fn test_inline_read_native_serialization() {
+        let mut bytes = 1681511376u32.to_le_bytes().to_vec();
+        bytes.extend([0; 4]);
+        let inline = DeletionVectorDescriptor {
+            storage_type: "i".to_string(),
+            path_or_inline_dv: z85::encode(&bytes),
+            offset: None,
+            size_in_bytes: bytes.len() as i32,
+            cardinality: 0,
+        };
+        let engine = SyncEngine::new();
+        let parent = Url::parse("http://not.used").unwrap();
+        let err = inline
+            .read_deleted_row_indices(&engine, &parent)
+            .unwrap_err();
+        assert!(matches!(err, Error::DeletionVector(_)), "{err}");
+    }
+
     #[test]
     fn test_deletion_vector_read() {
         let path =
@@ -388,7 +466,9 @@ mod tests {
 
         let expected: Vec<u64> = vec![0, 9];
         let found = tree_map.iter().collect::<Vec<_>>();
-        assert_eq!(found, expected)
+        assert_eq!(found, expected);
+        // the descriptor's cardinality matches the bitmap without reading it
+        assert_eq!(example.cardinality(), tree_map.len());
     }
 
     // this test is ignored by default as it's expensive to allocate such big vecs full of `true`. you can run it via:
@@ -448,4 +528,22 @@ mod tests {
         assert_eq!(row_idx.len(), 6);
         assert_eq!(&row_idx, &[3, 4, 7, 11, 18, 29]);
     }
+
+    #[test]
+    fn test_read_deleted_row_indices() {
+        let engine = SyncEngine::new();
+        let parent = Url::parse("http://not.used").unwrap();
+        let deleted = dv_inline()
+            .read_deleted_row_indices(&engine, &parent)
+            .unwrap();
+        assert_eq!(deleted, [3, 4, 7, 11, 18, 29]);
+
+        let path =
+            std::fs::canonicalize(PathBuf::from("./tests/data/table-with-dv-small/")).unwrap();
+        let table_root = url::Url::from_directory_path(path).unwrap();
+        let deleted = dv_example()
+            .read_deleted_row_indices(&engine, &table_root)
+            .unwrap();
+        assert_eq!(deleted, [0, 9]);
+    }
 }
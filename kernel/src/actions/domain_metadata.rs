@@ -0,0 +1,44 @@
+//! Log replay for `domainMetadata` actions.
+
+use std::collections::HashMap;
+use std::sync::{Arc, LazyLock};
+
+use crate::actions::get_log_domain_metadata_schema;
+use crate::actions::visitors::DomainMetadataVisitor;
+use crate::actions::DOMAIN_METADATA_NAME;
+use crate::log_segment::LogSegment;
+use crate::{DeltaResult, Engine, Expression as Expr, ExpressionRef, RowVisitor as _};
+
+/// Scan the Delta Log for the latest configuration of every metadata domain.
+///
+/// The latest `domainMetadata` action for a domain wins; domains whose latest action is a
+/// tombstone (`removed: true`) are omitted from the result.
+pub(crate) fn scan_domain_metadata(
+    log_segment: &LogSegment,
+    engine: &dyn Engine,
+) -> DeltaResult<HashMap<String, String>> {
+    let schema = get_log_domain_metadata_schema();
+    // All domain metadata actions end up in a single checkpoint part, so this lets the reader
+    // skip the parts (and row groups) that don't have any.
+    static META_PREDICATE: LazyLock<Option<ExpressionRef>> = LazyLock::new(|| {
+        Some(Arc::new(
+            Expr::column([DOMAIN_METADATA_NAME, "domain"]).is_not_null(),
+        ))
+    });
+    let mut visitor = DomainMetadataVisitor::default();
+    for batch in log_segment.read_actions(
+        engine,
+        schema.clone(), // Arc clone
+        schema.clone(), // Arc clone
+        META_PREDICATE.clone(),
+    )? {
+        let (batch, _) = batch?;
+        visitor.visit_rows_of(batch.as_ref())?;
+    }
+    Ok(visitor
+        .domain_metadatas
+        .into_values()
+        .filter(|domain_metadata| !domain_metadata.removed)
+        .map(|domain_metadata| (domain_metadata.domain, domain_metadata.configuration))
+        .collect())
+}
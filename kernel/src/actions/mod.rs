@@ -2,8 +2,7 @@
 //! specification](https://github.com/delta-io/delta/blob/master/PROTOCOL.md)
 
 use std::collections::HashMap;
-use std::fmt::{Debug, Display};
-use std::hash::Hash;
+use std::fmt::Debug;
 use std::str::FromStr;
 use std::sync::LazyLock;
 
@@ -12,7 +11,8 @@ use crate::actions::schemas::GetStructField;
 use crate::internal_mod;
 use crate::schema::{SchemaRef, StructType};
 use crate::table_features::{
-    ReaderFeature, WriterFeature, SUPPORTED_READER_FEATURES, SUPPORTED_WRITER_FEATURES,
+    ReaderFeature, TableFeature, WriterFeature, SUPPORTED_READER_FEATURES,
+    SUPPORTED_WRITER_FEATURES,
 };
 use crate::table_properties::TableProperties;
 use crate::utils::require;
@@ -23,10 +23,10 @@ use url::Url;
 use visitors::{MetadataVisitor, ProtocolVisitor};
 
 use delta_kernel_derive::{internal_api, Schema};
-use itertools::Itertools;
 use serde::{Deserialize, Serialize};
 
 pub mod deletion_vector;
+pub(crate) mod domain_metadata;
 pub mod set_transaction;
 
 pub(crate) mod schemas;
@@ -50,6 +50,8 @@ pub(crate) const CDC_NAME: &str = "cdc";
 pub(crate) const SIDECAR_NAME: &str = "sidecar";
 #[internal_api]
 pub(crate) const CHECKPOINT_METADATA_NAME: &str = "checkpointMetadata";
+#[internal_api]
+pub(crate) const DOMAIN_METADATA_NAME: &str = "domainMetadata";
 
 static LOG_ADD_SCHEMA: LazyLock<SchemaRef> =
     LazyLock::new(|| StructType::new([Option::<Add>::get_struct_field(ADD_NAME)]).into());
@@ -65,8 +67,7 @@ static LOG_SCHEMA: LazyLock<SchemaRef> = LazyLock::new(|| {
         Option::<Cdc>::get_struct_field(CDC_NAME),
         Option::<Sidecar>::get_struct_field(SIDECAR_NAME),
         Option::<CheckpointMetadata>::get_struct_field(CHECKPOINT_METADATA_NAME),
-        // We don't support the following actions yet
-        //Option::<DomainMetadata>::get_struct_field(DOMAIN_METADATA_NAME),
+        Option::<DomainMetadata>::get_struct_field(DOMAIN_METADATA_NAME),
     ])
     .into()
 });
@@ -82,6 +83,13 @@ static LOG_TXN_SCHEMA: LazyLock<SchemaRef> = LazyLock::new(|| {
     .into()
 });
 
+static LOG_DOMAIN_METADATA_SCHEMA: LazyLock<SchemaRef> = LazyLock::new(|| {
+    StructType::new([Option::<DomainMetadata>::get_struct_field(
+        DOMAIN_METADATA_NAME,
+    )])
+    .into()
+});
+
 #[internal_api]
 pub(crate) fn get_log_schema() -> &'static SchemaRef {
     &LOG_SCHEMA
@@ -100,6 +108,10 @@ pub(crate) fn get_log_txn_schema() -> &'static SchemaRef {
     &LOG_TXN_SCHEMA
 }
 
+pub(crate) fn get_log_domain_metadata_schema() -> &'static SchemaRef {
+    &LOG_DOMAIN_METADATA_SCHEMA
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Schema)]
 #[internal_api]
 #[cfg_attr(test, derive(Serialize), serde(rename_all = "camelCase"))]
@@ -119,10 +131,11 @@ impl Default for Format {
     }
 }
 
+/// The [metadata](https://github.com/delta-io/delta/blob/master/PROTOCOL.md#change-metadata) of a
+/// table, such as its schema, partition columns and configuration.
 #[derive(Debug, Default, Clone, PartialEq, Eq, Schema)]
 #[cfg_attr(test, derive(Serialize), serde(rename_all = "camelCase"))]
-#[internal_api]
-pub(crate) struct Metadata {
+pub struct Metadata {
     /// Unique identifier for this table
     pub(crate) id: String,
     /// User-provided identifier for this table
@@ -155,7 +168,7 @@ impl Metadata {
     }
 
     pub(crate) fn parse_schema(&self) -> DeltaResult<StructType> {
-        Ok(serde_json::from_str(&self.schema_string)?)
+        StructType::from_delta_json(&self.schema_string)
     }
 
     #[internal_api]
@@ -172,12 +185,13 @@ impl Metadata {
     }
 }
 
+/// The [protocol](https://github.com/delta-io/delta/blob/master/PROTOCOL.md#protocol-evolution)
+/// a table requires of its readers and writers.
 #[derive(Default, Debug, Clone, PartialEq, Eq, Schema, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
-#[internal_api]
 // TODO move to another module so that we disallow constructing this struct without using the
 // try_new function.
-pub(crate) struct Protocol {
+pub struct Protocol {
     /// The minimum version of the Delta read protocol that a client must implement
     /// in order to correctly read this table
     min_reader_version: i32,
@@ -255,24 +269,22 @@ impl Protocol {
     }
 
     /// This protocol's minimum reader version
-    #[internal_api]
-    pub(crate) fn min_reader_version(&self) -> i32 {
+    pub fn min_reader_version(&self) -> i32 {
         self.min_reader_version
     }
 
     /// This protocol's minimum writer version
-    #[internal_api]
-    pub(crate) fn min_writer_version(&self) -> i32 {
+    pub fn min_writer_version(&self) -> i32 {
         self.min_writer_version
     }
 
     /// Get the reader features for the protocol
-    pub(crate) fn reader_features(&self) -> Option<&[ReaderFeature]> {
+    pub fn reader_features(&self) -> Option<&[ReaderFeature]> {
         self.reader_features.as_deref()
     }
 
     /// Get the writer features for the protocol
-    pub(crate) fn writer_features(&self) -> Option<&[WriterFeature]> {
+    pub fn writer_features(&self) -> Option<&[WriterFeature]> {
         self.writer_features.as_deref()
     }
 
@@ -290,7 +302,8 @@ impl Protocol {
 
     /// Check if reading a table with this protocol is supported. That is: does the kernel support
     /// the specified protocol reader version and all enabled reader features? If yes, returns unit
-    /// type, otherwise will return an error.
+    /// type, otherwise will return an error naming the unsupported version or features.
+    #[internal_api]
     pub(crate) fn ensure_read_supported(&self) -> DeltaResult<()> {
         match &self.reader_features {
             // if min_reader_version = 3 and all reader features are subset of supported => OK
@@ -321,6 +334,7 @@ impl Protocol {
 
     /// Check if writing to a table with this protocol is supported. That is: does the kernel
     /// support the specified protocol writer version and all enabled writer features?
+    #[internal_api]
     pub(crate) fn ensure_write_supported(&self) -> DeltaResult<()> {
         match &self.writer_features {
             Some(writer_features) if self.min_writer_version == 7 => {
@@ -347,47 +361,30 @@ impl Protocol {
     }
 }
 
-// given `table_features`, check if they are subset of `supported_features`
+// given `table_features`, check if they are subset of `supported_features`. If not, returns an
+// [`Error::UnsupportedFeature`] naming the first unsupported feature.
 pub(crate) fn ensure_supported_features<T>(
     table_features: &[T],
     supported_features: &[T],
 ) -> DeltaResult<()>
 where
-    T: Display + FromStr + Hash + Eq,
-    <T as FromStr>::Err: Display,
+    T: Clone + Eq + Into<TableFeature>,
 {
-    // first check if all features are supported, else we proceed to craft an error message
-    if table_features
+    match table_features
         .iter()
-        .all(|feature| supported_features.contains(feature))
+        .find(|feature| !supported_features.contains(feature))
     {
-        return Ok(());
+        Some(feature) => Err(Error::unsupported_feature(feature.clone())),
+        None => Ok(()),
     }
-
-    // we get the type name (ReaderFeature/WriterFeature) for better error messages
-    let features_type = std::any::type_name::<T>()
-        .rsplit("::")
-        .next()
-        .unwrap_or("table feature");
-
-    // NB: we didn't do this above to avoid allocation in the common case
-    let mut unsupported = table_features
-        .iter()
-        .filter(|feature| !supported_features.contains(*feature));
-
-    Err(Error::Unsupported(format!(
-        "Unknown {}s: \"{}\". Supported {}s: \"{}\"",
-        features_type,
-        unsupported.join("\", \""),
-        features_type,
-        supported_features.iter().join("\", \""),
-    )))
 }
 
+/// Information about the commit a
+/// [commitInfo](https://github.com/delta-io/delta/blob/master/PROTOCOL.md#commit-provenance-information)
+/// action belongs to.
 #[derive(Debug, Clone, PartialEq, Eq, Schema)]
-#[internal_api]
 #[cfg_attr(test, derive(Serialize, Default), serde(rename_all = "camelCase"))]
-pub(crate) struct CommitInfo {
+pub struct CommitInfo {
     /// The time this logical file was created, as milliseconds since the epoch.
     /// Read: optional, write: required (that is, kernel always writes).
     pub(crate) timestamp: Option<i64>,
@@ -403,6 +400,8 @@ pub(crate) struct CommitInfo {
     /// Map of arbitrary string key-value pairs that provide additional information about the
     /// operation. This is specified by the engine. For now this is always empty on write.
     pub(crate) operation_parameters: Option<HashMap<String, String>>,
+    /// User-defined metadata attached to this commit by the writer. Kernel never writes it.
+    pub(crate) user_metadata: Option<String>,
     /// The version of the delta_kernel crate used to write this commit. The kernel will always
     /// write this field, but it is optional since many tables will not have this field (i.e. any
     /// tables not written by kernel).
@@ -412,10 +411,11 @@ pub(crate) struct CommitInfo {
     pub(crate) engine_commit_info: Option<HashMap<String, String>>,
 }
 
+/// A data file [added](https://github.com/delta-io/delta/blob/master/PROTOCOL.md#add-file-and-remove-file)
+/// to the table.
 #[derive(Debug, Clone, PartialEq, Eq, Schema)]
 #[cfg_attr(test, derive(Serialize, Default), serde(rename_all = "camelCase"))]
-#[internal_api]
-pub(crate) struct Add {
+pub struct Add {
     /// A relative path to a data file from the root of the table or an absolute path to a file
     /// that should be added to the table. The path is a URI as specified by
     /// [RFC 2396 URI Generic Syntax], which needs to be decoded to get the data file path.
@@ -475,12 +475,30 @@ impl Add {
     pub(crate) fn dv_unique_id(&self) -> Option<String> {
         self.deletion_vector.as_ref().map(|dv| dv.unique_id())
     }
+
+    /// The number of records of this file that are still live, i.e. the `numRecords` statistic
+    /// minus the rows removed by the file's deletion vector, if any. Returns `None` if the file has
+    /// no (valid) stats, or if the deletion vector claims to remove more rows than the file has.
+    pub fn num_live_records(&self) -> Option<u64> {
+        #[derive(Deserialize)]
+        #[serde(rename_all = "camelCase")]
+        struct NumRecords {
+            num_records: u64,
+        }
+        let stats: NumRecords = serde_json::from_str(self.stats.as_ref()?).ok()?;
+        let deleted = self
+            .deletion_vector
+            .as_ref()
+            .map_or(0, DeletionVectorDescriptor::cardinality);
+        stats.num_records.checked_sub(deleted)
+    }
 }
 
+/// A data file [removed](https://github.com/delta-io/delta/blob/master/PROTOCOL.md#add-file-and-remove-file)
+/// from the table.
 #[derive(Debug, Clone, PartialEq, Eq, Schema)]
-#[internal_api]
 #[cfg_attr(test, derive(Serialize, Default), serde(rename_all = "camelCase"))]
-pub(crate) struct Remove {
+pub struct Remove {
     /// A relative path to a data file from the root of the table or an absolute path to a file
     /// that should be added to the table. The path is a URI as specified by
     /// [RFC 2396 URI Generic Syntax], which needs to be decoded to get the data file path.
@@ -527,10 +545,11 @@ pub(crate) struct Remove {
     pub(crate) default_row_commit_version: Option<i64>,
 }
 
+/// A [change data file](https://github.com/delta-io/delta/blob/master/PROTOCOL.md#add-cdc-file) of
+/// a commit.
 #[derive(Debug, Clone, PartialEq, Eq, Schema)]
-#[internal_api]
 #[cfg_attr(test, derive(Serialize, Default), serde(rename_all = "camelCase"))]
-pub(crate) struct Cdc {
+pub struct Cdc {
     /// A relative path to a change data file from the root of the table or an absolute path to a
     /// change data file that should be added to the table. The path is a URI as specified by
     /// [RFC 2396 URI Generic Syntax], which needs to be decoded to get the file path.
@@ -559,9 +578,24 @@ pub(crate) struct Cdc {
     pub tags: Option<HashMap<String, String>>,
 }
 
+/// The configuration of a
+/// [metadata domain](https://github.com/delta-io/delta/blob/master/PROTOCOL.md#domain-metadata).
 #[derive(Debug, Clone, PartialEq, Eq, Schema)]
-#[internal_api]
-pub(crate) struct SetTransaction {
+pub struct DomainMetadata {
+    /// Identifier for this domain (system- or user-provided)
+    pub(crate) domain: String,
+
+    /// String containing configuration for the metadata domain
+    pub(crate) configuration: String,
+
+    /// When `true`, the action serves as a tombstone to logically delete the metadata domain
+    pub(crate) removed: bool,
+}
+
+/// The progress of an application writing to the table, as recorded by a
+/// [transaction identifier](https://github.com/delta-io/delta/blob/master/PROTOCOL.md#transaction-identifiers).
+#[derive(Debug, Clone, PartialEq, Eq, Schema)]
+pub struct SetTransaction {
     /// A unique identifier for the application performing the transaction.
     pub(crate) app_id: String,
 
@@ -655,12 +689,27 @@ pub(crate) struct CheckpointMetadata {
     pub(crate) tags: Option<HashMap<String, String>>,
 }
 
+/// A single parsed action of a commit file. Only the actions that may appear in a commit are
+/// represented; checkpoint-only actions like `sidecar` are not.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Action {
+    Add(Add),
+    Remove(Remove),
+    Metadata(Metadata),
+    Protocol(Protocol),
+    SetTransaction(SetTransaction),
+    CommitInfo(CommitInfo),
+    Cdc(Cdc),
+    DomainMetadata(DomainMetadata),
+}
+
 #[cfg(test)]
 mod tests {
     use std::sync::Arc;
 
     use super::*;
     use crate::schema::{ArrayType, DataType, MapType, StructField};
+    use crate::table_features::ReaderOrWriter;
 
     #[test]
     fn test_metadata_schema() {
@@ -727,6 +776,38 @@ mod tests {
         assert_eq!(schema, expected);
     }
 
+    #[test]
+    fn test_add_num_live_records() {
+        let add = Add {
+            stats: Some(r#"{"numRecords":10,"minValues":{"id":1}}"#.to_string()),
+            ..Default::default()
+        };
+        assert_eq!(add.num_live_records(), Some(10));
+
+        let add = Add {
+            deletion_vector: Some(DeletionVectorDescriptor {
+                storage_type: "u".to_string(),
+                path_or_inline_dv: "vBn[lx{q8@P<9BNH/isA".to_string(),
+                offset: Some(1),
+                size_in_bytes: 36,
+                cardinality: 3,
+            }),
+            ..add
+        };
+        assert_eq!(add.num_live_records(), Some(7));
+
+        // a negative cardinality removes no rows, and a cardinality above numRecords is invalid
+        let mut add = add;
+        add.deletion_vector.as_mut().unwrap().cardinality = -3;
+        assert_eq!(add.num_live_records(), Some(10));
+        add.deletion_vector.as_mut().unwrap().cardinality = 11;
+        assert_eq!(add.num_live_records(), None);
+
+        // unknown without stats
+        let add = Add { stats: None, ..add };
+        assert_eq!(add.num_live_records(), None);
+    }
+
     fn tags_field() -> StructField {
         StructField::nullable(
             "tags",
@@ -863,6 +944,7 @@ mod tests {
                     "operationParameters",
                     MapType::new(DataType::STRING, DataType::STRING, false),
                 ),
+                StructField::nullable("userMetadata", DataType::STRING),
                 StructField::nullable("kernelVersion", DataType::STRING),
                 StructField::nullable(
                     "engineCommitInfo",
@@ -1015,6 +1097,81 @@ mod tests {
         assert!(protocol.ensure_write_supported().is_err());
     }
 
+    #[test]
+    fn test_ensure_supported_rejects_unknown_features() {
+        let protocol = Protocol::try_new(
+            3,
+            7,
+            Some(["deletionVectors", "timestampNtz", "coolReaderFeature"]),
+            Some(["deletionVectors"]),
+        )
+        .unwrap();
+        let err = protocol.ensure_read_supported().unwrap_err();
+        assert!(
+            err.to_string()
+                .starts_with("Unsupported reader feature: \"coolReaderFeature\""),
+            "{err}"
+        );
+        match err {
+            Error::UnsupportedFeature {
+                feature: TableFeature::Reader(ReaderFeature::Unknown(name)),
+                kind: ReaderOrWriter::Reader,
+            } if name == "coolReaderFeature" => {}
+            _ => panic!("Expected unsupported reader feature error, got: {err}"),
+        }
+        assert!(protocol.ensure_write_supported().is_ok());
+
+        let protocol = Protocol::try_new(
+            3,
+            7,
+            Some(["columnMapping", "v2Checkpoint"]),
+            Some(["appendOnly", "coolWriterFeature"]),
+        )
+        .unwrap();
+        assert!(protocol.ensure_read_supported().is_ok());
+        let err = protocol.ensure_write_supported().unwrap_err();
+        assert!(
+            err.to_string()
+                .starts_with("Unsupported writer feature: \"coolWriterFeature\""),
+            "{err}"
+        );
+        assert!(matches!(
+            err,
+            Error::UnsupportedFeature {
+                feature: TableFeature::Writer(WriterFeature::Unknown(_)),
+                kind: ReaderOrWriter::Writer,
+            }
+        ));
+    }
+
+    #[test]
+    fn test_ensure_supported_rejects_unimplemented_writer_feature() {
+        let protocol = Protocol::try_new(
+            3,
+            7,
+            Some([ReaderFeature::DeletionVectors]),
+            Some([
+                WriterFeature::DeletionVectors,
+                WriterFeature::IdentityColumns,
+            ]),
+        )
+        .unwrap();
+        assert!(protocol.ensure_read_supported().is_ok());
+        let err = protocol.ensure_write_supported().unwrap_err();
+        assert!(
+            err.to_string()
+                .starts_with("Unsupported writer feature: \"identityColumns\""),
+            "{err}"
+        );
+        assert!(matches!(
+            err,
+            Error::UnsupportedFeature {
+                feature: TableFeature::Writer(WriterFeature::IdentityColumns),
+                kind: ReaderOrWriter::Writer,
+            }
+        ));
+    }
+
     #[test]
     fn test_ensure_supported_features() {
         let supported_features = [ReaderFeature::ColumnMapping, ReaderFeature::DeletionVectors];
@@ -1025,10 +1182,11 @@ mod tests {
         let table_features = vec![ReaderFeature::ColumnMapping, ReaderFeature::unknown("idk")];
         let error = ensure_supported_features(&table_features, &supported_features).unwrap_err();
         match error {
-            Error::Unsupported(e) if e ==
-                "Unknown ReaderFeatures: \"idk\". Supported ReaderFeatures: \"columnMapping\", \"deletionVectors\""
-            => {},
-            _ => panic!("Expected unsupported error, got: {error}"),
+            Error::UnsupportedFeature {
+                feature: TableFeature::Reader(ReaderFeature::Unknown(name)),
+                kind: ReaderOrWriter::Reader,
+            } if name == "idk" => {}
+            _ => panic!("Expected unsupported feature error, got: {error}"),
         }
     }
 
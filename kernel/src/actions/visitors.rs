@@ -7,14 +7,17 @@ use std::sync::LazyLock;
 use delta_kernel_derive::internal_api;
 
 use crate::engine_data::{GetData, RowVisitor, TypedGetData as _};
-use crate::schema::{column_name, ColumnName, ColumnNamesAndTypes, DataType};
+use crate::schema::{
+    column_name, ColumnName, ColumnNamesAndTypes, DataType, SchemaRef, StructField, StructType,
+};
 use crate::utils::require;
 use crate::{DeltaResult, Error};
 
 use super::deletion_vector::DeletionVectorDescriptor;
-use super::schemas::ToSchema as _;
+use super::schemas::{GetStructField as _, ToSchema as _};
 use super::{
-    Add, Cdc, Format, Metadata, Protocol, Remove, SetTransaction, Sidecar, ADD_NAME, CDC_NAME,
+    Action, Add, Cdc, CommitInfo, DomainMetadata, Format, Metadata, Protocol, Remove,
+    SetTransaction, Sidecar, ADD_NAME, CDC_NAME, COMMIT_INFO_NAME, DOMAIN_METADATA_NAME,
     METADATA_NAME, PROTOCOL_NAME, REMOVE_NAME, SET_TRANSACTION_NAME, SIDECAR_NAME,
 };
 
@@ -439,6 +442,50 @@ impl RowVisitor for SetTransactionVisitor {
     }
 }
 
+pub(crate) type DomainMetadataMap = HashMap<String, DomainMetadata>;
+
+/// Collects the latest `domainMetadata` action for each domain, including tombstones.
+#[derive(Default)]
+#[internal_api]
+pub(crate) struct DomainMetadataVisitor {
+    pub(crate) domain_metadatas: DomainMetadataMap,
+}
+
+impl RowVisitor for DomainMetadataVisitor {
+    fn selected_column_names_and_types(&self) -> (&'static [ColumnName], &'static [DataType]) {
+        static NAMES_AND_TYPES: LazyLock<ColumnNamesAndTypes> =
+            LazyLock::new(|| DomainMetadata::to_schema().leaves(DOMAIN_METADATA_NAME));
+        NAMES_AND_TYPES.as_ref()
+    }
+
+    fn visit<'a>(&mut self, row_count: usize, getters: &[&'a dyn GetData<'a>]) -> DeltaResult<()> {
+        require!(
+            getters.len() == 3,
+            Error::InternalError(format!(
+                "Wrong number of DomainMetadataVisitor getters: {}",
+                getters.len()
+            ))
+        );
+        // Assumes batches are visited in reverse order relative to the log, so the first action
+        // seen for a domain is the latest one
+        for i in 0..row_count {
+            // Since domain column is required, use it to detect presence of a DomainMetadata action
+            if let Some(domain) = getters[0].get_opt(i, "domainMetadata.domain")? {
+                if !self.domain_metadatas.contains_key(&domain) {
+                    let domain_metadata = DomainMetadata {
+                        domain,
+                        configuration: getters[1].get(i, "domainMetadata.configuration")?,
+                        removed: getters[2].get(i, "domainMetadata.removed")?,
+                    };
+                    self.domain_metadatas
+                        .insert(domain_metadata.domain.clone(), domain_metadata);
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
 #[derive(Default)]
 #[internal_api]
 pub(crate) struct SidecarVisitor {
@@ -484,6 +531,177 @@ impl RowVisitor for SidecarVisitor {
     }
 }
 
+/// Extracts the `inCommitTimestamp` of the first commitInfo action that carries one. When in-commit
+/// timestamps are enabled, every commit starts with a commitInfo action holding the timestamp.
+#[derive(Default)]
+pub(crate) struct InCommitTimestampVisitor {
+    pub(crate) in_commit_timestamp: Option<i64>,
+}
+
+impl RowVisitor for InCommitTimestampVisitor {
+    fn selected_column_names_and_types(&self) -> (&'static [ColumnName], &'static [DataType]) {
+        static NAMES_AND_TYPES: LazyLock<ColumnNamesAndTypes> = LazyLock::new(|| {
+            (
+                vec![column_name!("commitInfo.inCommitTimestamp")],
+                vec![DataType::LONG],
+            )
+                .into()
+        });
+        NAMES_AND_TYPES.as_ref()
+    }
+
+    fn visit<'a>(&mut self, row_count: usize, getters: &[&'a dyn GetData<'a>]) -> DeltaResult<()> {
+        require!(
+            getters.len() == 1,
+            Error::InternalError(format!(
+                "Wrong number of InCommitTimestampVisitor getters: {}",
+                getters.len()
+            ))
+        );
+        if self.in_commit_timestamp.is_some() {
+            return Ok(());
+        }
+        for i in 0..row_count {
+            if let Some(timestamp) = getters[0].get_opt(i, "commitInfo.inCommitTimestamp")? {
+                self.in_commit_timestamp = Some(timestamp);
+                break;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Extracts every action of a commit, in the order the actions appear in the commit file. The
+/// engine data must have been read with [`ActionVisitor::read_schema`].
+#[derive(Default)]
+#[internal_api]
+pub(crate) struct ActionVisitor {
+    pub(crate) actions: Vec<Action>,
+}
+
+impl ActionVisitor {
+    /// The schema to read commit files with, covering every action an [`Action`] can hold.
+    pub(crate) fn read_schema() -> SchemaRef {
+        static SCHEMA: LazyLock<SchemaRef> = LazyLock::new(|| {
+            StructType::new([
+                Option::<Add>::get_struct_field(ADD_NAME),
+                Option::<Remove>::get_struct_field(REMOVE_NAME),
+                Option::<Metadata>::get_struct_field(METADATA_NAME),
+                Option::<Protocol>::get_struct_field(PROTOCOL_NAME),
+                Option::<SetTransaction>::get_struct_field(SET_TRANSACTION_NAME),
+                Option::<CommitInfo>::get_struct_field(COMMIT_INFO_NAME),
+                Option::<Cdc>::get_struct_field(CDC_NAME),
+                Option::<DomainMetadata>::get_struct_field(DOMAIN_METADATA_NAME),
+            ])
+            .into()
+        });
+        SCHEMA.clone()
+    }
+
+    /// The number of leaf columns of each action of the [read schema](Self::read_schema), in
+    /// schema order. Each action gets this many consecutive getters in [`RowVisitor::visit`].
+    fn leaf_counts() -> &'static [usize] {
+        static LEAF_COUNTS: LazyLock<Vec<usize>> = LazyLock::new(|| {
+            let schema = ActionVisitor::read_schema();
+            let leaf_count = |field: &StructField| match field.data_type() {
+                DataType::Struct(action) => action.leaves(None).as_ref().0.len(),
+                _ => 1,
+            };
+            schema.fields().map(leaf_count).collect()
+        });
+        &LEAF_COUNTS
+    }
+
+    fn visit_commit_info<'a>(
+        row_index: usize,
+        getters: &[&'a dyn GetData<'a>],
+    ) -> DeltaResult<Option<CommitInfo>> {
+        let commit_info = CommitInfo {
+            timestamp: getters[0].get_opt(row_index, "commitInfo.timestamp")?,
+            in_commit_timestamp: getters[1].get_opt(row_index, "commitInfo.inCommitTimestamp")?,
+            operation: getters[2].get_opt(row_index, "commitInfo.operation")?,
+            operation_parameters: getters[3]
+                .get_opt(row_index, "commitInfo.operationParameters")?,
+            user_metadata: getters[4].get_opt(row_index, "commitInfo.userMetadata")?,
+            kernel_version: getters[5].get_opt(row_index, "commitInfo.kernelVersion")?,
+            engine_commit_info: getters[6].get_opt(row_index, "commitInfo.engineCommitInfo")?,
+        };
+        // No field of commitInfo is required, so it is present if any of its fields is
+        let is_present = commit_info.timestamp.is_some()
+            || commit_info.in_commit_timestamp.is_some()
+            || commit_info.operation.is_some()
+            || commit_info.operation_parameters.is_some()
+            || commit_info.user_metadata.is_some()
+            || commit_info.kernel_version.is_some()
+            || commit_info.engine_commit_info.is_some();
+        Ok(is_present.then_some(commit_info))
+    }
+}
+
+impl RowVisitor for ActionVisitor {
+    fn selected_column_names_and_types(&self) -> (&'static [ColumnName], &'static [DataType]) {
+        static NAMES_AND_TYPES: LazyLock<ColumnNamesAndTypes> =
+            LazyLock::new(|| ActionVisitor::read_schema().leaves(None));
+        NAMES_AND_TYPES.as_ref()
+    }
+
+    fn visit<'a>(&mut self, row_count: usize, getters: &[&'a dyn GetData<'a>]) -> DeltaResult<()> {
+        let leaf_counts = Self::leaf_counts();
+        require!(
+            getters.len() == leaf_counts.iter().sum::<usize>(),
+            Error::InternalError(format!(
+                "Wrong number of ActionVisitor getters: {}",
+                getters.len()
+            ))
+        );
+        // The getters of each action are laid out in the order of the read schema
+        let mut actions = Vec::with_capacity(leaf_counts.len());
+        let mut rest = getters;
+        for &leaf_count in leaf_counts {
+            let (action, tail) = rest.split_at(leaf_count);
+            actions.push(action);
+            rest = tail;
+        }
+        let &[add, remove, metadata, protocol, txn, commit_info, cdc, domain_metadata] =
+            actions.as_slice()
+        else {
+            return Err(Error::internal_error(
+                "ActionVisitor read schema does not have one field per action",
+            ));
+        };
+        for i in 0..row_count {
+            if let Some(path) = add[0].get_opt(i, "add.path")? {
+                let add = AddVisitor::visit_add(i, path, add)?;
+                self.actions.push(Action::Add(add));
+            } else if let Some(path) = remove[0].get_opt(i, "remove.path")? {
+                let remove = RemoveVisitor::visit_remove(i, path, remove)?;
+                self.actions.push(Action::Remove(remove));
+            } else if let Some(id) = metadata[0].get_opt(i, "metadata.id")? {
+                let metadata = MetadataVisitor::visit_metadata(i, id, metadata)?;
+                self.actions.push(Action::Metadata(metadata));
+            } else if let Some(mrv) = protocol[0].get_opt(i, "protocol.min_reader_version")? {
+                let protocol = ProtocolVisitor::visit_protocol(i, mrv, protocol)?;
+                self.actions.push(Action::Protocol(protocol));
+            } else if let Some(app_id) = txn[0].get_opt(i, "txn.appId")? {
+                let txn = SetTransactionVisitor::visit_txn(i, app_id, txn)?;
+                self.actions.push(Action::SetTransaction(txn));
+            } else if let Some(commit_info) = Self::visit_commit_info(i, commit_info)? {
+                self.actions.push(Action::CommitInfo(commit_info));
+            } else if let Some(path) = cdc[0].get_opt(i, "cdc.path")? {
+                let cdc = CdcVisitor::visit_cdc(i, path, cdc)?;
+                self.actions.push(Action::Cdc(cdc));
+            } else if let Some(domain) = domain_metadata[0].get_opt(i, "domainMetadata.domain")? {
+                self.actions.push(Action::DomainMetadata(DomainMetadata {
+                    domain,
+                    configuration: domain_metadata[1].get(i, "domainMetadata.configuration")?,
+                    removed: domain_metadata[2].get(i, "domainMetadata.removed")?,
+                }));
+            }
+        }
+        Ok(())
+    }
+}
+
 /// Get a DV out of some engine data. The caller is responsible for slicing the `getters` slice such
 /// that the first element contains the `storageType` element of the deletion vector.
 pub(crate) fn visit_deletion_vector_at<'a>(
@@ -516,8 +734,10 @@ mod tests {
 
     use crate::arrow::array::StringArray;
 
+    use crate::engine::sync::SyncEngine;
     use crate::table_features::{ReaderFeature, WriterFeature};
-    use crate::utils::test_utils::{action_batch, parse_json_batch};
+    use crate::utils::test_utils::{action_batch, parse_json_batch, string_array_to_engine_data};
+    use crate::Engine as _;
 
     #[test]
     fn test_parse_protocol() -> DeltaResult<()> {
@@ -692,6 +912,23 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_parse_in_commit_timestamp() -> DeltaResult<()> {
+        let json_strings: StringArray = vec![
+            r#"{"commitInfo":{"timestamp":1670892998177,"inCommitTimestamp":1670892998135,"operation":"WRITE"}}"#,
+            r#"{"protocol":{"minReaderVersion":1,"minWriterVersion":7,"writerFeatures":["inCommitTimestamp"]}}"#,
+        ]
+        .into();
+        let mut visitor = InCommitTimestampVisitor::default();
+        visitor.visit_rows_of(parse_json_batch(json_strings).as_ref())?;
+        assert_eq!(visitor.in_commit_timestamp, Some(1670892998135));
+
+        let mut visitor = InCommitTimestampVisitor::default();
+        visitor.visit_rows_of(action_batch().as_ref())?;
+        assert_eq!(visitor.in_commit_timestamp, None);
+        Ok(())
+    }
+
     #[test]
     fn test_parse_txn() {
         let json_strings: StringArray = vec![
@@ -724,4 +961,68 @@ mod tests {
             })
         );
     }
+
+    #[test]
+    fn test_parse_actions() -> DeltaResult<()> {
+        let json_strings: StringArray = vec![
+            r#"{"commitInfo":{"timestamp":1670892998177,"inCommitTimestamp":1670892998135,"operation":"WRITE","operationParameters":{"mode":"Append"},"userMetadata":"backfill"}}"#,
+            r#"{"txn":{"appId":"myApp","version": 3}}"#,
+            r#"{"remove":{"path":"a.parquet","deletionTimestamp":1670892998135,"dataChange":true}}"#,
+            r#"{"add":{"path":"b.parquet","partitionValues":{},"size":452,"modificationTime":1670892998137,"dataChange":true}}"#,
+            r#"{"cdc":{"path":"_change_data/c.parquet","partitionValues":{},"size":1033,"dataChange":false}}"#,
+            r#"{"domainMetadata":{"domain":"myDomain","configuration":"{}","removed":false}}"#,
+            r#"{"sidecar":{"path":"d.parquet","sizeInBytes":9268,"modificationTime":1714496113961}}"#,
+        ]
+        .into();
+        let batch = SyncEngine::new().json_handler().parse_json(
+            string_array_to_engine_data(json_strings),
+            ActionVisitor::read_schema(),
+        )?;
+        let mut visitor = ActionVisitor::default();
+        visitor.visit_rows_of(batch.as_ref())?;
+
+        // the sidecar action is not a commit action, so it is skipped
+        let expected = vec![
+            Action::CommitInfo(CommitInfo {
+                timestamp: Some(1670892998177),
+                in_commit_timestamp: Some(1670892998135),
+                operation: Some("WRITE".to_string()),
+                operation_parameters: Some(HashMap::from([(
+                    "mode".to_string(),
+                    "Append".to_string(),
+                )])),
+                user_metadata: Some("backfill".to_string()),
+                kernel_version: None,
+                engine_commit_info: None,
+            }),
+            Action::SetTransaction(SetTransaction::new("myApp".to_string(), 3, None)),
+            Action::Remove(Remove {
+                path: "a.parquet".to_string(),
+                deletion_timestamp: Some(1670892998135),
+                data_change: true,
+                ..Default::default()
+            }),
+            Action::Add(Add {
+                path: "b.parquet".to_string(),
+                size: 452,
+                modification_time: 1670892998137,
+                data_change: true,
+                ..Default::default()
+            }),
+            Action::Cdc(Cdc {
+                path: "_change_data/c.parquet".to_string(),
+                partition_values: HashMap::new(),
+                size: 1033,
+                data_change: false,
+                tags: None,
+            }),
+            Action::DomainMetadata(DomainMetadata {
+                domain: "myDomain".to_string(),
+                configuration: "{}".to_string(),
+                removed: false,
+            }),
+        ];
+        assert_eq!(visitor.actions, expected);
+        Ok(())
+    }
 }
@@ -11,7 +11,9 @@
 //!    and metadata actions.
 //! 2. **Txn Actions**: Keeps exactly one `txn` action for each unique app ID, always selecting
 //!    the latest one encountered.
-//! 3. **File Actions**: Resolves file actions to produce the latest state of the table, keeping
+//! 3. **Domain Metadata Actions**: Keeps the latest `domainMetadata` action for each domain, unless
+//!    it removes the domain.
+//! 4. **File Actions**: Resolves file actions to produce the latest state of the table, keeping
 //!    the most recent valid add actions and unexpired remove actions (tombstones) that are newer
 //!    than `minimum_file_retention_timestamp`.
 //!
@@ -49,7 +51,6 @@ use std::sync::{Arc, LazyLock};
 /// It processes each action batch via the `process_actions_batch` method, using the
 /// [`CheckpointVisitor`] to build an accompanying selection vector indicating which actions
 /// should be included in the checkpoint.
-#[allow(unused)] // TODO(seb): Remove once checkpoint api is implemented
 pub(crate) struct CheckpointLogReplayProcessor {
     /// Tracks file actions that have been seen during log replay to avoid duplicates.
     /// Contains (data file path, dv_unique_id) pairs as `FileActionKey` instances.
@@ -67,6 +68,8 @@ pub(crate) struct CheckpointLogReplayProcessor {
     seen_metadata: bool,
     /// Set of transaction app IDs that have been processed to avoid duplicates.
     seen_txns: HashSet<String>,
+    /// Set of metadata domains that have been processed to avoid duplicates.
+    seen_domains: HashSet<String>,
     /// Minimum timestamp for file retention, used for filtering expired tombstones.
     minimum_file_retention_timestamp: i64,
 }
@@ -97,6 +100,7 @@ impl LogReplayProcessor for CheckpointLogReplayProcessor {
             self.seen_protocol,
             self.seen_metadata,
             &mut self.seen_txns,
+            &mut self.seen_domains,
         );
         visitor.visit_rows_of(batch.as_ref())?;
 
@@ -127,7 +131,6 @@ impl LogReplayProcessor for CheckpointLogReplayProcessor {
 }
 
 impl CheckpointLogReplayProcessor {
-    #[allow(unused)] // TODO(seb): Remove once checkpoint api is implemented
     pub(crate) fn new(
         actions_count: Arc<AtomicI64>,
         add_actions_count: Arc<AtomicI64>,
@@ -140,6 +143,7 @@ impl CheckpointLogReplayProcessor {
             seen_protocol: false,
             seen_metadata: false,
             seen_txns: Default::default(),
+            seen_domains: Default::default(),
             minimum_file_retention_timestamp,
         }
     }
@@ -168,6 +172,8 @@ impl CheckpointLogReplayProcessor {
 /// - Keeps only the first protocol action (newest version)
 /// - Keeps only the first metadata action (most recent table metadata)
 /// - Keeps only the first txn action for each unique app ID
+/// - Keeps only the first domain metadata action for each domain, and omits it entirely if it
+///   removes the domain
 ///
 /// # Excluded Actions
 /// - CommitInfo, CDC, and CheckpointMetadata actions should not appear in the action
@@ -180,7 +186,7 @@ impl CheckpointLogReplayProcessor {
 ///
 /// # Memory Usage
 /// This struct has O(N + M) memory usage where:
-/// - N = number of txn actions with unique appIds
+/// - N = number of txn actions with unique appIds and domain metadata actions with unique domains
 /// - M = number of file actions with unique (path, dvId) pairs
 ///
 /// The resulting filtered set of actions are the actions which should be written to a
@@ -209,6 +215,8 @@ pub(crate) struct CheckpointVisitor<'seen> {
     // Set of transaction IDs to deduplicate by appId
     // This set has O(N) memory usage where N = number of txn actions with unique appIds
     seen_txns: &'seen mut HashSet<String>,
+    // Set of metadata domains to deduplicate domain metadata actions by domain
+    seen_domains: &'seen mut HashSet<String>,
 }
 
 #[allow(unused)]
@@ -226,6 +234,7 @@ impl CheckpointVisitor<'_> {
     const PROTOCOL_MIN_READER_VERSION: &'static str = "protocol.minReaderVersion";
     const METADATA_ID: &'static str = "metaData.id";
 
+    #[allow(clippy::too_many_arguments)]
     pub(crate) fn new<'seen>(
         seen_file_keys: &'seen mut HashSet<FileActionKey>,
         is_log_batch: bool,
@@ -234,6 +243,7 @@ impl CheckpointVisitor<'_> {
         seen_protocol: bool,
         seen_metadata: bool,
         seen_txns: &'seen mut HashSet<String>,
+        seen_domains: &'seen mut HashSet<String>,
     ) -> CheckpointVisitor<'seen> {
         CheckpointVisitor {
             deduplicator: FileActionDeduplicator::new(
@@ -252,6 +262,7 @@ impl CheckpointVisitor<'_> {
             seen_protocol,
             seen_metadata,
             seen_txns,
+            seen_domains,
         }
     }
 
@@ -386,13 +397,44 @@ impl CheckpointVisitor<'_> {
         Ok(true)
     }
 
+    /// Processes a potential domain metadata action to determine if it should be included in the
+    /// checkpoint.
+    ///
+    /// Returns Ok(true) if the row contains the latest domain metadata action of a domain that was
+    /// not removed.
+    /// Returns Ok(false) if the row doesn't contain a domain metadata action, is a duplicate, or
+    /// removes its domain.
+    /// Returns Err(...) if there was an error processing the action.
+    fn check_domain_metadata_action<'a>(
+        &mut self,
+        i: usize,
+        getters: &[&'a dyn GetData<'a>],
+    ) -> DeltaResult<bool> {
+        let Some(domain) = getters[0].get_str(i, "domainMetadata.domain")? else {
+            return Ok(false); // Not a domain metadata action
+        };
+
+        // Only the newest action of a domain counts. A removed domain needs no tombstone in the
+        // checkpoint, as no older action of the domain is kept either.
+        if !self.seen_domains.insert(domain.to_string())
+            || getters[1].get(i, "domainMetadata.removed")?
+        {
+            return Ok(false);
+        }
+
+        // Valid, non-duplicate domain metadata action to be included
+        self.non_file_actions_count += 1;
+        Ok(true)
+    }
+
     /// Determines if a row in the batch should be included in the checkpoint.
     ///
     /// This method checks each action type in sequence, short-circuiting as soon as a valid action is found.
     /// Actions are checked in order of expected frequency of occurrence to optimize performance:
     /// 1. File actions (most frequent)
     /// 2. Txn actions
-    /// 3. Protocol & Metadata actions (least frequent)
+    /// 3. Domain metadata actions
+    /// 4. Protocol & Metadata actions (least frequent)
     ///
     /// Returns Ok(true) if the row should be included in the checkpoint.
     /// Returns Ok(false) if the row should be skipped.
@@ -406,6 +448,7 @@ impl CheckpointVisitor<'_> {
         // the rest will not be evaluated.
         Ok(self.check_file_action(i, getters)?
             || self.check_txn_action(i, getters[11])?
+            || self.check_domain_metadata_action(i, &getters[12..14])?
             || self.check_protocol_action(i, getters[10])?
             || self.check_metadata_action(i, getters[9])?)
     }
@@ -419,10 +462,12 @@ impl RowVisitor for CheckpointVisitor<'_> {
         // 3. METADATA
         // 4. PROTOCOL
         // 5. TXN
+        // 6. DOMAIN METADATA
         static NAMES_AND_TYPES: LazyLock<ColumnNamesAndTypes> = LazyLock::new(|| {
             const STRING: DataType = DataType::STRING;
             const INTEGER: DataType = DataType::INTEGER;
             const LONG: DataType = DataType::LONG;
+            const BOOLEAN: DataType = DataType::BOOLEAN;
             let types_and_names = vec![
                 // File action columns
                 (STRING, column_name!("add.path")),
@@ -438,6 +483,8 @@ impl RowVisitor for CheckpointVisitor<'_> {
                 (STRING, column_name!("metaData.id")),
                 (INTEGER, column_name!("protocol.minReaderVersion")),
                 (STRING, column_name!("txn.appId")),
+                (STRING, column_name!("domainMetadata.domain")),
+                (BOOLEAN, column_name!("domainMetadata.removed")),
             ];
             let (types, names) = types_and_names.into_iter().unzip();
             (names, types).into()
@@ -447,7 +494,7 @@ impl RowVisitor for CheckpointVisitor<'_> {
 
     fn visit<'a>(&mut self, row_count: usize, getters: &[&'a dyn GetData<'a>]) -> DeltaResult<()> {
         require!(
-            getters.len() == 12,
+            getters.len() == 14,
             Error::InternalError(format!(
                 "Wrong number of visitor getters: {}",
                 getters.len()
@@ -500,6 +547,7 @@ mod tests {
         let data = action_batch();
         let mut seen_file_keys = HashSet::new();
         let mut seen_txns = HashSet::new();
+        let mut seen_domains = HashSet::new();
         let mut visitor = CheckpointVisitor::new(
             &mut seen_file_keys,
             true,
@@ -508,6 +556,7 @@ mod tests {
             false,
             false,
             &mut seen_txns,
+            &mut seen_domains,
         );
 
         visitor.visit_rows_of(data.as_ref())?;
@@ -555,6 +604,7 @@ mod tests {
 
         let mut seen_file_keys = HashSet::new();
         let mut seen_txns = HashSet::new();
+        let mut seen_domains = HashSet::new();
         let mut visitor = CheckpointVisitor::new(
             &mut seen_file_keys,
             true,
@@ -563,6 +613,7 @@ mod tests {
             false,
             false,
             &mut seen_txns,
+            &mut seen_domains,
         );
 
         visitor.visit_rows_of(batch.as_ref())?;
@@ -586,6 +637,7 @@ mod tests {
 
         let mut seen_file_keys = HashSet::new();
         let mut seen_txns = HashSet::new();
+        let mut seen_domains = HashSet::new();
         let mut visitor = CheckpointVisitor::new(
             &mut seen_file_keys,
             false, // is_log_batch = false (checkpoint batch)
@@ -594,6 +646,7 @@ mod tests {
             false,
             false,
             &mut seen_txns,
+            &mut seen_domains,
         );
 
         visitor.visit_rows_of(batch.as_ref())?;
@@ -625,6 +678,7 @@ mod tests {
 
         let mut seen_file_keys = HashSet::new();
         let mut seen_txns = HashSet::new();
+        let mut seen_domains = HashSet::new();
         let mut visitor = CheckpointVisitor::new(
             &mut seen_file_keys,
             true,
@@ -633,6 +687,7 @@ mod tests {
             false,
             false,
             &mut seen_txns,
+            &mut seen_domains,
         );
 
         visitor.visit_rows_of(batch.as_ref())?;
@@ -658,6 +713,7 @@ mod tests {
         // Pre-populate with txn app1
         let mut seen_file_keys = HashSet::new();
         let mut seen_txns = HashSet::new();
+        let mut seen_domains = HashSet::new();
         seen_txns.insert("app1".to_string());
 
         let mut visitor = CheckpointVisitor::new(
@@ -668,6 +724,7 @@ mod tests {
             true,           // The visior has already seen a protocol action
             true,           // The visitor has already seen a metadata action
             &mut seen_txns, // Pre-populated transaction
+            &mut seen_domains,
         );
 
         visitor.visit_rows_of(batch.as_ref())?;
@@ -698,6 +755,7 @@ mod tests {
 
         let mut seen_file_keys = HashSet::new();
         let mut seen_txns = HashSet::new();
+        let mut seen_domains = HashSet::new();
         let mut visitor = CheckpointVisitor::new(
             &mut seen_file_keys,
             true, // is_log_batch
@@ -706,6 +764,7 @@ mod tests {
             false,
             false,
             &mut seen_txns,
+            &mut seen_domains,
         );
 
         visitor.visit_rows_of(batch.as_ref())?;
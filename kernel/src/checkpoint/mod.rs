@@ -2,7 +2,158 @@
 //!
 //! This module implements the API for writing checkpoints in delta tables.
 //! Checkpoints provide a compact summary of the table state, enabling faster recovery by
-//! avoiding full log replay. This API supports three checkpoint types:
+//! avoiding full log replay.
 //!
-//! TODO!(seb): Include docs when implemented
+//! Currently only classic single-file checkpoints (`<version>.checkpoint.parquet`) following the
+//! V1 spec are written, see [`Snapshot::write_checkpoint`]. After the checkpoint is written, the
+//! `_last_checkpoint` file is updated to point to it.
+//!
+//! [`Snapshot::write_checkpoint`]: crate::snapshot::Snapshot::write_checkpoint
+use std::sync::atomic::{AtomicI64, Ordering};
+use std::sync::{Arc, LazyLock};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::actions::{
+    get_log_schema, ADD_NAME, DOMAIN_METADATA_NAME, METADATA_NAME, PROTOCOL_NAME, REMOVE_NAME,
+    SET_TRANSACTION_NAME, SIDECAR_NAME,
+};
+use crate::expressions::{Expression, Scalar};
+use crate::log_replay::LogReplayProcessor as _;
+use crate::path::ParsedLogPath;
+use crate::schema::{DataType, SchemaRef, StructField, StructType};
+use crate::snapshot::{Snapshot, LAST_CHECKPOINT_FILE_NAME};
+use crate::table_features::ReaderFeature;
+use crate::{DeltaResult, Engine, Error, EvaluationHandlerExtension as _, FilteredEngineData};
+
+use log_replay::CheckpointLogReplayProcessor;
+
 pub(crate) mod log_replay;
+
+/// The actions written to a V1 checkpoint, in the order of the columns of the checkpoint file.
+static CHECKPOINT_ACTIONS_SCHEMA: LazyLock<SchemaRef> = LazyLock::new(|| {
+    get_log_schema()
+        .project(&[
+            ADD_NAME,
+            REMOVE_NAME,
+            METADATA_NAME,
+            PROTOCOL_NAME,
+            SET_TRANSACTION_NAME,
+            DOMAIN_METADATA_NAME,
+        ])
+        .expect("checkpoint actions are part of the log schema")
+});
+
+/// The schema the log is read with when writing a checkpoint. Sidecars must be read so the file
+/// actions they reference are replayed, but they are not written to the checkpoint themselves.
+static CHECKPOINT_READ_SCHEMA: LazyLock<SchemaRef> = LazyLock::new(|| {
+    let fields = CHECKPOINT_ACTIONS_SCHEMA
+        .fields()
+        .cloned()
+        .chain(get_log_schema().field(SIDECAR_NAME).cloned());
+    Arc::new(StructType::new(fields))
+});
+
+/// The fields of the `_last_checkpoint` file written along with a checkpoint.
+static LAST_CHECKPOINT_SCHEMA: LazyLock<SchemaRef> = LazyLock::new(|| {
+    Arc::new(StructType::new([
+        StructField::not_null("version", DataType::LONG),
+        StructField::not_null("size", DataType::LONG),
+        StructField::nullable("numOfAddFiles", DataType::LONG),
+    ]))
+});
+
+/// Write a classic single-file checkpoint of `snapshot` at `<version>.checkpoint.parquet`, then
+/// point the `_last_checkpoint` file to it. See [`Snapshot::write_checkpoint`].
+pub(crate) fn write_checkpoint(snapshot: &Snapshot, engine: &dyn Engine) -> DeltaResult<()> {
+    // A V2 checkpoint table requires a checkpointMetadata action even in classic checkpoints
+    if snapshot
+        .protocol()
+        .has_reader_feature(&ReaderFeature::V2Checkpoint)
+    {
+        return Err(Error::unsupported(
+            "Writing checkpoints of tables with the v2Checkpoint feature is not supported",
+        ));
+    }
+
+    let version = i64::try_from(snapshot.version())
+        .map_err(|_| Error::generic("Snapshot version does not fit in an i64"))?;
+    let actions_count = Arc::new(AtomicI64::new(0));
+    let add_actions_count = Arc::new(AtomicI64::new(0));
+    let processor = CheckpointLogReplayProcessor::new(
+        actions_count.clone(),
+        add_actions_count.clone(),
+        minimum_file_retention_timestamp(snapshot)?,
+    );
+
+    let read_schema = CHECKPOINT_READ_SCHEMA.clone();
+    let actions = snapshot.log_segment().read_actions(
+        engine,
+        read_schema.clone(),
+        read_schema.clone(),
+        None,
+    )?;
+    // drop the sidecar column from the batches before they are written
+    let projection = Expression::struct_from(
+        CHECKPOINT_ACTIONS_SCHEMA
+            .fields()
+            .map(|field| Expression::column([field.name()])),
+    );
+    let evaluator = engine.evaluation_handler().new_expression_evaluator(
+        read_schema,
+        projection,
+        CHECKPOINT_ACTIONS_SCHEMA.as_ref().clone().into(),
+    );
+    let data = processor
+        .process_actions_iter(actions)
+        .map(move |filtered| -> DeltaResult<_> {
+            let FilteredEngineData {
+                data,
+                selection_vector,
+            } = filtered?;
+            Ok(FilteredEngineData {
+                data: evaluator.evaluate(data.as_ref())?,
+                selection_vector,
+            })
+        });
+
+    // Checkpoints are idempotent, so replacing an existing checkpoint of this version is fine.
+    let checkpoint_path = ParsedLogPath::new_classic_parquet_checkpoint_in_log(
+        &snapshot.log_segment().log_root,
+        snapshot.version(),
+    )?;
+    engine
+        .parquet_handler()
+        .write_parquet_file(&checkpoint_path.location, Box::new(data), true)?;
+
+    let last_checkpoint = engine.evaluation_handler().create_one(
+        LAST_CHECKPOINT_SCHEMA.clone(),
+        &[
+            Scalar::from(version),
+            Scalar::from(actions_count.load(Ordering::Relaxed)),
+            Scalar::from(add_actions_count.load(Ordering::Relaxed)),
+        ],
+    )?;
+    let last_checkpoint_path = snapshot
+        .log_segment()
+        .log_root
+        .join(LAST_CHECKPOINT_FILE_NAME)?;
+    engine.json_handler().write_json_file(
+        &last_checkpoint_path,
+        Box::new(std::iter::once(Ok(last_checkpoint))),
+        true,
+    )
+}
+
+/// Remove actions deleted at or before this timestamp (in milliseconds since the epoch) have
+/// expired, and are not written to the checkpoint. The retention period is configured by the
+/// `delta.deletedFileRetentionDuration` table property.
+fn minimum_file_retention_timestamp(snapshot: &Snapshot) -> DeltaResult<i64> {
+    let retention = snapshot
+        .table_properties()
+        .deleted_file_retention_duration();
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_err(|_| Error::generic("Failed to get current time for checkpoint"))?;
+    let timestamp = now.saturating_sub(retention).as_millis();
+    i64::try_from(timestamp).map_err(|_| Error::generic("Current time does not fit in an i64"))
+}
@@ -1,31 +1,388 @@
 //! Conversions from kernel types to arrow types
 
-use std::sync::Arc;
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::sync::{Arc, RwLock};
 
 use crate::arrow::datatypes::{
-    DataType as ArrowDataType, Field as ArrowField, Schema as ArrowSchema,
+    DataType as ArrowDataType, Field as ArrowField, FieldRef, Schema as ArrowSchema,
     SchemaRef as ArrowSchemaRef, TimeUnit,
 };
 use crate::arrow::error::ArrowError;
+use crate::parquet::arrow::PARQUET_FIELD_ID_META_KEY;
 use itertools::Itertools;
 
-use crate::error::Error;
+use crate::error::{DeltaResult, Error};
 use crate::schema::{
-    ArrayType, DataType, DictionaryType, MapType, MetadataValue, PrimitiveType, StructField,
-    StructType,
+    ArrayType, ColumnMetadataKey, DataType, DictionaryType, MapType, MetadataValue, PrimitiveType,
+    SchemaTransform, StructField, StructType,
 };
+use crate::STRING_VIEW_METADATA_KEY;
 
 pub(crate) const LIST_ARRAY_ROOT: &str = "item";
 pub(crate) const MAP_ROOT_DEFAULT: &str = "key_value";
 pub(crate) const MAP_KEY_DEFAULT: &str = "key";
 pub(crate) const MAP_VALUE_DEFAULT: &str = "value";
 
+/// Prefix shared by all kernel-internal field metadata keys
+const DELTA_METADATA_KEY_PREFIX: &str = "delta.";
+
+/// Prefix shared by all metadata keys arrow uses to describe extension types
+const ARROW_EXTENSION_KEY_PREFIX: &str = "ARROW:extension:";
+
+/// Timezone names and offsets that are equivalent to UTC. Delta's `TIMESTAMP` is always
+/// UTC-normalized, so any of these can be represented without loss.
+const UTC_TIMEZONES: &[&str] = &[
+    "UTC",
+    "Z",
+    "+00:00",
+    "-00:00",
+    "+0000",
+    "-0000",
+    "+00",
+    "-00",
+    "GMT",
+    "UCT",
+    "Zulu",
+    "Universal",
+    "Etc/UTC",
+    "Etc/UCT",
+    "Etc/GMT",
+    "Etc/GMT+0",
+    "Etc/GMT-0",
+    "Etc/GMT0",
+    "Etc/Zulu",
+    "Etc/Universal",
+];
+
+/// Returns an error naming `tz` unless it is a timezone equivalent to UTC.
+fn ensure_utc_timezone(tz: &str) -> Result<(), ArrowError> {
+    if UTC_TIMEZONES.iter().any(|utc| tz.eq_ignore_ascii_case(utc)) {
+        Ok(())
+    } else {
+        Err(ArrowError::SchemaError(format!(
+            "Timezone '{tz}' is not supported for Delta Lake timestamps: only UTC-equivalent \
+             timezones can be represented without loss"
+        )))
+    }
+}
+
+/// Options that control how types are converted between kernel and arrow.
+///
+/// The defaults match the child field names used by arrow-rs. Engines that expect different names
+/// (e.g. Spark emits `element` for list items) can override them so that a schema roundtripped
+/// through kernel keeps its original names. By default, arrow types without a Delta equivalent are
+/// rejected; the lenient options below opt in to mapping them instead.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConversionOptions {
+    /// Name of the child field of an arrow `List`.
+    pub list_element_name: String,
+    /// Name of the entries struct field of an arrow `Map`.
+    pub map_entries_name: String,
+    /// Name of the key field inside the entries struct of an arrow `Map`.
+    pub map_key_name: String,
+    /// Name of the value field inside the entries struct of an arrow `Map`.
+    pub map_value_name: String,
+    /// Map arrow `Null` columns to a nullable `STRING` instead of failing the conversion.
+    pub null_as_string: bool,
+    /// Map arrow `Duration` columns to `LONG` instead of failing the conversion. The value is the
+    /// count of the duration's time unit, which is lost from the schema.
+    pub duration_as_long: bool,
+    /// Drop kernel-internal field metadata (every key with the `delta.` prefix, such as column
+    /// mapping annotations) when converting to arrow.
+    pub strip_delta_metadata: bool,
+    /// How arrow unsigned integer types are converted to kernel types.
+    pub unsigned_ints: UnsignedIntPolicy,
+}
+
+/// How arrow unsigned integer types are converted to kernel types.
+///
+/// Unsigned integers are a kernel extension that other Delta implementations do not understand,
+/// so engines that need their tables to stay readable elsewhere can map them to the signed type
+/// of the same width instead. Values above the signed maximum then need to be handled (e.g.
+/// reinterpreted or rejected) by the engine when writing data.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum UnsignedIntPolicy {
+    /// Map `UInt8`/`UInt16`/`UInt32`/`UInt64` to `UBYTE`/`USHORT`/`UINTEGER`/`ULONG`.
+    #[default]
+    Preserve,
+    /// Map `UInt8`/`UInt16`/`UInt32`/`UInt64` to `BYTE`/`SHORT`/`INTEGER`/`LONG`.
+    CoerceToSigned,
+}
+
+impl Default for ConversionOptions {
+    fn default() -> Self {
+        Self {
+            list_element_name: LIST_ARRAY_ROOT.to_string(),
+            map_entries_name: MAP_ROOT_DEFAULT.to_string(),
+            map_key_name: MAP_KEY_DEFAULT.to_string(),
+            map_value_name: MAP_VALUE_DEFAULT.to_string(),
+            null_as_string: false,
+            duration_as_long: false,
+            strip_delta_metadata: false,
+            unsigned_ints: UnsignedIntPolicy::Preserve,
+        }
+    }
+}
+
+/// Convert a kernel [`StructType`] to an [`ArrowSchema`], naming list and map children according
+/// to `options`.
+pub fn to_arrow_schema_with_options(
+    schema: &StructType,
+    options: &ConversionOptions,
+) -> Result<ArrowSchema, ArrowError> {
+    let fields: Vec<ArrowField> = schema
+        .fields()
+        .map(|f| struct_field_to_arrow(f, options))
+        .try_collect()?;
+    Ok(ArrowSchema::new(fields))
+}
+
+/// Convert a kernel [`StructType`] to an [`ArrowSchema`] for consumers outside of Delta, dropping
+/// all kernel-internal field metadata (keys with the `delta.` prefix), including the column mapping
+/// id and the parquet field id derived from it. Other metadata is kept.
+pub fn to_arrow_schema_clean(schema: &StructType) -> Result<ArrowSchema, ArrowError> {
+    let options = ConversionOptions {
+        strip_delta_metadata: true,
+        ..Default::default()
+    };
+    to_arrow_schema_with_options(schema, &options)
+}
+
+/// Metadata key recording the length of an arrow `FixedSizeList` on the kernel field it was
+/// converted to, so that converting back to arrow restores the fixed-size list.
+pub const FIXED_SIZE_LIST_LENGTH_METADATA_KEY: &str = "delta.arrow.fixedSizeListLength";
+
+/// Metadata key under which [`to_physical_arrow_schema`] records the logical name of a field that
+/// was renamed to its physical name.
+pub const LOGICAL_NAME_METADATA_KEY: &str = "delta.columnMapping.logicalName";
+
+/// Convert a kernel [`StructType`] to an [`ArrowSchema`] whose field names are the physical names
+/// of the columns, i.e. the names used in the parquet files of a table with column mapping in
+/// `name` mode. Every renamed field records its logical name under
+/// [`LOGICAL_NAME_METADATA_KEY`]. Fields without a physical name keep their logical name.
+pub fn to_physical_arrow_schema(schema: &StructType) -> Result<ArrowSchema, ArrowError> {
+    struct MakePhysical;
+    impl<'a> SchemaTransform<'a> for MakePhysical {
+        fn transform_struct_field(
+            &mut self,
+            field: &'a StructField,
+        ) -> Option<Cow<'a, StructField>> {
+            let field = self.recurse_into_struct_field(field)?;
+            match field.get_config_value(&ColumnMetadataKey::ColumnMappingPhysicalName) {
+                Some(MetadataValue::String(physical_name)) => {
+                    let mut physical = field.with_name(physical_name);
+                    physical
+                        .metadata
+                        .insert(LOGICAL_NAME_METADATA_KEY.to_string(), field.name().into());
+                    Some(Cow::Owned(physical))
+                }
+                _ => Some(field),
+            }
+        }
+    }
+    // NOTE: unwrap is safe because the transformer is incapable of returning None
+    let physical = MakePhysical.transform_struct(schema).unwrap();
+    physical.as_ref().try_into()
+}
+
+fn struct_field_to_arrow(
+    f: &StructField,
+    options: &ConversionOptions,
+) -> Result<ArrowField, ArrowError> {
+    let mut metadata = f
+        .metadata()
+        .iter()
+        .map(|(key, val)| match &val {
+            &MetadataValue::String(val) => Ok((key.clone(), val.clone())),
+            _ => Ok((key.clone(), serde_json::to_string(val)?)),
+        })
+        .collect::<Result<HashMap<_, _>, serde_json::Error>>()
+        .map_err(|err| ArrowError::JsonError(err.to_string()))?;
+
+    // Surface the column mapping id as a parquet field id so that readers can resolve columns by id
+    if let Some(MetadataValue::Number(id)) = f.get_config_value(&ColumnMetadataKey::ColumnMappingId)
+    {
+        if !options.strip_delta_metadata {
+            metadata.insert(PARQUET_FIELD_ID_META_KEY.to_string(), id.to_string());
+        }
+    }
+
+    let mut data_type = data_type_to_arrow(f.data_type(), options)?;
+    if let Some(length) = metadata.remove(FIXED_SIZE_LIST_LENGTH_METADATA_KEY) {
+        let ArrowDataType::List(element) = data_type else {
+            return Err(ArrowError::SchemaError(format!(
+                "{FIXED_SIZE_LIST_LENGTH_METADATA_KEY} is only valid on array fields, found it on \
+                 '{}'",
+                f.name()
+            )));
+        };
+        let length = length.parse().map_err(|_| {
+            ArrowError::SchemaError(format!(
+                "Invalid {FIXED_SIZE_LIST_LENGTH_METADATA_KEY} for field '{}': {length}",
+                f.name()
+            ))
+        })?;
+        data_type = ArrowDataType::FixedSizeList(element, length);
+    }
+
+    if metadata.remove(STRING_VIEW_METADATA_KEY).as_deref() == Some("true") {
+        data_type = string_view_type(&data_type);
+    }
+
+    if options.strip_delta_metadata {
+        metadata.retain(|key, _| !key.starts_with(DELTA_METADATA_KEY_PREFIX));
+    }
+
+    let mut field = ArrowField::new(f.name(), data_type, f.is_nullable()).with_metadata(metadata);
+    if let DataType::Dictionary(dict) = f.data_type() {
+        field = field.with_dict_is_ordered(dict.is_ordered());
+    }
+
+    Ok(field)
+}
+
+/// Returns `data_type` with every string and binary type replaced by its view type.
+pub(crate) fn string_view_type(data_type: &ArrowDataType) -> ArrowDataType {
+    use ArrowDataType::*;
+    let view_field = |field: &FieldRef| -> FieldRef {
+        let data_type = string_view_type(field.data_type());
+        Arc::new(field.as_ref().clone().with_data_type(data_type))
+    };
+    match data_type {
+        Utf8 | LargeUtf8 => Utf8View,
+        Binary | LargeBinary => BinaryView,
+        Struct(fields) => Struct(fields.iter().map(view_field).collect()),
+        List(field) => List(view_field(field)),
+        LargeList(field) => LargeList(view_field(field)),
+        Map(field, sorted) => Map(view_field(field), *sorted),
+        other => other.clone(),
+    }
+}
+
+fn array_type_to_arrow(
+    a: &ArrayType,
+    options: &ConversionOptions,
+) -> Result<ArrowField, ArrowError> {
+    Ok(ArrowField::new(
+        &options.list_element_name,
+        data_type_to_arrow(a.element_type(), options)?,
+        a.contains_null(),
+    ))
+}
+
+fn map_type_to_arrow(m: &MapType, options: &ConversionOptions) -> Result<ArrowField, ArrowError> {
+    // Map keys are never null. An array or map key that itself holds nulls would be silently
+    // coerced by marking the arrow key non-nullable, so reject the malformed schema instead.
+    let nullable_key = match m.key_type() {
+        DataType::Array(a) => a.contains_null(),
+        DataType::Map(m) => m.value_contains_null(),
+        _ => false,
+    };
+    if nullable_key {
+        return Err(ArrowError::SchemaError(format!(
+            "Map keys must be non-nullable, but the key type {} contains nulls",
+            m.key_type()
+        )));
+    }
+    Ok(ArrowField::new(
+        &options.map_entries_name,
+        ArrowDataType::Struct(
+            vec![
+                ArrowField::new(
+                    &options.map_key_name,
+                    data_type_to_arrow(m.key_type(), options)?,
+                    false,
+                ),
+                ArrowField::new(
+                    &options.map_value_name,
+                    data_type_to_arrow(m.value_type(), options)?,
+                    m.value_contains_null(),
+                ),
+            ]
+            .into(),
+        ),
+        false, // always non-null
+    ))
+}
+
+fn dictionary_type_to_arrow(
+    d: &DictionaryType,
+    options: &ConversionOptions,
+) -> Result<ArrowDataType, ArrowError> {
+    Ok(ArrowDataType::Dictionary(
+        Box::new(data_type_to_arrow(d.key_type(), options)?),
+        Box::new(data_type_to_arrow(d.value_type(), options)?),
+    ))
+}
+
+fn data_type_to_arrow(
+    t: &DataType,
+    options: &ConversionOptions,
+) -> Result<ArrowDataType, ArrowError> {
+    match t {
+        DataType::Primitive(p) => {
+            match p {
+                PrimitiveType::String => Ok(ArrowDataType::Utf8),
+                PrimitiveType::Long => Ok(ArrowDataType::Int64), // undocumented type
+                PrimitiveType::ULong => Ok(ArrowDataType::UInt64),
+                PrimitiveType::Integer => Ok(ArrowDataType::Int32),
+                PrimitiveType::UInteger => Ok(ArrowDataType::UInt32),
+                PrimitiveType::Short => Ok(ArrowDataType::Int16),
+                PrimitiveType::UShort => Ok(ArrowDataType::UInt16),
+                PrimitiveType::Byte => Ok(ArrowDataType::Int8),
+                PrimitiveType::UByte => Ok(ArrowDataType::UInt8),
+                PrimitiveType::Float => Ok(ArrowDataType::Float32),
+                PrimitiveType::Double => Ok(ArrowDataType::Float64),
+                PrimitiveType::Boolean => Ok(ArrowDataType::Boolean),
+                PrimitiveType::Binary => Ok(ArrowDataType::Binary),
+                PrimitiveType::Decimal(dtype) => Ok(ArrowDataType::Decimal128(
+                    dtype.precision(),
+                    dtype.scale() as i8, // 0..=38
+                )),
+                PrimitiveType::Date => {
+                    // A calendar date, represented as a year-month-day triple without a
+                    // timezone. Stored as 4 bytes integer representing days since 1970-01-01
+                    Ok(ArrowDataType::Date32)
+                }
+                // TODO: https://github.com/delta-io/delta/issues/643
+                PrimitiveType::Timestamp => Ok(ArrowDataType::Timestamp(
+                    TimeUnit::Microsecond,
+                    Some("UTC".into()),
+                )),
+                PrimitiveType::TimestampNs => Ok(ArrowDataType::Timestamp(
+                    TimeUnit::Nanosecond,
+                    Some("UTC".into()),
+                )),
+                PrimitiveType::TimestampNtz => {
+                    Ok(ArrowDataType::Timestamp(TimeUnit::Microsecond, None))
+                }
+            }
+        }
+        DataType::Struct(s) => Ok(ArrowDataType::Struct(
+            s.fields()
+                .map(|f| struct_field_to_arrow(f, options))
+                .collect::<Result<Vec<ArrowField>, ArrowError>>()?
+                .into(),
+        )),
+        DataType::Array(a) => Ok(ArrowDataType::List(Arc::new(array_type_to_arrow(
+            a, options,
+        )?))),
+        DataType::Map(m) => Ok(ArrowDataType::Map(
+            Arc::new(map_type_to_arrow(m, options)?),
+            false,
+        )),
+        DataType::Dictionary(d) => dictionary_type_to_arrow(d, options),
+    }
+}
+
 impl TryFrom<&StructType> for ArrowSchema {
     type Error = ArrowError;
 
     fn try_from(s: &StructType) -> Result<Self, ArrowError> {
-        let fields: Vec<ArrowField> = s.fields().map(TryInto::try_into).try_collect()?;
-        Ok(ArrowSchema::new(fields))
+        to_arrow_schema_with_options(s, &ConversionOptions::default())
     }
 }
 
@@ -33,24 +390,7 @@ impl TryFrom<&StructField> for ArrowField {
     type Error = ArrowError;
 
     fn try_from(f: &StructField) -> Result<Self, ArrowError> {
-        let metadata = f
-            .metadata()
-            .iter()
-            .map(|(key, val)| match &val {
-                &MetadataValue::String(val) => Ok((key.clone(), val.clone())),
-                _ => Ok((key.clone(), serde_json::to_string(val)?)),
-            })
-            .collect::<Result<_, serde_json::Error>>()
-            .map_err(|err| ArrowError::JsonError(err.to_string()))?;
-
-        let field = ArrowField::new(
-            f.name(),
-            ArrowDataType::try_from(f.data_type())?,
-            f.is_nullable(),
-        )
-        .with_metadata(metadata);
-
-        Ok(field)
+        struct_field_to_arrow(f, &ConversionOptions::default())
     }
 }
 
@@ -58,37 +398,15 @@ impl TryFrom<&ArrayType> for ArrowField {
     type Error = ArrowError;
 
     fn try_from(a: &ArrayType) -> Result<Self, ArrowError> {
-        Ok(ArrowField::new(
-            LIST_ARRAY_ROOT,
-            ArrowDataType::try_from(a.element_type())?,
-            a.contains_null(),
-        ))
+        array_type_to_arrow(a, &ConversionOptions::default())
     }
 }
 
 impl TryFrom<&MapType> for ArrowField {
     type Error = ArrowError;
 
-    fn try_from(a: &MapType) -> Result<Self, ArrowError> {
-        Ok(ArrowField::new(
-            MAP_ROOT_DEFAULT,
-            ArrowDataType::Struct(
-                vec![
-                    ArrowField::new(
-                        MAP_KEY_DEFAULT,
-                        ArrowDataType::try_from(a.key_type())?,
-                        false,
-                    ),
-                    ArrowField::new(
-                        MAP_VALUE_DEFAULT,
-                        ArrowDataType::try_from(a.value_type())?,
-                        a.value_contains_null(),
-                    ),
-                ]
-                .into(),
-            ),
-            false, // always non-null
-        ))
+    fn try_from(m: &MapType) -> Result<Self, ArrowError> {
+        map_type_to_arrow(m, &ConversionOptions::default())
     }
 }
 
@@ -96,10 +414,7 @@ impl TryFrom<&DictionaryType> for ArrowDataType {
     type Error = ArrowError;
 
     fn try_from(d: &DictionaryType) -> Result<Self, ArrowError> {
-        Ok(ArrowDataType::Dictionary(
-            Box::new(d.key_type().try_into()?),
-            Box::new(d.value_type().try_into()?),
-        ))
+        dictionary_type_to_arrow(d, &ConversionOptions::default())
     }
 }
 
@@ -107,76 +422,29 @@ impl TryFrom<&DataType> for ArrowDataType {
     type Error = ArrowError;
 
     fn try_from(t: &DataType) -> Result<Self, ArrowError> {
-        match t {
-            DataType::Primitive(p) => {
-                match p {
-                    PrimitiveType::String => Ok(ArrowDataType::Utf8),
-                    PrimitiveType::Long => Ok(ArrowDataType::Int64), // undocumented type
-                    PrimitiveType::ULong => Ok(ArrowDataType::UInt64),
-                    PrimitiveType::Integer => Ok(ArrowDataType::Int32),
-                    PrimitiveType::UInteger => Ok(ArrowDataType::UInt32),
-                    PrimitiveType::Short => Ok(ArrowDataType::Int16),
-                    PrimitiveType::UShort => Ok(ArrowDataType::UInt16),
-                    PrimitiveType::Byte => Ok(ArrowDataType::Int8),
-                    PrimitiveType::UByte => Ok(ArrowDataType::UInt8),
-                    PrimitiveType::Float => Ok(ArrowDataType::Float32),
-                    PrimitiveType::Double => Ok(ArrowDataType::Float64),
-                    PrimitiveType::Boolean => Ok(ArrowDataType::Boolean),
-                    PrimitiveType::Binary => Ok(ArrowDataType::Binary),
-                    PrimitiveType::Decimal(dtype) => Ok(ArrowDataType::Decimal128(
-                        dtype.precision(),
-                        dtype.scale() as i8, // 0..=38
-                    )),
-                    PrimitiveType::Date => {
-                        // A calendar date, represented as a year-month-day triple without a
-                        // timezone. Stored as 4 bytes integer representing days since 1970-01-01
-                        Ok(ArrowDataType::Date32)
-                    }
-                    // TODO: https://github.com/delta-io/delta/issues/643
-                    PrimitiveType::Timestamp => Ok(ArrowDataType::Timestamp(
-                        TimeUnit::Microsecond,
-                        Some("UTC".into()),
-                    )),
-                    PrimitiveType::TimestampNs => Ok(ArrowDataType::Timestamp(
-                        TimeUnit::Nanosecond,
-                        Some("UTC".into()),
-                    )),
-                    PrimitiveType::TimestampNtz => {
-                        Ok(ArrowDataType::Timestamp(TimeUnit::Microsecond, None))
-                    }
-                }
-            }
-            DataType::Struct(s) => Ok(ArrowDataType::Struct(
-                s.fields()
-                    .map(TryInto::try_into)
-                    .collect::<Result<Vec<ArrowField>, ArrowError>>()?
-                    .into(),
-            )),
-            DataType::Array(a) => Ok(ArrowDataType::List(Arc::new(a.as_ref().try_into()?))),
-            DataType::Map(m) => Ok(ArrowDataType::Map(Arc::new(m.as_ref().try_into()?), false)),
-            DataType::Dictionary(d) => {
-                let key_type = ArrowDataType::try_from(d.key_type())?;
-                let value_type = ArrowDataType::try_from(d.value_type())?;
-
-                Ok(ArrowDataType::Dictionary(
-                    Box::new(key_type),
-                    Box::new(value_type),
-                ))
-            }
-        }
+        data_type_to_arrow(t, &ConversionOptions::default())
     }
 }
 
+/// Convert an [`ArrowSchema`] to a kernel [`StructType`], handling arrow types that have no Delta
+/// equivalent according to `options`.
+pub fn from_arrow_schema_with_options(
+    arrow_schema: &ArrowSchema,
+    options: &ConversionOptions,
+) -> Result<StructType, ArrowError> {
+    StructType::try_new(
+        arrow_schema
+            .fields()
+            .iter()
+            .map(|field| FromArrow::new(options).struct_field(field)),
+    )
+}
+
 impl TryFrom<&ArrowSchema> for StructType {
     type Error = ArrowError;
 
     fn try_from(arrow_schema: &ArrowSchema) -> Result<Self, ArrowError> {
-        StructType::try_new(
-            arrow_schema
-                .fields()
-                .iter()
-                .map(|field| field.as_ref().try_into()),
-        )
+        from_arrow_schema_with_options(arrow_schema, &ConversionOptions::default())
     }
 }
 
@@ -188,35 +456,287 @@ impl TryFrom<ArrowSchemaRef> for StructType {
     }
 }
 
-impl TryFrom<&ArrowField> for StructField {
-    type Error = ArrowError;
+/// Converts arrow schemas to kernel schemas, memoizing the results. Scans over many files usually
+/// see the same physical schema over and over, and walking a wide schema for every file adds up.
+///
+/// Schemas are looked up by a hash of their structure, so equal schemas hit the cache even when
+/// they come from different [`ArrowSchemaRef`]s. The cache is never evicted, so a converter should
+/// live as long as the set of schemas it sees is bounded (e.g. for one scan).
+#[derive(Debug, Default)]
+pub struct SchemaConverter {
+    options: ConversionOptions,
+    cache: RwLock<HashMap<u64, Vec<(ArrowSchemaRef, StructType)>>>,
+}
 
-    fn try_from(arrow_field: &ArrowField) -> Result<Self, ArrowError> {
-        Ok(StructField::new(
-            arrow_field.name().clone(),
-            DataType::try_from(arrow_field.data_type())?,
-            arrow_field.is_nullable(),
-        )
-        .with_metadata(arrow_field.metadata().iter().map(|(k, v)| (k.clone(), v))))
+impl SchemaConverter {
+    /// Create a converter that converts schemas according to `options`.
+    pub fn new(options: ConversionOptions) -> Self {
+        Self {
+            options,
+            cache: Default::default(),
+        }
+    }
+
+    /// Convert `arrow_schema` to a kernel [`StructType`], reusing the result of an earlier
+    /// conversion of an equal schema if there was one.
+    pub fn convert(&self, arrow_schema: &ArrowSchemaRef) -> DeltaResult<StructType> {
+        let key = Self::cache_key(arrow_schema);
+        let lookup = |entries: &Vec<(ArrowSchemaRef, StructType)>| {
+            entries
+                .iter()
+                .find(|(cached, _)| Arc::ptr_eq(cached, arrow_schema) || cached == arrow_schema)
+                .map(|(_, schema)| schema.clone())
+        };
+        let cache = self
+            .cache
+            .read()
+            .map_err(|_| Error::internal_error("SchemaConverter cache lock poisoned"))?;
+        if let Some(schema) = cache.get(&key).and_then(lookup) {
+            return Ok(schema);
+        }
+        drop(cache);
+
+        let schema = from_arrow_schema_with_options(arrow_schema, &self.options)
+            .map_err(Error::generic_err)?;
+        self.cache
+            .write()
+            .map_err(|_| Error::internal_error("SchemaConverter cache lock poisoned"))?
+            .entry(key)
+            .or_default()
+            .push((arrow_schema.clone(), schema.clone()));
+        Ok(schema)
+    }
+
+    fn cache_key(arrow_schema: &ArrowSchema) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        arrow_schema.fields().hash(&mut hasher);
+        arrow_schema
+            .metadata()
+            .iter()
+            .sorted()
+            .for_each(|entry| entry.hash(&mut hasher));
+        hasher.finish()
     }
 }
 
-impl TryFrom<&ArrowDataType> for DataType {
-    type Error = ArrowError;
+/// Recover a [`MetadataValue`] from its arrow string encoding. Non-string values are written to
+/// arrow as JSON, so anything that parses as a JSON number, boolean, array or object is restored to
+/// the matching variant. Everything else is kept as a plain string.
+fn parse_metadata_value(value: &str) -> MetadataValue {
+    match serde_json::from_str::<serde_json::Value>(value) {
+        Ok(serde_json::Value::Bool(b)) => MetadataValue::Boolean(b),
+        Ok(serde_json::Value::Number(n)) => match n.as_i64() {
+            Some(n) => MetadataValue::Number(n),
+            None => MetadataValue::Other(serde_json::Value::Number(n)),
+        },
+        Ok(v @ (serde_json::Value::Array(_) | serde_json::Value::Object(_))) => {
+            MetadataValue::Other(v)
+        }
+        _ => MetadataValue::String(value.to_string()),
+    }
+}
 
-    fn try_from(arrow_datatype: &ArrowDataType) -> Result<Self, ArrowError> {
+/// A coercion performed by [`DataType::try_from_arrow_lossy`] to convert an arrow type that has no
+/// exact Delta equivalent.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CoercionWarning {
+    /// The dot-separated path of the coerced field, empty for a top-level type.
+    pub path: String,
+    /// The original arrow type.
+    pub arrow_type: ArrowDataType,
+    /// The Delta type it was coerced to.
+    pub delta_type: DataType,
+}
+
+impl Display for CoercionWarning {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if !self.path.is_empty() {
+            write!(f, "'{}': ", self.path)?;
+        }
+        write!(f, "{} coerced to {}", self.arrow_type, self.delta_type)
+    }
+}
+
+impl DataType {
+    /// Convert an arrow type to a Delta type on a best-effort basis: arrow types without a Delta
+    /// equivalent are coerced to the closest Delta type instead of failing (e.g. `Interval` and
+    /// `Null` become `STRING`, `Time64` and `Duration` become `LONG`). Returns the coerced type
+    /// along with every coercion performed, so callers can log them. Malformed nested types (such
+    /// as a map without key and value fields) are still rejected.
+    pub fn try_from_arrow_lossy(
+        arrow_datatype: &ArrowDataType,
+    ) -> DeltaResult<(DataType, Vec<CoercionWarning>)> {
+        let options = ConversionOptions::default();
+        let mut converter = FromArrow::new(&options);
+        converter.coercions = Some(vec![]);
+        let data_type = converter
+            .data_type(arrow_datatype)
+            .map_err(Error::generic_err)?;
+        Ok((data_type, converter.coercions.unwrap_or_default()))
+    }
+}
+
+/// The closest Delta type for an arrow type that cannot be converted exactly.
+fn coerce_lossy(arrow_datatype: &ArrowDataType) -> DataType {
+    match arrow_datatype {
+        ArrowDataType::Time32(_) | ArrowDataType::Time64(_) | ArrowDataType::Duration(_) => {
+            DataType::LONG
+        }
+        ArrowDataType::Float16 => DataType::FLOAT,
+        ArrowDataType::Timestamp(TimeUnit::Nanosecond, Some(_)) => DataType::TIMESTAMP_NS,
+        ArrowDataType::Timestamp(_, Some(_)) => DataType::TIMESTAMP,
+        ArrowDataType::Timestamp(_, None) => DataType::TIMESTAMP_NTZ,
+        _ => DataType::STRING,
+    }
+}
+
+/// Converts arrow types to kernel types, tracking the path of the field being converted so that
+/// errors can name the offending column.
+struct FromArrow<'a> {
+    options: &'a ConversionOptions,
+    path: Vec<String>,
+    /// When set, unsupported leaf types are coerced (and recorded here) instead of rejected
+    coercions: Option<Vec<CoercionWarning>>,
+}
+
+impl<'a> FromArrow<'a> {
+    fn new(options: &'a ConversionOptions) -> Self {
+        Self {
+            options,
+            path: vec![],
+            coercions: None,
+        }
+    }
+
+    /// Build the error for an arrow type that cannot be converted, naming the field being
+    /// converted (if any) so that the offending column can be found in a large schema.
+    fn invalid_data_type(&self, detail: impl Display) -> ArrowError {
+        if self.path.is_empty() {
+            ArrowError::SchemaError(format!("Invalid data type for Delta Lake: {detail}"))
+        } else {
+            let path = self.path.join(".");
+            ArrowError::SchemaError(format!(
+                "Invalid data type for Delta Lake at '{path}': {detail}"
+            ))
+        }
+    }
+
+    /// Pick the kernel type for an arrow unsigned integer according to the configured policy.
+    fn unsigned(&self, unsigned: DataType, signed: DataType) -> DataType {
+        match self.options.unsigned_ints {
+            UnsignedIntPolicy::Preserve => unsigned,
+            UnsignedIntPolicy::CoerceToSigned => signed,
+        }
+    }
+
+    fn struct_field(&mut self, arrow_field: &ArrowField) -> Result<StructField, ArrowError> {
+        let mut metadata: HashMap<String, MetadataValue> = arrow_field
+            .metadata()
+            .iter()
+            .map(|(k, v)| {
+                // extension type metadata belongs to arrow and must roundtrip untouched
+                let value = if k.starts_with(ARROW_EXTENSION_KEY_PREFIX) {
+                    MetadataValue::String(v.clone())
+                } else {
+                    parse_metadata_value(v)
+                };
+                (k.clone(), value)
+            })
+            .collect();
+        // A parquet field id is carried as the column mapping id of the kernel field
+        if let Some(id) = metadata.remove(PARQUET_FIELD_ID_META_KEY) {
+            let id = id.to_string().parse::<i64>().map_err(|_| {
+                ArrowError::SchemaError(format!(
+                    "Invalid {PARQUET_FIELD_ID_META_KEY} for field '{}': {id}",
+                    arrow_field.name()
+                ))
+            })?;
+            metadata.insert(
+                ColumnMetadataKey::ColumnMappingId.as_ref().to_string(),
+                MetadataValue::Number(id),
+            );
+        }
+        if let ArrowDataType::FixedSizeList(_, length) = arrow_field.data_type() {
+            metadata.insert(
+                FIXED_SIZE_LIST_LENGTH_METADATA_KEY.to_string(),
+                MetadataValue::Number((*length).into()),
+            );
+        }
+        // a column of `Null` type can only ever hold nulls, whatever type it is mapped to
+        let nullable = arrow_field.is_nullable() || arrow_field.data_type() == &ArrowDataType::Null;
+        let mut data_type = self.child(arrow_field.name(), arrow_field.data_type())?;
+        // arrow tracks dictionary ordering and value nullability on the field, not the type
+        if let DataType::Dictionary(dict) = data_type {
+            let mut dict = dict.with_ordered(arrow_field.dict_is_ordered().unwrap_or_default());
+            dict.value_contains_null = arrow_field.is_nullable();
+            data_type = dict.into();
+        }
+        Ok(
+            StructField::new(arrow_field.name().clone(), data_type, nullable)
+                .with_metadata(metadata),
+        )
+    }
+
+    /// Convert the type of the child field `name` of the type currently being converted.
+    fn child(
+        &mut self,
+        name: &str,
+        arrow_datatype: &ArrowDataType,
+    ) -> Result<DataType, ArrowError> {
+        self.path.push(name.to_string());
+        let data_type = self.data_type(arrow_datatype)?;
+        self.path.pop();
+        Ok(data_type)
+    }
+
+    fn array_type(&mut self, field: &ArrowField) -> Result<DataType, ArrowError> {
+        let element_type = self.child("element", field.data_type())?;
+        Ok(ArrayType::new(element_type, field.is_nullable()).into())
+    }
+
+    fn data_type(&mut self, arrow_datatype: &ArrowDataType) -> Result<DataType, ArrowError> {
+        // nested types coerce their children instead, so their own errors are structural
+        let is_nested = matches!(
+            arrow_datatype,
+            ArrowDataType::Struct(_)
+                | ArrowDataType::List(_)
+                | ArrowDataType::ListView(_)
+                | ArrowDataType::LargeList(_)
+                | ArrowDataType::LargeListView(_)
+                | ArrowDataType::FixedSizeList(..)
+                | ArrowDataType::Map(..)
+                | ArrowDataType::Dictionary(..)
+        );
+        match self.exact_data_type(arrow_datatype) {
+            Err(_) if !is_nested && self.coercions.is_some() => {
+                let delta_type = coerce_lossy(arrow_datatype);
+                let path = self.path.join(".");
+                if let Some(coercions) = self.coercions.as_mut() {
+                    coercions.push(CoercionWarning {
+                        path,
+                        arrow_type: arrow_datatype.clone(),
+                        delta_type: delta_type.clone(),
+                    });
+                }
+                Ok(delta_type)
+            }
+            result => result,
+        }
+    }
+
+    fn exact_data_type(&mut self, arrow_datatype: &ArrowDataType) -> Result<DataType, ArrowError> {
         match arrow_datatype {
             ArrowDataType::Utf8 => Ok(DataType::STRING),
             ArrowDataType::LargeUtf8 => Ok(DataType::STRING),
             ArrowDataType::Utf8View => Ok(DataType::STRING),
             ArrowDataType::Int64 => Ok(DataType::LONG), // undocumented type
-            ArrowDataType::UInt64 => Ok(DataType::ULONG),
+            ArrowDataType::UInt64 => Ok(self.unsigned(DataType::ULONG, DataType::LONG)),
             ArrowDataType::Int32 => Ok(DataType::INTEGER),
-            ArrowDataType::UInt32 => Ok(DataType::UINTEGER),
+            ArrowDataType::UInt32 => Ok(self.unsigned(DataType::UINTEGER, DataType::INTEGER)),
             ArrowDataType::Int16 => Ok(DataType::SHORT),
-            ArrowDataType::UInt16 => Ok(DataType::USHORT),
+            ArrowDataType::UInt16 => Ok(self.unsigned(DataType::USHORT, DataType::SHORT)),
             ArrowDataType::Int8 => Ok(DataType::BYTE),
-            ArrowDataType::UInt8 => Ok(DataType::UBYTE),
+            ArrowDataType::UInt8 => Ok(self.unsigned(DataType::UBYTE, DataType::BYTE)),
             ArrowDataType::Float32 => Ok(DataType::FLOAT),
             ArrowDataType::Float64 => Ok(DataType::DOUBLE),
             ArrowDataType::Boolean => Ok(DataType::BOOLEAN),
@@ -227,74 +747,141 @@ impl TryFrom<&ArrowDataType> for DataType {
             ArrowDataType::Decimal128(p, s) => {
                 if *s < 0 {
                     return Err(ArrowError::from_external_error(
-                        Error::invalid_decimal("Negative scales are not supported in Delta").into(),
+                        Error::invalid_decimal(format!(
+                            "{arrow_datatype}: negative scale {s} is not supported in Delta"
+                        ))
+                        .into(),
                     ));
                 };
+                if *s as u8 > *p {
+                    return Err(ArrowError::from_external_error(
+                        Error::invalid_decimal(format!(
+                            "{arrow_datatype}: scale {s} exceeds precision {p}"
+                        ))
+                        .into(),
+                    ));
+                }
                 DataType::decimal(*p, *s as u8)
                     .map_err(|e| ArrowError::from_external_error(e.into()))
             }
             ArrowDataType::Date32 => Ok(DataType::DATE),
+            // Delta dates are days since the epoch; Date64 data (milliseconds) is rescaled to
+            // days when it is read, see `ensure_data_types`.
             ArrowDataType::Date64 => Ok(DataType::DATE),
             ArrowDataType::Timestamp(TimeUnit::Microsecond, None) => Ok(DataType::TIMESTAMP_NTZ),
-            ArrowDataType::Timestamp(TimeUnit::Microsecond, Some(tz))
-                if tz.eq_ignore_ascii_case("utc") =>
-            {
+            ArrowDataType::Timestamp(TimeUnit::Microsecond, Some(tz)) => {
+                ensure_utc_timezone(tz)?;
+                Ok(DataType::TIMESTAMP)
+            }
+            // Delta timestamps are microseconds; data in coarser units is upcast to microseconds
+            // when it is read, see `ensure_data_types`.
+            ArrowDataType::Timestamp(TimeUnit::Second | TimeUnit::Millisecond, None) => {
+                Ok(DataType::TIMESTAMP_NTZ)
+            }
+            ArrowDataType::Timestamp(TimeUnit::Second | TimeUnit::Millisecond, Some(tz)) => {
+                ensure_utc_timezone(tz)?;
                 Ok(DataType::TIMESTAMP)
             }
             ArrowDataType::Timestamp(TimeUnit::Nanosecond, None) => Ok(DataType::TIMESTAMP_NS),
-            ArrowDataType::Timestamp(TimeUnit::Nanosecond, Some(tz))
-                if tz.eq_ignore_ascii_case("utc") =>
-            {
+            ArrowDataType::Timestamp(TimeUnit::Nanosecond, Some(tz)) => {
+                ensure_utc_timezone(tz)?;
                 Ok(DataType::TIMESTAMP_NS)
             }
             ArrowDataType::Struct(fields) => {
-                DataType::try_struct_type(fields.iter().map(|field| field.as_ref().try_into()))
-            }
-            ArrowDataType::List(field) => {
-                Ok(ArrayType::new((*field).data_type().try_into()?, (*field).is_nullable()).into())
-            }
-            ArrowDataType::ListView(field) => {
-                Ok(ArrayType::new((*field).data_type().try_into()?, (*field).is_nullable()).into())
-            }
-            ArrowDataType::LargeList(field) => {
-                Ok(ArrayType::new((*field).data_type().try_into()?, (*field).is_nullable()).into())
-            }
-            ArrowDataType::LargeListView(field) => {
-                Ok(ArrayType::new((*field).data_type().try_into()?, (*field).is_nullable()).into())
-            }
-            ArrowDataType::FixedSizeList(field, _) => {
-                Ok(ArrayType::new((*field).data_type().try_into()?, (*field).is_nullable()).into())
+                DataType::try_struct_type(fields.iter().map(|field| self.struct_field(field)))
             }
+            ArrowDataType::List(field) => self.array_type(field),
+            ArrowDataType::ListView(field) => self.array_type(field),
+            ArrowDataType::LargeList(field) => self.array_type(field),
+            ArrowDataType::LargeListView(field) => self.array_type(field),
+            ArrowDataType::FixedSizeList(field, _) => self.array_type(field),
             ArrowDataType::Map(field, _) => {
-                if let ArrowDataType::Struct(struct_fields) = field.data_type() {
-                    let key_type = DataType::try_from(struct_fields[0].data_type())?;
-                    let value_type = DataType::try_from(struct_fields[1].data_type())?;
-                    let value_type_nullable = struct_fields[1].is_nullable();
-                    Ok(MapType::new(key_type, value_type, value_type_nullable).into())
-                } else {
-                    panic!("DataType::Map should contain a struct field child");
+                let ArrowDataType::Struct(struct_fields) = field.data_type() else {
+                    return Err(ArrowError::SchemaError(format!(
+                        "Invalid map at '{}': the map entry child must be a struct with key and \
+                         value fields, found {}",
+                        self.path.join("."),
+                        field.data_type()
+                    )));
+                };
+                let [key_field, value_field] = &struct_fields.iter().collect::<Vec<_>>()[..] else {
+                    return Err(ArrowError::SchemaError(format!(
+                        "Invalid map at '{}': the map entry struct must have exactly 2 fields \
+                         (key and value), found {}",
+                        self.path.join("."),
+                        struct_fields.len()
+                    )));
+                };
+                if key_field.is_nullable() {
+                    return Err(ArrowError::SchemaError(format!(
+                        "Map keys must be non-nullable, but the key field of '{}' is nullable",
+                        self.path.join(".")
+                    )));
                 }
+                let key_type = self.child("key", key_field.data_type())?;
+                let value_type = self.child("value", value_field.data_type())?;
+                Ok(MapType::new(key_type, value_type, value_field.is_nullable()).into())
             }
             ArrowDataType::Dictionary(key_type, value_type) => {
-                let key_type = DataType::try_from(&**key_type)?;
-                let value_type = DataType::try_from(&**value_type)?;
+                let key_type = self.data_type(key_type)?;
+                let value_type = self.data_type(value_type)?;
                 Ok(DictionaryType::new(key_type, value_type, true).into())
             }
-            s => Err(ArrowError::SchemaError(format!(
-                "Invalid data type for Delta Lake: {s}"
+            ArrowDataType::Time32(unit) | ArrowDataType::Time64(unit) => Err(self
+                .invalid_data_type(format!(
+                    "{arrow_datatype}. Delta has no time-of-day type; store the time in {unit:?} \
+                     units as an integer or as a string instead"
+                ))),
+            ArrowDataType::Duration(_) if self.options.duration_as_long => Ok(DataType::LONG),
+            ArrowDataType::Duration(unit) => Err(self.invalid_data_type(format!(
+                "{arrow_datatype}. Delta has no duration type; a duration can be stored as a long \
+                 holding the count of {unit:?} units"
             ))),
+            ArrowDataType::Null if self.options.null_as_string => Ok(DataType::STRING),
+            ArrowDataType::Null => Err(self.invalid_data_type(
+                "Null. A column of purely Null type cannot be represented in Delta and must be \
+                 cast to a concrete type first",
+            )),
+            s => Err(self.invalid_data_type(s)),
         }
     }
 }
 
+impl TryFrom<&ArrowField> for StructField {
+    type Error = ArrowError;
+
+    fn try_from(arrow_field: &ArrowField) -> Result<Self, ArrowError> {
+        FromArrow::new(&ConversionOptions::default()).struct_field(arrow_field)
+    }
+}
+
+impl TryFrom<&ArrowDataType> for DataType {
+    type Error = ArrowError;
+
+    fn try_from(arrow_datatype: &ArrowDataType) -> Result<Self, ArrowError> {
+        FromArrow::new(&ConversionOptions::default()).data_type(arrow_datatype)
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::engine::arrow_conversion::ArrowField;
+    use crate::arrow::datatypes::Schema as ArrowSchema;
+    use crate::arrow::datatypes::{DataType as ArrowDataType, IntervalUnit, TimeUnit};
+    use crate::engine::arrow_conversion::{
+        from_arrow_schema_with_options, to_arrow_schema_clean, to_arrow_schema_with_options,
+        to_physical_arrow_schema, ArrowField, CoercionWarning, ConversionOptions, SchemaConverter,
+        UnsignedIntPolicy, FIXED_SIZE_LIST_LENGTH_METADATA_KEY, LOGICAL_NAME_METADATA_KEY,
+    };
+    use crate::parquet::arrow::PARQUET_FIELD_ID_META_KEY;
     use crate::{
-        schema::{DataType, StructField},
+        schema::{
+            ArrayType, ColumnMetadataKey, DataType, DictionaryType, MapType, MetadataValue,
+            StructField, StructType,
+        },
         DeltaResult,
     };
     use std::collections::HashMap;
+    use std::sync::Arc;
 
     #[test]
     fn test_metadata_string_conversion() -> DeltaResult<()> {
@@ -311,4 +898,539 @@ mod tests {
         );
         Ok(())
     }
+
+    #[test]
+    fn test_utc_equivalent_timezones() {
+        for tz in ["UTC", "utc", "Z", "+00:00", "Etc/UTC"] {
+            let arrow_type = ArrowDataType::Timestamp(TimeUnit::Microsecond, Some(tz.into()));
+            assert_eq!(
+                DataType::try_from(&arrow_type).unwrap(),
+                DataType::TIMESTAMP
+            );
+            let arrow_type = ArrowDataType::Timestamp(TimeUnit::Nanosecond, Some(tz.into()));
+            assert_eq!(
+                DataType::try_from(&arrow_type).unwrap(),
+                DataType::TIMESTAMP_NS
+            );
+        }
+    }
+
+    #[test]
+    fn test_coarse_timestamp_units() {
+        for unit in [TimeUnit::Second, TimeUnit::Millisecond] {
+            let arrow_type = ArrowDataType::Timestamp(unit, Some("UTC".into()));
+            assert_eq!(
+                DataType::try_from(&arrow_type).unwrap(),
+                DataType::TIMESTAMP
+            );
+            let arrow_type = ArrowDataType::Timestamp(unit, None);
+            assert_eq!(
+                DataType::try_from(&arrow_type).unwrap(),
+                DataType::TIMESTAMP_NTZ
+            );
+        }
+    }
+
+    #[test]
+    fn test_non_utc_timezone_rejected() {
+        for unit in [
+            TimeUnit::Second,
+            TimeUnit::Millisecond,
+            TimeUnit::Microsecond,
+            TimeUnit::Nanosecond,
+        ] {
+            let arrow_type = ArrowDataType::Timestamp(unit, Some("+05:30".into()));
+            let err = DataType::try_from(&arrow_type).unwrap_err();
+            assert!(err.to_string().contains("'+05:30'"), "{err}");
+        }
+    }
+
+    #[test]
+    fn test_roundtrip_with_custom_child_names() -> DeltaResult<()> {
+        let schema = StructType::new([
+            StructField::nullable("list", ArrayType::new(DataType::INTEGER, true)),
+            StructField::nullable("map", MapType::new(DataType::STRING, DataType::LONG, true)),
+        ]);
+        let options = ConversionOptions {
+            list_element_name: "element".to_string(),
+            map_entries_name: "entries".to_string(),
+            ..Default::default()
+        };
+        let arrow_schema = to_arrow_schema_with_options(&schema, &options)?;
+
+        let ArrowDataType::List(element) = arrow_schema.field(0).data_type() else {
+            panic!("expected a list");
+        };
+        assert_eq!(element.name(), "element");
+        let ArrowDataType::Map(entries, _) = arrow_schema.field(1).data_type() else {
+            panic!("expected a map");
+        };
+        assert_eq!(entries.name(), "entries");
+
+        // the names survive a roundtrip through kernel
+        let roundtripped = StructType::try_from(&arrow_schema)?;
+        assert_eq!(roundtripped, schema);
+        assert_eq!(
+            to_arrow_schema_with_options(&roundtripped, &options)?,
+            arrow_schema
+        );
+
+        // the default conversion is unchanged
+        let default_schema = ArrowSchema::try_from(&schema)?;
+        let ArrowDataType::List(element) = default_schema.field(0).data_type() else {
+            panic!("expected a list");
+        };
+        assert_eq!(element.name(), "item");
+        Ok(())
+    }
+
+    #[test]
+    fn test_field_id_roundtrip() -> DeltaResult<()> {
+        let arrow_field = ArrowField::new("id", ArrowDataType::Int64, false).with_metadata(
+            HashMap::from([(PARQUET_FIELD_ID_META_KEY.to_string(), "7".to_string())]),
+        );
+        let struct_field = StructField::try_from(&arrow_field)?;
+        assert_eq!(
+            struct_field.get_config_value(&ColumnMetadataKey::ColumnMappingId),
+            Some(&MetadataValue::Number(7))
+        );
+        assert!(!struct_field
+            .metadata()
+            .contains_key(PARQUET_FIELD_ID_META_KEY));
+
+        // the id is emitted both as the column mapping id and as the parquet field id
+        let arrow_field = ArrowField::try_from(&struct_field)?;
+        assert_eq!(
+            arrow_field.metadata(),
+            &HashMap::from([
+                (PARQUET_FIELD_ID_META_KEY.to_string(), "7".to_string()),
+                (
+                    ColumnMetadataKey::ColumnMappingId.as_ref().to_string(),
+                    "7".to_string()
+                ),
+            ])
+        );
+        assert_eq!(StructField::try_from(&arrow_field)?, struct_field);
+        Ok(())
+    }
+
+    #[test]
+    fn test_invalid_field_id() {
+        let arrow_field = ArrowField::new("id", ArrowDataType::Int64, false).with_metadata(
+            HashMap::from([(PARQUET_FIELD_ID_META_KEY.to_string(), "abc".to_string())]),
+        );
+        let err = StructField::try_from(&arrow_field).unwrap_err();
+        assert!(err.to_string().contains("abc"), "{err}");
+    }
+
+    #[test]
+    fn test_metadata_value_types_roundtrip() -> DeltaResult<()> {
+        let struct_field = StructField::nullable("name", DataType::STRING).with_metadata([
+            ("number", MetadataValue::Number(3)),
+            ("bool", MetadataValue::Boolean(true)),
+            ("string", MetadataValue::String("hello".to_string())),
+            (
+                "array",
+                MetadataValue::Other(serde_json::json!([{"expression": "x > 3"}])),
+            ),
+        ]);
+        let arrow_field = ArrowField::try_from(&struct_field)?;
+        assert_eq!(arrow_field.metadata().get("number").unwrap(), "3");
+        assert_eq!(StructField::try_from(&arrow_field)?, struct_field);
+        Ok(())
+    }
+
+    #[test]
+    fn test_time_types_rejected() {
+        for (arrow_type, unit) in [
+            (ArrowDataType::Time32(TimeUnit::Second), "Second"),
+            (ArrowDataType::Time32(TimeUnit::Millisecond), "Millisecond"),
+            (ArrowDataType::Time64(TimeUnit::Microsecond), "Microsecond"),
+            (ArrowDataType::Time64(TimeUnit::Nanosecond), "Nanosecond"),
+        ] {
+            let err = DataType::try_from(&arrow_type).unwrap_err().to_string();
+            assert!(err.contains("no time-of-day type"), "{err}");
+            assert!(err.contains(&format!("in {unit} units")), "{err}");
+        }
+    }
+
+    #[test]
+    fn test_null_type() -> DeltaResult<()> {
+        let arrow_schema =
+            ArrowSchema::new(vec![ArrowField::new("empty", ArrowDataType::Null, false)]);
+        let err = StructType::try_from(&arrow_schema).unwrap_err().to_string();
+        assert!(err.contains("must be cast to a concrete type"), "{err}");
+
+        let options = ConversionOptions {
+            null_as_string: true,
+            ..Default::default()
+        };
+        let schema = from_arrow_schema_with_options(&arrow_schema, &options)?;
+        assert_eq!(
+            schema,
+            StructType::new([StructField::nullable("empty", DataType::STRING)])
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_physical_arrow_schema() -> DeltaResult<()> {
+        let schema = StructType::new([
+            StructField::not_null("user_id", DataType::LONG).with_metadata([(
+                ColumnMetadataKey::ColumnMappingPhysicalName.as_ref(),
+                "col-abc123",
+            )]),
+            StructField::nullable("name", DataType::STRING),
+        ]);
+        let arrow_schema = to_physical_arrow_schema(&schema)?;
+
+        let field = arrow_schema.field(0);
+        assert_eq!(field.name(), "col-abc123");
+        assert_eq!(
+            field.metadata().get(LOGICAL_NAME_METADATA_KEY),
+            Some(&"user_id".to_string())
+        );
+        let field = arrow_schema.field(1);
+        assert_eq!(field.name(), "name");
+        assert!(!field.metadata().contains_key(LOGICAL_NAME_METADATA_KEY));
+        Ok(())
+    }
+
+    #[test]
+    fn test_fixed_size_list_roundtrip() -> DeltaResult<()> {
+        let arrow_field = ArrowField::new(
+            "embedding",
+            ArrowDataType::FixedSizeList(
+                Arc::new(ArrowField::new("item", ArrowDataType::Int32, true)),
+                128,
+            ),
+            true,
+        );
+        let struct_field = StructField::try_from(&arrow_field)?;
+        assert_eq!(
+            struct_field.data_type(),
+            &DataType::from(ArrayType::new(DataType::INTEGER, true))
+        );
+        assert_eq!(
+            struct_field
+                .metadata()
+                .get(FIXED_SIZE_LIST_LENGTH_METADATA_KEY),
+            Some(&MetadataValue::Number(128))
+        );
+        assert_eq!(ArrowField::try_from(&struct_field)?, arrow_field);
+        Ok(())
+    }
+
+    #[test]
+    fn test_error_names_nested_field_path() {
+        let arrow_schema = ArrowSchema::new(vec![ArrowField::new_struct(
+            "events",
+            vec![ArrowField::new_struct(
+                "payload",
+                vec![ArrowField::new(
+                    "ts",
+                    ArrowDataType::Duration(TimeUnit::Second),
+                    true,
+                )],
+                true,
+            )],
+            true,
+        )]);
+        let err = StructType::try_from(&arrow_schema).unwrap_err().to_string();
+        assert!(
+            err.starts_with(
+                "Schema error: Invalid data type for Delta Lake at 'events.payload.ts': \
+                 Duration(Second)"
+            ),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn test_unsigned_int_policy() -> DeltaResult<()> {
+        let arrow_schema = ArrowSchema::new(vec![
+            ArrowField::new("u8", ArrowDataType::UInt8, true),
+            ArrowField::new("u16", ArrowDataType::UInt16, true),
+            ArrowField::new("u32", ArrowDataType::UInt32, true),
+            ArrowField::new("u64", ArrowDataType::UInt64, false),
+        ]);
+
+        let schema = StructType::try_from(&arrow_schema)?;
+        let expected = StructType::new([
+            StructField::nullable("u8", DataType::UBYTE),
+            StructField::nullable("u16", DataType::USHORT),
+            StructField::nullable("u32", DataType::UINTEGER),
+            StructField::not_null("u64", DataType::ULONG),
+        ]);
+        assert_eq!(schema, expected);
+
+        let options = ConversionOptions {
+            unsigned_ints: UnsignedIntPolicy::CoerceToSigned,
+            ..Default::default()
+        };
+        let schema = from_arrow_schema_with_options(&arrow_schema, &options)?;
+        let expected = StructType::new([
+            StructField::nullable("u8", DataType::BYTE),
+            StructField::nullable("u16", DataType::SHORT),
+            StructField::nullable("u32", DataType::INTEGER),
+            StructField::not_null("u64", DataType::LONG),
+        ]);
+        assert_eq!(schema, expected);
+        Ok(())
+    }
+
+    #[test]
+    fn test_duration_types() -> DeltaResult<()> {
+        let lenient = ConversionOptions {
+            duration_as_long: true,
+            ..Default::default()
+        };
+        for unit in [
+            TimeUnit::Second,
+            TimeUnit::Millisecond,
+            TimeUnit::Microsecond,
+            TimeUnit::Nanosecond,
+        ] {
+            let arrow_schema = ArrowSchema::new(vec![ArrowField::new(
+                "elapsed",
+                ArrowDataType::Duration(unit),
+                true,
+            )]);
+            let err = StructType::try_from(&arrow_schema).unwrap_err().to_string();
+            assert!(err.contains(&format!("count of {unit:?} units")), "{err}");
+
+            let schema = from_arrow_schema_with_options(&arrow_schema, &lenient)?;
+            assert_eq!(
+                schema,
+                StructType::new([StructField::nullable("elapsed", DataType::LONG)])
+            );
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_nullable_map_key_rejected() {
+        let map_type = MapType::new(ArrayType::new(DataType::STRING, true), DataType::LONG, true);
+        let err = ArrowField::try_from(&map_type).unwrap_err().to_string();
+        assert!(err.contains("Map keys must be non-nullable"), "{err}");
+
+        let entries = ArrowField::new_struct(
+            "key_value",
+            vec![
+                ArrowField::new("key", ArrowDataType::Utf8, true),
+                ArrowField::new("value", ArrowDataType::Int64, true),
+            ],
+            false,
+        );
+        let arrow_field = ArrowField::new("m", ArrowDataType::Map(Arc::new(entries), false), true);
+        let err = StructField::try_from(&arrow_field).unwrap_err().to_string();
+        assert!(err.contains("the key field of 'm' is nullable"), "{err}");
+    }
+
+    #[test]
+    fn test_map_without_struct_child() {
+        let arrow_field = ArrowField::new(
+            "m",
+            ArrowDataType::Map(
+                Arc::new(ArrowField::new("entries", ArrowDataType::Utf8, false)),
+                false,
+            ),
+            true,
+        );
+        let err = StructField::try_from(&arrow_field).unwrap_err().to_string();
+        assert!(
+            err.contains("the map entry child must be a struct"),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn test_map_entry_field_count() {
+        let map_field = |children: Vec<ArrowField>| {
+            let entries = ArrowField::new_struct("key_value", children, false);
+            ArrowField::new("m", ArrowDataType::Map(Arc::new(entries), false), true)
+        };
+        let key = ArrowField::new("key", ArrowDataType::Utf8, false);
+        let value = ArrowField::new("value", ArrowDataType::Int64, true);
+        let extra = ArrowField::new("extra", ArrowDataType::Int64, true);
+        for (children, len) in [(vec![key.clone()], 1), (vec![key, value, extra], 3)] {
+            let err = StructField::try_from(&map_field(children))
+                .unwrap_err()
+                .to_string();
+            assert!(
+                err.contains(&format!(
+                    "must have exactly 2 fields (key and value), found {len}"
+                )),
+                "{err}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_lossy_conversion() -> DeltaResult<()> {
+        let arrow_type = ArrowDataType::Struct(
+            vec![
+                ArrowField::new("id", ArrowDataType::LargeUtf8, false),
+                ArrowField::new(
+                    "period",
+                    ArrowDataType::Interval(IntervalUnit::MonthDayNano),
+                    true,
+                ),
+                ArrowField::new("empty", ArrowDataType::Null, true),
+            ]
+            .into(),
+        );
+        // the strict conversion fails
+        assert!(DataType::try_from(&arrow_type).is_err());
+
+        let (data_type, coercions) = DataType::try_from_arrow_lossy(&arrow_type)?;
+        assert_eq!(
+            data_type,
+            DataType::struct_type([
+                StructField::not_null("id", DataType::STRING),
+                StructField::nullable("period", DataType::STRING),
+                StructField::nullable("empty", DataType::STRING),
+            ])
+        );
+        assert_eq!(
+            coercions,
+            vec![
+                CoercionWarning {
+                    path: "period".to_string(),
+                    arrow_type: ArrowDataType::Interval(IntervalUnit::MonthDayNano),
+                    delta_type: DataType::STRING,
+                },
+                CoercionWarning {
+                    path: "empty".to_string(),
+                    arrow_type: ArrowDataType::Null,
+                    delta_type: DataType::STRING,
+                },
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_dictionary_ordering_roundtrip() -> DeltaResult<()> {
+        let dict_type = ArrowDataType::Dictionary(
+            Box::new(ArrowDataType::Int32),
+            Box::new(ArrowDataType::Utf8),
+        );
+        for ordered in [true, false] {
+            let arrow_field =
+                ArrowField::new("d", dict_type.clone(), false).with_dict_is_ordered(ordered);
+            let struct_field = StructField::try_from(&arrow_field)?;
+            assert_eq!(
+                struct_field.data_type(),
+                &DataType::from(
+                    DictionaryType::new(DataType::INTEGER, DataType::STRING, false)
+                        .with_ordered(ordered)
+                )
+            );
+            let roundtripped = ArrowField::try_from(&struct_field)?;
+            assert_eq!(roundtripped, arrow_field);
+            assert_eq!(roundtripped.dict_is_ordered(), Some(ordered));
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_schema_converter_matches_uncached() -> DeltaResult<()> {
+        let arrow_schema = Arc::new(ArrowSchema::new(vec![
+            ArrowField::new("id", ArrowDataType::Int64, false),
+            ArrowField::new_struct(
+                "nested",
+                vec![ArrowField::new("name", ArrowDataType::Utf8, true)],
+                true,
+            ),
+        ]));
+        let converter = SchemaConverter::default();
+        let expected = StructType::try_from(arrow_schema.as_ref())?;
+        assert_eq!(converter.convert(&arrow_schema)?, expected);
+        // a cache hit, both for the same schema ref and for an equal schema in a new ref
+        assert_eq!(converter.convert(&arrow_schema)?, expected);
+        let equal_schema = Arc::new(arrow_schema.as_ref().clone());
+        assert_eq!(converter.convert(&equal_schema)?, expected);
+        assert_eq!(converter.cache.read().unwrap().len(), 1);
+
+        let other_schema = Arc::new(ArrowSchema::new(vec![ArrowField::new(
+            "id",
+            ArrowDataType::Int32,
+            false,
+        )]));
+        assert_eq!(
+            converter.convert(&other_schema)?,
+            StructType::try_from(other_schema.as_ref())?
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_arrow_extension_metadata_roundtrip() -> DeltaResult<()> {
+        let arrow_field = ArrowField::new("id", ArrowDataType::FixedSizeBinary(16), false)
+            .with_metadata(HashMap::from([
+                ("ARROW:extension:name".to_string(), "arrow.uuid".to_string()),
+                (
+                    "ARROW:extension:metadata".to_string(),
+                    r#"{"origin": 1}"#.to_string(),
+                ),
+            ]));
+        let struct_field = StructField::try_from(&arrow_field)?;
+        assert_eq!(struct_field.arrow_extension_name(), Some("arrow.uuid"));
+
+        let roundtripped = ArrowField::try_from(&struct_field)?;
+        assert_eq!(roundtripped.metadata(), arrow_field.metadata());
+        Ok(())
+    }
+
+    #[test]
+    fn test_invalid_decimal_errors() {
+        let err = DataType::try_from(&ArrowDataType::Decimal128(10, -2))
+            .unwrap_err()
+            .to_string();
+        assert!(
+            err.contains("Decimal128(10, -2): negative scale -2 is not supported in Delta"),
+            "{err}"
+        );
+
+        let err = DataType::try_from(&ArrowDataType::Decimal128(5, 7))
+            .unwrap_err()
+            .to_string();
+        assert!(
+            err.contains("Decimal128(5, 7): scale 7 exceeds precision 5"),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn test_clean_arrow_schema_strips_delta_metadata() -> DeltaResult<()> {
+        let schema =
+            StructType::new([StructField::nullable("id", DataType::LONG).with_metadata([
+                (
+                    ColumnMetadataKey::ColumnMappingId.as_ref(),
+                    MetadataValue::Number(3),
+                ),
+                (
+                    ColumnMetadataKey::ColumnMappingPhysicalName.as_ref(),
+                    MetadataValue::String("col-1".to_string()),
+                ),
+                ("comment", MetadataValue::String("the id".to_string())),
+            ])]);
+        let arrow_schema = to_arrow_schema_clean(&schema)?;
+        assert_eq!(
+            arrow_schema.field(0).metadata(),
+            &HashMap::from([("comment".to_string(), "the id".to_string())])
+        );
+
+        // the default conversion keeps them, next to the parquet field id
+        let arrow_schema = ArrowSchema::try_from(&schema)?;
+        let metadata = arrow_schema.field(0).metadata();
+        assert!(metadata.contains_key(ColumnMetadataKey::ColumnMappingId.as_ref()));
+        assert!(metadata.contains_key(ColumnMetadataKey::ColumnMappingPhysicalName.as_ref()));
+        assert_eq!(
+            metadata.get(PARQUET_FIELD_ID_META_KEY),
+            Some(&"3".to_string())
+        );
+        Ok(())
+    }
 }
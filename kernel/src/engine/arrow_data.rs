@@ -1,16 +1,25 @@
 use crate::engine_data::{EngineData, EngineList, EngineMap, GetData, RowVisitor};
-use crate::schema::{ColumnName, DataType};
+use crate::schema::{ColumnName, DataType, PrimitiveType, StructType};
 use crate::{DeltaResult, Error};
 
 use crate::arrow::array::cast::AsArray;
-use crate::arrow::array::types::{Int32Type, Int64Type};
+use crate::arrow::array::types::{
+    ArrowPrimitiveType, Date32Type, Decimal128Type, Float32Type, Float64Type, Int16Type, Int32Type,
+    Int64Type, Int8Type, TimestampMicrosecondType, TimestampMillisecondType,
+    TimestampNanosecondType, TimestampSecondType, UInt16Type, UInt32Type, UInt64Type, UInt8Type,
+};
 use crate::arrow::array::{
-    Array, ArrayRef, GenericListArray, MapArray, OffsetSizeTrait, RecordBatch, StructArray,
+    make_array, Array, ArrayRef, GenericListArray, MapArray, OffsetSizeTrait, PrimitiveArray,
+    RecordBatch, StringArray, StructArray,
 };
-use crate::arrow::datatypes::{DataType as ArrowDataType, FieldRef};
+use crate::arrow::buffer::NullBuffer;
+use crate::arrow::compute;
+use crate::arrow::datatypes::{DataType as ArrowDataType, FieldRef, TimeUnit};
+use crate::arrow::util::display::array_value_to_string;
 use tracing::debug;
 
 use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
 
 pub use crate::engine::arrow_utils::fix_nested_null_masks;
 
@@ -29,6 +38,18 @@ impl ArrowEngineData {
         ArrowEngineData { data }
     }
 
+    /// Wrap `batch` as kernel [`EngineData`], e.g. to pass it to kernel APIs from an engine built
+    /// on arrow. This does not check that the schema of `batch` can be converted to a Delta
+    /// schema; use `ArrowEngineData::try_from(&batch)` for that.
+    pub fn from_record_batch(batch: RecordBatch) -> Self {
+        Self::new(batch)
+    }
+
+    /// Unwrap the `RecordBatch` this `ArrowEngineData` is wrapping
+    pub fn into_record_batch(self) -> RecordBatch {
+        self.data
+    }
+
     /// Utility constructor to get a `Box<ArrowEngineData>` out of a `Box<dyn EngineData>`
     pub fn try_from_engine_data(engine_data: Box<dyn EngineData>) -> DeltaResult<Box<Self>> {
         engine_data
@@ -49,6 +70,18 @@ impl From<RecordBatch> for ArrowEngineData {
     }
 }
 
+/// Wraps a clone of the batch, after checking that its schema can be converted to a Delta schema.
+/// Unlike the infallible `From<RecordBatch>` conversion, this fails for batches with columns of
+/// arrow types that Delta has no equivalent for, such as intervals.
+impl TryFrom<&RecordBatch> for ArrowEngineData {
+    type Error = Error;
+
+    fn try_from(batch: &RecordBatch) -> DeltaResult<Self> {
+        StructType::try_from(batch.schema_ref().as_ref())?;
+        Ok(Self::new(batch.clone()))
+    }
+}
+
 impl From<StructArray> for ArrowEngineData {
     fn from(value: StructArray) -> Self {
         ArrowEngineData::new(value.into())
@@ -292,14 +325,386 @@ impl ArrowEngineData {
     }
 }
 
+/// Number of characters kept in string min/max statistics, as with Delta's default
+/// `delta.dataSkippingStringPrefixLength`.
+const STATS_STRING_PREFIX_LENGTH: usize = 32;
+
+/// Compute the `stats` of an add action for a data file containing `batch`, as the JSON string
+/// Delta expects: `numRecords`, followed by `minValues`, `maxValues` and `nullCount` for the first
+/// `num_indexed_cols` leaf columns of `schema` (pass `usize::MAX` to index every column).
+///
+/// Null counts are collected for every indexed column, but min/max values only for types that
+/// support data skipping (numbers, dates, timestamps and strings). As in Delta, string bounds are
+/// truncated to 32 characters (padding a truncated max so it still bounds the real value), and
+/// timestamp bounds are widened to millisecond precision.
+pub fn compute_stats(
+    batch: &RecordBatch,
+    schema: &StructType,
+    num_indexed_cols: usize,
+) -> DeltaResult<String> {
+    let mut collector = StatsCollector {
+        remaining: num_indexed_cols,
+        path: vec![],
+    };
+    let stats = collector.collect_struct(schema, batch, None)?;
+    let mut json = format!("{{\"numRecords\":{}", batch.num_rows());
+    for (name, values) in [
+        ("minValues", stats.min_values),
+        ("maxValues", stats.max_values),
+        ("nullCount", stats.null_count),
+    ] {
+        json.push_str(&format!(",\"{name}\":"));
+        StatsJson::Object(values).write(&mut json);
+    }
+    json.push('}');
+    Ok(json)
+}
+
+// Stats values are pre-rendered json (so that e.g. decimals keep their exact representation),
+// nested in objects that keep the schema's field order.
+enum StatsJson {
+    Value(String),
+    Object(Vec<(String, StatsJson)>),
+}
+
+impl StatsJson {
+    fn write(&self, out: &mut String) {
+        match self {
+            StatsJson::Value(value) => out.push_str(value),
+            StatsJson::Object(fields) => {
+                out.push('{');
+                for (i, (name, value)) in fields.iter().enumerate() {
+                    if i > 0 {
+                        out.push(',');
+                    }
+                    out.push_str(&json_string(name));
+                    out.push(':');
+                    value.write(out);
+                }
+                out.push('}');
+            }
+        }
+    }
+}
+
+#[derive(Default)]
+struct StructStats {
+    min_values: Vec<(String, StatsJson)>,
+    max_values: Vec<(String, StatsJson)>,
+    null_count: Vec<(String, StatsJson)>,
+}
+
+struct StatsCollector {
+    // how many more leaf columns may be indexed
+    remaining: usize,
+    path: Vec<String>,
+}
+
+impl StatsCollector {
+    fn collect_struct(
+        &mut self,
+        schema: &StructType,
+        data: &dyn ProvidesColumnsAndFields,
+        parent_nulls: Option<&NullBuffer>,
+    ) -> DeltaResult<StructStats> {
+        let mut stats = StructStats::default();
+        for field in schema.fields() {
+            if self.remaining == 0 {
+                break;
+            }
+            self.path.push(field.name().clone());
+            let index = data
+                .fields()
+                .iter()
+                .position(|f| f.name() == field.name())
+                .ok_or_else(|| Error::missing_column(ColumnName::new(&self.path)))?;
+            let column = &data.columns()[index];
+            // a value is null if it, or any of the structs containing it, is null
+            let nulls = NullBuffer::union(parent_nulls, column.logical_nulls().as_ref());
+            let name = field.name().clone();
+            if let DataType::Struct(struct_type) = field.data_type() {
+                let struct_array = column.as_struct_opt().ok_or_else(|| {
+                    Error::UnexpectedColumnType(format!(
+                        "Type mismatch on {}: expected struct, got {}",
+                        ColumnName::new(&self.path),
+                        column.data_type()
+                    ))
+                })?;
+                let child = self.collect_struct(struct_type, struct_array, nulls.as_ref())?;
+                for (values, child_values) in [
+                    (&mut stats.min_values, child.min_values),
+                    (&mut stats.max_values, child.max_values),
+                    (&mut stats.null_count, child.null_count),
+                ] {
+                    if !child_values.is_empty() {
+                        values.push((name.clone(), StatsJson::Object(child_values)));
+                    }
+                }
+            } else {
+                self.remaining -= 1;
+                let null_count = nulls.as_ref().map_or(0, |nulls| nulls.null_count());
+                let skippable = matches!(
+                    field.data_type(),
+                    DataType::Primitive(ptype)
+                        if !matches!(ptype, PrimitiveType::Boolean | PrimitiveType::Binary)
+                );
+                if skippable {
+                    if let Some(min) = column_bound(column, nulls.as_ref(), false)? {
+                        stats.min_values.push((name.clone(), StatsJson::Value(min)));
+                    }
+                    if let Some(max) = column_bound(column, nulls.as_ref(), true)? {
+                        stats.max_values.push((name.clone(), StatsJson::Value(max)));
+                    }
+                }
+                let null_count = StatsJson::Value(null_count.to_string());
+                stats.null_count.push((name, null_count));
+            }
+            self.path.pop();
+        }
+        Ok(stats)
+    }
+}
+
+/// The min (or max) non-null value of `column` rendered as json, or None if it has no such value
+/// or its type has no min/max statistics.
+fn column_bound(
+    column: &dyn Array,
+    nulls: Option<&NullBuffer>,
+    max: bool,
+) -> DeltaResult<Option<String>> {
+    // the aggregate kernels only see the column's own nulls, so apply those of its ancestors too
+    let masked;
+    let column = match nulls {
+        Some(nulls) if column.nulls() != Some(nulls) => {
+            let data = column.to_data().into_builder().nulls(Some(nulls.clone()));
+            masked = make_array(data.build()?);
+            masked.as_ref()
+        }
+        _ => column,
+    };
+    use ArrowDataType::*;
+    let bound = match column.data_type() {
+        Int8 => primitive_bound::<Int8Type>(column, max, |_| true),
+        Int16 => primitive_bound::<Int16Type>(column, max, |_| true),
+        Int32 => primitive_bound::<Int32Type>(column, max, |_| true),
+        Int64 => primitive_bound::<Int64Type>(column, max, |_| true),
+        UInt8 => primitive_bound::<UInt8Type>(column, max, |_| true),
+        UInt16 => primitive_bound::<UInt16Type>(column, max, |_| true),
+        UInt32 => primitive_bound::<UInt32Type>(column, max, |_| true),
+        UInt64 => primitive_bound::<UInt64Type>(column, max, |_| true),
+        Float32 => primitive_bound::<Float32Type>(column, max, f32::is_finite),
+        Float64 => primitive_bound::<Float64Type>(column, max, f64::is_finite),
+        Decimal128(..) => primitive_bound::<Decimal128Type>(column, max, |_| true),
+        Date32 => primitive_bound::<Date32Type>(column, max, |_| true),
+        Timestamp(TimeUnit::Second, _) => {
+            primitive_bound::<TimestampSecondType>(column, max, |_| true)
+        }
+        Timestamp(TimeUnit::Millisecond, _) => {
+            primitive_bound::<TimestampMillisecondType>(column, max, |_| true)
+        }
+        Timestamp(TimeUnit::Microsecond, _) => {
+            primitive_bound::<TimestampMicrosecondType>(column, max, |_| true)
+        }
+        Timestamp(TimeUnit::Nanosecond, _) => {
+            primitive_bound::<TimestampNanosecondType>(column, max, |_| true)
+        }
+        Utf8 | LargeUtf8 | Utf8View => {
+            let value = match (column.data_type(), max) {
+                (Utf8, false) => compute::min_string(column.as_string::<i32>()),
+                (Utf8, true) => compute::max_string(column.as_string::<i32>()),
+                (LargeUtf8, false) => compute::min_string(column.as_string::<i64>()),
+                (LargeUtf8, true) => compute::max_string(column.as_string::<i64>()),
+                (_, false) => compute::min_string_view(column.as_string_view()),
+                (_, true) => compute::max_string_view(column.as_string_view()),
+            };
+            value.map(|value| Arc::new(StringArray::from(vec![value])) as ArrayRef)
+        }
+        _ => None,
+    };
+    match bound {
+        Some(bound) => render_bound(&bound, 0, max),
+        None => Ok(None),
+    }
+}
+
+/// The min (or max) value of a primitive `column` as a single-element array of the same type,
+/// skipping values for which `is_finite` is false since they have no json representation.
+fn primitive_bound<T: ArrowPrimitiveType>(
+    column: &dyn Array,
+    max: bool,
+    is_finite: impl Fn(T::Native) -> bool,
+) -> Option<ArrayRef> {
+    let column = column.as_primitive::<T>();
+    let mut bound = if max {
+        compute::max(column)
+    } else {
+        compute::min(column)
+    }?;
+    // NaN orders above every other float, so only scan the values when it (or an infinity) won
+    if !is_finite(bound) {
+        bound = column
+            .iter()
+            .flatten()
+            .filter(|value| is_finite(*value))
+            .reduce(|a, b| if (b > a) == max { b } else { a })?;
+    }
+    let bound =
+        PrimitiveArray::<T>::from_value(bound, 1).with_data_type(column.data_type().clone());
+    Some(Arc::new(bound))
+}
+
+fn render_bound(column: &dyn Array, index: usize, max: bool) -> DeltaResult<Option<String>> {
+    let value = match column.data_type() {
+        ArrowDataType::Float32 => {
+            let value = column.as_primitive::<Float32Type>().value(index);
+            value.is_finite().then(|| value.to_string())
+        }
+        ArrowDataType::Float64 => {
+            let value = column.as_primitive::<Float64Type>().value(index);
+            value.is_finite().then(|| value.to_string())
+        }
+        ArrowDataType::Date32 => Some(json_string(&array_value_to_string(column, index)?)),
+        ArrowDataType::Timestamp(unit, tz) => {
+            let (value, per_milli) = match unit {
+                TimeUnit::Second => {
+                    let value = column.as_primitive::<TimestampSecondType>().value(index);
+                    let millis = value.checked_mul(1000).ok_or_else(|| {
+                        Error::generic(format!("Timestamp out of range: {value}"))
+                    })?;
+                    (millis, 1)
+                }
+                TimeUnit::Millisecond => (
+                    column
+                        .as_primitive::<TimestampMillisecondType>()
+                        .value(index),
+                    1,
+                ),
+                TimeUnit::Microsecond => (
+                    column
+                        .as_primitive::<TimestampMicrosecondType>()
+                        .value(index),
+                    1_000,
+                ),
+                TimeUnit::Nanosecond => (
+                    column
+                        .as_primitive::<TimestampNanosecondType>()
+                        .value(index),
+                    1_000_000,
+                ),
+            };
+            // round outwards, so that the bound still holds at millisecond precision
+            let millis = value.div_euclid(per_milli) + i64::from(max && value % per_milli != 0);
+            let timestamp = chrono::DateTime::from_timestamp_millis(millis)
+                .ok_or_else(|| Error::generic(format!("Timestamp out of range: {value}")))?;
+            let suffix = if tz.is_some() { "Z" } else { "" };
+            let timestamp = timestamp.format("%Y-%m-%dT%H:%M:%S%.3f");
+            Some(json_string(&format!("{timestamp}{suffix}")))
+        }
+        ArrowDataType::Utf8 | ArrowDataType::LargeUtf8 | ArrowDataType::Utf8View => {
+            let value = match column.data_type() {
+                ArrowDataType::Utf8 => column.as_string::<i32>().value(index),
+                ArrowDataType::LargeUtf8 => column.as_string::<i64>().value(index),
+                _ => column.as_string_view().value(index),
+            };
+            let mut prefix: String = value.chars().take(STATS_STRING_PREFIX_LENGTH).collect();
+            if max && prefix.len() < value.len() {
+                // the largest code point makes the truncated max still sort after the value
+                prefix.push(char::MAX);
+            }
+            Some(json_string(&prefix))
+        }
+        // integers and decimals render as json numbers
+        _ => Some(array_value_to_string(column, index)?),
+    };
+    Ok(value)
+}
+
+fn json_string(value: &str) -> String {
+    serde_json::Value::from(value).to_string()
+}
+
 #[cfg(test)]
 mod tests {
+    use std::sync::Arc;
+
     use crate::actions::{get_log_schema, Metadata, Protocol};
-    use crate::arrow::array::StringArray;
+    use crate::arrow::array::types::Int64Type;
+    use crate::arrow::array::{
+        ArrayRef, AsArray, BinaryArray, BooleanArray, Date32Array, Decimal128Array, Float64Array,
+        Int32Array, Int64Array, IntervalYearMonthArray, RecordBatch, StringArray, StringViewArray,
+        StructArray, TimestampMicrosecondArray, TimestampSecondArray,
+    };
+    use crate::arrow::buffer::NullBuffer;
+    use crate::arrow::datatypes::{
+        DataType as ArrowDataType, Field, Fields, IntervalUnit, Schema, TimeUnit,
+    };
     use crate::engine::sync::SyncEngine;
+    use crate::schema::{DataType, StructField, StructType};
     use crate::table_features::{ReaderFeature, WriterFeature};
     use crate::utils::test_utils::string_array_to_engine_data;
-    use crate::{DeltaResult, Engine as _};
+    use crate::{DeltaResult, Engine as _, EngineData as _};
+
+    use super::{compute_stats, ArrowEngineData};
+
+    #[test]
+    fn test_record_batch_conversions() -> DeltaResult<()> {
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("id", ArrowDataType::Int32, false),
+            Field::new("s", ArrowDataType::Utf8, true),
+        ]));
+        let batch = RecordBatch::try_new(
+            schema,
+            vec![
+                Arc::new(Int32Array::from(vec![1, 2])),
+                Arc::new(StringArray::from(vec![Some("a"), None])),
+            ],
+        )?;
+
+        let data = ArrowEngineData::from_record_batch(batch.clone());
+        assert_eq!(data.len(), 2);
+        assert_eq!(data.into_record_batch(), batch);
+
+        let data = ArrowEngineData::try_from(&batch)?;
+        assert_eq!(data.record_batch(), &batch);
+
+        // intervals have no Delta equivalent
+        let schema = Arc::new(Schema::new(vec![Field::new(
+            "interval",
+            ArrowDataType::Interval(IntervalUnit::YearMonth),
+            true,
+        )]));
+        let batch = RecordBatch::try_new(
+            schema,
+            vec![Arc::new(IntervalYearMonthArray::from(vec![12]))],
+        )?;
+        assert!(ArrowEngineData::try_from(&batch).is_err());
+        // wrapping without validation still works
+        assert_eq!(ArrowEngineData::from_record_batch(batch).len(), 1);
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_json_string_views() -> DeltaResult<()> {
+        let engine = SyncEngine::new();
+        let stats = StringViewArray::from(vec![Some(r#"{"numRecords":3}"#), None]);
+        let schema = Arc::new(Schema::new(vec![Field::new(
+            "stats",
+            ArrowDataType::Utf8View,
+            true,
+        )]));
+        let batch = RecordBatch::try_new(schema, vec![Arc::new(stats)])?;
+        let output_schema = Arc::new(StructType::new([StructField::nullable(
+            "numRecords",
+            DataType::LONG,
+        )]));
+        let parsed = engine
+            .json_handler()
+            .parse_json(Box::new(ArrowEngineData::new(batch)), output_schema)?;
+        let parsed = ArrowEngineData::try_from_engine_data(parsed)?;
+        let num_records = parsed.record_batch().column(0).as_primitive::<Int64Type>();
+        assert_eq!(num_records.iter().collect::<Vec<_>>(), [Some(3), None]);
+        Ok(())
+    }
 
     #[test]
     fn test_md_extract() -> DeltaResult<()> {
@@ -345,4 +750,128 @@ mod tests {
         );
         Ok(())
     }
+
+    fn stats_test_batch() -> (RecordBatch, StructType) {
+        let nested_fields = Fields::from(vec![
+            Field::new("x", ArrowDataType::Int32, true),
+            Field::new("y", ArrowDataType::Float64, true),
+        ]);
+        let nested = StructArray::new(
+            nested_fields.clone(),
+            vec![
+                Arc::new(Int32Array::from(vec![Some(7), Some(-3), Some(100)])),
+                Arc::new(Float64Array::from(vec![f64::NAN, 1.5, 2.25])),
+            ],
+            // the struct is null in the last row, so its children don't count there
+            Some(NullBuffer::from(vec![true, true, false])),
+        );
+        let long_string = "abcdefghijklmnopqrstuvwxyz0123456789";
+        let arrow_schema = Arc::new(Schema::new(vec![
+            Field::new("id", ArrowDataType::Int64, false),
+            Field::new("name", ArrowDataType::Utf8, true),
+            Field::new("price", ArrowDataType::Decimal128(5, 2), true),
+            Field::new("date", ArrowDataType::Date32, true),
+            Field::new(
+                "ts",
+                ArrowDataType::Timestamp(TimeUnit::Microsecond, Some("UTC".into())),
+                true,
+            ),
+            Field::new("flag", ArrowDataType::Boolean, true),
+            Field::new("bin", ArrowDataType::Binary, true),
+            Field::new("nested", ArrowDataType::Struct(nested_fields), true),
+        ]));
+        let columns: Vec<ArrayRef> = vec![
+            Arc::new(Int64Array::from(vec![3, 1, 2])),
+            Arc::new(StringArray::from(vec![Some("b"), None, Some(long_string)])),
+            Arc::new(
+                Decimal128Array::from(vec![Some(1234), Some(-5), None])
+                    .with_precision_and_scale(5, 2)
+                    .unwrap(),
+            ),
+            Arc::new(Date32Array::from(vec![Some(18628), Some(0), None])),
+            Arc::new(
+                TimestampMicrosecondArray::from(vec![Some(1_609_459_201_500_100), Some(0), None])
+                    .with_timezone("UTC"),
+            ),
+            Arc::new(BooleanArray::from(vec![Some(true), None, Some(false)])),
+            Arc::new(BinaryArray::from(vec![Some(b"a".as_ref()), None, None])),
+            Arc::new(nested),
+        ];
+        let batch = RecordBatch::try_new(arrow_schema, columns).unwrap();
+        let schema = StructType::new([
+            StructField::not_null("id", DataType::LONG),
+            StructField::nullable("name", DataType::STRING),
+            StructField::nullable("price", DataType::decimal(5, 2).unwrap()),
+            StructField::nullable("date", DataType::DATE),
+            StructField::nullable("ts", DataType::TIMESTAMP),
+            StructField::nullable("flag", DataType::BOOLEAN),
+            StructField::nullable("bin", DataType::BINARY),
+            StructField::nullable(
+                "nested",
+                StructType::new([
+                    StructField::nullable("x", DataType::INTEGER),
+                    StructField::nullable("y", DataType::DOUBLE),
+                ]),
+            ),
+        ]);
+        (batch, schema)
+    }
+
+    #[test]
+    fn test_compute_stats() -> DeltaResult<()> {
+        let (batch, schema) = stats_test_batch();
+        let stats = compute_stats(&batch, &schema, usize::MAX)?;
+        let expected = concat!(
+            r#"{"numRecords":3,"#,
+            r#""minValues":{"id":1,"name":"abcdefghijklmnopqrstuvwxyz012345","price":-0.05,"#,
+            r#""date":"1970-01-01","ts":"1970-01-01T00:00:00.000Z","nested":{"x":-3,"y":1.5}},"#,
+            r#""maxValues":{"id":3,"name":"b","price":12.34,"date":"2021-01-01","#,
+            r#""ts":"2021-01-01T00:00:01.501Z","nested":{"x":7,"y":1.5}},"#,
+            r#""nullCount":{"id":0,"name":1,"price":1,"date":1,"ts":1,"flag":1,"bin":2,"#,
+            r#""nested":{"x":1,"y":1}}}"#,
+        );
+        assert_eq!(stats, expected);
+        // the output is valid json
+        serde_json::from_str::<serde_json::Value>(&stats).unwrap();
+        Ok(())
+    }
+
+    #[test]
+    fn test_compute_stats_limits() -> DeltaResult<()> {
+        let (batch, schema) = stats_test_batch();
+
+        // only the first two leaf columns are indexed
+        let stats = compute_stats(&batch, &schema, 2)?;
+        let expected = concat!(
+            r#"{"numRecords":3,"#,
+            r#""minValues":{"id":1,"name":"abcdefghijklmnopqrstuvwxyz012345"},"#,
+            r#""maxValues":{"id":3,"name":"b"},"nullCount":{"id":0,"name":1}}"#,
+        );
+        assert_eq!(stats, expected);
+
+        // a truncated string max is padded so that it still bounds the real value
+        let names = StringArray::from(vec!["abcdefghijklmnopqrstuvwxyz0123456789"]);
+        let batch = RecordBatch::try_from_iter([("name", Arc::new(names) as ArrayRef)])?;
+        let schema = StructType::new([StructField::nullable("name", DataType::STRING)]);
+        let stats = compute_stats(&batch, &schema, usize::MAX)?;
+        let stats: serde_json::Value = serde_json::from_str(&stats).unwrap();
+        let max = stats["maxValues"]["name"].as_str().unwrap();
+        assert_eq!(max, "abcdefghijklmnopqrstuvwxyz012345\u{10FFFF}");
+        assert!(max > "abcdefghijklmnopqrstuvwxyz0123456789");
+
+        // no indexed columns
+        let stats = compute_stats(&batch, &schema, 0)?;
+        assert_eq!(
+            stats,
+            r#"{"numRecords":1,"minValues":{},"maxValues":{},"nullCount":{}}"#
+        );
+
+        // seconds that overflow when converted to milliseconds are an error, not a wrapped bound
+        let ts = TimestampSecondArray::from(vec![i64::MAX]);
+        let batch = RecordBatch::try_from_iter([("ts", Arc::new(ts) as ArrayRef)])?;
+        let schema = StructType::new([StructField::nullable("ts", DataType::TIMESTAMP_NTZ)]);
+        let err = compute_stats(&batch, &schema, usize::MAX).unwrap_err();
+        assert!(err.to_string().contains("Timestamp out of range"));
+        Ok(())
+    }
 }
@@ -7,13 +7,16 @@ use itertools::Itertools;
 use crate::arrow::array::{
     Array, ArrayRef, AsArray, ListArray, MapArray, RecordBatch, StructArray,
 };
+use crate::arrow::compute::cast;
 use crate::arrow::datatypes::Schema as ArrowSchema;
 use crate::arrow::datatypes::{DataType as ArrowDataType, Field as ArrowField};
 
 use super::super::arrow_utils::make_arrow_error;
+use crate::engine::arrow_conversion::string_view_type;
 use crate::engine::ensure_data_types::ensure_data_types;
 use crate::error::{DeltaResult, Error};
 use crate::schema::{ArrayType, DataType, MapType, Schema, StructField};
+use crate::STRING_VIEW_METADATA_KEY;
 
 // Apply a schema to an array. The array _must_ be a `StructArray`. Returns a `RecordBatch where the
 // names of fields, nullable, and metadata in the struct have been transformed to match those in
@@ -72,12 +75,20 @@ fn transform_struct(
             .zip(target_fields)
             .map(|(sa_col, target_field)| -> DeltaResult<_> {
                 let target_field = target_field.borrow();
-                let transformed_col = apply_schema_to(&sa_col, target_field.data_type())?;
+                let mut transformed_col = apply_schema_to(&sa_col, target_field.data_type())?;
+                let mut metadata = target_field.metadata_with_string_values();
+                // columns that weren't read as views, e.g. partition values, are cast to views
+                if metadata.remove(STRING_VIEW_METADATA_KEY).as_deref() == Some("true") {
+                    let view_type = string_view_type(transformed_col.data_type());
+                    if &view_type != transformed_col.data_type() {
+                        transformed_col = cast(&transformed_col, &view_type)?;
+                    }
+                }
                 let transformed_field = new_field_with_metadata(
                     &target_field.name,
                     transformed_col.data_type(),
                     target_field.nullable,
-                    Some(target_field.metadata_with_string_values()),
+                    Some(metadata),
                 );
                 Ok((transformed_field, transformed_col))
             });
@@ -0,0 +1,93 @@
+//! Compiles kernel predicates into parquet [`ArrowPredicate`]s, so that engines with their own
+//! parquet readers can push a scan's predicate down into the reader as a [`RowFilter`].
+//!
+//! [`RowFilter`]: crate::parquet::arrow::arrow_reader::RowFilter
+use std::collections::BTreeSet;
+
+use crate::arrow::array::{AsArray, BooleanArray};
+use crate::arrow::datatypes::Schema as ArrowSchema;
+use crate::arrow::error::ArrowError;
+use crate::expressions::{
+    BinaryExpression, BinaryOperator, Expression, JunctionExpression, UnaryExpression,
+};
+use crate::parquet::arrow::arrow_reader::{ArrowPredicate, ArrowPredicateFn};
+use crate::parquet::arrow::{ArrowSchemaConverter, ProjectionMask};
+use crate::schema::DataType;
+use crate::{DeltaResult, Error};
+
+use super::evaluate_expression::evaluate_expression;
+
+/// Compile `predicate` into an [`ArrowPredicate`] for a parquet file whose arrow schema is
+/// `schema` (e.g. [`ArrowReaderBuilder::schema`]). The predicate only decodes the top-level
+/// columns it references, and keeps the rows for which it evaluates to true (NULL rows are
+/// dropped, matching SQL `WHERE` semantics).
+///
+/// Supported nodes are column references, literals, comparisons (including `DISTINCT`, `IN` and
+/// `NOT IN`), `AND`, `OR`, `NOT` and `IS NULL`. Any other node (e.g. arithmetic), or a column that
+/// does not exist in `schema`, is an error rather than being silently dropped from the filter.
+///
+/// [`ArrowReaderBuilder::schema`]: crate::parquet::arrow::arrow_reader::ArrowReaderBuilder::schema
+pub fn to_arrow_predicate(
+    predicate: &Expression,
+    schema: &ArrowSchema,
+) -> DeltaResult<Box<dyn ArrowPredicate>> {
+    let mut root_indices = BTreeSet::new();
+    collect_root_columns(predicate, schema, &mut root_indices)?;
+    let parquet_schema = ArrowSchemaConverter::new().convert(schema)?;
+    let projection = ProjectionMask::roots(&parquet_schema, root_indices);
+
+    let predicate = predicate.clone();
+    let evaluate = move |batch| -> Result<BooleanArray, ArrowError> {
+        let result = evaluate_expression(&predicate, &batch, Some(&DataType::BOOLEAN))
+            .map_err(|err| ArrowError::ExternalError(Box::new(err)))?;
+        result.as_boolean_opt().cloned().ok_or_else(|| {
+            ArrowError::ComputeError(format!(
+                "Predicate evaluated to {} instead of a boolean",
+                result.data_type()
+            ))
+        })
+    };
+    Ok(Box::new(ArrowPredicateFn::new(projection, evaluate)))
+}
+
+/// Check that every node of `expr` can be pushed down, collecting the index in `schema` of each
+/// top-level column it references.
+fn collect_root_columns(
+    expr: &Expression,
+    schema: &ArrowSchema,
+    root_indices: &mut BTreeSet<usize>,
+) -> DeltaResult<()> {
+    use BinaryOperator::*;
+    match expr {
+        Expression::Literal(_) => Ok(()),
+        Expression::Column(name) => {
+            let root = name
+                .first()
+                .ok_or_else(|| Error::generic("Cannot push down an empty column name"))?;
+            let (index, _) = schema
+                .column_with_name(root)
+                .ok_or_else(|| Error::missing_column(name.to_string()))?;
+            root_indices.insert(index);
+            Ok(())
+        }
+        Expression::Unary(UnaryExpression { expr, .. }) => {
+            collect_root_columns(expr, schema, root_indices)
+        }
+        Expression::Binary(BinaryExpression { op, left, right }) => match op {
+            LessThan | LessThanOrEqual | GreaterThan | GreaterThanOrEqual | Equal | NotEqual
+            | Distinct | In | NotIn => {
+                collect_root_columns(left, schema, root_indices)?;
+                collect_root_columns(right, schema, root_indices)
+            }
+            Plus | Minus | Multiply | Divide => Err(Error::unsupported(format!(
+                "Cannot push down arithmetic expression {expr}"
+            ))),
+        },
+        Expression::Junction(JunctionExpression { exprs, .. }) => exprs
+            .iter()
+            .try_for_each(|expr| collect_root_columns(expr, schema, root_indices)),
+        Expression::Struct(_) => Err(Error::unsupported(format!(
+            "Cannot push down struct expression {expr}"
+        ))),
+    }
+}
@@ -1,12 +1,12 @@
 //! Expression handling based on arrow-rs compute kernels.
 use crate::arrow::array::types::*;
 use crate::arrow::array::{
-    Array, ArrayRef, AsArray, BooleanArray, Datum, RecordBatch, StructArray,
+    Array, ArrayRef, AsArray, BooleanArray, Datum, RecordBatch, Scalar as ArrowScalar, StructArray,
 };
 use crate::arrow::compute::kernels::cmp::{distinct, eq, gt, gt_eq, lt, lt_eq, neq};
 use crate::arrow::compute::kernels::comparison::in_list_utf8;
 use crate::arrow::compute::kernels::numeric::{add, div, mul, sub};
-use crate::arrow::compute::{and_kleene, is_null, not, or_kleene};
+use crate::arrow::compute::{and_kleene, cast, is_null, not, or_kleene};
 use crate::arrow::datatypes::{
     DataType as ArrowDataType, Field as ArrowField, IntervalUnit, TimeUnit,
 };
@@ -31,6 +31,25 @@ fn wrap_comparison_result(arr: BooleanArray) -> ArrayRef {
     Arc::new(arr) as _
 }
 
+// Arrow's comparison kernels require both sides to have the same type, so when one side is a
+// string or binary view (e.g. a column read with views) and the other is the corresponding plain
+// type (e.g. a literal), cast the plain side to the view type.
+fn align_view_types(left: ArrayRef, right: ArrayRef) -> DeltaResult<(ArrayRef, ArrayRef)> {
+    use ArrowDataType::*;
+    let view_type = |plain: &ArrowDataType, other: &ArrowDataType| match (plain, other) {
+        (Utf8 | LargeUtf8, Utf8View) => Some(Utf8View),
+        (Binary | LargeBinary, BinaryView) => Some(BinaryView),
+        _ => None,
+    };
+    if let Some(target) = view_type(left.data_type(), right.data_type()) {
+        Ok((cast(&left, &target)?, right))
+    } else if let Some(target) = view_type(right.data_type(), left.data_type()) {
+        Ok((left, cast(&right, &target)?))
+    } else {
+        Ok((left, right))
+    }
+}
+
 trait ProvidesColumnByName {
     fn column_by_name(&self, name: &str) -> Option<&ArrayRef>;
 }
@@ -174,6 +193,24 @@ pub(crate) fn evaluate_expression(
                     (ArrowDataType::Decimal256(_, _), Decimal256Type)
                 }
             }
+            (Column(_), Literal(Scalar::Array(ad))) => {
+                // `col IN (v1, v2, ...)` is `col = v1 OR col = v2 OR ...`, which also produces the
+                // SQL result for NULLs (NULL unless some element matches)
+                let left_arr = evaluate_expression(left.as_ref(), batch, None)?;
+                #[allow(deprecated)]
+                let elements = ad.array_elements();
+                let no_match = BooleanArray::from(vec![false; left_arr.len()]);
+                let result =
+                    elements
+                        .iter()
+                        .try_fold(no_match, |acc, element| -> DeltaResult<_> {
+                            let (left_arr, element) =
+                                align_view_types(left_arr.clone(), element.to_array(1)?)?;
+                            let element = ArrowScalar::new(element);
+                            Ok(or_kleene(&acc, &eq(&left_arr, &element)?)?)
+                        })?;
+                Ok(wrap_comparison_result(result))
+            }
             (Literal(lit), Literal(Scalar::Array(ad))) => {
                 #[allow(deprecated)]
                 let exists = ad.array_elements().contains(lit);
@@ -199,6 +236,7 @@ pub(crate) fn evaluate_expression(
         (Binary(BinaryExpression { op, left, right }), _) => {
             let left_arr = evaluate_expression(left.as_ref(), batch, None)?;
             let right_arr = evaluate_expression(right.as_ref(), batch, None)?;
+            let (left_arr, right_arr) = align_view_types(left_arr, right_arr)?;
 
             type Operation = fn(&dyn Datum, &dyn Datum) -> Result<ArrayRef, ArrowError>;
             let eval: Operation = match op {
@@ -1,9 +1,9 @@
 //! Expression handling based on arrow-rs compute kernels.
 use crate::arrow::array::{
-    Array, ArrayRef, BinaryArray, BooleanArray, Date32Array, Decimal128Array, Float32Array,
-    Float64Array, Int16Array, Int32Array, Int64Array, Int8Array, ListArray, RecordBatch,
-    StringArray, StructArray, TimestampMicrosecondArray, TimestampNanosecondArray, UInt16Array,
-    UInt32Array, UInt64Array, UInt8Array,
+    new_null_array, Array, ArrayRef, BinaryArray, BooleanArray, Date32Array, Decimal128Array,
+    Float32Array, Float64Array, Int16Array, Int32Array, Int64Array, Int8Array, ListArray,
+    RecordBatch, StringArray, StructArray, TimestampMicrosecondArray, TimestampNanosecondArray,
+    UInt16Array, UInt32Array, UInt64Array, UInt8Array,
 };
 use crate::arrow::buffer::OffsetBuffer;
 use crate::arrow::compute::concat;
@@ -25,7 +25,10 @@ use tracing::debug;
 use apply_schema::{apply_schema, apply_schema_to};
 use evaluate_expression::evaluate_expression;
 
+pub use arrow_predicate::to_arrow_predicate;
+
 mod apply_schema;
+mod arrow_predicate;
 mod evaluate_expression;
 
 #[cfg(test)]
@@ -125,15 +128,8 @@ impl Scalar {
                 let field = ArrowField::new(LIST_ARRAY_ROOT, t.element_type().try_into()?, true);
                 Arc::new(ListArray::new_null(Arc::new(field), num_rows))
             }
-            Null(DataType::Map { .. }) => {
-                return Err(Error::unsupported(
-                    "Scalar::to_array does not yet support Map types",
-                ));
-            }
-            Null(DataType::Dictionary { .. }) => {
-                return Err(Error::unsupported(
-                    "Scalar::to_array does not yet support Dictionary types",
-                ));
+            Null(data_type @ (DataType::Map(_) | DataType::Dictionary(_))) => {
+                new_null_array(&ArrowDataType::try_from(data_type)?, num_rows)
             }
         };
         Ok(arr)
@@ -200,7 +196,9 @@ impl ExpressionEvaluator for DefaultExpressionEvaluator {
             apply_schema(&array_ref, &self.output_type)?
         } else {
             let array_ref = apply_schema_to(&array_ref, &self.output_type)?;
-            let arrow_type: ArrowDataType = ArrowDataType::try_from(&self.output_type)?;
+            // the output may be of a compatible arrow type other than the default one for the
+            // output type, e.g. a string view
+            let arrow_type = array_ref.data_type().clone();
             let schema = ArrowSchema::new(vec![ArrowField::new("output", arrow_type, true)]);
             RecordBatch::try_new(Arc::new(schema), vec![array_ref])?
         };
@@ -1,7 +1,8 @@
 use std::ops::{Add, Div, Mul, Sub};
 
 use crate::arrow::array::{
-    create_array, ArrayRef, BooleanArray, GenericStringArray, Int32Array, ListArray, StructArray,
+    create_array, ArrayRef, BinaryViewArray, BooleanArray, GenericStringArray, Int32Array,
+    ListArray, StringViewArray, StructArray,
 };
 use crate::arrow::buffer::{OffsetBuffer, ScalarBuffer};
 use crate::arrow::datatypes::{DataType, Field, Fields, Schema};
@@ -275,6 +276,45 @@ fn test_binary_cmp() {
     assert_eq!(results.as_ref(), expected.as_ref());
 }
 
+#[test]
+fn test_string_view_cmp() {
+    let schema = Schema::new(vec![
+        Field::new("s", DataType::Utf8View, true),
+        Field::new("b", DataType::BinaryView, true),
+    ]);
+    let strings = StringViewArray::from(vec![Some("a"), Some("b"), None, Some("c")]);
+    let binaries = BinaryViewArray::from(vec![Some(&b"a"[..]), Some(b"b"), None, Some(b"c")]);
+    let batch = RecordBatch::try_new(
+        Arc::new(schema),
+        vec![Arc::new(strings), Arc::new(binaries)],
+    )
+    .unwrap();
+
+    let expression = column_expr!("s").eq(Expr::literal("b"));
+    let results = evaluate_expression(&expression, &batch, None).unwrap();
+    let expected = BooleanArray::from(vec![Some(false), Some(true), None, Some(false)]);
+    assert_eq!(results.as_ref(), &expected);
+
+    let expression = Expr::literal("b").lt(column_expr!("s"));
+    let results = evaluate_expression(&expression, &batch, None).unwrap();
+    let expected = BooleanArray::from(vec![Some(false), Some(false), None, Some(true)]);
+    assert_eq!(results.as_ref(), &expected);
+
+    let in_list = Scalar::Array(ArrayData::new(
+        ArrayType::new(DeltaDataTypes::STRING, false),
+        ["a", "c"],
+    ));
+    let expression = Expr::binary(BinaryOperator::In, column_expr!("s"), in_list);
+    let results = evaluate_expression(&expression, &batch, None).unwrap();
+    let expected = BooleanArray::from(vec![Some(true), Some(false), None, Some(true)]);
+    assert_eq!(results.as_ref(), &expected);
+
+    let expression = column_expr!("b").ge(Expr::literal(Scalar::Binary(b"b".to_vec())));
+    let results = evaluate_expression(&expression, &batch, None).unwrap();
+    let expected = BooleanArray::from(vec![Some(false), Some(true), None, Some(true)]);
+    assert_eq!(results.as_ref(), &expected);
+}
+
 #[test]
 fn test_logical() {
     let schema = Schema::new(vec![
@@ -518,3 +558,120 @@ fn test_create_one_top_level_null() {
         Err(Error::InvalidStructData(_))
     ));
 }
+
+#[test]
+fn test_to_arrow_predicate() {
+    use crate::arrow::array::{AsArray as _, Int64Array, StringArray};
+    use crate::arrow::compute::kernels::cmp::{eq, gt, lt_eq};
+    use crate::arrow::compute::{and_kleene, filter_record_batch, is_null, not, or_kleene};
+    use crate::parquet::arrow::arrow_reader::{ParquetRecordBatchReaderBuilder, RowFilter};
+    use crate::parquet::arrow::ArrowWriter;
+
+    let nested = StructArray::from(vec![(
+        Arc::new(Field::new("z", DataType::Int64, true)),
+        Arc::new(Int64Array::from(vec![10, 20, 30, 40, 50, 60])) as ArrayRef,
+    )]);
+    let batch = RecordBatch::try_from_iter([
+        (
+            "a",
+            Arc::new(Int32Array::from(vec![
+                Some(1),
+                None,
+                Some(3),
+                Some(4),
+                None,
+                Some(6),
+            ])) as ArrayRef,
+        ),
+        (
+            "s",
+            Arc::new(StringArray::from(vec!["x", "y", "x", "z", "x", "y"])) as ArrayRef,
+        ),
+        ("n", Arc::new(nested) as ArrayRef),
+    ])
+    .unwrap();
+    let mut data = vec![];
+    let mut writer = ArrowWriter::try_new(&mut data, batch.schema(), None).unwrap();
+    writer.write(&batch).unwrap();
+    writer.close().unwrap();
+    let data = bytes::Bytes::from(data);
+
+    let a = batch
+        .column(0)
+        .as_primitive::<crate::arrow::datatypes::Int32Type>();
+    let s = batch.column(1);
+    let z = batch.column(2).as_struct().column(0);
+    let s_is_x = eq(s, &StringArray::new_scalar("x")).unwrap();
+    let cases = [
+        (
+            Expr::gt(column_expr!("a"), Expr::literal(2)),
+            gt(a, &Int32Array::new_scalar(2)).unwrap(),
+        ),
+        (
+            Expr::and(
+                Expr::eq(column_expr!("s"), Expr::literal("x")),
+                Expr::le(column_expr!("n.z"), Expr::literal(30i64)),
+            ),
+            and_kleene(&s_is_x, &lt_eq(z, &Int64Array::new_scalar(30)).unwrap()).unwrap(),
+        ),
+        (
+            Expr::or(
+                Expr::is_null(column_expr!("a")),
+                Expr::not(Expr::eq(column_expr!("s"), Expr::literal("x"))),
+            ),
+            or_kleene(&is_null(a).unwrap(), &not(&s_is_x).unwrap()).unwrap(),
+        ),
+        (
+            Expr::binary(
+                BinaryOperator::In,
+                column_expr!("a"),
+                Scalar::Array(ArrayData::new(
+                    ArrayType::new(DeltaDataTypes::INTEGER, false),
+                    [3, 6],
+                )),
+            ),
+            BooleanArray::from(vec![false, false, true, false, false, true]),
+        ),
+    ];
+    for (predicate, mask) in cases {
+        let builder = ParquetRecordBatchReaderBuilder::try_new(data.clone()).unwrap();
+        let arrow_predicate = to_arrow_predicate(&predicate, builder.schema()).unwrap();
+        let reader = builder
+            .with_row_filter(RowFilter::new(vec![arrow_predicate]))
+            .build()
+            .unwrap();
+        let filtered: usize = reader.map(|batch| batch.unwrap().num_rows()).sum();
+        let expected = filter_record_batch(&batch, &mask).unwrap().num_rows();
+        assert_eq!(filtered, expected, "{predicate}");
+    }
+
+    // unsupported nodes and unknown columns are errors rather than being dropped
+    let schema = batch.schema();
+    let predicate = Expr::gt(
+        Expr::binary(BinaryOperator::Plus, column_expr!("a"), Expr::literal(1)),
+        Expr::literal(2),
+    );
+    assert!(to_arrow_predicate(&predicate, &schema).is_err());
+    let predicate = Expr::gt(column_expr!("missing"), Expr::literal(2));
+    assert!(to_arrow_predicate(&predicate, &schema).is_err());
+}
+
+#[test]
+fn test_null_scalar_to_array() {
+    use crate::schema::{DictionaryType, MapType};
+
+    let types = [
+        DeltaDataTypes::STRING,
+        DeltaDataTypes::INTEGER,
+        DeltaDataTypes::DATE,
+        DeltaDataTypes::decimal(10, 2).unwrap(),
+        MapType::new(DeltaDataTypes::STRING, DeltaDataTypes::LONG, true).into(),
+        DictionaryType::new(DeltaDataTypes::INTEGER, DeltaDataTypes::STRING, true).into(),
+    ];
+    for data_type in types {
+        let array = Scalar::Null(data_type.clone()).to_array(3).unwrap();
+        assert_eq!(array.len(), 3);
+        assert_eq!(array.null_count(), 3);
+        assert_eq!(array.data_type(), &DataType::try_from(&data_type).unwrap());
+    }
+}
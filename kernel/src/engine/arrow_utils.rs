@@ -1,29 +1,37 @@
 //! Some utilities for working with arrow data types
 
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::io::{BufRead, BufReader};
+use std::ops::Range;
 use std::sync::Arc;
 
+use crate::engine::arrow_conversion::string_view_type;
 use crate::engine::ensure_data_types::DataTypeCompat;
 use crate::{
     engine::arrow_data::ArrowEngineData,
-    schema::{DataType, Schema, SchemaRef, StructField, StructType},
+    schema::{
+        ColumnMetadataKey, DataType, MetadataValue, Schema, SchemaRef, StructField, StructType,
+    },
     utils::require,
-    DeltaResult, EngineData, Error,
+    DeltaResult, EngineData, Error, FilteredEngineData, ROW_INDEX_COLUMN_NAME,
+    STRING_VIEW_METADATA_KEY,
 };
 
 use crate::arrow::array::{
-    cast::AsArray, make_array, new_null_array, Array as ArrowArray, GenericListArray,
-    OffsetSizeTrait, RecordBatch, StringArray, StructArray,
+    cast::AsArray, make_array, new_null_array, Array as ArrowArray, ArrayRef as ArrowArrayRef,
+    Decimal128Array, GenericListArray, Int64Array, OffsetSizeTrait, RecordBatch, StringArray,
+    StructArray,
 };
 use crate::arrow::buffer::NullBuffer;
-use crate::arrow::compute::concat_batches;
+use crate::arrow::compute::{concat_batches, filter_record_batch};
 use crate::arrow::datatypes::{
-    DataType as ArrowDataType, Field as ArrowField, FieldRef as ArrowFieldRef, Fields,
-    SchemaRef as ArrowSchemaRef,
+    DataType as ArrowDataType, Decimal128Type, DecimalType as _, Field as ArrowField,
+    FieldRef as ArrowFieldRef, Fields, Schema as ArrowSchema, SchemaRef as ArrowSchemaRef,
 };
 use crate::arrow::json::{LineDelimitedWriter, ReaderBuilder};
-use crate::parquet::{arrow::ProjectionMask, schema::types::SchemaDescriptor};
+use crate::parquet::arrow::arrow_reader::{ArrowReaderMetadata, ArrowReaderOptions};
+use crate::parquet::arrow::{ArrowWriter, ProjectionMask, PARQUET_FIELD_ID_META_KEY};
+use crate::parquet::schema::types::SchemaDescriptor;
 use itertools::Itertools;
 use tracing::debug;
 
@@ -72,15 +80,43 @@ pub(crate) fn make_arrow_error(s: impl Into<String>) -> Error {
 pub(crate) fn fixup_parquet_read<T>(
     batch: RecordBatch,
     requested_ordering: &[ReorderIndex],
+    row_indexes: &mut RowIndexes,
 ) -> DeltaResult<T>
 where
     StructArray: Into<T>,
 {
-    let data = reorder_struct_array(batch.into(), requested_ordering)?;
+    let row_indexes = requested_ordering
+        .iter()
+        .any(|reorder_index| matches!(reorder_index.transform, ReorderIndexTransform::RowIndex(_)))
+        .then(|| row_indexes.next_batch(batch.num_rows()))
+        .transpose()?;
+    let data = reorder_struct_array_impl(batch.into(), requested_ordering, row_indexes)?;
     let data = fix_nested_null_masks(data);
     Ok(data.into())
 }
 
+/// The file row indexes of the rows a parquet reader produces, in order. Built from the row ranges
+/// of the row groups that are read (see [`RowGroupSelection::row_ranges`]), and used to fill in a
+/// requested [`ROW_INDEX_COLUMN_NAME`] column.
+///
+/// [`RowGroupSelection::row_ranges`]: crate::engine::parquet_row_group_skipping::RowGroupSelection::row_ranges
+pub(crate) struct RowIndexes(Box<dyn Iterator<Item = u64> + Send>);
+
+impl RowIndexes {
+    pub(crate) fn new(row_ranges: Vec<Range<u64>>) -> Self {
+        Self(Box::new(row_ranges.into_iter().flatten()))
+    }
+
+    fn next_batch(&mut self, num_rows: usize) -> DeltaResult<ArrowArrayRef> {
+        let indexes: Vec<_> = self.0.by_ref().take(num_rows).map(|i| i as i64).collect();
+        require!(
+            indexes.len() == num_rows,
+            Error::internal_error("Read more rows than the selected row groups contain")
+        );
+        Ok(Arc::new(Int64Array::from(indexes)))
+    }
+}
+
 /*
 * The code below implements proper pruning of columns when reading parquet, reordering of columns to
 * match the specified schema, and insertion of null columns if the requested schema includes a
@@ -189,6 +225,9 @@ where
 pub(crate) struct ReorderIndex {
     pub(crate) index: usize,
     transform: ReorderIndexTransform,
+    /// The name the column should have in the output, if it differs from its name in the parquet
+    /// file (which happens when the column was resolved by field id)
+    rename: Option<String>,
 }
 
 #[derive(Debug, PartialEq)]
@@ -201,11 +240,17 @@ pub(crate) enum ReorderIndexTransform {
     Identity,
     /// Data is missing, fill in with a null column
     Missing(ArrowFieldRef),
+    /// The [`ROW_INDEX_COLUMN_NAME`] column, fill in with the row indexes of the rows read
+    RowIndex(ArrowFieldRef),
 }
 
 impl ReorderIndex {
     fn new(index: usize, transform: ReorderIndexTransform) -> Self {
-        ReorderIndex { index, transform }
+        ReorderIndex {
+            index,
+            transform,
+            rename: None,
+        }
     }
 
     fn cast(index: usize, target: ArrowDataType) -> Self {
@@ -224,12 +269,21 @@ impl ReorderIndex {
         ReorderIndex::new(index, ReorderIndexTransform::Missing(field))
     }
 
+    fn row_index(index: usize, field: ArrowFieldRef) -> Self {
+        ReorderIndex::new(index, ReorderIndexTransform::RowIndex(field))
+    }
+
     /// Check if this reordering requires a transformation anywhere. See comment below on
     /// [`ordering_needs_transform`] to understand why this is needed.
     fn needs_transform(&self) -> bool {
+        if self.rename.is_some() {
+            return true;
+        }
         match self.transform {
-            // if we're casting or inserting null, we need to transform
-            ReorderIndexTransform::Cast(_) | ReorderIndexTransform::Missing(_) => true,
+            // if we're casting or inserting a column, we need to transform
+            ReorderIndexTransform::Cast(_)
+            | ReorderIndexTransform::Missing(_)
+            | ReorderIndexTransform::RowIndex(_) => true,
             // if our nested ordering needs a transform, we need a transform
             ReorderIndexTransform::Nested(ref children) => ordering_needs_transform(children),
             // no transform needed
@@ -256,6 +310,109 @@ fn _count_cols(dt: &ArrowDataType) -> usize {
     }
 }
 
+/// The field id the parquet file recorded for `field`, if any
+fn parquet_field_id(field: &ArrowField) -> Option<i64> {
+    field
+        .metadata()
+        .get(PARQUET_FIELD_ID_META_KEY)?
+        .parse()
+        .ok()
+}
+
+/// The parquet field ids the fields of `requested_schema` must be resolved by, mapped to the index
+/// of the field. With column mapping in id mode the requested fields carry their column mapping
+/// id, which is the parquet field id and stays stable even if the column was renamed after the file
+/// was written.
+fn requested_field_ids(requested_schema: &Schema) -> HashMap<i64, usize> {
+    requested_schema
+        .fields()
+        .enumerate()
+        .filter_map(|(index, field)| {
+            match field.get_config_value(&ColumnMetadataKey::ColumnMappingId) {
+                Some(MetadataValue::Number(id)) => Some((*id, index)),
+                _ => None,
+            }
+        })
+        .collect()
+}
+
+/// The index, name and field of the field of `requested_schema` that the parquet `field` resolves
+/// to, if any: by field id if the requested fields carry ids (see [`requested_field_ids`]), and by
+/// name otherwise.
+fn find_requested_field<'a>(
+    requested_schema: &'a Schema,
+    requested_ids: &HashMap<i64, usize>,
+    field: &ArrowField,
+) -> Option<(usize, &'a String, &'a StructField)> {
+    match parquet_field_id(field) {
+        Some(id) if !requested_ids.is_empty() => requested_ids.get(&id).and_then(|&index| {
+            let (name, requested_field) = requested_schema.fields.get_index(index)?;
+            Some((index, name, requested_field))
+        }),
+        _ => requested_schema.fields.get_full(field.name()),
+    }
+}
+
+/// Re-creates the reader `metadata` of a parquet file so that the fields of `requested_schema`
+/// marked with [`STRING_VIEW_METADATA_KEY`] are decoded directly into string and binary views.
+pub(crate) fn with_string_view_read_schema(
+    requested_schema: &Schema,
+    metadata: ArrowReaderMetadata,
+) -> DeltaResult<ArrowReaderMetadata> {
+    match string_view_read_schema(requested_schema, metadata.schema()) {
+        Some(read_schema) => {
+            let options = ArrowReaderOptions::new().with_schema(read_schema);
+            Ok(ArrowReaderMetadata::try_new(
+                metadata.metadata().clone(),
+                options,
+            )?)
+        }
+        None => Ok(metadata),
+    }
+}
+
+// The arrow schema to read a parquet file whose arrow schema is `parquet_schema` with, or `None` if
+// no field of `requested_schema` prefers string views
+fn string_view_read_schema(
+    requested_schema: &Schema,
+    parquet_schema: &ArrowSchemaRef,
+) -> Option<ArrowSchemaRef> {
+    let requested_ids = requested_field_ids(requested_schema);
+    let prefers_string_view = |field: &ArrowField| {
+        find_requested_field(requested_schema, &requested_ids, field).is_some_and(
+            |(_, _, requested_field)| {
+                matches!(
+                    requested_field.metadata().get(STRING_VIEW_METADATA_KEY),
+                    Some(MetadataValue::Boolean(true))
+                )
+            },
+        )
+    };
+    if !parquet_schema
+        .fields()
+        .iter()
+        .any(|f| prefers_string_view(f))
+    {
+        return None;
+    }
+    let fields = parquet_schema
+        .fields()
+        .iter()
+        .map(|field| match prefers_string_view(field) {
+            true => Arc::new(
+                field
+                    .as_ref()
+                    .clone()
+                    .with_data_type(string_view_type(field.data_type())),
+            ),
+            false => field.clone(),
+        });
+    Some(Arc::new(ArrowSchema::new_with_metadata(
+        fields.collect_vec(),
+        parquet_schema.metadata().clone(),
+    )))
+}
+
 /// helper function, does the same as `get_requested_indices` but at an offset. used to recurse into
 /// structs, lists, and maps. `parquet_offset` is how many parquet fields exist before processing
 /// this potentially nested schema. returns the number of parquet fields in `fields` (regardless of
@@ -269,11 +426,12 @@ fn get_indices(
     let mut found_fields = HashSet::with_capacity(requested_schema.fields.len());
     let mut reorder_indices = Vec::with_capacity(requested_schema.fields.len());
     let mut parquet_offset = start_parquet_offset;
+    let requested_ids = requested_field_ids(requested_schema);
     // for each field, get its position in the parquet (via enumerate), a reference to the arrow
     // field, and info about where it appears in the requested_schema, or None if the field is not
     // requested
     let all_field_info = fields.iter().enumerate().map(|(parquet_index, field)| {
-        let field_info = requested_schema.fields.get_full(field.name());
+        let field_info = find_requested_field(requested_schema, &requested_ids, field);
         (parquet_index, field, field_info)
     });
     for (parquet_index, field, field_info) in all_field_info {
@@ -399,6 +557,12 @@ fn get_indices(
                     mask_indices.push(parquet_offset + parquet_index);
                 }
             }
+            if field.name() != requested_field.name() {
+                // matched by field id, so the output must take the requested name
+                if let Some(reorder_index) = reorder_indices.last_mut() {
+                    reorder_index.rename = Some(requested_field.name().clone());
+                }
+            }
         } else {
             // We're NOT selecting this field, but we still need to track how many leaf columns we
             // skipped over
@@ -413,7 +577,13 @@ fn get_indices(
         // some fields are missing, but they might be nullable, need to insert them into the reorder_indices
         for (requested_position, field) in requested_schema.fields().enumerate() {
             if !found_fields.contains(field.name()) {
-                if field.nullable {
+                if field.name() == ROW_INDEX_COLUMN_NAME {
+                    debug!("Inserting row index field: {}", field.name());
+                    reorder_indices.push(ReorderIndex::row_index(
+                        requested_position,
+                        Arc::new(field.try_into()?),
+                    ));
+                } else if field.nullable {
                     debug!("Inserting missing and nullable field: {}", field.name());
                     reorder_indices.push(ReorderIndex::missing(
                         requested_position,
@@ -495,11 +665,71 @@ fn ordering_needs_transform(requested_ordering: &[ReorderIndex]) -> bool {
 // of this type and then set elements of the Vec to Some(FieldArrayOpt) for each column
 type FieldArrayOpt = Option<(Arc<ArrowField>, Arc<dyn ArrowArray>)>;
 
+/// Cast a leaf column to the `target` type.
+fn cast_column(col: &dyn ArrowArray, target: &ArrowDataType) -> DeltaResult<ArrowArrayRef> {
+    match (col.data_type(), target) {
+        // arrow's cast would silently round away digits and turn overflows into nulls
+        (ArrowDataType::Decimal128(_, _), ArrowDataType::Decimal128(precision, scale)) => {
+            let rescaled = rescale_decimal(col.as_primitive(), *precision, *scale)?;
+            Ok(Arc::new(rescaled))
+        }
+        _ => Ok(crate::arrow::compute::cast(col, target)?),
+    }
+}
+
+/// Rescale the values of a decimal array to `scale`, as needed when a data file stores a decimal
+/// column at a different precision or scale than the table schema. Fails if a value does not fit
+/// in `precision` digits, or if it has more fractional digits than `scale` can hold.
+fn rescale_decimal(
+    array: &Decimal128Array,
+    precision: u8,
+    scale: i8,
+) -> DeltaResult<Decimal128Array> {
+    let (source_precision, source_scale) = (array.precision(), array.scale());
+    let format = |value| Decimal128Type::format_decimal(value, source_precision, source_scale);
+    let scale_diff = i16::from(scale) - i16::from(source_scale);
+    let factor = 10i128.checked_pow(scale_diff.unsigned_abs().into());
+    let rescaled = if scale_diff >= 0 {
+        array.try_unary(|value| {
+            factor
+                .and_then(|factor| value.checked_mul(factor))
+                .ok_or_else(|| {
+                    Error::generic(format!(
+                        "Decimal value {} overflows when rescaled to Decimal({precision}, {scale})",
+                        format(value)
+                    ))
+                })
+        })?
+    } else {
+        array.try_unary(|value| match factor {
+            Some(factor) if value % factor == 0 => Ok(value / factor),
+            // values with fewer digits than the scale reduction can only be zero
+            None if value == 0 => Ok(0),
+            _ => Err(Error::generic(format!(
+                "Decimal value {} loses precision when rescaled to Decimal({precision}, {scale})",
+                format(value)
+            ))),
+        })?
+    };
+    let rescaled = rescaled.with_precision_and_scale(precision, scale)?;
+    rescaled.validate_decimal_precision(precision)?;
+    Ok(rescaled)
+}
+
 /// Reorder a RecordBatch to match `requested_ordering`. For each non-zero value in
 /// `requested_ordering`, the column at that index will be added in order to returned batch
 pub(crate) fn reorder_struct_array(
     input_data: StructArray,
     requested_ordering: &[ReorderIndex],
+) -> DeltaResult<StructArray> {
+    reorder_struct_array_impl(input_data, requested_ordering, None)
+}
+
+// `row_indexes` fills in a top-level row index column, which nested structs can't have
+fn reorder_struct_array_impl(
+    input_data: StructArray,
+    requested_ordering: &[ReorderIndex],
+    row_indexes: Option<ArrowArrayRef>,
 ) -> DeltaResult<StructArray> {
     debug!("Reordering {input_data:?} with ordering: {requested_ordering:?}");
     if !ordering_needs_transform(requested_ordering) {
@@ -518,8 +748,7 @@ pub(crate) fn reorder_struct_array(
             // requested_ordering tells us where it is in the parquet data
             match &reorder_index.transform {
                 ReorderIndexTransform::Cast(target) => {
-                    let col = input_cols[parquet_position].as_ref();
-                    let col = Arc::new(crate::arrow::compute::cast(col, target)?);
+                    let col = cast_column(input_cols[parquet_position].as_ref(), target)?;
                     let new_field = Arc::new(
                         input_fields[parquet_position]
                             .as_ref()
@@ -578,6 +807,19 @@ pub(crate) fn reorder_struct_array(
                     let field = field.clone(); // cheap Arc clone
                     final_fields_cols[reorder_index.index] = Some((field, null_array));
                 }
+                ReorderIndexTransform::RowIndex(field) => {
+                    let row_indexes = row_indexes.clone().ok_or_else(|| {
+                        Error::generic(format!(
+                            "{ROW_INDEX_COLUMN_NAME} is only supported as a top-level column"
+                        ))
+                    })?;
+                    final_fields_cols[reorder_index.index] = Some((field.clone(), row_indexes));
+                }
+            }
+            if let Some(name) = &reorder_index.rename {
+                if let Some((field, _)) = &mut final_fields_cols[reorder_index.index] {
+                    *field = Arc::new(field.as_ref().clone().with_name(name));
+                }
             }
         }
         let num_cols = final_fields_cols.len();
@@ -682,13 +924,17 @@ pub(crate) fn parse_json(
     schema: SchemaRef,
 ) -> DeltaResult<Box<dyn EngineData>> {
     let json_strings: RecordBatch = ArrowEngineData::try_from_engine_data(json_strings)?.into();
-    let json_strings = json_strings
-        .column(0)
-        .as_any()
-        .downcast_ref::<StringArray>()
-        .ok_or_else(|| {
-            Error::generic("Expected json_strings to be a StringArray, found something else")
-        })?;
+    let json_strings = json_strings.column(0);
+    let json_strings = match json_strings.as_string_view_opt() {
+        // parse views like plain strings, e.g. stats read by an engine that prefers views
+        Some(views) => &StringArray::from_iter(views.iter()),
+        None => json_strings
+            .as_any()
+            .downcast_ref::<StringArray>()
+            .ok_or_else(|| {
+                Error::generic("Expected json_strings to be a StringArray, found something else")
+            })?,
+    };
     let schema: ArrowSchemaRef = Arc::new(schema.as_ref().try_into()?);
     let result = parse_json_impl(json_strings, schema)?;
     Ok(Box::new(ArrowEngineData::new(result)))
@@ -741,6 +987,33 @@ pub(crate) fn to_json_bytes(
     Ok(writer.into_inner())
 }
 
+/// Encode the selected rows of `data` as a single parquet file. The schema of the first batch is
+/// the schema of the file, and all other batches must match it. Rows beyond the end of a
+/// selection vector are selected.
+pub(crate) fn to_parquet_bytes(
+    data: impl Iterator<Item = DeltaResult<FilteredEngineData>>,
+) -> DeltaResult<Vec<u8>> {
+    let mut writer: Option<ArrowWriter<Vec<u8>>> = None;
+    for chunk in data {
+        let FilteredEngineData {
+            data,
+            mut selection_vector,
+        } = chunk?;
+        let arrow_data = ArrowEngineData::try_from_engine_data(data)?;
+        let record_batch = arrow_data.record_batch();
+        selection_vector.resize(record_batch.num_rows(), true);
+        let record_batch = filter_record_batch(record_batch, &selection_vector.into())?;
+        let writer = match writer.as_mut() {
+            Some(writer) => writer,
+            None => writer.insert(ArrowWriter::try_new(vec![], record_batch.schema(), None)?),
+        };
+        writer.write(&record_batch)?;
+    }
+    let writer =
+        writer.ok_or_else(|| Error::generic("Cannot write a parquet file without data"))?;
+    Ok(writer.into_inner()?)
+}
+
 #[cfg(test)]
 mod tests {
     use std::sync::Arc;
@@ -897,6 +1170,44 @@ mod tests {
         assert_eq!(reorder_indices, expect_reorder);
     }
 
+    #[test]
+    fn field_id_indices() {
+        let with_id = |field: StructField, id: i64| {
+            field.with_metadata([(
+                ColumnMetadataKey::ColumnMappingId.as_ref(),
+                MetadataValue::Number(id),
+            )])
+        };
+        let requested_schema = Arc::new(StructType::new([
+            with_id(StructField::not_null("a", DataType::INTEGER), 1),
+            with_id(StructField::nullable("b", DataType::STRING), 2),
+        ]));
+        // the names in the file don't match, and "a" is a different column than the requested one
+        let parquet_schema = Arc::new(ArrowSchema::new(vec![
+            ArrowField::new("a", ArrowDataType::Utf8, true).with_metadata(HashMap::from([(
+                PARQUET_FIELD_ID_META_KEY.to_string(),
+                "2".to_string(),
+            )])),
+            ArrowField::new("old_a", ArrowDataType::Int32, false).with_metadata(HashMap::from([(
+                PARQUET_FIELD_ID_META_KEY.to_string(),
+                "1".to_string(),
+            )])),
+        ]));
+        let (mask_indices, reorder_indices) =
+            get_requested_indices(&requested_schema, &parquet_schema).unwrap();
+        let rename = |mut index: ReorderIndex, name: &str| {
+            index.rename = Some(name.to_string());
+            index
+        };
+        let expect_mask = vec![0, 1];
+        let expect_reorder = vec![
+            rename(ReorderIndex::identity(1), "b"),
+            rename(ReorderIndex::identity(0), "a"),
+        ];
+        assert_eq!(mask_indices, expect_mask);
+        assert_eq!(reorder_indices, expect_reorder);
+    }
+
     #[test]
     fn simple_reorder_indices() {
         let requested_schema = Arc::new(StructType::new([
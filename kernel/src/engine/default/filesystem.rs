@@ -1,3 +1,4 @@
+use std::ops::Range;
 use std::sync::Arc;
 
 use bytes::Bytes;
@@ -10,7 +11,7 @@ use crate::object_store::{DynObjectStore, ObjectStore};
 
 use super::UrlExt;
 use crate::engine::default::executor::TaskExecutor;
-use crate::{DeltaResult, Error, FileMeta, FileSlice, StorageHandler};
+use crate::{DeltaResult, Error, FileIndex, FileMeta, FileSlice, StorageHandler};
 
 #[derive(Debug)]
 pub struct ObjectStoreStorageHandler<E: TaskExecutor> {
@@ -150,8 +151,15 @@ impl<E: TaskExecutor> StorageHandler for ObjectStoreStorageHandler<E> {
                     let store = store.clone();
                     async move {
                         if url.is_presigned() {
+                            let mut request = reqwest::Client::new().get(url);
+                            if let Some(rng) = range {
+                                // HTTP byte ranges are inclusive of the last byte
+                                let end = rng.end.saturating_sub(1);
+                                let value = format!("bytes={}-{end}", rng.start);
+                                request = request.header(reqwest::header::RANGE, value);
+                            }
                             // have to annotate type here or rustc can't figure it out
-                            Ok::<bytes::Bytes, Error>(reqwest::get(url).await?.bytes().await?)
+                            Ok::<bytes::Bytes, Error>(request.send().await?.bytes().await?)
                         } else if let Some(rng) = range {
                             // TODO: remove after arrow 54 is dropped
                             #[cfg(feature = "arrow-54")]
@@ -182,11 +190,17 @@ impl<E: TaskExecutor> StorageHandler for ObjectStoreStorageHandler<E> {
 
         Ok(Box::new(receiver.into_iter()))
     }
+
+    /// Read the bytes in `range` of the file at `path` with a single ranged GET.
+    fn read_file_range(&self, path: &Url, range: Range<FileIndex>) -> DeltaResult<Bytes> {
+        self.read_files(vec![(path.clone(), Some(range))])?
+            .next()
+            .ok_or_else(|| Error::generic(format!("No data returned when reading {path}")))?
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use std::ops::Range;
     use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
     use crate::object_store::memory::InMemory;
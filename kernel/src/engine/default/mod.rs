@@ -19,6 +19,7 @@ use self::json::DefaultJsonHandler;
 use self::parquet::DefaultParquetHandler;
 use super::arrow_data::ArrowEngineData;
 use super::arrow_expression::ArrowEvaluationHandler;
+use crate::schema::compatibility::check_write_nullability;
 use crate::schema::Schema;
 use crate::transaction::WriteContext;
 use crate::{
@@ -92,6 +93,14 @@ impl<E: TaskExecutor> DefaultEngine<E> {
         Some(self.object_store.clone())
     }
 
+    /// Write `data` as a parquet file into the table of `write_context`, returning the metadata of
+    /// the written file to add to the transaction with [`Transaction::add_write_metadata`].
+    ///
+    /// Nullable columns of `data` may be written into non-nullable table columns, but the write
+    /// fails with [`Incompatibility::NullValue`] if they hold any nulls.
+    ///
+    /// [`Transaction::add_write_metadata`]: crate::transaction::Transaction::add_write_metadata
+    /// [`Incompatibility::NullValue`]: crate::schema::compatibility::Incompatibility::NullValue
     pub async fn write_parquet(
         &self,
         data: &ArrowEngineData,
@@ -99,6 +108,7 @@ impl<E: TaskExecutor> DefaultEngine<E> {
         partition_values: HashMap<String, String>,
         data_change: bool,
     ) -> DeltaResult<Box<dyn EngineData>> {
+        check_write_nullability(write_context.schema(), data.record_batch())?;
         let transform = write_context.logical_to_physical();
         let input_schema: Schema = data.record_batch().schema().try_into()?;
         let output_schema = write_context.schema();
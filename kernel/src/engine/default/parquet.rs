@@ -7,25 +7,30 @@ use std::sync::Arc;
 use crate::arrow::array::builder::{MapBuilder, MapFieldNames, StringBuilder};
 use crate::arrow::array::{BooleanArray, Int64Array, RecordBatch, StringArray};
 use crate::object_store::path::Path;
-use crate::object_store::DynObjectStore;
-use crate::parquet::arrow::arrow_reader::{
-    ArrowReaderMetadata, ArrowReaderOptions, ParquetRecordBatchReaderBuilder,
-};
+use crate::object_store::{self, DynObjectStore, PutMode};
+use crate::parquet::arrow::arrow_reader::{ArrowReaderMetadata, ParquetRecordBatchReaderBuilder};
 use crate::parquet::arrow::arrow_writer::ArrowWriter;
 use crate::parquet::arrow::async_reader::{ParquetObjectReader, ParquetRecordBatchStreamBuilder};
+use crate::parquet::arrow::parquet_to_arrow_schema;
+use crate::parquet::file::metadata::ParquetMetaDataReader;
+use crate::parquet::file::FOOTER_SIZE;
 use futures::StreamExt;
 use uuid::Uuid;
 
 use super::file_stream::{FileOpenFuture, FileOpener, FileStream};
 use super::UrlExt;
 use crate::engine::arrow_data::ArrowEngineData;
-use crate::engine::arrow_utils::{fixup_parquet_read, generate_mask, get_requested_indices};
+use crate::engine::arrow_utils::{
+    fixup_parquet_read, generate_mask, get_requested_indices, to_parquet_bytes,
+    with_string_view_read_schema, RowIndexes,
+};
 use crate::engine::default::executor::TaskExecutor;
-use crate::engine::parquet_row_group_skipping::ParquetRowGroupSkipping;
-use crate::schema::SchemaRef;
+use crate::engine::default::filesystem::ObjectStoreStorageHandler;
+use crate::engine::parquet_row_group_skipping::{ParquetRowGroupSkipping, RowGroupSelection};
+use crate::schema::{SchemaRef, StructType};
 use crate::{
     DeltaResult, EngineData, Error, ExpressionRef, FileDataReadResultIterator, FileMeta,
-    ParquetHandler,
+    FilteredEngineData, ParquetHandler, StorageHandler,
 };
 
 #[derive(Debug)]
@@ -225,6 +230,78 @@ impl<E: TaskExecutor> ParquetHandler for DefaultParquetHandler<E> {
             self.readahead,
         )
     }
+
+    fn read_parquet_schema(&self, file: &FileMeta) -> DeltaResult<SchemaRef> {
+        let storage =
+            ObjectStoreStorageHandler::new(self.store.clone(), self.task_executor.clone());
+        read_parquet_schema_from_footer(&storage, file)
+    }
+
+    // note: for now we just buffer all the data and write it out all at once
+    fn write_parquet_file(
+        &self,
+        location: &url::Url,
+        data: Box<dyn Iterator<Item = DeltaResult<FilteredEngineData>> + Send + '_>,
+        overwrite: bool,
+    ) -> DeltaResult<()> {
+        let buffer = to_parquet_bytes(data)?;
+        let put_mode = if overwrite {
+            PutMode::Overwrite
+        } else {
+            PutMode::Create
+        };
+
+        let store = self.store.clone(); // cheap Arc
+        let path = Path::from_url_path(location.path())?;
+        let path_str = path.to_string();
+        self.task_executor
+            .block_on(async move { store.put_opts(&path, buffer.into(), put_mode.into()).await })
+            .map_err(|e| match e {
+                object_store::Error::AlreadyExists { .. } => Error::FileAlreadyExists(path_str),
+                e => e.into(),
+            })?;
+        Ok(())
+    }
+}
+
+/// Read the schema of a parquet file, fetching only its footer via
+/// [`StorageHandler::read_file_range`].
+///
+/// This issues two small reads: one for the fixed-size footer tail, which records the length of
+/// the metadata, and one for the metadata itself.
+fn read_parquet_schema_from_footer(
+    storage: &dyn StorageHandler,
+    file: &FileMeta,
+) -> DeltaResult<SchemaRef> {
+    let footer_size = FOOTER_SIZE as u64;
+    let footer_start = file.size.checked_sub(footer_size).ok_or_else(|| {
+        Error::generic(format!(
+            "{} is too small to be a parquet file",
+            file.location
+        ))
+    })?;
+    let tail = storage.read_file_range(&file.location, footer_start..file.size)?;
+    let tail: &[u8; FOOTER_SIZE] = tail.as_ref().try_into().map_err(|_| {
+        Error::generic(format!(
+            "Short read of parquet footer for {}",
+            file.location
+        ))
+    })?;
+    let metadata_len = ParquetMetaDataReader::decode_footer_tail(tail)?.metadata_length() as u64;
+    let metadata_start = footer_start.checked_sub(metadata_len).ok_or_else(|| {
+        Error::generic(format!(
+            "Parquet metadata length exceeds file size for {}",
+            file.location
+        ))
+    })?;
+    let metadata = storage.read_file_range(&file.location, metadata_start..footer_start)?;
+    let metadata = ParquetMetaDataReader::decode_metadata(&metadata)?;
+    let file_metadata = metadata.file_metadata();
+    let arrow_schema = parquet_to_arrow_schema(
+        file_metadata.schema_descr(),
+        file_metadata.key_value_metadata(),
+    )?;
+    Ok(Arc::new(StructType::try_from(&arrow_schema)?))
 }
 
 /// Implements [`FileOpener`] for a parquet file
@@ -276,12 +353,12 @@ impl FileOpener for ParquetOpener {
                 ParquetObjectReader::new(store, meta)
             };
             let metadata = ArrowReaderMetadata::load_async(&mut reader, Default::default()).await?;
+            let metadata = with_string_view_read_schema(&table_schema, metadata)?;
             let parquet_schema = metadata.schema();
             let (indices, requested_ordering) =
                 get_requested_indices(&table_schema, parquet_schema)?;
-            let options = ArrowReaderOptions::new(); //.with_page_index(enable_page_index);
             let mut builder =
-                ParquetRecordBatchStreamBuilder::new_with_options(reader, options).await?;
+                ParquetRecordBatchStreamBuilder::new_with_metadata(reader, metadata.clone());
             if let Some(mask) = generate_mask(
                 &table_schema,
                 parquet_schema,
@@ -291,16 +368,17 @@ impl FileOpener for ParquetOpener {
                 builder = builder.with_projection(mask)
             }
 
-            if let Some(ref predicate) = predicate {
-                builder = builder.with_row_group_filter(predicate);
-            }
+            let selection = RowGroupSelection::new(builder.metadata(), predicate.as_deref());
+            builder = builder.with_row_group_selection(&selection);
+            let mut row_indexes = RowIndexes::new(selection.row_ranges().to_vec());
             if let Some(limit) = limit {
                 builder = builder.with_limit(limit)
             }
 
             let stream = builder.with_batch_size(batch_size).build()?;
 
-            let stream = stream.map(move |rbr| fixup_parquet_read(rbr?, &requested_ordering));
+            let stream = stream
+                .map(move |rbr| fixup_parquet_read(rbr?, &requested_ordering, &mut row_indexes));
             Ok(stream.boxed())
         }))
     }
@@ -343,13 +421,13 @@ impl FileOpener for PresignedUrlOpener {
             // fetch the file from the interweb
             let reader = client.get(file_meta.location).send().await?.bytes().await?;
             let metadata = ArrowReaderMetadata::load(&reader, Default::default())?;
+            let metadata = with_string_view_read_schema(&table_schema, metadata)?;
             let parquet_schema = metadata.schema();
             let (indices, requested_ordering) =
                 get_requested_indices(&table_schema, parquet_schema)?;
 
-            let options = ArrowReaderOptions::new();
             let mut builder =
-                ParquetRecordBatchReaderBuilder::try_new_with_options(reader, options)?;
+                ParquetRecordBatchReaderBuilder::new_with_metadata(reader, metadata.clone());
             if let Some(mask) = generate_mask(
                 &table_schema,
                 parquet_schema,
@@ -359,9 +437,9 @@ impl FileOpener for PresignedUrlOpener {
                 builder = builder.with_projection(mask)
             }
 
-            if let Some(ref predicate) = predicate {
-                builder = builder.with_row_group_filter(predicate);
-            }
+            let selection = RowGroupSelection::new(builder.metadata(), predicate.as_deref());
+            builder = builder.with_row_group_selection(&selection);
+            let mut row_indexes = RowIndexes::new(selection.row_ranges().to_vec());
             if let Some(limit) = limit {
                 builder = builder.with_limit(limit)
             }
@@ -369,7 +447,8 @@ impl FileOpener for PresignedUrlOpener {
             let reader = builder.with_batch_size(batch_size).build()?;
 
             let stream = futures::stream::iter(reader);
-            let stream = stream.map(move |rbr| fixup_parquet_read(rbr?, &requested_ordering));
+            let stream = stream
+                .map(move |rbr| fixup_parquet_read(rbr?, &requested_ordering, &mut row_indexes));
             Ok(stream.boxed())
         }))
     }
@@ -378,14 +457,19 @@ impl FileOpener for PresignedUrlOpener {
 #[cfg(test)]
 mod tests {
     use std::path::PathBuf;
+    use std::sync::Mutex;
     use std::time::{SystemTime, UNIX_EPOCH};
 
-    use crate::arrow::array::{Array, RecordBatch};
+    use bytes::Bytes;
+
+    use crate::arrow::array::{Array, AsArray as _, RecordBatch};
+    use crate::arrow::datatypes::Int64Type;
     use crate::object_store::{local::LocalFileSystem, memory::InMemory, ObjectStore};
     use url::Url;
 
     use crate::engine::arrow_data::ArrowEngineData;
     use crate::engine::default::executor::tokio::TokioBackgroundExecutor;
+    use crate::schema::{DataType, StructField};
     use crate::EngineData;
 
     use itertools::Itertools;
@@ -438,6 +522,67 @@ mod tests {
         assert_eq!(data[0].num_rows(), 10);
     }
 
+    /// Storage handler over a single in-memory file that records every range read from it
+    struct RangeRecordingStorage {
+        data: Bytes,
+        ranges: Mutex<Vec<Range<u64>>>,
+    }
+
+    impl StorageHandler for RangeRecordingStorage {
+        fn list_from(
+            &self,
+            _path: &Url,
+        ) -> DeltaResult<Box<dyn Iterator<Item = DeltaResult<FileMeta>>>> {
+            unimplemented!()
+        }
+
+        fn read_files(
+            &self,
+            _files: Vec<crate::FileSlice>,
+        ) -> DeltaResult<Box<dyn Iterator<Item = DeltaResult<Bytes>>>> {
+            panic!("schema probing should not read whole files")
+        }
+
+        fn read_file_range(&self, _path: &Url, range: Range<u64>) -> DeltaResult<Bytes> {
+            self.ranges.lock().unwrap().push(range.clone());
+            Ok(self.data.slice(range.start as usize..range.end as usize))
+        }
+    }
+
+    #[test]
+    fn test_read_parquet_schema_reads_only_footer() {
+        let path = std::fs::canonicalize(PathBuf::from(
+            "./tests/data/table-with-dv-small/part-00000-fae5310a-a37d-4e51-827b-c3d5516560ca-c000.snappy.parquet"
+        )).unwrap();
+        let data = Bytes::from(std::fs::read(&path).unwrap());
+        let size = data.len() as u64;
+        let expected: StructType = ParquetRecordBatchReaderBuilder::try_new(data.clone())
+            .unwrap()
+            .schema()
+            .as_ref()
+            .try_into()
+            .unwrap();
+
+        let storage = RangeRecordingStorage {
+            data,
+            ranges: Mutex::new(vec![]),
+        };
+        let file = FileMeta {
+            location: Url::from_file_path(path).unwrap(),
+            last_modified: 0,
+            size,
+        };
+        let schema = read_parquet_schema_from_footer(&storage, &file).unwrap();
+        assert_eq!(schema.as_ref(), &expected);
+
+        // one read for the footer tail, then one for the metadata that ends right before it
+        let ranges = storage.ranges.into_inner().unwrap();
+        assert_eq!(ranges.len(), 2);
+        assert_eq!(ranges[0], size - 8..size);
+        assert_eq!(ranges[1].end, size - 8);
+        assert!(ranges[1].start > 0);
+    }
+
     #[test]
     fn test_as_record_batch() {
         let location = Url::parse("file:///test_url").unwrap();
@@ -558,6 +703,57 @@ mod tests {
         assert_eq!(data[0].num_rows(), 3);
     }
 
+    #[tokio::test]
+    async fn test_read_parquet_files_as_string_views() {
+        let store = Arc::new(InMemory::new());
+        let parquet_handler =
+            DefaultParquetHandler::new(store.clone(), Arc::new(TokioBackgroundExecutor::new()));
+
+        let data = Box::new(ArrowEngineData::new(
+            RecordBatch::try_from_iter(vec![
+                (
+                    "s",
+                    Arc::new(StringArray::from(vec!["a", "b"])) as Arc<dyn Array>,
+                ),
+                (
+                    "t",
+                    Arc::new(StringArray::from(vec!["c", "d"])) as Arc<dyn Array>,
+                ),
+            ])
+            .unwrap(),
+        ));
+        let write_metadata = parquet_handler
+            .write_parquet(&Url::parse("memory:///data/").unwrap(), data)
+            .await
+            .unwrap();
+
+        // only `s` asks for a view; `t` keeps the plain string layout
+        let schema = Arc::new(StructType::new([
+            StructField::nullable("s", DataType::STRING)
+                .with_metadata([(crate::STRING_VIEW_METADATA_KEY, true)]),
+            StructField::nullable("t", DataType::STRING),
+        ]));
+        let data: Vec<RecordBatch> = parquet_handler
+            .read_parquet_files(&[write_metadata.file_meta], schema, None)
+            .unwrap()
+            .map(into_record_batch)
+            .try_collect()
+            .unwrap();
+
+        assert_eq!(data.len(), 1);
+        let batch = &data[0];
+        assert_eq!(
+            batch.column(0).data_type(),
+            &crate::arrow::datatypes::DataType::Utf8View
+        );
+        assert_eq!(
+            batch.column(1).data_type(),
+            &crate::arrow::datatypes::DataType::Utf8
+        );
+        let s = batch.column(0).as_string_view();
+        assert_eq!(s.iter().collect_vec(), vec![Some("a"), Some("b")]);
+    }
+
     #[tokio::test]
     async fn test_disallow_non_trailing_slash() {
         let store = Arc::new(InMemory::new());
@@ -577,4 +773,101 @@ mod tests {
             .await
             .is_err());
     }
+
+    #[test]
+    fn test_write_parquet_file_filtered() {
+        let store = Arc::new(InMemory::new());
+        let parquet_handler =
+            DefaultParquetHandler::new(store.clone(), Arc::new(TokioBackgroundExecutor::new()));
+        let location = Url::parse("memory:///data/file.parquet").unwrap();
+        let batch = |values: Vec<i64>, selection_vector: Vec<bool>| {
+            let data = RecordBatch::try_from_iter(vec![(
+                "a",
+                Arc::new(Int64Array::from(values)) as Arc<dyn Array>,
+            )])
+            .unwrap();
+            Ok(FilteredEngineData {
+                data: Box::new(ArrowEngineData::new(data)),
+                selection_vector,
+            })
+        };
+        let write = |overwrite| {
+            // a short selection vector selects the remaining rows
+            let data = [
+                batch(vec![1, 2, 3], vec![true, false, true]),
+                batch(vec![4, 5], vec![false]),
+            ];
+            // the inherent async `write_parquet_file` shadows the trait method
+            let data = Box::new(data.into_iter());
+            ParquetHandler::write_parquet_file(&parquet_handler, &location, data, overwrite)
+        };
+
+        write(false).unwrap();
+        assert!(matches!(write(false), Err(Error::FileAlreadyExists(_))));
+        write(true).unwrap();
+
+        let data = futures::executor::block_on(async {
+            store.get(&Path::from(location.path())).await?.bytes().await
+        })
+        .unwrap();
+        let file = FileMeta::new(location.clone(), 0, data.len() as u64);
+        let schema = Arc::new(StructType::new([crate::schema::StructField::nullable(
+            "a",
+            crate::schema::DataType::LONG,
+        )]));
+        let data: Vec<RecordBatch> = parquet_handler
+            .read_parquet_files(&[file], schema, None)
+            .unwrap()
+            .map(into_record_batch)
+            .try_collect()
+            .unwrap();
+        let values: Vec<_> = data
+            .iter()
+            .flat_map(|batch| {
+                batch
+                    .column(0)
+                    .as_primitive::<Int64Type>()
+                    .values()
+                    .to_vec()
+            })
+            .collect();
+        assert_eq!(values, [1, 3, 5]);
+    }
+
+    #[test]
+    fn test_read_parquet_schema_field_ids() {
+        use crate::arrow::datatypes::{DataType as ArrowDataType, Field, Schema as ArrowSchema};
+        use crate::parquet::arrow::PARQUET_FIELD_ID_META_KEY;
+        use crate::schema::{ColumnMetadataKey, DataType, StructField};
+
+        let store = Arc::new(InMemory::new());
+        let parquet_handler =
+            DefaultParquetHandler::new(store.clone(), Arc::new(TokioBackgroundExecutor::new()));
+        let location = Url::parse("memory:///data/file.parquet").unwrap();
+        let field =
+            Field::new("col-1", ArrowDataType::Int64, true).with_metadata(HashMap::from([(
+                PARQUET_FIELD_ID_META_KEY.to_string(),
+                "3".to_string(),
+            )]));
+        let batch = RecordBatch::try_new(
+            Arc::new(ArrowSchema::new(vec![field])),
+            vec![Arc::new(Int64Array::from(vec![1, 2]))],
+        )
+        .unwrap();
+        let data = FilteredEngineData {
+            data: Box::new(ArrowEngineData::new(batch)),
+            selection_vector: vec![],
+        };
+        let data = Box::new(std::iter::once(Ok(data)));
+        ParquetHandler::write_parquet_file(&parquet_handler, &location, data, false).unwrap();
+
+        let size = futures::executor::block_on(store.head(&Path::from(location.path())))
+            .unwrap()
+            .size;
+        let file = FileMeta::new(location, 0, size);
+        let schema = parquet_handler.read_parquet_schema(&file).unwrap();
+        let expected = StructType::new([StructField::nullable("col-1", DataType::LONG)
+            .with_metadata([(ColumnMetadataKey::ColumnMappingId.as_ref(), 3)])]);
+        assert_eq!(schema.as_ref(), &expected);
+    }
 }
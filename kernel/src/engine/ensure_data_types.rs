@@ -66,8 +66,10 @@ impl EnsureDataTypes {
             }
             // strings, bools, and binary  aren't primitive in arrow
             (&DataType::BOOLEAN, ArrowDataType::Boolean)
-            | (&DataType::STRING, ArrowDataType::Utf8)
-            | (&DataType::BINARY, ArrowDataType::Binary) => Ok(DataTypeCompat::Identical),
+            | (&DataType::STRING, ArrowDataType::Utf8 | ArrowDataType::Utf8View)
+            | (&DataType::BINARY, ArrowDataType::Binary | ArrowDataType::BinaryView) => {
+                Ok(DataTypeCompat::Identical)
+            }
             (DataType::Array(inner_type), ArrowDataType::List(arrow_list_field)) => {
                 self.ensure_nullability(
                     "List",
@@ -194,7 +196,11 @@ fn check_cast_compat(
     match (source_type, &target_type) {
         (source_type, target_type) if source_type == target_type => Ok(DataTypeCompat::Identical),
         (&ArrowDataType::Timestamp(_, _), &ArrowDataType::Timestamp(_, _)) => {
-            // timestamps are able to be cast between each other
+            // timestamps are able to be cast between each other, which also rescales values stored
+            // in a different unit (e.g. milliseconds) to the target unit. Note that casting a
+            // timestamp without a timezone to UTC keeps its value, so legacy files that store a
+            // logical `timestamp` without the UTC annotation (e.g. Spark's int96) read back
+            // unshifted.
             Ok(DataTypeCompat::NeedsCast(target_type))
         }
         // Allow up-casting to a larger type if it's safe and can't cause overflow or loss of precision.
@@ -205,7 +211,13 @@ fn check_cast_compat(
         (_, Decimal128(p, s)) if can_upcast_to_decimal(source_type, *p, *s) => {
             Ok(DataTypeCompat::NeedsCast(target_type))
         }
+        // A decimal stored at a different precision or scale (e.g. after its type was changed) is
+        // rescaled on read, which fails for any value the target type cannot represent exactly.
+        (Decimal128(_, _), Decimal128(_, _)) => Ok(DataTypeCompat::NeedsCast(target_type)),
         (Date32, Timestamp(_, None)) => Ok(DataTypeCompat::NeedsCast(target_type)),
+        // Date64 counts milliseconds rather than days, so it must be rescaled (which the arrow
+        // cast does) rather than reinterpreted as a Delta date.
+        (Date64, Date32) => Ok(DataTypeCompat::NeedsCast(target_type)),
         _ => Err(make_arrow_error(format!(
             "Incorrect datatype. Expected {}, got {}",
             target_type, source_type
@@ -328,17 +340,42 @@ mod tests {
         assert!(!can_upcast_to_decimal(&Int64, 20u8, 1i8));
     }
 
+    #[test]
+    fn ensure_dates() {
+        use super::DataTypeCompat;
+
+        assert!(matches!(
+            ensure_data_types(&DataType::DATE, &ArrowDataType::Date32, false),
+            Ok(DataTypeCompat::Identical)
+        ));
+        assert!(matches!(
+            ensure_data_types(&DataType::DATE, &ArrowDataType::Date64, false),
+            Ok(DataTypeCompat::NeedsCast(ArrowDataType::Date32))
+        ));
+    }
+
     #[test]
     fn ensure_decimals() {
+        use super::DataTypeCompat;
+
         assert!(ensure_data_types(
             &DataType::decimal_unchecked(5, 2),
             &ArrowDataType::Decimal128(5, 2),
             false
         )
         .is_ok());
+        // decimals stored at a different scale are rescaled
+        assert!(matches!(
+            ensure_data_types(
+                &DataType::decimal_unchecked(5, 2),
+                &ArrowDataType::Decimal128(5, 3),
+                false
+            ),
+            Ok(DataTypeCompat::NeedsCast(ArrowDataType::Decimal128(5, 2)))
+        ));
         assert!(ensure_data_types(
             &DataType::decimal_unchecked(5, 2),
-            &ArrowDataType::Decimal128(5, 3),
+            &ArrowDataType::Int64,
             false
         )
         .is_err());
@@ -0,0 +1,337 @@
+//! A single threaded [`Engine`] whose file system lives entirely in memory, for tests that should
+//! not touch the local filesystem or an object store.
+//!
+//! The engine reads and writes data exactly like the [`SyncEngine`], but files are stored in a
+//! map from [`Url`] to [`Bytes`] shared by all its handlers. Tables are pre-populated with
+//! [`MemoryEngine::put_commit`] and [`MemoryEngine::put_parquet`] (or [`MemoryEngine::put_file`]
+//! for arbitrary bytes), and commits written through the engine use put-if-absent semantics, so
+//! commit conflicts can be tested as well.
+//!
+//! [`SyncEngine`]: crate::engine::sync::SyncEngine
+
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use bytes::{Buf as _, Bytes};
+use url::Url;
+
+use super::arrow_expression::ArrowEvaluationHandler;
+use super::sync::json::try_create_from_json;
+use super::sync::parquet::{read_schema_from_parquet, try_create_from_parquet};
+use super::sync::read_files_with;
+use crate::arrow::array::RecordBatch;
+use crate::engine::arrow_utils::{parse_json as arrow_parse_json, to_json_bytes, to_parquet_bytes};
+use crate::parquet::arrow::ArrowWriter;
+use crate::path::ParsedLogPath;
+use crate::{
+    DeltaResult, Engine, EngineData, Error, EvaluationHandler, ExpressionRef,
+    FileDataReadResultIterator, FileMeta, FileSlice, FilteredEngineData, JsonHandler,
+    ParquetHandler, SchemaRef, StorageHandler, Version,
+};
+
+/// A file stored by the [`MemoryEngine`].
+#[derive(Debug, Clone)]
+struct MemoryFile {
+    data: Bytes,
+    /// Milliseconds since the epoch, taken when the file was stored
+    last_modified: i64,
+}
+
+/// The in-memory file system shared by the handlers of a [`MemoryEngine`].
+#[derive(Debug, Default)]
+struct MemoryFileSystem {
+    files: RwLock<HashMap<Url, MemoryFile>>,
+}
+
+impl MemoryFileSystem {
+    fn put(&self, location: Url, data: Bytes, overwrite: bool) -> DeltaResult<()> {
+        let last_modified = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .ok()
+            .and_then(|d| i64::try_from(d.as_millis()).ok())
+            .unwrap_or_default();
+        let mut files = self.files.write().map_err(|_| lock_poisoned())?;
+        if !overwrite && files.contains_key(&location) {
+            return Err(Error::FileAlreadyExists(location.to_string()));
+        }
+        files.insert(
+            location,
+            MemoryFile {
+                data,
+                last_modified,
+            },
+        );
+        Ok(())
+    }
+
+    fn get(&self, location: &Url) -> DeltaResult<Bytes> {
+        let files = self.files.read().map_err(|_| lock_poisoned())?;
+        files
+            .get(location)
+            .map(|file| file.data.clone())
+            .ok_or_else(|| Error::file_not_found(location.path()))
+    }
+}
+
+fn lock_poisoned() -> Error {
+    Error::generic("MemoryEngine file system lock poisoned")
+}
+
+/// An [`Engine`] backed by an in-memory file system. See the [module level docs](self).
+///
+/// Cloning the engine is cheap, and clones share the same file system.
+#[derive(Clone)]
+pub struct MemoryEngine {
+    storage_handler: Arc<MemoryStorageHandler>,
+    json_handler: Arc<MemoryJsonHandler>,
+    parquet_handler: Arc<MemoryParquetHandler>,
+    evaluation_handler: Arc<ArrowEvaluationHandler>,
+    file_system: Arc<MemoryFileSystem>,
+}
+
+impl MemoryEngine {
+    /// Create a new engine with an empty file system.
+    #[allow(clippy::new_without_default)]
+    pub fn new() -> Self {
+        let file_system = Arc::new(MemoryFileSystem::default());
+        MemoryEngine {
+            storage_handler: Arc::new(MemoryStorageHandler(file_system.clone())),
+            json_handler: Arc::new(MemoryJsonHandler(file_system.clone())),
+            parquet_handler: Arc::new(MemoryParquetHandler(file_system.clone())),
+            evaluation_handler: Arc::new(ArrowEvaluationHandler {}),
+            file_system,
+        }
+    }
+
+    /// Store `data` at `location`, replacing any existing file.
+    pub fn put_file(&self, location: Url, data: impl Into<Bytes>) -> DeltaResult<()> {
+        self.file_system.put(location, data.into(), true)
+    }
+
+    /// Store the commit for `version` of the table at `table_root`. `commit` is the newline
+    /// delimited JSON content of the commit. Fails if the commit already exists.
+    pub fn put_commit(
+        &self,
+        table_root: &Url,
+        version: Version,
+        commit: impl Into<Bytes>,
+    ) -> DeltaResult<()> {
+        let commit_path = ParsedLogPath::new_commit(table_root, version)?;
+        self.file_system
+            .put(commit_path.location, commit.into(), false)
+    }
+
+    /// Write `data` as a parquet file at `location`, replacing any existing file.
+    pub fn put_parquet(&self, location: Url, data: &RecordBatch) -> DeltaResult<()> {
+        let mut buffer = vec![];
+        let mut writer = ArrowWriter::try_new(&mut buffer, data.schema(), None)?;
+        writer.write(data)?;
+        writer.close()?;
+        self.put_file(location, buffer)
+    }
+
+    /// Get the content of the file at `location`.
+    pub fn get_file(&self, location: &Url) -> DeltaResult<Bytes> {
+        self.file_system.get(location)
+    }
+}
+
+impl Engine for MemoryEngine {
+    fn evaluation_handler(&self) -> Arc<dyn EvaluationHandler> {
+        self.evaluation_handler.clone()
+    }
+
+    fn storage_handler(&self) -> Arc<dyn StorageHandler> {
+        self.storage_handler.clone()
+    }
+
+    fn json_handler(&self) -> Arc<dyn JsonHandler> {
+        self.json_handler.clone()
+    }
+
+    fn parquet_handler(&self) -> Arc<dyn ParquetHandler> {
+        self.parquet_handler.clone()
+    }
+}
+
+struct MemoryStorageHandler(Arc<MemoryFileSystem>);
+
+impl StorageHandler for MemoryStorageHandler {
+    fn list_from(
+        &self,
+        path: &Url,
+    ) -> DeltaResult<Box<dyn Iterator<Item = DeltaResult<FileMeta>>>> {
+        // A directory-like path lists the whole directory, otherwise the final part of the path
+        // is the file name to list from.
+        let (dir, min_file_name) = match path.path().rsplit_once('/') {
+            Some((dir, "")) => (dir, None),
+            Some((dir, file_name)) => (dir, Some(file_name)),
+            None => {
+                return Err(Error::generic(format!(
+                    "Invalid path for list_from: {path}"
+                )))
+            }
+        };
+        let files = self.0.files.read().map_err(|_| lock_poisoned())?;
+        let mut listed: Vec<_> = files
+            .iter()
+            .filter(|(location, _)| {
+                location.scheme() == path.scheme()
+                    && location.authority() == path.authority()
+                    && location
+                        .path()
+                        .rsplit_once('/')
+                        .is_some_and(|(parent, name)| {
+                            parent == dir && min_file_name.map_or(true, |min| name > min)
+                        })
+            })
+            .map(|(location, file)| FileMeta {
+                location: location.clone(),
+                last_modified: file.last_modified,
+                size: file.data.len() as _,
+            })
+            .collect();
+        listed.sort();
+        Ok(Box::new(listed.into_iter().map(Ok)))
+    }
+
+    fn read_files(
+        &self,
+        files: Vec<FileSlice>,
+    ) -> DeltaResult<Box<dyn Iterator<Item = DeltaResult<Bytes>>>> {
+        let file_system = self.0.clone();
+        let iter = files.into_iter().map(move |(location, range)| {
+            let data = file_system.get(&location)?;
+            match range {
+                Some(range) => {
+                    let (start, end) = (range.start as usize, range.end as usize);
+                    if start > end || end > data.len() {
+                        return Err(Error::generic(format!(
+                            "Range {range:?} is out of bounds for {location} of size {}",
+                            data.len()
+                        )));
+                    }
+                    Ok(data.slice(start..end))
+                }
+                None => Ok(data),
+            }
+        });
+        Ok(Box::new(iter))
+    }
+}
+
+struct MemoryJsonHandler(Arc<MemoryFileSystem>);
+
+impl JsonHandler for MemoryJsonHandler {
+    fn read_json_files(
+        &self,
+        files: &[FileMeta],
+        schema: SchemaRef,
+        predicate: Option<ExpressionRef>,
+    ) -> DeltaResult<FileDataReadResultIterator> {
+        let file_system = self.0.clone();
+        let open_file = move |location: &Url| Ok(file_system.get(location)?.reader());
+        read_files_with(files, schema, predicate, open_file, try_create_from_json)
+    }
+
+    fn parse_json(
+        &self,
+        json_strings: Box<dyn EngineData>,
+        output_schema: SchemaRef,
+    ) -> DeltaResult<Box<dyn EngineData>> {
+        arrow_parse_json(json_strings, output_schema)
+    }
+
+    fn write_json_file(
+        &self,
+        path: &Url,
+        data: Box<dyn Iterator<Item = DeltaResult<Box<dyn EngineData>>> + Send + '_>,
+        overwrite: bool,
+    ) -> DeltaResult<()> {
+        let buffer = to_json_bytes(data)?;
+        self.0.put(path.clone(), buffer.into(), overwrite)
+    }
+}
+
+struct MemoryParquetHandler(Arc<MemoryFileSystem>);
+
+impl ParquetHandler for MemoryParquetHandler {
+    fn read_parquet_files(
+        &self,
+        files: &[FileMeta],
+        schema: SchemaRef,
+        predicate: Option<ExpressionRef>,
+    ) -> DeltaResult<FileDataReadResultIterator> {
+        let file_system = self.0.clone();
+        let open_file = move |location: &Url| file_system.get(location);
+        read_files_with(files, schema, predicate, open_file, try_create_from_parquet)
+    }
+
+    fn read_parquet_schema(&self, file: &FileMeta) -> DeltaResult<SchemaRef> {
+        read_schema_from_parquet(&self.0.get(&file.location)?)
+    }
+
+    fn write_parquet_file(
+        &self,
+        location: &Url,
+        data: Box<dyn Iterator<Item = DeltaResult<FilteredEngineData>> + Send + '_>,
+        overwrite: bool,
+    ) -> DeltaResult<()> {
+        let buffer = to_parquet_bytes(data)?;
+        self.0.put(location.clone(), buffer.into(), overwrite)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::tests::test_arrow_engine;
+
+    #[test]
+    fn test_memory_engine() {
+        let url = Url::parse("memory:///table/").unwrap();
+        let engine = MemoryEngine::new();
+        test_arrow_engine(&engine, &url);
+    }
+
+    #[test]
+    fn test_put_if_absent() {
+        let engine = MemoryEngine::new();
+        let location = Url::parse("memory:///table/file.json").unwrap();
+        let json = engine.json_handler();
+        let write =
+            |overwrite| json.write_json_file(&location, Box::new(std::iter::empty()), overwrite);
+
+        write(false).unwrap();
+        assert!(matches!(write(false), Err(Error::FileAlreadyExists(_))));
+        write(true).unwrap();
+
+        engine.put_file(location.clone(), "data").unwrap();
+        assert_eq!(engine.get_file(&location).unwrap(), "data");
+        let bytes = engine
+            .storage_handler()
+            .read_file_range(&location, 1..3)
+            .unwrap();
+        assert_eq!(bytes, "at");
+    }
+
+    #[test]
+    fn test_list_from() {
+        let engine = MemoryEngine::new();
+        let root = Url::parse("memory:///table/").unwrap();
+        for path in ["b", "a", "c", "nested/d"] {
+            engine.put_file(root.join(path).unwrap(), "").unwrap();
+        }
+        engine
+            .put_file(Url::parse("memory:///other/a").unwrap(), "")
+            .unwrap();
+        let list = |url: Url| -> Vec<_> {
+            let files = engine.storage_handler().list_from(&url).unwrap();
+            files.map(|file| file.unwrap().location).collect()
+        };
+        let expected = ["a", "b", "c"].map(|path| root.join(path).unwrap());
+        assert_eq!(list(root.clone()), expected);
+        assert_eq!(list(root.join("a").unwrap()), &expected[1..]);
+    }
+}
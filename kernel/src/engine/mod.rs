@@ -3,7 +3,7 @@
 //! related modules for more information.
 
 #[cfg(feature = "arrow-conversion")]
-pub(crate) mod arrow_conversion;
+pub mod arrow_conversion;
 
 #[cfg(all(
     feature = "arrow-expression",
@@ -19,6 +19,9 @@ pub mod default;
 #[cfg(feature = "sync-engine")]
 pub mod sync;
 
+#[cfg(feature = "memory-engine")]
+pub mod memory;
+
 #[cfg(any(feature = "default-engine-base", feature = "sync-engine"))]
 pub mod arrow_data;
 #[cfg(any(feature = "default-engine-base", feature = "sync-engine"))]
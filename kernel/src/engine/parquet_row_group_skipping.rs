@@ -2,12 +2,13 @@
 use crate::expressions::{ColumnName, DecimalData, Expression, Scalar};
 use crate::kernel_predicates::parquet_stats_skipping::ParquetStatsProvider;
 use crate::parquet::arrow::arrow_reader::ArrowReaderBuilder;
-use crate::parquet::file::metadata::RowGroupMetaData;
+use crate::parquet::file::metadata::{ParquetMetaData, RowGroupMetaData};
 use crate::parquet::file::statistics::Statistics;
 use crate::parquet::schema::types::ColumnDescPtr;
 use crate::schema::{DataType, DecimalType, PrimitiveType};
 use chrono::{DateTime, Days};
 use std::collections::HashMap;
+use std::ops::Range;
 use tracing::debug;
 
 #[cfg(test)]
@@ -15,24 +16,61 @@ mod tests;
 
 /// An extension trait for [`ArrowReaderBuilder`] that injects row group skipping capability.
 pub(crate) trait ParquetRowGroupSkipping {
-    /// Instructs the parquet reader to perform row group skipping, eliminating any row group whose
-    /// stats prove that none of the group's rows can satisfy the given `predicate`.
-    fn with_row_group_filter(self, predicate: &Expression) -> Self;
+    /// Instructs the parquet reader to decode only the row groups chosen by `selection`.
+    fn with_row_group_selection(self, selection: &RowGroupSelection) -> Self;
 }
 impl<T> ParquetRowGroupSkipping for ArrowReaderBuilder<T> {
-    fn with_row_group_filter(self, predicate: &Expression) -> Self {
-        let indices = self
-            .metadata()
-            .row_groups()
-            .iter()
-            .enumerate()
-            .filter_map(|(index, row_group)| {
-                // If the group survives the filter, return Some(index) so filter_map keeps it.
-                RowGroupFilter::apply(row_group, predicate).then_some(index)
-            })
-            .collect();
-        debug!("with_row_group_filter({predicate:#?}) = {indices:?})");
-        self.with_row_groups(indices)
+    fn with_row_group_selection(self, selection: &RowGroupSelection) -> Self {
+        self.with_row_groups(selection.row_groups.clone())
+    }
+}
+
+/// The row groups of a parquet file that survive row group skipping, along with the range of file
+/// row indexes that each of them covers.
+///
+/// Deletion vectors address rows by their index within the whole file, so once row groups are
+/// skipped the rows a reader produces no longer line up with a file-level selection vector. Use
+/// [`RowGroupSelection::row_ranges`] to recover the file row index of each row actually read.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RowGroupSelection {
+    row_groups: Vec<usize>,
+    row_ranges: Vec<Range<u64>>,
+}
+
+impl RowGroupSelection {
+    /// Selects every row group of the file whose stats do not prove that `predicate` is false for
+    /// all of its rows. With no predicate, every row group is selected.
+    pub fn new(metadata: &ParquetMetaData, predicate: Option<&Expression>) -> Self {
+        let mut row_groups = vec![];
+        let mut row_ranges = vec![];
+        let mut offset = 0;
+        for (index, row_group) in metadata.row_groups().iter().enumerate() {
+            let num_rows = u64::try_from(row_group.num_rows()).unwrap_or_default();
+            if predicate.map_or(true, |predicate| {
+                RowGroupFilter::apply(row_group, predicate)
+            }) {
+                row_groups.push(index);
+                row_ranges.push(offset..offset + num_rows);
+            }
+            offset += num_rows;
+        }
+        if let Some(predicate) = predicate {
+            debug!("RowGroupSelection::new({predicate:#?}) = {row_groups:?}");
+        }
+        Self {
+            row_groups,
+            row_ranges,
+        }
+    }
+
+    /// The indexes of the selected row groups, in file order.
+    pub fn row_groups(&self) -> &[usize] {
+        &self.row_groups
+    }
+
+    /// The file row indexes covered by each selected row group, in file order.
+    pub fn row_ranges(&self) -> &[Range<u64>] {
+        &self.row_ranges
     }
 }
 
@@ -412,3 +412,103 @@ fn test_get_stat_values() {
         )
     );
 }
+
+/// Writes a parquet file with a single `x: long` column holding `0..9`, split into three row
+/// groups of three rows each.
+fn three_row_group_file() -> bytes::Bytes {
+    use crate::arrow::array::{Int64Array, RecordBatch};
+    use crate::arrow::datatypes::{DataType as ArrowDataType, Field, Schema as ArrowSchema};
+    use crate::parquet::arrow::ArrowWriter;
+    use crate::parquet::file::properties::WriterProperties;
+    use std::sync::Arc;
+
+    let schema = Arc::new(ArrowSchema::new(vec![Field::new(
+        "x",
+        ArrowDataType::Int64,
+        false,
+    )]));
+    let batch = RecordBatch::try_new(
+        schema.clone(),
+        vec![Arc::new(Int64Array::from_iter_values(0..9))],
+    )
+    .unwrap();
+    let props = WriterProperties::builder()
+        .set_max_row_group_size(3)
+        .build();
+    let mut buffer = vec![];
+    let mut writer = ArrowWriter::try_new(&mut buffer, schema, Some(props)).unwrap();
+    writer.write(&batch).unwrap();
+    writer.close().unwrap();
+    buffer.into()
+}
+
+/// Reads the selected row groups of `data` and returns the `x` values of the rows that
+/// `selection_vector` keeps, looking up each row by its file row index.
+fn read_selected(
+    data: bytes::Bytes,
+    selection: &RowGroupSelection,
+    selection_vector: &[bool],
+) -> Vec<i64> {
+    use crate::arrow::array::{AsArray as _, RecordBatch};
+    use crate::arrow::datatypes::Int64Type;
+    use crate::parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+
+    let batches: Vec<RecordBatch> = ParquetRecordBatchReaderBuilder::try_new(data)
+        .unwrap()
+        .with_row_group_selection(selection)
+        .build()
+        .unwrap()
+        .map(Result::unwrap)
+        .collect();
+    let values = batches.iter().flat_map(|batch| {
+        batch
+            .column(0)
+            .as_primitive::<Int64Type>()
+            .values()
+            .to_vec()
+    });
+    let row_indexes = selection.row_ranges().iter().flat_map(Range::clone);
+    assert_eq!(row_indexes.clone().count(), values.clone().count());
+    let mask = row_indexes.map(|row| {
+        // as with deletion vectors, rows past the end of the selection vector are selected
+        selection_vector.get(row as usize).copied().unwrap_or(true)
+    });
+    values
+        .zip(mask)
+        .filter_map(|(value, selected)| selected.then_some(value))
+        .collect()
+}
+
+#[test]
+fn test_row_group_selection() {
+    let data = three_row_group_file();
+    let metadata = ArrowReaderMetadata::load(&data, Default::default()).unwrap();
+    let metadata = metadata.metadata();
+    assert_eq!(metadata.num_row_groups(), 3);
+
+    // deletion vector for rows 1 and 7, as a file-level selection vector
+    let dv = [true, false, true, true, true, true, true, false];
+
+    let selection = RowGroupSelection::new(metadata, None);
+    assert_eq!(selection.row_groups(), &[0, 1, 2]);
+    assert_eq!(selection.row_ranges(), &[0..3, 3..6, 6..9]);
+    let expected = vec![0, 2, 3, 4, 5, 6, 8];
+    assert_eq!(read_selected(data.clone(), &selection, &dv), expected);
+
+    // prunes the first two row groups
+    let predicate = column_expr!("x").gt(Expression::literal(6i64));
+    let selection = RowGroupSelection::new(metadata, Some(&predicate));
+    assert_eq!(selection.row_groups(), &[2]);
+    assert_eq!(selection.row_ranges(), &[Range { start: 6, end: 9 }]);
+    assert_eq!(read_selected(data.clone(), &selection, &dv), vec![6, 8]);
+
+    // prunes the middle row group, so the offsets of the last one must skip over it
+    let predicate = Expression::or(
+        column_expr!("x").lt(Expression::literal(1i64)),
+        column_expr!("x").gt(Expression::literal(7i64)),
+    );
+    let selection = RowGroupSelection::new(metadata, Some(&predicate));
+    assert_eq!(selection.row_groups(), &[0, 2]);
+    assert_eq!(selection.row_ranges(), &[0..3, 6..9]);
+    assert_eq!(read_selected(data, &selection, &dv), vec![0, 2, 6, 8]);
+}
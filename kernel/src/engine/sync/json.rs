@@ -1,4 +1,4 @@
-use std::{fs::File, io::BufReader, io::Write};
+use std::io::{BufReader, Read, Write};
 
 use crate::arrow::datatypes::SchemaRef as ArrowSchemaRef;
 use crate::arrow::json::ReaderBuilder;
@@ -17,8 +17,8 @@ use crate::{
 
 pub(crate) struct SyncJsonHandler;
 
-fn try_create_from_json(
-    file: File,
+pub(crate) fn try_create_from_json(
+    file: impl Read,
     _schema: SchemaRef,
     arrow_schema: ArrowSchemaRef,
     _predicate: Option<ExpressionRef>,
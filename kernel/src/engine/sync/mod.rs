@@ -12,9 +12,10 @@ use itertools::Itertools;
 use std::fs::File;
 use std::sync::Arc;
 use tracing::debug;
+use url::Url;
 
 pub(crate) mod json;
-mod parquet;
+pub(crate) mod parquet;
 mod storage;
 
 /// This is a simple implementation of [`Engine`]. It only supports reading data from the local
@@ -57,17 +58,50 @@ impl Engine for SyncEngine {
     }
 }
 
+/// Read `files` with the local filesystem, see [`read_files_with`].
 fn read_files<F, I>(
     files: &[FileMeta],
     schema: SchemaRef,
     predicate: Option<ExpressionRef>,
-    mut try_create_from_file: F,
+    try_create_from_file: F,
 ) -> DeltaResult<FileDataReadResultIterator>
 where
     I: Iterator<Item = DeltaResult<ArrowEngineData>> + Send + 'static,
     F: FnMut(File, SchemaRef, ArrowSchemaRef, Option<ExpressionRef>) -> DeltaResult<I>
         + Send
         + 'static,
+{
+    read_files_with(
+        files,
+        schema,
+        predicate,
+        open_local_file,
+        try_create_from_file,
+    )
+}
+
+fn open_local_file(location: &Url) -> DeltaResult<File> {
+    let path = location
+        .to_file_path()
+        .map_err(|_| Error::generic("can only read local files"))?;
+    Ok(File::open(path)?)
+}
+
+/// Lazily read `files` one after the other: each file is opened with `open_file` and decoded into
+/// batches with `try_create_from_file`.
+pub(crate) fn read_files_with<R, O, F, I>(
+    files: &[FileMeta],
+    schema: SchemaRef,
+    predicate: Option<ExpressionRef>,
+    open_file: O,
+    mut try_create_from_file: F,
+) -> DeltaResult<FileDataReadResultIterator>
+where
+    I: Iterator<Item = DeltaResult<ArrowEngineData>> + Send + 'static,
+    O: Fn(&Url) -> DeltaResult<R> + Send + 'static,
+    F: FnMut(R, SchemaRef, ArrowSchemaRef, Option<ExpressionRef>) -> DeltaResult<I>
+        + Send
+        + 'static,
 {
     debug!("Reading files: {files:#?} with schema {schema:#?} and predicate {predicate:#?}");
     if files.is_empty() {
@@ -81,11 +115,8 @@ where
         .map(move |file| {
             let location = file.location;
             debug!("Reading {location:#?} with schema {schema:#?} and predicate {predicate:#?}");
-            let path = location
-                .to_file_path()
-                .map_err(|_| Error::generic("can only read local files"))?;
             try_create_from_file(
-                File::open(path)?,
+                open_file(&location)?,
                 schema.clone(),
                 arrow_schema.clone(),
                 predicate.clone(),
@@ -1,35 +1,52 @@
-use std::fs::File;
+use std::io::Write as _;
+use std::sync::Arc;
 
 use crate::arrow::datatypes::SchemaRef as ArrowSchemaRef;
 use crate::parquet::arrow::arrow_reader::{ArrowReaderMetadata, ParquetRecordBatchReaderBuilder};
+use crate::parquet::file::reader::ChunkReader;
+use tempfile::NamedTempFile;
+use url::Url;
 
-use super::read_files;
+use super::{open_local_file, read_files};
 use crate::engine::arrow_data::ArrowEngineData;
-use crate::engine::arrow_utils::{fixup_parquet_read, generate_mask, get_requested_indices};
-use crate::engine::parquet_row_group_skipping::ParquetRowGroupSkipping;
-use crate::schema::SchemaRef;
-use crate::{DeltaResult, ExpressionRef, FileDataReadResultIterator, FileMeta, ParquetHandler};
+use crate::engine::arrow_utils::{
+    fixup_parquet_read, generate_mask, get_requested_indices, to_parquet_bytes,
+    with_string_view_read_schema, RowIndexes,
+};
+use crate::engine::parquet_row_group_skipping::{ParquetRowGroupSkipping, RowGroupSelection};
+use crate::schema::{SchemaRef, StructType};
+use crate::{
+    DeltaResult, Error, ExpressionRef, FileDataReadResultIterator, FileMeta, FilteredEngineData,
+    ParquetHandler,
+};
 
 pub(crate) struct SyncParquetHandler;
 
-fn try_create_from_parquet(
-    file: File,
+pub(crate) fn try_create_from_parquet(
+    file: impl ChunkReader + 'static,
     schema: SchemaRef,
     _arrow_schema: ArrowSchemaRef,
     predicate: Option<ExpressionRef>,
 ) -> DeltaResult<impl Iterator<Item = DeltaResult<ArrowEngineData>>> {
     let metadata = ArrowReaderMetadata::load(&file, Default::default())?;
+    let metadata = with_string_view_read_schema(&schema, metadata)?;
     let parquet_schema = metadata.schema();
-    let mut builder = ParquetRecordBatchReaderBuilder::try_new(file)?;
+    let mut builder = ParquetRecordBatchReaderBuilder::new_with_metadata(file, metadata.clone());
     let (indices, requested_ordering) = get_requested_indices(&schema, parquet_schema)?;
     if let Some(mask) = generate_mask(&schema, parquet_schema, builder.parquet_schema(), &indices) {
         builder = builder.with_projection(mask);
     }
-    if let Some(predicate) = predicate {
-        builder = builder.with_row_group_filter(predicate.as_ref());
-    }
+    let selection = RowGroupSelection::new(builder.metadata(), predicate.as_deref());
+    builder = builder.with_row_group_selection(&selection);
+    let mut row_indexes = RowIndexes::new(selection.row_ranges().to_vec());
     let stream = builder.build()?;
-    Ok(stream.map(move |rbr| fixup_parquet_read(rbr?, &requested_ordering)))
+    Ok(stream.map(move |rbr| fixup_parquet_read(rbr?, &requested_ordering, &mut row_indexes)))
+}
+
+/// Read the schema of the parquet `file` from its footer.
+pub(crate) fn read_schema_from_parquet(file: &impl ChunkReader) -> DeltaResult<SchemaRef> {
+    let metadata = ArrowReaderMetadata::load(file, Default::default())?;
+    Ok(Arc::new(StructType::try_from(metadata.schema().as_ref())?))
 }
 
 impl ParquetHandler for SyncParquetHandler {
@@ -41,4 +58,45 @@ impl ParquetHandler for SyncParquetHandler {
     ) -> DeltaResult<FileDataReadResultIterator> {
         read_files(files, schema, predicate, try_create_from_parquet)
     }
+
+    fn read_parquet_schema(&self, file: &FileMeta) -> DeltaResult<SchemaRef> {
+        read_schema_from_parquet(&open_local_file(&file.location)?)
+    }
+
+    // Like the sync JSON writer, the data is written to a tmp file which is then atomically renamed
+    // to the final path.
+    fn write_parquet_file(
+        &self,
+        location: &Url,
+        data: Box<dyn Iterator<Item = DeltaResult<FilteredEngineData>> + Send + '_>,
+        overwrite: bool,
+    ) -> DeltaResult<()> {
+        let path = location
+            .to_file_path()
+            .map_err(|_| Error::generic("sync client can only write local files"))?;
+        let Some(parent) = path.parent() else {
+            return Err(Error::generic(format!("no parent found for {path:?}")));
+        };
+        if !parent.exists() {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        let mut tmp_file = NamedTempFile::new_in(parent)?;
+        tmp_file.write_all(&to_parquet_bytes(data)?)?;
+        tmp_file.flush()?;
+
+        let persist_result = if overwrite {
+            tmp_file.persist(path.clone())
+        } else {
+            tmp_file.persist_noclobber(path.clone())
+        };
+        persist_result.map_err(|e| {
+            if !overwrite && e.error.kind() == std::io::ErrorKind::AlreadyExists {
+                Error::FileAlreadyExists(path.to_string_lossy().to_string())
+            } else {
+                Error::IOError(e.into())
+            }
+        })?;
+        Ok(())
+    }
 }
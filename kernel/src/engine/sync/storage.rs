@@ -118,6 +118,20 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_read_file_range() -> Result<(), Box<dyn std::error::Error>> {
+        let storage = SyncStorageHandler;
+        let tmp_dir = tempfile::tempdir().unwrap();
+        let path = tmp_dir.path().join(get_json_filename(0));
+        std::fs::write(&path, "kernel-data")?;
+        let url = Url::from_file_path(path).unwrap();
+
+        assert_eq!(storage.read_file_range(&url, 0..6)?, "kernel");
+        assert_eq!(storage.read_file_range(&url, 7..11)?, "data");
+        assert!(storage.read_file_range(&url, 7..12).is_err());
+        Ok(())
+    }
+
     #[test]
     fn test_list_from() -> Result<(), Box<dyn std::error::Error>> {
         let storage = SyncStorageHandler;
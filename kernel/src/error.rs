@@ -7,6 +7,7 @@ use std::{
 };
 
 use crate::schema::{DataType, StructType};
+use crate::table_features::{ReaderOrWriter, TableFeature};
 use crate::table_properties::ParseIntervalError;
 use crate::Version;
 
@@ -187,6 +188,13 @@ pub enum Error {
     #[error("Unsupported: {0}")]
     Unsupported(String),
 
+    /// The table requires a reader or writer feature that the kernel does not support
+    #[error("Unsupported {kind} feature: \"{feature}\"")]
+    UnsupportedFeature {
+        feature: TableFeature,
+        kind: ReaderOrWriter,
+    },
+
     /// Parsing error when attempting to deserialize an interval
     #[error(transparent)]
     ParseIntervalError(#[from] ParseIntervalError),
@@ -201,6 +209,10 @@ pub enum Error {
     #[error("Invalid Checkpoint: {0}")]
     InvalidCheckpoint(String),
 
+    /// Data to be written is not compatible with the table schema
+    #[error(transparent)]
+    IncompatibleWriteSchema(#[from] crate::schema::compatibility::Error),
+
     /// Error while transforming a schema + leaves into an Expression of literals
     #[error(transparent)]
     LiteralExpressionTransformError(
@@ -269,6 +281,11 @@ impl Error {
     pub fn unsupported(msg: impl ToString) -> Self {
         Self::Unsupported(msg.to_string())
     }
+    pub fn unsupported_feature(feature: impl Into<TableFeature>) -> Self {
+        let feature = feature.into();
+        let kind = feature.kind();
+        Self::UnsupportedFeature { feature, kind }
+    }
     pub fn change_data_feed_unsupported(version: impl Into<Version>) -> Self {
         Self::ChangeDataFeedUnsupported(version.into())
     }
@@ -616,6 +616,23 @@ mod test {
         }
     }
 
+    #[test]
+    fn test_column_name_with_dotted_field() {
+        // a top-level field literally named `a.b` is distinct from field `b` nested in `a`
+        let dotted = ColumnName::new(["a.b"]);
+        let nested = ColumnName::new(["a", "b"]);
+        assert_ne!(dotted, nested);
+        assert_eq!(dotted.to_string(), "`a.b`");
+        assert_eq!(nested.to_string(), "a.b");
+        assert_eq!(dotted.to_string().parse::<ColumnName>().unwrap(), dotted);
+        assert_eq!(nested.to_string().parse::<ColumnName>().unwrap(), nested);
+
+        let joined = dotted.join(&ColumnName::new(["c.d"]));
+        assert_eq!(joined.path(), ["a.b", "c.d"]);
+        assert_eq!(joined.to_string(), "`a.b`.`c.d`");
+        assert_eq!(joined.to_string().parse::<ColumnName>().unwrap(), joined);
+    }
+
     #[test]
     fn test_parse_column_name_list() {
         let cases = [
@@ -1,6 +1,6 @@
 //! Definitions and functions to create and manipulate kernel expressions
 
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::fmt::{Display, Formatter};
 
 use itertools::Itertools;
@@ -11,11 +11,13 @@ pub use self::column_names::{
 pub use self::scalars::{ArrayData, DecimalData, Scalar, StructData};
 use self::transforms::GetColumnReferences;
 pub use self::transforms::{ExpressionDepthChecker, ExpressionTransform};
-use crate::DataType;
+use crate::kernel_predicates::{DefaultKernelPredicateEvaluator, KernelPredicateEvaluator as _};
+use crate::{DataType, DeltaResult, Error};
 
 mod column_names;
 pub(crate) mod literal_expression_transform;
 mod scalars;
+mod simplify;
 pub mod transforms;
 
 pub type ExpressionRef = std::sync::Arc<Expression>;
@@ -193,6 +195,26 @@ impl Expression {
         references.into_inner()
     }
 
+    /// Evaluates this predicate against a single row, given as the value of each column it
+    /// references, with SQL's three-valued logic: `Some(true)`, `Some(false)`, or `None` for NULL.
+    /// Comparisons, `AND`, `OR`, `NOT`, `IS [NOT] NULL`, `DISTINCT` and `[NOT] IN` are supported.
+    /// Comparisons involving a NULL value, as well as unsupported (e.g. arithmetic) or ill-typed
+    /// sub-expressions, evaluate to NULL.
+    ///
+    /// A NULL column value is given as [`Scalar::Null`]. Fails if a referenced column is missing
+    /// from `row`.
+    pub fn evaluate_row(&self, row: &HashMap<ColumnName, Scalar>) -> DeltaResult<Option<bool>> {
+        if let Some(missing) = self
+            .references()
+            .into_iter()
+            .find(|col| !row.contains_key(*col))
+        {
+            return Err(Error::missing_column(missing));
+        }
+        let evaluator = DefaultKernelPredicateEvaluator::from(row.clone());
+        Ok(evaluator.eval_expr(self, false))
+    }
+
     /// Create a new column name expression from input satisfying `FromIterator for ColumnName`.
     pub fn column<A>(field_names: impl IntoIterator<Item = A>) -> Expression
     where
@@ -312,6 +334,32 @@ impl Expression {
     }
 }
 
+/// Create a column reference expression from a dot-separated column path, e.g. `col("a.b")`
+/// references the nested field `b` of the struct column `a`. Field names that themselves contain
+/// dots cannot be referenced this way, use [`Expression::column`] instead. Together with [`lit`]
+/// and the comparison methods of [`Expression`] and its `&` (AND) and `|` (OR) operators, this
+/// allows building predicates fluently:
+///
+/// ```
+/// # use delta_kernel::expressions::{col, column_expr, lit, Expression};
+/// let predicate = col("a").gt(lit(5)) & col("b.c").is_not_null();
+/// assert_eq!(
+///     predicate,
+///     Expression::and_from([
+///         Expression::gt(column_expr!("a"), Expression::literal(5)),
+///         Expression::not(Expression::is_null(column_expr!("b.c"))),
+///     ])
+/// );
+/// ```
+pub fn col(name: impl AsRef<str>) -> Expression {
+    Expression::Column(ColumnName::from_naive_str_split(name))
+}
+
+/// Create a literal expression from any value that converts into a [`Scalar`].
+pub fn lit(value: impl Into<Scalar>) -> Expression {
+    Expression::literal(value)
+}
+
 ////////////////////////////////////////////////////////////////////////
 // Trait impls
 ////////////////////////////////////////////////////////////////////////
@@ -417,9 +465,206 @@ impl<R: Into<Expression>> std::ops::Div<R> for Expression {
     }
 }
 
+impl<R: Into<Expression>> std::ops::BitAnd<R> for Expression {
+    type Output = Self;
+
+    fn bitand(self, rhs: R) -> Self {
+        Self::and(self, rhs)
+    }
+}
+
+impl<R: Into<Expression>> std::ops::BitOr<R> for Expression {
+    type Output = Self;
+
+    fn bitor(self, rhs: R) -> Self {
+        Self::or(self, rhs)
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use super::{column_expr, Expression as Expr};
+    use std::collections::HashMap;
+
+    use super::{
+        col, column_expr, column_name, lit, ArrayData, BinaryOperator, ColumnName,
+        Expression as Expr, JunctionOperator, Scalar, UnaryOperator,
+    };
+    use crate::schema::{ArrayType, DataType};
+
+    #[test]
+    fn test_fluent_builder() {
+        let expr = col("a").gt(lit(5)) & col("b").is_not_null();
+        let expected = Expr::junction(
+            JunctionOperator::And,
+            [
+                Expr::binary(
+                    BinaryOperator::GreaterThan,
+                    Expr::Column(ColumnName::new(["a"])),
+                    Expr::Literal(Scalar::Integer(5)),
+                ),
+                Expr::unary(
+                    UnaryOperator::Not,
+                    Expr::unary(UnaryOperator::IsNull, Expr::Column(ColumnName::new(["b"]))),
+                ),
+            ],
+        );
+        assert_eq!(expr, expected);
+
+        // dotted paths reference nested fields
+        let expr = col("a.b.c").eq(lit("x")) | col("d").le(lit(1.5));
+        let expected = Expr::junction(
+            JunctionOperator::Or,
+            [
+                Expr::binary(
+                    BinaryOperator::Equal,
+                    Expr::Column(ColumnName::new(["a", "b", "c"])),
+                    Expr::Literal(Scalar::String("x".into())),
+                ),
+                Expr::binary(
+                    BinaryOperator::LessThanOrEqual,
+                    Expr::Column(ColumnName::new(["d"])),
+                    Expr::Literal(Scalar::Double(1.5)),
+                ),
+            ],
+        );
+        assert_eq!(expr, expected);
+
+        // literals of each scalar type
+        let literals = [
+            (lit(1i8), Scalar::Byte(1)),
+            (lit(1i16), Scalar::Short(1)),
+            (lit(1i32), Scalar::Integer(1)),
+            (lit(1i64), Scalar::Long(1)),
+            (lit(1.0f32), Scalar::Float(1.0)),
+            (lit(1.0f64), Scalar::Double(1.0)),
+            (lit(true), Scalar::Boolean(true)),
+            (lit("s"), Scalar::String("s".into())),
+            (lit(String::from("s")), Scalar::String("s".into())),
+            (lit(&[1u8, 2][..]), Scalar::Binary(vec![1, 2])),
+        ];
+        for (expr, scalar) in literals {
+            assert_eq!(expr, Expr::Literal(scalar));
+        }
+
+        let expr = col("a").ne(lit(1)) & (col("a").lt(col("b")) | col("c").is_null());
+        assert_eq!(
+            expr,
+            Expr::and_from([
+                Expr::ne(column_expr!("a"), Expr::literal(1)),
+                Expr::or_from([
+                    Expr::lt(column_expr!("a"), column_expr!("b")),
+                    Expr::is_null(column_expr!("c")),
+                ]),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_evaluate_row_junctions() {
+        let values = [Some(true), Some(false), None];
+        let literal = |value: Option<bool>| match value {
+            Some(value) => Scalar::from(value),
+            None => Scalar::Null(DataType::BOOLEAN),
+        };
+        // SQL's three-valued AND/OR: FALSE (TRUE) dominates AND (OR), otherwise NULL dominates
+        let and = |a, b| match (a, b) {
+            (Some(false), _) | (_, Some(false)) => Some(false),
+            (Some(true), Some(true)) => Some(true),
+            _ => None,
+        };
+        let or = |a, b| match (a, b) {
+            (Some(true), _) | (_, Some(true)) => Some(true),
+            (Some(false), Some(false)) => Some(false),
+            _ => None,
+        };
+        for a in values {
+            for b in values {
+                let row = HashMap::from([
+                    (column_name!("a"), literal(a)),
+                    (column_name!("b"), literal(b)),
+                ]);
+                let expr = Expr::and(column_expr!("a"), column_expr!("b"));
+                assert_eq!(
+                    expr.evaluate_row(&row).unwrap(),
+                    and(a, b),
+                    "{a:?} AND {b:?}"
+                );
+                let expr = Expr::or(column_expr!("a"), column_expr!("b"));
+                assert_eq!(expr.evaluate_row(&row).unwrap(), or(a, b), "{a:?} OR {b:?}");
+                let expr = Expr::not(column_expr!("a"));
+                assert_eq!(expr.evaluate_row(&row).unwrap(), a.map(|a| !a), "NOT {a:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_evaluate_row() {
+        let row = HashMap::from([
+            (column_name!("x"), Scalar::from(5)),
+            (column_name!("s"), Scalar::from("b")),
+            (column_name!("n"), Scalar::Null(DataType::INTEGER)),
+        ]);
+        let in_list = |values: [i32; 2]| {
+            Scalar::Array(ArrayData::new(
+                ArrayType::new(DataType::INTEGER, true),
+                values,
+            ))
+        };
+        let cases = [
+            (column_expr!("x").lt(Expr::literal(10)), Some(true)),
+            (column_expr!("x").ge(Expr::literal(10)), Some(false)),
+            (Expr::literal(5).eq(column_expr!("x")), Some(true)),
+            (column_expr!("s").ne(Expr::literal("a")), Some(true)),
+            (column_expr!("x").gt(column_expr!("x")), Some(false)),
+            // comparisons with NULL are NULL
+            (column_expr!("n").lt(Expr::literal(10)), None),
+            (column_expr!("n").eq(column_expr!("x")), None),
+            // but null checks are not
+            (column_expr!("n").is_null(), Some(true)),
+            (column_expr!("n").is_not_null(), Some(false)),
+            (column_expr!("x").is_null(), Some(false)),
+            (column_expr!("n").distinct(Expr::literal(1)), Some(true)),
+            (
+                Expr::binary(
+                    super::BinaryOperator::In,
+                    column_expr!("x"),
+                    in_list([1, 5]),
+                ),
+                Some(true),
+            ),
+            (
+                Expr::binary(
+                    super::BinaryOperator::NotIn,
+                    column_expr!("x"),
+                    in_list([1, 2]),
+                ),
+                Some(true),
+            ),
+            (
+                Expr::binary(
+                    super::BinaryOperator::In,
+                    column_expr!("n"),
+                    in_list([1, 5]),
+                ),
+                None,
+            ),
+            // a type mismatch can't be compared
+            (column_expr!("x").eq(Expr::literal("5")), None),
+        ];
+        for (expr, expected) in cases {
+            assert_eq!(expr.evaluate_row(&row).unwrap(), expected, "{expr}");
+        }
+
+        let expr = Expr::and(
+            column_expr!("x").is_null(),
+            column_expr!("missing").is_null(),
+        );
+        let err = expr.evaluate_row(&row).unwrap_err();
+        assert!(err.to_string().starts_with("missing"), "{err}");
+        let nested: ColumnName = column_name!("a.b");
+        let err = Expr::from(nested).evaluate_row(&row).unwrap_err();
+        assert!(err.to_string().starts_with("a.b"), "{err}");
+    }
 
     #[test]
     fn test_expression_format() {
@@ -43,6 +43,22 @@ impl DecimalData {
     pub fn scale(&self) -> u8 {
         self.ty.scale()
     }
+
+    /// Compares the values of two decimals, regardless of their precision and scale. The integer
+    /// parts are compared first, so only the fractional parts (which are below `10^38`) need to be
+    /// brought to a common scale, which can't overflow.
+    fn cmp_aligned(&self, other: &DecimalData) -> Ordering {
+        let split = |d: &DecimalData| {
+            let factor = 10_i128.pow(d.scale().into());
+            (d.bits / factor, d.bits % factor)
+        };
+        let ((int_a, frac_a), (int_b, frac_b)) = (split(self), split(other));
+        let scale = self.scale().max(other.scale());
+        let align = |frac: i128, d: &DecimalData| frac * 10_i128.pow((scale - d.scale()).into());
+        int_a
+            .cmp(&int_b)
+            .then_with(|| align(frac_a, self).cmp(&align(frac_b, other)))
+    }
 }
 
 /// Computes the decimal precision of a 128-bit number. The largest possible magnitude is i128::MIN
@@ -220,6 +236,50 @@ impl Scalar {
         Ok(Self::Decimal(dval))
     }
 
+    /// Constructs a scalar of the given type from its string representation, e.g. a literal a
+    /// user typed into a filter. Strings are parsed like partition values, except that decimals
+    /// may be written with fewer fractional digits than the type's scale. An empty string is a
+    /// null of that type.
+    ///
+    /// Fails if `value` is not a valid value of `data_type`, or if `data_type` is not primitive.
+    pub fn try_from_string(value: &str, data_type: &DataType) -> DeltaResult<Self> {
+        match data_type {
+            DataType::Primitive(PrimitiveType::Decimal(dtype)) if !value.is_empty() => {
+                PrimitiveType::parse_decimal_rescaled(value, *dtype)
+            }
+            DataType::Primitive(primitive) => primitive.parse_scalar(value),
+            _ => Err(Error::generic(format!(
+                "Cannot construct a scalar of type {data_type} from a string"
+            ))),
+        }
+    }
+
+    /// Compares two scalars with Delta's comparison semantics. Unlike [`PartialOrd::partial_cmp`],
+    /// which only orders values of identical types, this aligns the scales of decimals of
+    /// different types, orders dates against the timestamps of their midnight, and orders
+    /// microsecond against nanosecond timestamps. Returns `None` if the values are incomparable,
+    /// e.g. because either is null or their types can't be compared.
+    pub fn partial_cmp_typed(&self, other: &Scalar) -> Option<Ordering> {
+        use Scalar::*;
+        const MICROS_PER_DAY: i128 = 86_400_000_000;
+        match (self, other) {
+            (Decimal(a), Decimal(b)) => Some(a.cmp_aligned(b)),
+            (Date(days), Timestamp(micros) | TimestampNtz(micros)) => {
+                Some((i128::from(*days) * MICROS_PER_DAY).cmp(&i128::from(*micros)))
+            }
+            (Timestamp(micros) | TimestampNtz(micros), Date(days)) => {
+                Some(i128::from(*micros).cmp(&(i128::from(*days) * MICROS_PER_DAY)))
+            }
+            (Timestamp(micros), TimestampNs(nanos)) => {
+                Some((i128::from(*micros) * 1000).cmp(&i128::from(*nanos)))
+            }
+            (TimestampNs(nanos), Timestamp(micros)) => {
+                Some(i128::from(*nanos).cmp(&(i128::from(*micros) * 1000)))
+            }
+            _ => self.partial_cmp(other),
+        }
+    }
+
     /// Constructs a Scalar timestamp with no timezone from an `i64` millisecond since unix epoch
     pub(crate) fn timestamp_ntz_from_millis(millis: i64) -> DeltaResult<Self> {
         let Some(timestamp) = DateTime::from_timestamp_millis(millis) else {
@@ -575,6 +635,34 @@ impl PrimitiveType {
     }
 
     fn parse_decimal(raw: &str, dtype: DecimalType) -> Result<Scalar, Error> {
+        let parse_error = || PrimitiveType::from(dtype).parse_error(raw);
+        let (int, scale) = Self::parse_decimal_parts(raw, parse_error)?;
+        let scale: u8 = scale.try_into().map_err(|_| parse_error())?;
+        require!(scale == dtype.scale(), parse_error());
+        Ok(Scalar::Decimal(DecimalData::try_new(int, dtype)?))
+    }
+
+    /// Like [`PrimitiveType::parse_decimal`], but accepts any value that can be represented at
+    /// `dtype`'s scale without losing digits, e.g. `1.5` as a `decimal(5, 2)` is `1.50`.
+    fn parse_decimal_rescaled(raw: &str, dtype: DecimalType) -> Result<Scalar, Error> {
+        let parse_error = || PrimitiveType::from(dtype).parse_error(raw);
+        let (int, scale) = Self::parse_decimal_parts(raw, parse_error)?;
+        let shift: u32 = (i128::from(dtype.scale()) - scale)
+            .try_into()
+            .map_err(|_| parse_error())?;
+        let int = 10_i128
+            .checked_pow(shift)
+            .and_then(|factor| int.checked_mul(factor))
+            .ok_or_else(parse_error)?;
+        Ok(Scalar::Decimal(DecimalData::try_new(int, dtype)?))
+    }
+
+    /// Splits a decimal string into its unscaled integer value and its scale, which is negative
+    /// for values like `12E3`.
+    fn parse_decimal_parts(
+        raw: &str,
+        parse_error: impl Fn() -> Error,
+    ) -> Result<(i128, i128), Error> {
         let (base, exp): (&str, i128) = match raw.find(['e', 'E']) {
             None => (raw, 0), // no 'e' or 'E', so there's no exponent
             Some(pos) => {
@@ -583,7 +671,6 @@ impl PrimitiveType {
                 (base, exp[1..].parse()?)
             }
         };
-        let parse_error = || PrimitiveType::from(dtype).parse_error(raw);
         require!(!base.is_empty(), parse_error());
 
         // now split on any '.' and parse
@@ -605,13 +692,11 @@ impl PrimitiveType {
         // we can assume this won't underflow since `frac_digits` is at minimum 0, and exp is at
         // most i128::MAX, and 0-i128::MAX doesn't underflow
         let scale = frac_digits - exp;
-        let scale: u8 = scale.try_into().map_err(|_| parse_error())?;
-        require!(scale == dtype.scale(), parse_error());
         let int: i128 = match frac_part {
             None => int_part.parse()?,
             Some(frac_part) => format!("{}{}", int_part, frac_part).parse()?,
         };
-        Ok(Scalar::Decimal(DecimalData::try_new(int, dtype)?))
+        Ok((int, scale))
     }
 }
 
@@ -855,6 +940,82 @@ mod tests {
         assert_eq!(null.partial_cmp(&null), None);
     }
 
+    #[test]
+    fn test_try_from_string() -> DeltaResult<()> {
+        let date = Scalar::try_from_string("2024-02-29", &DataType::DATE)?;
+        assert!(matches!(date, Scalar::Date(19782)));
+
+        let decimal = DataType::decimal(5, 2)?;
+        let value = Scalar::try_from_string("1.5", &decimal)?;
+        assert_eq!(value, Scalar::decimal(150, 5, 2)?);
+        let value = Scalar::try_from_string("-12.25", &decimal)?;
+        assert_eq!(value, Scalar::decimal(-1225, 5, 2)?);
+        let value = Scalar::try_from_string("1E2", &decimal)?;
+        assert_eq!(value, Scalar::decimal(10000, 5, 2)?);
+        // too many fractional digits, or too many digits overall
+        Scalar::try_from_string("1.234", &decimal).expect_err("loses digits");
+        Scalar::try_from_string("1234", &decimal).expect_err("exceeds precision");
+
+        let value = Scalar::try_from_string("abc", &DataType::STRING)?;
+        assert_eq!(value, Scalar::from("abc"));
+        assert!(Scalar::try_from_string("", &DataType::STRING)?.is_null());
+
+        // type mismatches
+        let err = Scalar::try_from_string("abc", &DataType::INTEGER).unwrap_err();
+        assert!(
+            matches!(err, Error::ParseError(_, DataType::INTEGER)),
+            "{err}"
+        );
+        let err = Scalar::try_from_string("2024-13-01", &DataType::DATE).unwrap_err();
+        assert!(matches!(err, Error::ParseError(_, DataType::DATE)), "{err}");
+        let array = ArrayType::new(DataType::INTEGER, false).into();
+        Scalar::try_from_string("[1]", &array).expect_err("not a primitive type");
+        Ok(())
+    }
+
+    #[test]
+    fn test_partial_cmp_typed() -> DeltaResult<()> {
+        // decimals are compared by value, whatever their scale
+        let a = Scalar::decimal(150, 5, 2)?; // 1.50
+        let b = Scalar::decimal(15, 3, 1)?; // 1.5
+        let c = Scalar::decimal(1499, 10, 3)?; // 1.499
+        let d = Scalar::decimal(-15, 3, 1)?; // -1.5
+        assert_eq!(a.partial_cmp(&b), None);
+        assert_eq!(a.partial_cmp_typed(&b), Some(Ordering::Equal));
+        assert_eq!(a.partial_cmp_typed(&c), Some(Ordering::Greater));
+        assert_eq!(c.partial_cmp_typed(&b), Some(Ordering::Less));
+        assert_eq!(d.partial_cmp_typed(&c), Some(Ordering::Less));
+        let max = Scalar::decimal(10_i128.pow(38) - 1, 38, 38)?;
+        let one = Scalar::decimal(1, 38, 0)?;
+        assert_eq!(max.partial_cmp_typed(&one), Some(Ordering::Less));
+
+        // dates are ordered against the timestamp of their midnight
+        let date = Scalar::try_from_string("2024-01-02", &DataType::DATE)?;
+        let midnight = Scalar::try_from_string("2024-01-02 00:00:00", &DataType::TIMESTAMP)?;
+        let noon = Scalar::try_from_string("2024-01-02 12:00:00", &DataType::TIMESTAMP_NTZ)?;
+        let earlier = Scalar::try_from_string("2024-01-01", &DataType::DATE)?;
+        assert_eq!(date.partial_cmp_typed(&midnight), Some(Ordering::Equal));
+        assert_eq!(date.partial_cmp_typed(&noon), Some(Ordering::Less));
+        assert_eq!(noon.partial_cmp_typed(&date), Some(Ordering::Greater));
+        assert_eq!(earlier.partial_cmp_typed(&date), Some(Ordering::Less));
+        let nanos = Scalar::TimestampNs(1);
+        assert_eq!(
+            Scalar::Timestamp(0).partial_cmp_typed(&nanos),
+            Some(Ordering::Less)
+        );
+
+        let a = Scalar::from("apple");
+        let b = Scalar::from("banana");
+        assert_eq!(a.partial_cmp_typed(&b), Some(Ordering::Less));
+
+        // incomparable types and nulls
+        assert_eq!(a.partial_cmp_typed(&Scalar::Integer(1)), None);
+        assert_eq!(date.partial_cmp_typed(&Scalar::from("2024-01-02")), None);
+        let null = Scalar::Null(DataType::DATE);
+        assert_eq!(date.partial_cmp_typed(&null), None);
+        Ok(())
+    }
+
     #[test]
     fn test_partial_eq() {
         let a = Scalar::Integer(1);
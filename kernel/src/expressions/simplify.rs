@@ -0,0 +1,369 @@
+//! Semantics-preserving simplification of predicates, see [`Expression::simplify`].
+
+use super::{
+    BinaryExpression, BinaryOperator, Expression, JunctionExpression, JunctionOperator, Scalar,
+    UnaryExpression, UnaryOperator,
+};
+use crate::kernel_predicates::KernelPredicateEvaluatorDefaults;
+use crate::DataType;
+
+impl Expression {
+    /// Simplify this predicate without changing its (SQL, three-valued) semantics. The rewrites
+    /// are:
+    ///
+    /// - Constant folding: `NOT`, `IS NULL` and comparisons of literals are evaluated, e.g.
+    ///   `1 < 2` becomes `TRUE` and `NULL = 1` becomes `NULL`.
+    /// - Identity and dominant elements: `AND(TRUE, x)` becomes `x`, and `OR(TRUE, x)` becomes
+    ///   `TRUE` (likewise for `FALSE`). `NULL` inputs are kept, since e.g. `AND(NULL, x)` is not
+    ///   equivalent to `x`.
+    /// - `NOT` is pushed inward: double negations cancel, De Morgan's laws apply to junctions, and
+    ///   negated comparisons are inverted, e.g. `NOT(x < 1)` becomes `x >= 1`.
+    /// - Nested junctions of the same operator are flattened, e.g. `AND(a, AND(b, c))` becomes
+    ///   `AND(a, b, c)`, and junctions of a single expression are replaced by that expression.
+    ///
+    /// The result is a fixed point: simplifying it again returns it unchanged.
+    pub fn simplify(self) -> Self {
+        match self {
+            Self::Unary(UnaryExpression {
+                op: UnaryOperator::Not,
+                expr,
+            }) => negate(expr.simplify()),
+            Self::Unary(UnaryExpression {
+                op: UnaryOperator::IsNull,
+                expr,
+            }) => match expr.simplify() {
+                Self::Literal(value) => Self::literal(value.is_null()),
+                expr => expr.is_null(),
+            },
+            Self::Binary(BinaryExpression { op, left, right }) => {
+                fold_binary(op, left.simplify(), right.simplify())
+            }
+            Self::Junction(JunctionExpression { op, exprs }) => {
+                junction(op, exprs.into_iter().map(Self::simplify))
+            }
+            Self::Struct(exprs) => Self::Struct(exprs.into_iter().map(Self::simplify).collect()),
+            expr @ (Self::Literal(_) | Self::Column(_)) => expr,
+        }
+    }
+}
+
+// The negation of an already simplified expression, with the `NOT` pushed as far inward as
+// possible. The result is simplified as well.
+fn negate(expr: Expression) -> Expression {
+    match expr {
+        Expression::Literal(Scalar::Boolean(value)) => Expression::literal(!value),
+        // NOT(NULL) is NULL
+        expr @ Expression::Literal(Scalar::Null(_)) => expr,
+        Expression::Unary(UnaryExpression {
+            op: UnaryOperator::Not,
+            expr,
+        }) => *expr,
+        Expression::Junction(JunctionExpression { op, exprs }) => {
+            junction(op.invert(), exprs.into_iter().map(negate))
+        }
+        Expression::Binary(BinaryExpression { op, left, right }) => match negated_comparison(op) {
+            Some(op) => Expression::Binary(BinaryExpression { op, left, right }),
+            None => Expression::not(Expression::Binary(BinaryExpression { op, left, right })),
+        },
+        expr => Expression::not(expr),
+    }
+}
+
+// Returns `<op2>` (if any) such that `A <op2> B` is equivalent to `NOT(A <op> B)`. NULL inputs
+// produce NULL either way.
+fn negated_comparison(op: BinaryOperator) -> Option<BinaryOperator> {
+    use BinaryOperator::*;
+    match op {
+        LessThan => Some(GreaterThanOrEqual),
+        LessThanOrEqual => Some(GreaterThan),
+        GreaterThan => Some(LessThanOrEqual),
+        GreaterThanOrEqual => Some(LessThan),
+        Equal => Some(NotEqual),
+        NotEqual => Some(Equal),
+        In => Some(NotIn),
+        NotIn => Some(In),
+        Distinct | Plus | Minus | Multiply | Divide => None,
+    }
+}
+
+// Evaluate a binary operation of two (simplified) operands if they are both literals and the
+// operation is a comparison whose result is known.
+fn fold_binary(op: BinaryOperator, left: Expression, right: Expression) -> Expression {
+    let folded = match (&left, &right) {
+        (Expression::Literal(l), Expression::Literal(r)) if op.is_null_intolerant_comparison() => {
+            if l.is_null() || r.is_null() {
+                Some(Expression::null_literal(DataType::BOOLEAN))
+            } else {
+                KernelPredicateEvaluatorDefaults::eval_binary_scalars(op, l, r, false)
+                    .map(Expression::literal)
+            }
+        }
+        (Expression::Literal(l), Expression::Literal(r)) if op == BinaryOperator::Distinct => {
+            match (l.is_null(), r.is_null()) {
+                (true, true) => Some(Expression::literal(false)),
+                (true, false) | (false, true) => Some(Expression::literal(true)),
+                (false, false) => KernelPredicateEvaluatorDefaults::eval_binary_scalars(
+                    BinaryOperator::NotEqual,
+                    l,
+                    r,
+                    false,
+                )
+                .map(Expression::literal),
+            }
+        }
+        _ => None,
+    };
+    folded.unwrap_or_else(|| Expression::binary(op, left, right))
+}
+
+// Build a junction of (simplified) expressions: flatten nested junctions of the same operator, and
+// drop identity elements, or collapse to the dominant element if there is one.
+fn junction(op: JunctionOperator, exprs: impl IntoIterator<Item = Expression>) -> Expression {
+    let dominator = op == JunctionOperator::Or;
+    let mut flattened = vec![];
+    for expr in exprs {
+        match expr {
+            Expression::Junction(JunctionExpression {
+                op: inner_op,
+                exprs,
+            }) if inner_op == op => flattened.extend(exprs),
+            Expression::Literal(Scalar::Boolean(value)) if value == dominator => {
+                return Expression::literal(dominator);
+            }
+            Expression::Literal(Scalar::Boolean(_)) => {} // identity element
+            expr => flattened.push(expr),
+        }
+    }
+    match flattened.len() {
+        0 => Expression::literal(!dominator),
+        1 => flattened.remove(0),
+        _ => Expression::junction(op, flattened),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::expressions::{column_expr, ArrayData};
+    use crate::schema::ArrayType;
+
+    use Expression as Expr;
+
+    // NULL literals never compare equal, so compare the structure of the expressions instead
+    fn assert_simplifies_to(expr: Expr, expected: Expr) {
+        let simplified = expr.clone().simplify();
+        assert_eq!(format!("{simplified:?}"), format!("{expected:?}"), "{expr}");
+    }
+
+    #[test]
+    fn test_constant_folding() {
+        let cases = [
+            (
+                Expr::lt(Expr::literal(1), Expr::literal(2)),
+                Expr::literal(true),
+            ),
+            (
+                Expr::eq(Expr::literal("a"), Expr::literal("b")),
+                Expr::literal(false),
+            ),
+            (
+                Expr::eq(Expr::null_literal(DataType::INTEGER), Expr::literal(1)),
+                Expr::null_literal(DataType::BOOLEAN),
+            ),
+            (
+                Expr::null_literal(DataType::INTEGER).distinct(Expr::literal(1)),
+                Expr::literal(true),
+            ),
+            (
+                Expr::null_literal(DataType::INTEGER)
+                    .distinct(Expr::null_literal(DataType::INTEGER)),
+                Expr::literal(false),
+            ),
+            (
+                Expr::literal(1).distinct(Expr::literal(1)),
+                Expr::literal(false),
+            ),
+            (Expr::literal(1).is_null(), Expr::literal(false)),
+            (
+                Expr::null_literal(DataType::LONG).is_not_null(),
+                Expr::literal(false),
+            ),
+            (Expr::not(Expr::literal(true)), Expr::literal(false)),
+            (
+                Expr::not(Expr::null_literal(DataType::BOOLEAN)),
+                Expr::null_literal(DataType::BOOLEAN),
+            ),
+            // incomparable literals are left for the evaluator to reject
+            (
+                Expr::lt(Expr::literal(1), Expr::literal("a")),
+                Expr::lt(Expr::literal(1), Expr::literal("a")),
+            ),
+            // arithmetic is not folded
+            (
+                Expr::literal(1) + Expr::literal(2),
+                Expr::literal(1) + Expr::literal(2),
+            ),
+        ];
+        for (expr, expected) in cases {
+            assert_simplifies_to(expr, expected);
+        }
+    }
+
+    #[test]
+    fn test_identity_and_dominant_elements() {
+        let x = || column_expr!("x").gt(Expr::literal(1));
+        let null = || Expr::null_literal(DataType::BOOLEAN);
+        let cases = [
+            (Expr::and(Expr::literal(true), x()), x()),
+            (Expr::and(Expr::literal(false), x()), Expr::literal(false)),
+            (Expr::or(Expr::literal(false), x()), x()),
+            (Expr::or(x(), Expr::literal(true)), Expr::literal(true)),
+            (Expr::and_from([]), Expr::literal(true)),
+            (Expr::or_from([]), Expr::literal(false)),
+            (Expr::and_from([x()]), x()),
+            // NULL is neither an identity nor a dominant element
+            (Expr::and(null(), x()), Expr::and(null(), x())),
+            (
+                Expr::and(null(), Expr::literal(false)),
+                Expr::literal(false),
+            ),
+            (Expr::or(null(), Expr::literal(false)), null()),
+            // folded operands take part as well
+            (
+                Expr::and(Expr::lt(Expr::literal(1), Expr::literal(2)), x()),
+                x(),
+            ),
+        ];
+        for (expr, expected) in cases {
+            assert_simplifies_to(expr, expected);
+        }
+    }
+
+    #[test]
+    fn test_push_down_not() {
+        let a = || column_expr!("a");
+        let b = || column_expr!("b");
+        let list = || {
+            let array_type = ArrayType::new(DataType::INTEGER, false);
+            #[allow(deprecated)]
+            let data = ArrayData::new(array_type, vec![Scalar::from(1), Scalar::from(2)]);
+            Expr::literal(Scalar::Array(data))
+        };
+        let cases = [
+            (Expr::not(Expr::not(a())), a()),
+            (Expr::not(Expr::not(Expr::not(a()))), Expr::not(a())),
+            (
+                Expr::not(a().lt(Expr::literal(1))),
+                a().ge(Expr::literal(1)),
+            ),
+            (
+                Expr::not(a().le(Expr::literal(1))),
+                a().gt(Expr::literal(1)),
+            ),
+            (
+                Expr::not(a().eq(Expr::literal(1))),
+                a().ne(Expr::literal(1)),
+            ),
+            (
+                Expr::not(a().ne(Expr::literal(1))),
+                a().eq(Expr::literal(1)),
+            ),
+            (
+                Expr::not(Expr::binary(BinaryOperator::In, a(), list())),
+                Expr::binary(BinaryOperator::NotIn, a(), list()),
+            ),
+            // neither DISTINCT nor IS NULL has a negated form
+            (Expr::not(a().distinct(b())), Expr::not(a().distinct(b()))),
+            (a().is_not_null(), a().is_not_null()),
+            // De Morgan
+            (
+                Expr::not(Expr::and(a().lt(Expr::literal(1)), b().is_null())),
+                Expr::or(a().ge(Expr::literal(1)), b().is_not_null()),
+            ),
+            (
+                Expr::not(Expr::or(
+                    a().lt(Expr::literal(1)),
+                    Expr::not(b().gt(Expr::literal(2))),
+                )),
+                Expr::and(a().ge(Expr::literal(1)), b().gt(Expr::literal(2))),
+            ),
+            (
+                Expr::not(Expr::and(a().lt(Expr::literal(1)), Expr::literal(true))),
+                a().ge(Expr::literal(1)),
+            ),
+        ];
+        for (expr, expected) in cases {
+            assert_simplifies_to(expr, expected);
+        }
+    }
+
+    #[test]
+    fn test_flatten_junctions() {
+        let col = |name: &str| Expr::column([name]).gt(Expr::literal(1));
+        let cases = [
+            (
+                Expr::and(col("a"), Expr::and(col("b"), Expr::and(col("c"), col("d")))),
+                Expr::and_from([col("a"), col("b"), col("c"), col("d")]),
+            ),
+            // different operators are not flattened
+            (
+                Expr::and(col("a"), Expr::or(col("b"), col("c"))),
+                Expr::and(col("a"), Expr::or(col("b"), col("c"))),
+            ),
+            // an OR under NOT(AND) turns into an OR of the outer OR
+            (
+                Expr::or(
+                    col("a"),
+                    Expr::not(Expr::and(col("b"), Expr::not(Expr::or(col("c"), col("d"))))),
+                ),
+                Expr::or_from([
+                    col("a"),
+                    Expr::column(["b"]).le(Expr::literal(1)),
+                    col("c"),
+                    col("d"),
+                ]),
+            ),
+            // removing identity elements leaves a nested junction to flatten
+            (
+                Expr::and(
+                    col("a"),
+                    Expr::or(Expr::literal(false), Expr::and(col("b"), col("c"))),
+                ),
+                Expr::and_from([col("a"), col("b"), col("c")]),
+            ),
+        ];
+        for (expr, expected) in cases {
+            assert_simplifies_to(expr, expected);
+        }
+    }
+
+    #[test]
+    fn test_simplify_is_idempotent() {
+        let a = || column_expr!("a");
+        let b = || column_expr!("b");
+        let exprs = [
+            Expr::not(Expr::and_from([
+                Expr::not(Expr::or(a().lt(Expr::literal(1)), Expr::literal(false))),
+                Expr::or(Expr::null_literal(DataType::BOOLEAN), b().is_null()),
+                Expr::and(Expr::literal(true), Expr::not(Expr::not(a().distinct(b())))),
+            ])),
+            Expr::or_from([
+                Expr::and(
+                    a().eq(b()),
+                    Expr::not(Expr::literal(1).lt(Expr::literal(2))),
+                ),
+                Expr::not(Expr::not(Expr::or(
+                    a().gt(Expr::literal(3)),
+                    Expr::not(b().ne(Expr::literal(4))),
+                ))),
+                Expr::struct_from([Expr::not(Expr::not(a()))]).is_null(),
+            ]),
+            Expr::and(Expr::literal(true), Expr::literal(true)),
+            Expr::not(a().distinct(Expr::literal(1)) + Expr::literal(2)),
+        ];
+        for expr in exprs {
+            let simplified = expr.simplify();
+            assert_simplifies_to(simplified.clone(), simplified);
+        }
+    }
+}
@@ -165,11 +165,22 @@ pub(crate) trait KernelPredicateEvaluator {
         }
     }
 
-    /// A (possibly inverted) IN-list check, e.g. `<col> [NOT] IN <array-value>`.
-    ///
-    /// Unsupported by default, but implementations can override it if they wish.
-    fn eval_in(&self, _col: &ColumnName, _val: &Scalar, _inverted: bool) -> Option<Self::Output> {
-        None // TODO?
+    /// A (possibly inverted) IN-list check, e.g. `<col> [NOT] IN <array-value>`. The check is
+    /// equivalent to `OR(<col> = <value1>, <col> = <value2>, ...)`, which also gives the SQL
+    /// semantics for NULL list elements (NULL unless some other element matches), so an IN-list
+    /// can skip exactly when every one of its equality comparisons can. `NOT IN` becomes
+    /// `AND(<col> != <value1>, ...)` which, like `!=`, rarely allows skipping.
+    fn eval_in(&self, col: &ColumnName, val: &Scalar, inverted: bool) -> Option<Self::Output> {
+        let Scalar::Array(values) = val else {
+            debug!("Unsupported IN-list operand: {val:?}");
+            return None;
+        };
+        #[allow(deprecated)]
+        let exprs = values
+            .array_elements()
+            .iter()
+            .map(|value| self.eval_eq(col, value, inverted));
+        self.finish_eval_junction(JunctionOperator::Or, exprs, inverted)
     }
 
     /// Dispatches a (possibly inverted) binary expression to each operator's specific implementation.
@@ -463,6 +463,48 @@ fn test_eval_distinct() {
     );
 }
 
+#[test]
+fn test_eval_in() {
+    let list = |values: &[Option<i32>]| {
+        let values = values.iter().map(|value| match value {
+            Some(value) => Scalar::from(*value),
+            None => Scalar::Null(DataType::INTEGER),
+        });
+        Scalar::Array(ArrayData::new(
+            ArrayType::new(DataType::INTEGER, true),
+            values,
+        ))
+    };
+    let filter = DefaultKernelPredicateEvaluator::from(Scalar::from(1));
+    let col = &column_name!("x");
+    let cases = [
+        (list(&[Some(1), Some(2)]), Some(true)),
+        (list(&[Some(2), Some(3)]), Some(false)),
+        (list(&[Some(1), None]), Some(true)),
+        (list(&[Some(2), None]), None),
+        (list(&[]), Some(false)),
+    ];
+    for (values, expect) in cases {
+        expect_eq!(
+            filter.eval_in(col, &values, false),
+            expect,
+            "x IN {values} (x = 1)"
+        );
+        expect_eq!(
+            filter.eval_in(col, &values, true),
+            expect.map(|result| !result),
+            "x NOT IN {values} (x = 1)"
+        );
+    }
+
+    // not an IN-list
+    expect_eq!(
+        filter.eval_in(col, &Scalar::from(1), false),
+        None,
+        "x IN 1 (x = 1)"
+    );
+}
+
 // NOTE: We're testing routing here -- the actual comparisons are already validated by
 // test_eval_binary_scalars.
 #[test]
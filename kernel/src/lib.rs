@@ -100,7 +100,7 @@ internal_mod!(pub(crate) mod log_replay);
 internal_mod!(pub(crate) mod log_segment);
 
 pub use delta_kernel_derive;
-pub use engine_data::{EngineData, RowVisitor};
+pub use engine_data::{EngineData, FilteredEngineData, RowVisitor};
 pub use error::{DeltaResult, Error};
 pub use expressions::{Expression, ExpressionRef};
 pub use table::Table;
@@ -423,6 +423,27 @@ pub trait StorageHandler: AsAny {
         &self,
         files: Vec<FileSlice>,
     ) -> DeltaResult<Box<dyn Iterator<Item = DeltaResult<Bytes>>>>;
+
+    /// Read the bytes in `range` of the file at `path`.
+    ///
+    /// The default implementation reads the whole file via [`Self::read_files`] and slices out the
+    /// requested range. Engines that support ranged reads should override this to avoid fetching
+    /// more than needed.
+    fn read_file_range(&self, path: &Url, range: Range<FileIndex>) -> DeltaResult<Bytes> {
+        let bytes = self
+            .read_files(vec![(path.clone(), None)])?
+            .next()
+            .ok_or_else(|| Error::generic(format!("No data returned when reading {path}")))??;
+        let start = usize::try_from(range.start).map_err(|_| Error::generic("range overflow"))?;
+        let end = usize::try_from(range.end).map_err(|_| Error::generic("range overflow"))?;
+        if start > end || end > bytes.len() {
+            return Err(Error::generic(format!(
+                "Range {range:?} is out of bounds for {path} of size {}",
+                bytes.len()
+            )));
+        }
+        Ok(bytes.slice(start..end))
+    }
 }
 
 /// Provides JSON handling functionality to Delta Kernel.
@@ -496,6 +517,23 @@ pub trait JsonHandler: AsAny {
     ) -> DeltaResult<()>;
 }
 
+/// Name of the row index column a [`ParquetHandler`] must fill in, rather than read from the file,
+/// when it appears at the top level of the requested physical schema. It is a non-null `long`
+/// holding the position of each row within its file (counting rows of skipped row groups too).
+/// Kernel requests it to compute row tracking ids, see [`ScanBuilder::with_row_tracking`].
+///
+/// [`ScanBuilder::with_row_tracking`]: crate::scan::ScanBuilder::with_row_tracking
+pub const ROW_INDEX_COLUMN_NAME: &str = "__delta_kernel_row_index";
+
+/// Field metadata key through which kernel asks a [`ParquetHandler`] to return the string and
+/// binary data of a field of the requested physical schema, including that of nested fields, as
+/// string and binary views (arrow's `Utf8View` and `BinaryView`) when set to `true`. Kernel sets it
+/// on every field for scans built with [`ScanBuilder::with_prefer_string_view`]. Handlers without
+/// such types may ignore it.
+///
+/// [`ScanBuilder::with_prefer_string_view`]: crate::scan::ScanBuilder::with_prefer_string_view
+pub const STRING_VIEW_METADATA_KEY: &str = "delta.arrow.stringView";
+
 /// Provides Parquet file related functionalities to Delta Kernel.
 ///
 /// Connectors can leverage this trait to provide their own custom
@@ -503,7 +541,9 @@ pub trait JsonHandler: AsAny {
 pub trait ParquetHandler: AsAny {
     /// Read and parse the Parquet file at given locations and return the data as EngineData with
     /// the columns requested by physical schema . The ParquetHandler _must_ return exactly the
-    /// columns specified in `physical_schema`, and they _must_ be in schema order.
+    /// columns specified in `physical_schema`, and they _must_ be in schema order. A requested
+    /// [`ROW_INDEX_COLUMN_NAME`] column is filled with the file row index of each row, and fields
+    /// marked with [`STRING_VIEW_METADATA_KEY`] may be returned as string and binary views.
     ///
     /// # Parameters
     ///
@@ -516,6 +556,46 @@ pub trait ParquetHandler: AsAny {
         physical_schema: SchemaRef,
         predicate: Option<ExpressionRef>,
     ) -> DeltaResult<FileDataReadResultIterator>;
+
+    /// Read the schema of the Parquet file `file` from its footer, without reading any data. The
+    /// field id of a Parquet field, if any, is returned as its
+    /// [`ColumnMetadataKey::ColumnMappingId`] metadata. Kernel uses this to resolve the columns a
+    /// data file contains, see [`Scan::read_schema_for_file`].
+    ///
+    /// The default implementation returns [`Error::Unsupported`].
+    ///
+    /// [`ColumnMetadataKey::ColumnMappingId`]: crate::schema::ColumnMetadataKey::ColumnMappingId
+    /// [`Scan::read_schema_for_file`]: crate::scan::Scan::read_schema_for_file
+    fn read_parquet_schema(&self, file: &FileMeta) -> DeltaResult<SchemaRef> {
+        Err(Error::unsupported(format!(
+            "This ParquetHandler cannot read the schema of {}",
+            file.location
+        )))
+    }
+
+    /// Write the selected rows of `data` as a single Parquet file at `location`. Only rows whose
+    /// selection vector entry is `true` are written, and all batches share the same schema, which
+    /// becomes the schema of the file. Kernel uses this to write checkpoints.
+    ///
+    /// # Parameters
+    ///
+    /// - `location` - URL specifying the location to write the Parquet file
+    /// - `data` - Iterator of filtered EngineData to write to the file, in order
+    /// - `overwrite` - If true, overwrite the file if it exists. If false, the call must fail if
+    ///   the file exists.
+    ///
+    /// The default implementation returns [`Error::Unsupported`]. Engines that write checkpoints
+    /// should override this.
+    fn write_parquet_file(
+        &self,
+        location: &Url,
+        _data: Box<dyn Iterator<Item = DeltaResult<FilteredEngineData>> + Send + '_>,
+        _overwrite: bool,
+    ) -> DeltaResult<()> {
+        Err(Error::unsupported(format!(
+            "This ParquetHandler cannot write {location}"
+        )))
+    }
 }
 
 /// The `Engine` trait encapsulates all the functionality an engine or connector needs to provide
@@ -1,6 +1,6 @@
 //! Represents a segment of a delta log. [`LogSegment`] wraps a set of  checkpoint and commit
 //! files.
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::convert::identity;
 use std::sync::{Arc, LazyLock};
 
@@ -10,12 +10,13 @@ use crate::actions::{
     SIDECAR_NAME,
 };
 use crate::path::{LogPathFileType, ParsedLogPath};
+use crate::scan::log_replay::scan_action_iter;
 use crate::schema::SchemaRef;
 use crate::snapshot::LastCheckpointHint;
 use crate::utils::require;
 use crate::{
-    DeltaResult, Engine, EngineData, Error, Expression, ExpressionRef, ParquetHandler, RowVisitor,
-    StorageHandler, Version,
+    DeltaResult, Engine, EngineData, Error, Expression, ExpressionRef, FileMeta, ParquetHandler,
+    RowVisitor, StorageHandler, Version,
 };
 use delta_kernel_derive::internal_api;
 
@@ -51,6 +52,15 @@ pub(crate) struct LogSegment {
     pub checkpoint_parts: Vec<ParsedLogPath>,
 }
 
+/// The files of a complete checkpoint, as supplied to [`LogSegment::from_commit_files`] instead of
+/// being found by listing. A single-file (classic or UUID-named) checkpoint has one part, and a
+/// multi-part checkpoint must have all of its parts.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[internal_api]
+pub(crate) struct CheckpointMeta {
+    pub parts: Vec<FileMeta>,
+}
+
 impl LogSegment {
     pub(crate) fn try_new(
         mut ascending_commit_files: Vec<ParsedLogPath>,
@@ -121,6 +131,7 @@ impl LogSegment {
     ///
     /// The options for constructing a LogSegment for Snapshot are as follows:
     /// - `checkpoint_hint`: a `LastCheckpointHint` to start the log segment from (e.g. from reading the `last_checkpoint` file).
+    ///   The log is only listed from the hinted checkpoint on, unless the hint turns out to be stale.
     /// - `time_travel_version`: The version of the log that the Snapshot will be at.
     ///
     /// [`Snapshot`]: crate::snapshot::Snapshot
@@ -150,6 +161,98 @@ impl LogSegment {
         )
     }
 
+    /// Constructs a [`LogSegment`] from an explicit list of log files, bypassing the listing of
+    /// the `_delta_log` directory. In catalog-managed (coordinated commits) tables the commits are
+    /// tracked by the catalog and cannot be discovered by listing, so the catalog supplies them.
+    ///
+    /// `commits` are the commit files up to and including the end version of the segment, in any
+    /// order, and `checkpoint` is the checkpoint the segment starts from, if any. Commits at or
+    /// before the checkpoint version are ignored. All files must be in the same `_delta_log`
+    /// directory, which becomes the log root of the segment. The files are validated as in
+    /// [`LogSegment::try_new`]: the commits must be contiguous and follow the checkpoint without a
+    /// gap, and the checkpoint parts must form one complete checkpoint.
+    #[allow(dead_code)] // only used by tests until catalog-managed tables are supported
+    #[internal_api]
+    pub(crate) fn from_commit_files(
+        checkpoint: Option<CheckpointMeta>,
+        commits: Vec<FileMeta>,
+    ) -> DeltaResult<Self> {
+        let parse = |file: FileMeta| {
+            let location = file.location.clone();
+            ParsedLogPath::try_from(file)?.ok_or_else(|| Error::invalid_log_path(location))
+        };
+
+        let mut ascending_commit_files: Vec<_> = commits
+            .into_iter()
+            .map(|file| {
+                let path = parse(file)?;
+                require!(
+                    path.is_commit(),
+                    Error::generic(format!(
+                        "Expected a commit file, got {}",
+                        path.location.location
+                    ))
+                );
+                Ok(path)
+            })
+            .try_collect()?;
+        ascending_commit_files.sort_by_key(|path| path.version);
+
+        let checkpoint_parts = match checkpoint {
+            Some(checkpoint) => {
+                let mut parts: Vec<_> = checkpoint.parts.into_iter().map(parse).try_collect()?;
+                require!(
+                    parts.iter().all(|part| part.is_checkpoint()),
+                    Error::InvalidCheckpoint("Expected only checkpoint files".to_string())
+                );
+                require!(
+                    parts.iter().map(|part| part.version).all_equal(),
+                    Error::InvalidCheckpoint(
+                        "Checkpoint parts have different versions".to_string()
+                    )
+                );
+                // `group_checkpoint_parts` expects the parts of a multi-part checkpoint in order
+                parts.sort_by_key(|part| match part.file_type {
+                    LogPathFileType::MultiPartCheckpoint { part_num, .. } => part_num,
+                    _ => 1,
+                });
+                let num_parts = parts.len();
+                group_checkpoint_parts(parts)
+                    .into_iter()
+                    // `num_parts` is guaranteed to be non-negative and within `usize` range
+                    .find(|(expected, part_files)| {
+                        part_files.len() == *expected as usize && part_files.len() == num_parts
+                    })
+                    .map(|(_, part_files)| part_files)
+                    .ok_or_else(|| {
+                        Error::InvalidCheckpoint(
+                            "Checkpoint parts do not form one complete checkpoint".to_string(),
+                        )
+                    })?
+            }
+            None => vec![],
+        };
+
+        // The log root is the directory all the files are in
+        let log_dir = |path: &ParsedLogPath| path.location.location.join(".");
+        let mut files = ascending_commit_files.iter().chain(&checkpoint_parts);
+        let log_root = match files.next() {
+            Some(file) => log_dir(file)?,
+            None => return Err(Error::generic("No files in log segment")),
+        };
+        for file in files {
+            require!(
+                log_dir(file)? == log_root,
+                Error::generic(format!(
+                    "Log file {} is not in the log directory {}",
+                    file.location.location, log_root
+                ))
+            );
+        }
+
+        LogSegment::try_new(ascending_commit_files, checkpoint_parts, log_root, None)
+    }
+
     /// Constructs a [`LogSegment`] to be used for `TableChanges`. For a TableChanges between versions
     /// `start_version` and `end_version`: Its LogSegment is made of zero checkpoints and all commits
     /// between versions `start_version` (inclusive) and `end_version` (inclusive). If no `end_version`
@@ -195,7 +298,8 @@ impl LogSegment {
     /// bool) pairs, where the boolean flag indicates whether the data was read from a commit file
     /// (true) or a checkpoint file (false).
     ///
-    /// The log files will be read from most recent to oldest.
+    /// The log files will be read from most recent to oldest, and only as the returned iterator is
+    /// consumed, so replaying the log never requires holding all of its actions in memory.
     ///
     /// `commit_read_schema` is the (physical) schema to read the commit files with, and
     /// `checkpoint_read_schema` is the (physical) schema to read checkpoint files with. This can be
@@ -230,6 +334,74 @@ impl LogSegment {
         Ok(commit_stream.chain(checkpoint_stream))
     }
 
+    /// Checks that this log segment's checkpoint describes the same table as the commits it
+    /// replaces. The set of active files is reconstructed once from the checkpoint alone and once
+    /// by replaying the JSON commits from version 0 up to the checkpoint version, and the two sets
+    /// must be identical.
+    ///
+    /// This replays the whole log, so it is meant for opt-in data-quality checks rather than
+    /// regular reads. A log segment without a checkpoint trivially passes. Fails if the commits
+    /// preceding the checkpoint are no longer available, and with [`Error::InvalidCheckpoint`]
+    /// naming every differing path if the file sets don't match.
+    #[internal_api]
+    pub(crate) fn verify_checkpoint(&self, engine: &dyn Engine) -> DeltaResult<()> {
+        let Some(checkpoint_version) = self.checkpoint_version else {
+            return Ok(());
+        };
+        let checkpoint_segment = LogSegment::try_new(
+            vec![],
+            self.checkpoint_parts.clone(),
+            self.log_root.clone(),
+            Some(checkpoint_version),
+        )?;
+        let commit_segment = LogSegment::for_table_changes(
+            engine.storage_handler().as_ref(),
+            self.log_root.clone(),
+            0,
+            checkpoint_version,
+        )?;
+        let from_checkpoint = checkpoint_segment.active_file_paths(engine)?;
+        let from_commits = commit_segment.active_file_paths(engine)?;
+        if from_checkpoint == from_commits {
+            return Ok(());
+        }
+
+        let missing = from_commits
+            .difference(&from_checkpoint)
+            .sorted()
+            .join(", ");
+        let unexpected = from_checkpoint
+            .difference(&from_commits)
+            .sorted()
+            .join(", ");
+        Err(Error::invalid_checkpoint(format!(
+            "Checkpoint at version {checkpoint_version} does not match the replay of its commits. \
+             Missing from checkpoint: [{missing}]. Not in commits: [{unexpected}]"
+        )))
+    }
+
+    // Replays this log segment to find the paths of the files that are part of the table at its
+    // end version.
+    fn active_file_paths(&self, engine: &dyn Engine) -> DeltaResult<HashSet<String>> {
+        let (metadata, _) = self.read_metadata(engine)?;
+        let schema = Arc::new(metadata.parse_schema()?);
+        let commit_read_schema = get_log_schema().project(&[ADD_NAME, REMOVE_NAME])?;
+        let checkpoint_read_schema = get_log_schema().project(&[ADD_NAME, SIDECAR_NAME])?;
+        let actions =
+            self.read_actions(engine, commit_read_schema, checkpoint_read_schema, None)?;
+        scan_action_iter(engine, actions, schema, None, None, None).try_fold(
+            HashSet::new(),
+            |paths, scan_metadata| {
+                scan_metadata?.visit_scan_files(
+                    paths,
+                    |paths: &mut HashSet<String>, path, _, _, _, _, _| {
+                        paths.insert(path.to_string());
+                    },
+                )
+            },
+        )
+    }
+
     /// Returns an iterator over checkpoint data, processing sidecar files when necessary.
     ///
     /// By default, `create_checkpoint_stream` checks for the presence of sidecar files, and
@@ -423,7 +595,9 @@ impl LogSegment {
 /// not specified, the files will begin from version number 0. If `end_version` is not specified, files up to
 /// the most recent version will be included.
 ///
-/// Note: this calls [`StorageHandler::list_from`] to get the list of log files.
+/// Note: this calls [`StorageHandler::list_from`] to get the list of log files. The listing is
+/// consumed lazily, and stops as soon as the commit of `end_version` is found, so no file of a
+/// later version is ever pulled from it.
 fn list_log_files(
     storage: &dyn StorageHandler,
     log_root: &Url,
@@ -443,9 +617,41 @@ fn list_log_files(
         .take_while(move |path_res| match path_res {
             Ok(path) => !end_version.is_some_and(|end_version| end_version < path.version),
             Err(_) => true,
+        })
+        // The commit sorts after all other log files of its version, so there is nothing left to
+        // list once the commit of `end_version` is found.
+        .take_while_inclusive(move |path_res| match path_res {
+            Ok(path) => !(path.is_commit() && end_version == Some(path.version)),
+            Err(_) => true,
         }))
 }
 
+/// List all commit files with versions up to `end_version` (inclusive, or all commits if `None`),
+/// sorted in ascending order by version. Unlike [`list_log_files_with_version`], commits before a
+/// checkpoint are kept.
+pub(crate) fn list_commit_files(
+    storage: &dyn StorageHandler,
+    log_root: &Url,
+    end_version: impl Into<Option<Version>>,
+) -> DeltaResult<Vec<ParsedLogPath>> {
+    list_log_files(storage, log_root, None, end_version)?
+        .filter_ok(|path| path.is_commit())
+        .try_collect()
+}
+
+/// Find the commit file of `version`, or `None` if it does not exist (e.g. because it was cleaned
+/// up after a checkpoint).
+pub(crate) fn find_commit_file(
+    storage: &dyn StorageHandler,
+    log_root: &Url,
+    version: Version,
+) -> DeltaResult<Option<ParsedLogPath>> {
+    list_log_files(storage, log_root, version, version)?
+        .filter_ok(|path| path.is_commit())
+        .next()
+        .transpose()
+}
+
 /// List all commit and checkpoint files with versions above the provided `start_version` (inclusive).
 /// If successful, this returns a tuple `(ascending_commit_files, checkpoint_parts)` of type
 /// `(Vec<ParsedLogPath>, Vec<ParsedLogPath>)`. The commit files are guaranteed to be sorted in
@@ -551,6 +757,12 @@ fn group_checkpoint_parts(parts: Vec<ParsedLogPath>) -> HashMap<u32, Vec<ParsedL
 /// List all commit and checkpoint files after the provided checkpoint. It is guaranteed that all
 /// the returned [`ParsedLogPath`]s will have a version less than or equal to the `end_version`.
 /// See [`list_log_files_with_version`] for details on the return type.
+///
+/// The hint only decides where the listing starts, the listing itself is the source of truth: a
+/// newer complete checkpoint than the hinted one is used if there is one, and a hinted checkpoint
+/// whose `parts` or `sizeInBytes` don't match the files found is used as listed. If no complete
+/// checkpoint is found at or after the hinted version (e.g. the hinted checkpoint was deleted or
+/// is missing a part), the hint is stale and the whole log is listed instead.
 fn list_log_files_with_checkpoint(
     checkpoint_metadata: &LastCheckpointHint,
     storage: &dyn StorageHandler,
@@ -565,10 +777,11 @@ fn list_log_files_with_checkpoint(
     )?;
 
     let Some(latest_checkpoint) = checkpoint_parts.last() else {
-        // TODO: We could potentially recover here
-        return Err(Error::invalid_checkpoint(
-            "Had a _last_checkpoint hint but didn't find any checkpoints",
-        ));
+        warn!(
+            "_last_checkpoint hint points to version {}, but no complete checkpoint was found at or after it. Listing the whole log instead",
+            checkpoint_metadata.version
+        );
+        return list_log_files_with_version(storage, log_root, None, end_version);
     };
     if latest_checkpoint.version != checkpoint_metadata.version {
         warn!(
@@ -576,12 +789,25 @@ fn list_log_files_with_checkpoint(
             checkpoint_metadata.version,
             latest_checkpoint.version
         );
-    } else if checkpoint_parts.len() != checkpoint_metadata.parts.unwrap_or(1) {
-        return Err(Error::InvalidCheckpoint(format!(
+        return Ok((commit_files, checkpoint_parts));
+    }
+
+    let expected_parts = checkpoint_metadata.parts.unwrap_or(1);
+    if checkpoint_parts.len() != expected_parts {
+        warn!(
             "_last_checkpoint indicated that checkpoint should have {} parts, but it has {}",
-            checkpoint_metadata.parts.unwrap_or(1),
+            expected_parts,
             checkpoint_parts.len()
-        )));
+        );
+    }
+    let size_in_bytes: u64 = checkpoint_parts.iter().map(|part| part.location.size).sum();
+    if let Some(expected_size) = checkpoint_metadata.size_in_bytes {
+        if u64::try_from(expected_size).ok() != Some(size_in_bytes) {
+            warn!(
+                "_last_checkpoint indicated that checkpoint should have {} bytes, but it has {}",
+                expected_size, size_in_bytes
+            );
+        }
     }
     Ok((commit_files, checkpoint_parts))
 }
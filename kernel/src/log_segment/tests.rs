@@ -1,3 +1,5 @@
+use std::collections::HashSet;
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::LazyLock;
 use std::{path::PathBuf, sync::Arc};
 
@@ -8,7 +10,7 @@ use url::Url;
 
 use crate::actions::visitors::AddVisitor;
 use crate::actions::{
-    get_log_add_schema, get_log_schema, Add, Sidecar, ADD_NAME, METADATA_NAME, REMOVE_NAME,
+    get_log_add_schema, get_log_schema, Add, Remove, Sidecar, ADD_NAME, METADATA_NAME, REMOVE_NAME,
     SIDECAR_NAME,
 };
 use crate::engine::arrow_data::ArrowEngineData;
@@ -16,17 +18,20 @@ use crate::engine::default::executor::tokio::TokioBackgroundExecutor;
 use crate::engine::default::filesystem::ObjectStoreStorageHandler;
 use crate::engine::default::DefaultEngine;
 use crate::engine::sync::SyncEngine;
-use crate::log_segment::LogSegment;
+use crate::log_segment::{CheckpointMeta, LogSegment};
 use crate::parquet::arrow::ArrowWriter;
 use crate::path::ParsedLogPath;
+use crate::scan::log_replay::scan_action_iter;
 use crate::scan::test_utils::{
     add_batch_simple, add_batch_with_remove, sidecar_batch_with_given_paths,
 };
-use crate::snapshot::LastCheckpointHint;
-use crate::utils::test_utils::{assert_batch_matches, Action};
+use crate::schema::{DataType, SchemaRef, StructField, StructType};
+use crate::snapshot::{LastCheckpointHint, Snapshot};
+use crate::utils::test_utils::{assert_batch_matches, parse_json_batch, Action};
 use crate::{
-    DeltaResult, Engine as _, EngineData, Expression, ExpressionRef, FileMeta, RowVisitor,
-    StorageHandler, Table,
+    DeltaResult, Engine, EngineData, Error, EvaluationHandler, Expression, ExpressionRef,
+    FileDataReadResultIterator, FileMeta, FileSlice, JsonHandler, ParquetHandler, RowVisitor,
+    StorageHandler, Table, Version,
 };
 use test_utils::delta_path_for_version;
 
@@ -422,7 +427,7 @@ fn build_snapshot_with_correct_last_multipart_checkpoint() {
 }
 
 #[test]
-fn build_snapshot_with_missing_checkpoint_part_from_hint_fails() {
+fn build_snapshot_with_missing_checkpoint_part_from_hint() {
     let checkpoint_metadata = LastCheckpointHint {
         version: 5,
         size: 10,
@@ -452,12 +457,21 @@ fn build_snapshot_with_missing_checkpoint_part_from_hint_fails() {
         Some(&checkpoint_metadata),
     );
 
+    // The hinted checkpoint is incomplete, so the whole log is listed and the Snapshot is made
+    // of checkpoint 3 and commit files 4 to 7.
     let log_segment =
-        LogSegment::for_snapshot(storage.as_ref(), log_root, checkpoint_metadata, None);
-    assert!(log_segment.is_err())
+        LogSegment::for_snapshot(storage.as_ref(), log_root, checkpoint_metadata, None).unwrap();
+    assert_eq!(log_segment.checkpoint_parts.len(), 1);
+    assert_eq!(log_segment.checkpoint_parts[0].version, 3);
+    let versions = log_segment
+        .ascending_commit_files
+        .iter()
+        .map(|x| x.version)
+        .collect_vec();
+    assert_eq!(versions, [4, 5, 6, 7]);
 }
 #[test]
-fn build_snapshot_with_bad_checkpoint_hint_fails() {
+fn build_snapshot_with_wrong_parts_in_checkpoint_hint() {
     let checkpoint_metadata = LastCheckpointHint {
         version: 5,
         size: 10,
@@ -486,9 +500,184 @@ fn build_snapshot_with_bad_checkpoint_hint_fails() {
         Some(&checkpoint_metadata),
     );
 
+    // The listing is trusted over the hint: the complete two-part checkpoint is used
     let log_segment =
-        LogSegment::for_snapshot(storage.as_ref(), log_root, checkpoint_metadata, None);
-    assert!(log_segment.is_err())
+        LogSegment::for_snapshot(storage.as_ref(), log_root, checkpoint_metadata, None).unwrap();
+    assert_eq!(log_segment.checkpoint_parts.len(), 2);
+    assert_eq!(log_segment.checkpoint_parts[0].version, 5);
+    let versions = log_segment
+        .ascending_commit_files
+        .iter()
+        .map(|x| x.version)
+        .collect_vec();
+    assert_eq!(versions, [6, 7]);
+}
+
+/// A [`StorageHandler`] that records the paths it is asked to list from, and the files it lists.
+struct ListingStorageHandler {
+    inner: Box<dyn StorageHandler>,
+    listed: std::sync::Mutex<Vec<String>>,
+    listed_files: Arc<std::sync::Mutex<Vec<Url>>>,
+}
+
+impl ListingStorageHandler {
+    fn new(inner: Box<dyn StorageHandler>) -> Self {
+        Self {
+            inner,
+            listed: Default::default(),
+            listed_files: Default::default(),
+        }
+    }
+}
+
+impl StorageHandler for ListingStorageHandler {
+    fn list_from(
+        &self,
+        path: &Url,
+    ) -> DeltaResult<Box<dyn Iterator<Item = DeltaResult<FileMeta>>>> {
+        let name = path.path_segments().unwrap().next_back().unwrap();
+        self.listed.lock().unwrap().push(name.to_string());
+        let listed_files = self.listed_files.clone();
+        let files = self.inner.list_from(path)?.inspect(move |file| {
+            if let Ok(file) = file {
+                listed_files.lock().unwrap().push(file.location.clone());
+            }
+        });
+        Ok(Box::new(files))
+    }
+
+    fn read_files(
+        &self,
+        files: Vec<FileSlice>,
+    ) -> DeltaResult<Box<dyn Iterator<Item = DeltaResult<bytes::Bytes>>>> {
+        self.inner.read_files(files)
+    }
+}
+
+// Build the log segment of a Snapshot of the log at `paths` from the checkpoint `hint`, returning
+// its checkpoint and commit versions, and the files the log was listed from.
+fn log_segment_with_checkpoint_hint(
+    paths: &[Path],
+    hint: Option<LastCheckpointHint>,
+) -> (Option<Version>, Vec<Version>, Vec<String>) {
+    let (storage, log_root) = build_log_with_paths_and_checkpoint(paths, hint.as_ref());
+    let storage = ListingStorageHandler::new(storage);
+    let log_segment = LogSegment::for_snapshot(&storage, log_root, hint, None).unwrap();
+    let commits = log_segment
+        .ascending_commit_files
+        .iter()
+        .map(|x| x.version)
+        .collect();
+    let listed = storage.listed.into_inner().unwrap();
+    (log_segment.checkpoint_version, commits, listed)
+}
+
+fn checkpoint_hint(version: Version, size_in_bytes: Option<i64>) -> LastCheckpointHint {
+    LastCheckpointHint {
+        version,
+        size: 10,
+        parts: None,
+        size_in_bytes,
+        num_of_add_files: Some(4),
+        checkpoint_schema: None,
+        checksum: None,
+    }
+}
+
+fn log_with_checkpoints_at(versions: &[Version]) -> Vec<Path> {
+    (0..8)
+        .flat_map(|version| {
+            let checkpoint = versions
+                .contains(&version)
+                .then(|| delta_path_for_version(version, "checkpoint.parquet"));
+            checkpoint
+                .into_iter()
+                .chain([delta_path_for_version(version, "json")])
+        })
+        .collect()
+}
+
+#[test]
+fn build_snapshot_with_valid_checkpoint_hint_lists_from_checkpoint() {
+    // every log file holds the 11 bytes "kernel-data"
+    for size_in_bytes in [None, Some(11), Some(42)] {
+        let (checkpoint, commits, listed) = log_segment_with_checkpoint_hint(
+            &log_with_checkpoints_at(&[3, 5]),
+            Some(checkpoint_hint(5, size_in_bytes)),
+        );
+        assert_eq!(checkpoint, Some(5));
+        assert_eq!(commits, [6, 7]);
+        assert_eq!(listed, [format!("{:020}", 5)]);
+    }
+}
+
+#[test]
+fn build_snapshot_with_stale_checkpoint_hint_falls_back_to_listing() {
+    // the hinted checkpoint 5 was deleted, so the whole log is listed to find checkpoint 3
+    let (checkpoint, commits, listed) = log_segment_with_checkpoint_hint(
+        &log_with_checkpoints_at(&[3]),
+        Some(checkpoint_hint(5, None)),
+    );
+    assert_eq!(checkpoint, Some(3));
+    assert_eq!(commits, [4, 5, 6, 7]);
+    assert_eq!(listed, [format!("{:020}", 5), format!("{:020}", 0)]);
+
+    // the hint points past the end of the log
+    let (checkpoint, commits, _) = log_segment_with_checkpoint_hint(
+        &log_with_checkpoints_at(&[3]),
+        Some(checkpoint_hint(9, None)),
+    );
+    assert_eq!(checkpoint, Some(3));
+    assert_eq!(commits, [4, 5, 6, 7]);
+}
+
+#[test]
+fn build_snapshot_without_checkpoint_hint_lists_whole_log() {
+    let (checkpoint, commits, listed) =
+        log_segment_with_checkpoint_hint(&log_with_checkpoints_at(&[3, 5]), None);
+    assert_eq!(checkpoint, Some(5));
+    assert_eq!(commits, [6, 7]);
+    assert_eq!(listed, [format!("{:020}", 0)]);
+}
+
+#[test]
+fn build_snapshot_as_of_version_never_lists_later_files() {
+    // a checkpoint the snapshot can start from, and a later one the hint points to
+    let paths = (0..1000)
+        .flat_map(|version| {
+            let checkpoint = [3, 900]
+                .contains(&version)
+                .then(|| delta_path_for_version(version, "checkpoint.parquet"));
+            checkpoint
+                .into_iter()
+                .chain([delta_path_for_version(version, "json")])
+        })
+        .collect_vec();
+    let hint = checkpoint_hint(900, None);
+    let (storage, log_root) = build_log_with_paths_and_checkpoint(&paths, Some(&hint));
+    let storage = ListingStorageHandler::new(storage);
+
+    let log_segment = LogSegment::for_snapshot(&storage, log_root, Some(hint), Some(5)).unwrap();
+    assert_eq!(log_segment.checkpoint_version, Some(3));
+    let commits = log_segment
+        .ascending_commit_files
+        .iter()
+        .map(|x| x.version)
+        .collect_vec();
+    assert_eq!(commits, [4, 5]);
+
+    // the listing stops at the commit of version 5
+    let listed_files = storage.listed_files.lock().unwrap();
+    let listed_versions: Vec<Version> = listed_files
+        .iter()
+        .map(|location| {
+            ParsedLogPath::try_from(location.clone())
+                .unwrap()
+                .unwrap()
+                .version
+        })
+        .collect();
+    assert_eq!(listed_versions.iter().max(), Some(&5));
 }
 
 #[test]
@@ -776,6 +965,115 @@ fn test_non_contiguous_log() {
     assert!(log_segment_res.is_err());
 }
 
+#[test]
+fn build_snapshot_from_commit_files_matches_listing() -> DeltaResult<()> {
+    let path = std::fs::canonicalize(PathBuf::from(
+        "./tests/data/with_checkpoint_no_last_checkpoint/",
+    ))?;
+    let table_root = Url::from_directory_path(path).unwrap();
+    let log_root = table_root.join("_delta_log/")?;
+    let engine = SyncEngine::new();
+
+    // the log has commits 0 to 3 and a checkpoint at version 2
+    let (checkpoint_parts, commits): (Vec<_>, Vec<_>) = engine
+        .storage_handler()
+        .list_from(&log_root)?
+        .try_collect::<_, Vec<_>, _>()?
+        .into_iter()
+        .filter(|file| {
+            file.location.path().ends_with(".json") || file.location.path().ends_with(".parquet")
+        })
+        .partition(|file| file.location.path().contains(".checkpoint."));
+    assert_eq!((checkpoint_parts.len(), commits.len()), (1, 4));
+
+    let listed = LogSegment::for_snapshot(
+        engine.storage_handler().as_ref(),
+        log_root.clone(),
+        None,
+        None,
+    )?;
+    let checkpoint = CheckpointMeta {
+        parts: checkpoint_parts,
+    };
+    // the order of the supplied commits doesn't matter
+    let reversed = commits.iter().rev().cloned().collect();
+    let log_segment = LogSegment::from_commit_files(Some(checkpoint), reversed)?;
+    assert_eq!(log_segment, listed);
+    assert_eq!(log_segment.checkpoint_version, Some(2));
+    assert_eq!(log_segment.ascending_commit_files.len(), 1);
+
+    let snapshot = Snapshot::try_new_from_log_segment(table_root.clone(), log_segment, &engine)?;
+    let expected = Table::new(table_root.clone()).snapshot(&engine, None)?;
+    assert_eq!(snapshot, expected);
+
+    // without the checkpoint, all commits are replayed to the same table state
+    let log_segment = LogSegment::from_commit_files(None, commits)?;
+    assert_eq!(log_segment.checkpoint_version, None);
+    assert_eq!(log_segment.end_version, 3);
+    assert_eq!(
+        log_segment.active_file_paths(&engine)?,
+        expected.log_segment().active_file_paths(&engine)?
+    );
+    let snapshot = Snapshot::try_new_from_log_segment(table_root, log_segment, &engine)?;
+    assert_eq!(snapshot.version(), expected.version());
+    assert_eq!(snapshot.schema(), expected.schema());
+    Ok(())
+}
+
+#[test]
+fn build_log_segment_from_invalid_commit_files() {
+    let file = |path: &str| FileMeta::new(Url::parse(path).unwrap(), 0, 10);
+    let commit = |version: u64| file(&format!("memory:///_delta_log/{version:020}.json"));
+    let checkpoint = |parts: Vec<FileMeta>| Some(CheckpointMeta { parts });
+    let part = |version: u64, part_num: u32, num_parts: u32| {
+        file(&format!(
+            "memory:///_delta_log/{version:020}.checkpoint.{part_num:010}.{num_parts:010}.parquet"
+        ))
+    };
+
+    // a multi-part checkpoint given in any order, followed by commits 6 and 7
+    let log_segment = LogSegment::from_commit_files(
+        checkpoint(vec![part(5, 2, 2), part(5, 1, 2)]),
+        (3..8).map(commit).collect(),
+    )
+    .unwrap();
+    assert_eq!(log_segment.checkpoint_version, Some(5));
+    assert_eq!(log_segment.checkpoint_parts.len(), 2);
+    assert_eq!(log_segment.end_version, 7);
+    assert_eq!(log_segment.log_root.as_str(), "memory:///_delta_log/");
+
+    let invalid = [
+        // no files at all
+        (None, vec![]),
+        // commit 1 is missing
+        (None, vec![commit(0), commit(2)]),
+        // commit 6 is missing after the checkpoint
+        (checkpoint(vec![part(5, 1, 1)]), vec![commit(7)]),
+        // not a commit file
+        (None, vec![part(0, 1, 1)]),
+        (None, vec![file("memory:///_delta_log/_last_checkpoint")]),
+        // a checkpoint missing a part, or with parts of different checkpoints
+        (checkpoint(vec![part(5, 1, 2)]), vec![]),
+        (checkpoint(vec![part(5, 1, 2), part(5, 2, 3)]), vec![]),
+        (checkpoint(vec![part(4, 1, 1), part(5, 1, 1)]), vec![]),
+        (checkpoint(vec![]), vec![commit(0)]),
+        // not a checkpoint file
+        (checkpoint(vec![commit(5)]), vec![commit(6)]),
+        // files of another table
+        (
+            None,
+            vec![
+                commit(0),
+                file("memory:///other/_delta_log/00000000000000000001.json"),
+            ],
+        ),
+    ];
+    for (checkpoint, commits) in invalid {
+        let res = LogSegment::from_commit_files(checkpoint.clone(), commits.clone());
+        assert!(res.is_err(), "{checkpoint:?} {commits:?}");
+    }
+}
+
 #[test]
 fn table_changes_fails_with_larger_start_version_than_end() {
     // Commit with version 1 is missing
@@ -1255,3 +1553,252 @@ fn test_create_checkpoint_stream_reads_checkpoint_file_and_returns_sidecar_batch
 
     Ok(())
 }
+
+/// A [`JsonHandler`] that reads files one at a time on demand and counts how many it has opened.
+struct CountingJsonHandler {
+    inner: Arc<dyn JsonHandler>,
+    files_read: Arc<AtomicUsize>,
+}
+
+impl JsonHandler for CountingJsonHandler {
+    fn parse_json(
+        &self,
+        json_strings: Box<dyn EngineData>,
+        output_schema: SchemaRef,
+    ) -> DeltaResult<Box<dyn EngineData>> {
+        self.inner.parse_json(json_strings, output_schema)
+    }
+
+    fn read_json_files(
+        &self,
+        files: &[FileMeta],
+        physical_schema: SchemaRef,
+        predicate: Option<ExpressionRef>,
+    ) -> DeltaResult<FileDataReadResultIterator> {
+        let inner = self.inner.clone();
+        let files_read = self.files_read.clone();
+        let files = files.to_vec();
+        let batches = files.into_iter().flat_map(move |file| {
+            files_read.fetch_add(1, Ordering::SeqCst);
+            match inner.read_json_files(&[file], physical_schema.clone(), predicate.clone()) {
+                Ok(batches) => batches,
+                Err(err) => Box::new(std::iter::once(Err(err))),
+            }
+        });
+        Ok(Box::new(batches))
+    }
+
+    fn write_json_file(
+        &self,
+        path: &Url,
+        data: Box<dyn Iterator<Item = DeltaResult<Box<dyn EngineData>>> + Send + '_>,
+        overwrite: bool,
+    ) -> DeltaResult<()> {
+        self.inner.write_json_file(path, data, overwrite)
+    }
+}
+
+struct CountingEngine {
+    inner: DefaultEngine<TokioBackgroundExecutor>,
+    json: Arc<CountingJsonHandler>,
+}
+
+impl Engine for CountingEngine {
+    fn evaluation_handler(&self) -> Arc<dyn EvaluationHandler> {
+        self.inner.evaluation_handler()
+    }
+
+    fn storage_handler(&self) -> Arc<dyn StorageHandler> {
+        self.inner.storage_handler()
+    }
+
+    fn json_handler(&self) -> Arc<dyn JsonHandler> {
+        self.json.clone()
+    }
+
+    fn parquet_handler(&self) -> Arc<dyn ParquetHandler> {
+        self.inner.parquet_handler()
+    }
+}
+
+#[test]
+fn test_read_actions_reads_lazily_and_yields_final_file_set() -> DeltaResult<()> {
+    let (store, log_root) = new_in_memory_store();
+    let inner = DefaultEngine::new(store.clone(), Arc::new(TokioBackgroundExecutor::new()));
+    let files_read = Arc::new(AtomicUsize::new(0));
+    let engine = CountingEngine {
+        json: Arc::new(CountingJsonHandler {
+            inner: inner.json_handler(),
+            files_read: files_read.clone(),
+        }),
+        inner,
+    };
+
+    let add = |path: &str| {
+        Action::Add(Add {
+            path: path.into(),
+            data_change: true,
+            ..Default::default()
+        })
+    };
+    let remove = |path: &str| {
+        Action::Remove(Remove {
+            path: path.into(),
+            data_change: true,
+            ..Default::default()
+        })
+    };
+    let commits = [add("a"), add("b"), remove("a"), add("c")];
+    for (version, action) in commits.into_iter().enumerate() {
+        write_json_to_store(&store, vec![action], &format!("{version:020}.json"))?;
+    }
+
+    let log_segment =
+        LogSegment::for_snapshot(engine.storage_handler().as_ref(), log_root, None, None)?;
+    let commit_schema = get_log_schema().project(&[ADD_NAME, REMOVE_NAME])?;
+    let checkpoint_schema = get_log_schema().project(&[ADD_NAME, SIDECAR_NAME])?;
+    let mut actions = log_segment.read_actions(&engine, commit_schema, checkpoint_schema, None)?;
+    assert_eq!(files_read.load(Ordering::SeqCst), 0);
+
+    // Pulling the first batch must only read the most recent commit
+    let first = actions.next().unwrap()?;
+    assert!(first.1);
+    assert_eq!(files_read.load(Ordering::SeqCst), 1);
+
+    // The kernel's add/remove dedup must see through the streamed batches
+    let table_schema = Arc::new(StructType::new([StructField::nullable(
+        "value",
+        DataType::INTEGER,
+    )]));
+    let live_files = scan_action_iter(
+        &engine,
+        std::iter::once(Ok(first)).chain(actions),
+        table_schema,
+        None,
+        None,
+        None,
+    )
+    .try_fold(HashSet::new(), |paths, scan_metadata| {
+        scan_metadata?.visit_scan_files(
+            paths,
+            |paths: &mut HashSet<String>, path, _, _, _, _, _| {
+                paths.insert(path.to_string());
+            },
+        )
+    })?;
+    assert_eq!(files_read.load(Ordering::SeqCst), 4);
+    assert_eq!(
+        live_files,
+        HashSet::from(["b".to_string(), "c".to_string()])
+    );
+    Ok(())
+}
+
+// Writes a checkpoint at version 1 whose actions are split across the given parts and returns the
+// paths of the files in the table reconstructed from it.
+fn checkpoint_file_set(parts: &[&[&str]]) -> DeltaResult<Vec<String>> {
+    let (store, log_root) = new_in_memory_store();
+    let engine = DefaultEngine::new(store.clone(), Arc::new(TokioBackgroundExecutor::new()));
+    let num_parts = parts.len() as u32;
+    for (part_num, actions) in (1..).zip(parts) {
+        let filename = if num_parts == 1 {
+            "00000000000000000001.checkpoint.parquet".to_string()
+        } else {
+            format!("00000000000000000001.checkpoint.{part_num:010}.{num_parts:010}.parquet")
+        };
+        add_checkpoint_to_store(&store, parse_json_batch(actions.to_vec().into()), &filename)?;
+    }
+
+    let log_segment = LogSegment::for_snapshot(
+        engine.storage_handler().as_ref(),
+        log_root.clone(),
+        None,
+        None,
+    )?;
+    assert_eq!(log_segment.checkpoint_version, Some(1));
+    assert_eq!(log_segment.checkpoint_parts.len(), parts.len());
+    assert!(log_segment.ascending_commit_files.is_empty());
+
+    let table_root = log_root.join("..")?;
+    let snapshot = Arc::new(Table::new(table_root).snapshot(&engine, None)?);
+    let mut paths = vec![];
+    for scan_metadata in snapshot.scan_builder().build()?.scan_metadata(&engine)? {
+        paths = scan_metadata?
+            .visit_scan_files(paths, |paths: &mut Vec<String>, path, _, _, _, _, _| {
+                paths.push(path.to_string())
+            })?;
+    }
+    paths.sort();
+    Ok(paths)
+}
+
+#[test]
+fn test_multi_part_checkpoint_matches_single_part_checkpoint() -> DeltaResult<()> {
+    let protocol = r#"{"protocol":{"minReaderVersion":1,"minWriterVersion":2}}"#;
+    let metadata = r#"{"metaData":{"id":"testId","format":{"provider":"parquet","options":{}},"schemaString":"{\"type\":\"struct\",\"fields\":[{\"name\":\"value\",\"type\":\"integer\",\"nullable\":true,\"metadata\":{}}]}","partitionColumns":[],"configuration":{},"createdTime":1677811175819}}"#;
+    let add = |path: &str| {
+        format!(
+            r#"{{"add":{{"path":"{path}","partitionValues":{{}},"size":635,"modificationTime":1677811178336,"dataChange":true}}}}"#
+        )
+    };
+    let (add_a, add_b, add_c) = (add("a.parquet"), add("b.parquet"), add("c.parquet"));
+
+    let single_part =
+        checkpoint_file_set(&[&[protocol, metadata, add_a.as_str(), add_b.as_str(), &add_c]])?;
+    let multi_part = checkpoint_file_set(&[
+        &[protocol, metadata],
+        &[add_a.as_str(), add_b.as_str()],
+        &[add_c.as_str()],
+    ])?;
+
+    assert_eq!(single_part, ["a.parquet", "b.parquet", "c.parquet"]);
+    assert_eq!(multi_part, single_part);
+    Ok(())
+}
+
+#[test]
+fn test_verify_checkpoint_names_missing_add() -> DeltaResult<()> {
+    let protocol = r#"{"protocol":{"minReaderVersion":1,"minWriterVersion":2}}"#;
+    let metadata = r#"{"metaData":{"id":"testId","format":{"provider":"parquet","options":{}},"schemaString":"{\"type\":\"struct\",\"fields\":[{\"name\":\"value\",\"type\":\"integer\",\"nullable\":true,\"metadata\":{}}]}","partitionColumns":[],"configuration":{},"createdTime":1677811175819}}"#;
+    let add = |path: &str| {
+        format!(
+            r#"{{"add":{{"path":"{path}","partitionValues":{{}},"size":635,"modificationTime":1677811178336,"dataChange":true}}}}"#
+        )
+    };
+    let remove =
+        r#"{"remove":{"path":"a.parquet","deletionTimestamp":1677811178336,"dataChange":true}}"#;
+    let commits = [
+        vec![protocol.to_string(), metadata.to_string(), add("a.parquet")],
+        vec![add("b.parquet"), add("c.parquet")],
+        vec![remove.to_string()],
+    ];
+
+    let verify = |checkpoint: &[&str]| -> DeltaResult<()> {
+        let (store, log_root) = new_in_memory_store();
+        let engine = DefaultEngine::new(store.clone(), Arc::new(TokioBackgroundExecutor::new()));
+        for (version, actions) in commits.iter().enumerate() {
+            let path = Path::from(format!("_delta_log/{version:020}.json"));
+            block_on(store.put(&path, actions.join("\n").into()))?;
+        }
+        add_checkpoint_to_store(
+            &store,
+            parse_json_batch(checkpoint.to_vec().into()),
+            "00000000000000000002.checkpoint.parquet",
+        )?;
+        let snapshot = Snapshot::try_new(log_root.join("..")?, &engine, None)?;
+        assert_eq!(snapshot.log_segment().checkpoint_version, Some(2));
+        snapshot.verify_checkpoint(&engine)
+    };
+
+    let (add_b, add_c) = (add("b.parquet"), add("c.parquet"));
+    verify(&[protocol, metadata, &add_b, &add_c])?;
+
+    let err = verify(&[protocol, metadata, &add_b]).unwrap_err();
+    assert!(matches!(err, Error::InvalidCheckpoint(_)), "{err}");
+    assert!(
+        err.to_string()
+            .contains("Missing from checkpoint: [c.parquet]. Not in commits: []"),
+        "{err}"
+    );
+    Ok(())
+}
@@ -180,7 +180,12 @@ impl ParsedLogPath<Url> {
 
     /// Helper method to create a path with the given filename generator
     fn create_path(table_root: &Url, filename: String) -> DeltaResult<Self> {
-        let location = table_root.join(Self::DELTA_LOG_DIR)?.join(&filename)?;
+        Self::create_log_path(&table_root.join(Self::DELTA_LOG_DIR)?, filename)
+    }
+
+    /// Like [`Self::create_path`], but for a file directly in `log_root`
+    fn create_log_path(log_root: &Url, filename: String) -> DeltaResult<Self> {
+        let location = log_root.join(&filename)?;
         Self::try_from(location)?.ok_or_else(|| {
             Error::internal_error(format!("Attempted to create an invalid path: {}", filename))
         })
@@ -188,8 +193,13 @@ impl ParsedLogPath<Url> {
 
     /// Create a new ParsedCommitPath<Url> for a new json commit file
     pub(crate) fn new_commit(table_root: &Url, version: Version) -> DeltaResult<Self> {
+        Self::new_commit_in_log(&table_root.join(Self::DELTA_LOG_DIR)?, version)
+    }
+
+    /// Create a new ParsedCommitPath<Url> for a new json commit file in the log at `log_root`
+    pub(crate) fn new_commit_in_log(log_root: &Url, version: Version) -> DeltaResult<Self> {
         let filename = format!("{:020}.json", version);
-        let path = Self::create_path(table_root, filename)?;
+        let path = Self::create_log_path(log_root, filename)?;
         if !path.is_commit() {
             return Err(Error::internal_error(
                 "ParsedLogPath::new_commit created a non-commit path",
@@ -203,9 +213,18 @@ impl ParsedLogPath<Url> {
     pub(crate) fn new_classic_parquet_checkpoint(
         table_root: &Url,
         version: Version,
+    ) -> DeltaResult<Self> {
+        Self::new_classic_parquet_checkpoint_in_log(&table_root.join(Self::DELTA_LOG_DIR)?, version)
+    }
+
+    /// Create a new ParsedCheckpointPath<Url> for a classic parquet checkpoint file in the log at
+    /// `log_root`
+    pub(crate) fn new_classic_parquet_checkpoint_in_log(
+        log_root: &Url,
+        version: Version,
     ) -> DeltaResult<Self> {
         let filename = format!("{:020}.checkpoint.parquet", version);
-        let path = Self::create_path(table_root, filename)?;
+        let path = Self::create_log_path(log_root, filename)?;
         if !path.is_checkpoint() {
             return Err(Error::internal_error(
                 "ParsedLogPath::new_classic_parquet_checkpoint created a non-checkpoint path",
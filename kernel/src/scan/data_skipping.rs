@@ -1,5 +1,6 @@
 use std::borrow::Cow;
 use std::cmp::Ordering;
+use std::collections::HashSet;
 use std::sync::{Arc, LazyLock};
 
 use tracing::debug;
@@ -38,13 +39,16 @@ mod tests;
 ///   expression is dropped.
 #[cfg(test)]
 fn as_data_skipping_predicate(expr: &Expr) -> Option<Expr> {
-    DataSkippingPredicateCreator.eval(expr)
+    DataSkippingPredicateCreator::default().eval(expr)
 }
 
 /// Like `as_data_skipping_predicate`, but invokes [`KernelPredicateEvaluator::eval_sql_where`]
-/// instead of [`KernelPredicateEvaluator::eval`].
-fn as_sql_data_skipping_predicate(expr: &Expr) -> Option<Expr> {
-    DataSkippingPredicateCreator.eval_sql_where(expr)
+/// instead of [`KernelPredicateEvaluator::eval`], and only uses the stats of `stats_columns`.
+fn as_sql_data_skipping_predicate(expr: &Expr, stats_columns: HashSet<ColumnName>) -> Option<Expr> {
+    DataSkippingPredicateCreator {
+        stats_columns: Some(stats_columns),
+    }
+    .eval_sql_where(expr)
 }
 
 pub(crate) struct DataSkippingFilter {
@@ -73,6 +77,10 @@ impl DataSkippingFilter {
             LazyLock::new(|| column_expr!("predicate").distinct(Expr::literal(false)));
 
         let (predicate, referenced_schema) = physical_predicate?;
+        if referenced_schema.fields_len() == 0 {
+            // none of the referenced columns have stats
+            return None;
+        }
         debug!("Creating a data skipping filter for {:#?}", predicate);
 
         // Convert all fields into nullable, as stats may not be available for all columns
@@ -111,6 +119,11 @@ impl DataSkippingFilter {
         let stats_schema = NullableStatsTransform
             .transform_struct(&referenced_schema)?
             .into_owned();
+        // the skipping predicate may only use stats that are part of the stats schema
+        let mut stats_columns = HashSet::new();
+        stats_schema.walk_leaves(|column, _| {
+            stats_columns.insert(column.clone());
+        });
 
         let nullcount_schema = NullCountStatsTransform
             .transform_struct(&stats_schema)?
@@ -142,7 +155,7 @@ impl DataSkippingFilter {
 
         let skipping_evaluator = engine.evaluation_handler().new_expression_evaluator(
             stats_schema.clone(),
-            Expr::struct_from([as_sql_data_skipping_predicate(&predicate)?]),
+            Expr::struct_from([as_sql_data_skipping_predicate(&predicate, stats_columns)?]),
             PREDICATE_SCHEMA.clone(),
         );
 
@@ -194,7 +207,19 @@ impl DataSkippingFilter {
     }
 }
 
-struct DataSkippingPredicateCreator;
+#[derive(Default)]
+struct DataSkippingPredicateCreator {
+    /// The columns that have stats, or `None` if every column does
+    stats_columns: Option<HashSet<ColumnName>>,
+}
+
+impl DataSkippingPredicateCreator {
+    fn has_stats(&self, col: &ColumnName) -> bool {
+        self.stats_columns
+            .as_ref()
+            .map_or(true, |stats_columns| stats_columns.contains(col))
+    }
+}
 
 impl DataSkippingPredicateEvaluator for DataSkippingPredicateCreator {
     type Output = Expr;
@@ -203,17 +228,20 @@ impl DataSkippingPredicateEvaluator for DataSkippingPredicateCreator {
 
     /// Retrieves the minimum value of a column, if it exists and has the requested type.
     fn get_min_stat(&self, col: &ColumnName, _data_type: &DataType) -> Option<Expr> {
-        Some(joined_column_expr!("minValues", col))
+        self.has_stats(col)
+            .then(|| joined_column_expr!("minValues", col))
     }
 
     /// Retrieves the maximum value of a column, if it exists and has the requested type.
     fn get_max_stat(&self, col: &ColumnName, _data_type: &DataType) -> Option<Expr> {
-        Some(joined_column_expr!("maxValues", col))
+        self.has_stats(col)
+            .then(|| joined_column_expr!("maxValues", col))
     }
 
     /// Retrieves the null count of a column, if it exists.
     fn get_nullcount_stat(&self, col: &ColumnName) -> Option<Expr> {
-        Some(joined_column_expr!("nullCount", col))
+        self.has_stats(col)
+            .then(|| joined_column_expr!("nullCount", col))
     }
 
     /// Retrieves the row count of a column (parquet footers always include this stat).
@@ -1,7 +1,8 @@
 use super::*;
 
-use crate::expressions::column_name;
+use crate::expressions::{column_name, ArrayData};
 use crate::kernel_predicates::{DefaultKernelPredicateEvaluator, UnimplementedColumnResolver};
+use crate::schema::ArrayType;
 use std::collections::HashMap;
 
 const TRUE: Option<bool> = Some(true);
@@ -192,6 +193,51 @@ fn test_eval_junction() {
 // DISTINCT is actually quite complex internally. It indirectly exercises IS [NOT] NULL and
 // AND/OR. A different test validates min/max comparisons, so here we're mostly worried about NULL
 // vs. non-NULL literals and nullcount/rowcount stats.
+#[test]
+fn test_eval_in() {
+    let col = &column_expr!("status");
+    let values = Scalar::Array(ArrayData::new(
+        ArrayType::new(DataType::STRING, false),
+        ["active", "pending"],
+    ));
+    let expressions = [
+        Expr::binary(BinaryOperator::In, col.clone(), values.clone()),
+        Expr::binary(BinaryOperator::NotIn, col.clone(), values),
+    ];
+
+    let do_test = |min: &str, max: &str, expected: &[Option<bool>]| {
+        let resolver = HashMap::from_iter([
+            (column_name!("numRecords"), Scalar::from(2i64)),
+            (column_name!("nullCount.status"), Scalar::from(0i64)),
+            (column_name!("minValues.status"), Scalar::from(min)),
+            (column_name!("maxValues.status"), Scalar::from(max)),
+        ]);
+        let filter = DefaultKernelPredicateEvaluator::from(resolver);
+        for (expr, expect) in expressions.iter().zip(expected) {
+            let pred = as_data_skipping_predicate(expr).unwrap();
+            expect_eq!(
+                filter.eval_expr(&pred, false),
+                *expect,
+                "{expr:#?} became {pred:#?} ({min}..{max})"
+            );
+        }
+    };
+
+    // no listed value falls within [min, max]
+    do_test("b", "c", &[FALSE, TRUE]);
+    do_test("q", "z", &[FALSE, TRUE]);
+    do_test("activf", "pendinf", &[FALSE, TRUE]);
+
+    // some listed value falls within [min, max]
+    do_test("a", "b", &[TRUE, TRUE]);
+    do_test("b", "pending", &[TRUE, TRUE]);
+    do_test("a", "z", &[TRUE, TRUE]);
+
+    // NOT IN can only skip files whose values are all one of the listed values
+    do_test("active", "active", &[TRUE, FALSE]);
+    do_test("pending", "pending", &[TRUE, FALSE]);
+}
+
 #[test]
 fn test_eval_distinct() {
     let col = &column_expr!("x");
@@ -293,7 +339,8 @@ fn test_sql_where() {
                 expect,
                 "{expr:#?} became {pred:#?} ({min}..{max}, {nulls} nulls)"
             );
-            let sql_pred = as_sql_data_skipping_predicate(expr).unwrap();
+            let sql_pred =
+                as_sql_data_skipping_predicate(expr, HashSet::from([column_name!("x")])).unwrap();
             expect_eq!(
                 filter.eval_expr(&sql_pred, false),
                 expect_sql,
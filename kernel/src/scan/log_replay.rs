@@ -8,25 +8,27 @@ use super::data_skipping::DataSkippingFilter;
 use super::{ScanMetadata, Transform};
 use crate::actions::get_log_add_schema;
 use crate::engine_data::{GetData, RowVisitor, TypedGetData as _};
-use crate::expressions::{column_expr, column_name, ColumnName, Expression, ExpressionRef};
+use crate::expressions::{
+    column_expr, column_name, BinaryOperator, ColumnName, Expression, ExpressionRef,
+};
 use crate::kernel_predicates::{DefaultKernelPredicateEvaluator, KernelPredicateEvaluator as _};
 use crate::log_replay::{FileActionDeduplicator, FileActionKey, LogReplayProcessor};
-use crate::scan::{Scalar, TransformExpr};
+use crate::scan::{PredicateSplit, Scalar, TransformExpr};
 use crate::schema::{ColumnNamesAndTypes, DataType, MapType, SchemaRef, StructField, StructType};
 use crate::utils::require;
-use crate::{DeltaResult, Engine, EngineData, Error, ExpressionEvaluator};
+use crate::{DeltaResult, Engine, EngineData, Error, ExpressionEvaluator, ROW_INDEX_COLUMN_NAME};
 
 /// [`ScanLogReplayProcessor`] performs log replay (processes actions) specifically for doing a table scan.
 ///
 /// During a table scan, the processor reads batches of log actions (in reverse chronological order)
 /// and performs the following steps:
 ///
-/// - Data Skipping: Applies a predicate-based filter (via [`DataSkippingFilter`]) to quickly skip
-///   files that are irrelevant for the query.
-/// - Partition Pruning: Uses an optional partition filter (extracted from a physical predicate)
-///   to exclude actions whose partition values do not meet the required criteria.
+/// - Partition Pruning: Uses the partition-only conjuncts of the physical predicate (see
+///   [`PredicateSplit`]) to exclude actions whose partition values do not meet the required criteria.
 /// - Action Deduplication: Leverages the [`FileActionDeduplicator`] to ensure that for each unique file
 ///   (identified by its path and deletion vector unique ID), only the latest valid Add action is processed.
+/// - Data Skipping: Applies the remaining data conjuncts as a stats-based filter (via
+///   [`DataSkippingFilter`]) to skip the surviving files that are irrelevant for the query.
 /// - Transformation: Applies a built-in transformation (`add_transform`) to convert selected Add actions
 ///   into [`ScanMetadata`], the intermediate format passed to the engine.
 /// - Row Transform Passthrough: Any user-provided row-level transformation expressions (e.g. those derived
@@ -44,6 +46,7 @@ pub(crate) struct ScanLogReplayProcessor {
     add_transform: Arc<dyn ExpressionEvaluator>,
     logical_schema: SchemaRef,
     transform: Option<Arc<Transform>>,
+    file_filter: Option<Arc<HashSet<String>>>,
     /// A set of (data file path, dv_unique_id) pairs that have been seen thus
     /// far in the log. This is used to filter out files with Remove actions as
     /// well as duplicate entries in the log.
@@ -57,10 +60,41 @@ impl ScanLogReplayProcessor {
         physical_predicate: Option<(ExpressionRef, SchemaRef)>,
         logical_schema: SchemaRef,
         transform: Option<Arc<Transform>>,
+        file_filter: Option<Arc<HashSet<String>>>,
     ) -> Self {
+        // Partition columns are always top-level, and the predicate uses their physical names
+        let partition_columns: HashSet<ColumnName> = transform
+            .iter()
+            .flat_map(|transform| transform.iter())
+            .filter_map(|transform_expr| match transform_expr {
+                TransformExpr::Partition(field_idx) => logical_schema
+                    .fields
+                    .get_index(*field_idx)
+                    .map(|(_, field)| ColumnName::new([field.physical_name()])),
+                _ => None,
+            })
+            .collect();
+        let (partition_filter, data_predicate) = match physical_predicate {
+            Some((predicate, referenced_schema)) => {
+                let split = PredicateSplit::new(&predicate, &partition_columns);
+                // Only ask data skipping to read stats for the columns the data conjuncts use
+                let data_predicate = split.data.map(|data| {
+                    let references = data.references();
+                    let data_schema = StructType::new(
+                        referenced_schema
+                            .fields()
+                            .filter(|f| references.iter().any(|c| c.path()[0] == *f.name()))
+                            .cloned(),
+                    );
+                    (data, Arc::new(data_schema))
+                });
+                (split.partition, data_predicate)
+            }
+            None => (None, None),
+        };
         Self {
-            partition_filter: physical_predicate.as_ref().map(|(e, _)| e.clone()),
-            data_skipping_filter: DataSkippingFilter::new(engine, physical_predicate),
+            partition_filter,
+            data_skipping_filter: DataSkippingFilter::new(engine, data_predicate),
             add_transform: engine.evaluation_handler().new_expression_evaluator(
                 get_log_add_schema().clone(),
                 get_add_transform_expr(),
@@ -69,6 +103,7 @@ impl ScanLogReplayProcessor {
             seen_file_keys: Default::default(),
             logical_schema,
             transform,
+            file_filter,
         }
     }
 }
@@ -83,17 +118,38 @@ struct AddRemoveDedupVisitor<'seen> {
     logical_schema: SchemaRef,
     transform: Option<Arc<Transform>>,
     partition_filter: Option<ExpressionRef>,
+    file_filter: Option<Arc<HashSet<String>>>,
     row_transform_exprs: Vec<Option<ExpressionRef>>,
 }
 
+/// The fields of an Add action used to materialize row ids, row commit versions, and file paths.
+struct AddValues<'a> {
+    path: &'a str,
+    base_row_id: Option<i64>,
+    default_row_commit_version: Option<i64>,
+}
+
+impl AddValues<'_> {
+    fn require(&self, value: Option<i64>, name: &str) -> DeltaResult<i64> {
+        value.ok_or_else(|| {
+            Error::generic(format!(
+                "Row tracking requires {name}, but it is missing from the add action for {}",
+                self.path
+            ))
+        })
+    }
+}
+
 impl AddRemoveDedupVisitor<'_> {
     // These index positions correspond to the order of columns defined in
     // `selected_column_names_and_types()`
     const ADD_PATH_INDEX: usize = 0; // Position of "add.path" in getters
     const ADD_PARTITION_VALUES_INDEX: usize = 1; // Position of "add.partitionValues" in getters
     const ADD_DV_START_INDEX: usize = 2; // Start position of add deletion vector columns
-    const REMOVE_PATH_INDEX: usize = 5; // Position of "remove.path" in getters
-    const REMOVE_DV_START_INDEX: usize = 6; // Start position of remove deletion vector columns
+    const ADD_BASE_ROW_ID_INDEX: usize = 5; // Position of "add.baseRowId" in getters
+    const ADD_DEFAULT_ROW_COMMIT_VERSION_INDEX: usize = 6; // Position of "add.defaultRowCommitVersion"
+    const REMOVE_PATH_INDEX: usize = 7; // Position of "remove.path" in getters
+    const REMOVE_DV_START_INDEX: usize = 8; // Start position of remove deletion vector columns
 
     fn new(
         seen: &mut HashSet<FileActionKey>,
@@ -101,6 +157,7 @@ impl AddRemoveDedupVisitor<'_> {
         logical_schema: SchemaRef,
         transform: Option<Arc<Transform>>,
         partition_filter: Option<ExpressionRef>,
+        file_filter: Option<Arc<HashSet<String>>>,
         is_log_batch: bool,
     ) -> AddRemoveDedupVisitor<'_> {
         AddRemoveDedupVisitor {
@@ -116,6 +173,7 @@ impl AddRemoveDedupVisitor<'_> {
             logical_schema,
             transform,
             partition_filter,
+            file_filter,
             row_transform_exprs: Vec::new(),
         }
     }
@@ -132,8 +190,10 @@ impl AddRemoveDedupVisitor<'_> {
             )));
         };
         let name = field.physical_name();
-        let partition_value =
-            super::parse_partition_value(partition_values.get(name), field.data_type())?;
+        let partition_value = super::parse_partition_value(
+            partition_values.get(name).map(String::as_str),
+            field.data_type(),
+        )?;
         Ok((field_idx, (name.to_string(), partition_value)))
     }
 
@@ -148,7 +208,7 @@ impl AddRemoveDedupVisitor<'_> {
                 TransformExpr::Partition(field_idx) => {
                     Some(self.parse_partition_value(*field_idx, partition_values))
                 }
-                TransformExpr::Static(_) => None,
+                _ => None,
             })
             .try_collect()
     }
@@ -158,6 +218,7 @@ impl AddRemoveDedupVisitor<'_> {
         &self,
         transform: &Transform,
         mut partition_values: HashMap<usize, (String, Scalar)>,
+        add: &AddValues<'_>,
     ) -> DeltaResult<ExpressionRef> {
         let transforms = transform
             .iter()
@@ -171,6 +232,26 @@ impl AddRemoveDedupVisitor<'_> {
                     Ok(partition_value.into())
                 }
                 TransformExpr::Static(field_expr) => Ok(field_expr.clone()),
+                TransformExpr::RowId => {
+                    // the row id of a row is its file's base row id plus its index in the file
+                    let base_row_id = add.require(add.base_row_id, "baseRowId")?;
+                    Ok(Expression::binary(
+                        BinaryOperator::Plus,
+                        Expression::literal(base_row_id),
+                        Expression::column([ROW_INDEX_COLUMN_NAME]),
+                    ))
+                }
+                TransformExpr::RowCommitVersion => {
+                    let version =
+                        add.require(add.default_row_commit_version, "defaultRowCommitVersion")?;
+                    Ok(Expression::literal(version))
+                }
+                TransformExpr::FilePath(table_root) => {
+                    let location = super::state::resolve_file_path(table_root, add.path)?;
+                    Ok(Expression::struct_from([Expression::literal(
+                        location.to_string(),
+                    )]))
+                }
             })
             .try_collect()?;
         Ok(Arc::new(Expression::Struct(transforms)))
@@ -212,6 +293,17 @@ impl AddRemoveDedupVisitor<'_> {
             return Ok(false);
         };
 
+        // Skip adds of files the scan was not restricted to. Like partition pruning below, this
+        // must not apply to removes.
+        if is_add
+            && self
+                .file_filter
+                .as_ref()
+                .is_some_and(|paths| !paths.contains(&file_key.path))
+        {
+            return Ok(false);
+        }
+
         // Apply partition pruning (to adds only) before deduplication, so that we don't waste memory
         // tracking pruned files. Removes don't get pruned and we'll still have to track them.
         //
@@ -238,7 +330,16 @@ impl AddRemoveDedupVisitor<'_> {
         let transform = self
             .transform
             .as_ref()
-            .map(|transform| self.get_transform_expr(transform, partition_values))
+            .map(|transform| -> DeltaResult<_> {
+                let add = AddValues {
+                    path: getters[Self::ADD_PATH_INDEX].get(i, "add.path")?,
+                    base_row_id: getters[Self::ADD_BASE_ROW_ID_INDEX]
+                        .get_opt(i, "add.baseRowId")?,
+                    default_row_commit_version: getters[Self::ADD_DEFAULT_ROW_COMMIT_VERSION_INDEX]
+                        .get_opt(i, "add.defaultRowCommitVersion")?,
+                };
+                self.get_transform_expr(transform, partition_values, &add)
+            })
             .transpose()?;
         if transform.is_some() {
             // fill in any needed `None`s for previous rows
@@ -255,6 +356,7 @@ impl RowVisitor for AddRemoveDedupVisitor<'_> {
         static NAMES_AND_TYPES: LazyLock<ColumnNamesAndTypes> = LazyLock::new(|| {
             const STRING: DataType = DataType::STRING;
             const INTEGER: DataType = DataType::INTEGER;
+            const LONG: DataType = DataType::LONG;
             let ss_map: DataType = MapType::new(STRING, STRING, true).into();
             let types_and_names = vec![
                 (STRING, column_name!("add.path")),
@@ -262,6 +364,8 @@ impl RowVisitor for AddRemoveDedupVisitor<'_> {
                 (STRING, column_name!("add.deletionVector.storageType")),
                 (STRING, column_name!("add.deletionVector.pathOrInlineDv")),
                 (INTEGER, column_name!("add.deletionVector.offset")),
+                (LONG, column_name!("add.baseRowId")),
+                (LONG, column_name!("add.defaultRowCommitVersion")),
                 (STRING, column_name!("remove.path")),
                 (STRING, column_name!("remove.deletionVector.storageType")),
                 (STRING, column_name!("remove.deletionVector.pathOrInlineDv")),
@@ -276,13 +380,13 @@ impl RowVisitor for AddRemoveDedupVisitor<'_> {
         } else {
             // All checkpoint actions are already reconciled and Remove actions in checkpoint files
             // only serve as tombstones for vacuum jobs. So we only need to examine the adds here.
-            (&names[..5], &types[..5])
+            (&names[..7], &types[..7])
         }
     }
 
     fn visit<'a>(&mut self, row_count: usize, getters: &[&'a dyn GetData<'a>]) -> DeltaResult<()> {
         let is_log_batch = self.deduplicator.is_log_batch();
-        let expected_getters = if is_log_batch { 9 } else { 5 };
+        let expected_getters = if is_log_batch { 11 } else { 7 };
         require!(
             getters.len() == expected_getters,
             Error::InternalError(format!(
@@ -348,8 +452,9 @@ impl LogReplayProcessor for ScanLogReplayProcessor {
         is_log_batch: bool,
     ) -> DeltaResult<Self::Output> {
         // Build an initial selection vector for the batch which has had the data skipping filter
-        // applied. The selection vector is further updated by the deduplication visitor to remove
-        // rows that are not valid adds.
+        // applied. The selection vector is further updated by the deduplication visitor, which
+        // prunes files by their partition values before recording them as seen, to remove rows
+        // that are not valid adds.
         let selection_vector = self.build_selection_vector(actions_batch.as_ref())?;
         assert_eq!(selection_vector.len(), actions_batch.len());
 
@@ -359,6 +464,7 @@ impl LogReplayProcessor for ScanLogReplayProcessor {
             self.logical_schema.clone(),
             self.transform.clone(),
             self.partition_filter.clone(),
+            self.file_filter.clone(),
             is_log_batch,
         );
         visitor.visit_rows_of(actions_batch.as_ref())?;
@@ -384,15 +490,24 @@ impl LogReplayProcessor for ScanLogReplayProcessor {
 ///
 /// Note: The iterator of (engine_data, bool) tuples 'action_iter' parameter must be sorted by the
 /// order of the actions in the log from most recent to least recent.
+///
+/// If a `file_filter` is given, only the adds of the files with those paths are selected.
 pub(crate) fn scan_action_iter(
     engine: &dyn Engine,
     action_iter: impl Iterator<Item = DeltaResult<(Box<dyn EngineData>, bool)>>,
     logical_schema: SchemaRef,
     transform: Option<Arc<Transform>>,
     physical_predicate: Option<(ExpressionRef, SchemaRef)>,
+    file_filter: Option<Arc<HashSet<String>>>,
 ) -> impl Iterator<Item = DeltaResult<ScanMetadata>> {
-    ScanLogReplayProcessor::new(engine, physical_predicate, logical_schema, transform)
-        .process_actions_iter(action_iter)
+    ScanLogReplayProcessor::new(
+        engine,
+        physical_predicate,
+        logical_schema,
+        transform,
+        file_filter,
+    )
+    .process_actions_iter(action_iter)
 }
 
 #[cfg(test)]
@@ -407,6 +522,7 @@ mod tests {
         run_with_validate_callback,
     };
     use crate::scan::{get_state_info, Scan};
+    use crate::table_features::ColumnMappingMode;
     use crate::Expression as Expr;
     use crate::{
         engine::sync::SyncEngine,
@@ -415,6 +531,11 @@ mod tests {
     };
 
     use super::scan_action_iter;
+    use crate::expressions::column_expr;
+    use crate::scan::{PhysicalPredicate, PredicateSplit};
+    use crate::schema::PrimitiveType;
+    use itertools::Itertools;
+    use std::collections::HashSet;
 
     // dv-info is more complex to validate, we validate that works in the test for visit_scan_files
     // in state.rs
@@ -472,6 +593,7 @@ mod tests {
             logical_schema,
             None,
             None,
+            None,
         );
         for res in iter {
             let scan_metadata = res.unwrap();
@@ -489,8 +611,13 @@ mod tests {
             StructField::new("date", DataType::DATE, true),
         ]));
         let partition_cols = ["date".to_string()];
-        let state_info = get_state_info(schema.as_ref(), &partition_cols).unwrap();
-        let static_transform = Some(Arc::new(Scan::get_static_transform(&state_info.all_fields)));
+        let state_info =
+            get_state_info(schema.as_ref(), &partition_cols, ColumnMappingMode::None).unwrap();
+        let static_transform = Some(Arc::new(Scan::get_static_transform(
+            &state_info.all_fields,
+            false,
+            None,
+        )));
         let batch = vec![add_batch_with_partition_col()];
         let iter = scan_action_iter(
             &SyncEngine::new(),
@@ -498,6 +625,7 @@ mod tests {
             schema,
             static_transform,
             None,
+            None,
         );
 
         fn validate_transform(transform: Option<&ExpressionRef>, expected_date_offset: i32) {
@@ -534,4 +662,86 @@ mod tests {
             validate_transform(transforms[3].as_ref(), 17510);
         }
     }
+
+    #[test]
+    fn test_predicate_split() {
+        let partition_columns = HashSet::from([column_name!("year")]);
+        let year = Expr::eq(column_expr!("year"), Expr::literal(2021));
+        let value = Expr::gt(column_expr!("value"), Expr::literal(100));
+
+        let split =
+            PredicateSplit::new(&Expr::and(year.clone(), value.clone()), &partition_columns);
+        assert_eq!(split.partition.as_deref(), Some(&year));
+        assert_eq!(split.data.as_deref(), Some(&value));
+
+        // conjuncts mixing partition and data columns can only be checked against stats
+        let mixed = Expr::or(year.clone(), value.clone());
+        let split =
+            PredicateSplit::new(&Expr::and(year.clone(), mixed.clone()), &partition_columns);
+        assert_eq!(split.partition.as_deref(), Some(&year));
+        assert_eq!(split.data.as_deref(), Some(&mixed));
+
+        let split = PredicateSplit::new(&year, &partition_columns);
+        assert_eq!(split.partition.as_deref(), Some(&year));
+        assert_eq!(split.data, None);
+
+        let split = PredicateSplit::new(&value, &partition_columns);
+        assert_eq!(split.partition, None);
+        assert_eq!(split.data.as_deref(), Some(&value));
+    }
+
+    #[test]
+    fn test_partition_and_data_pruning() {
+        let schema: SchemaRef = Arc::new(StructType::new([
+            StructField::new("value", DataType::INTEGER, true),
+            StructField::new("date", DataType::DATE, true),
+        ]));
+        let partition_cols = ["date".to_string()];
+        let state_info =
+            get_state_info(schema.as_ref(), &partition_cols, ColumnMappingMode::None).unwrap();
+        let static_transform = Some(Arc::new(Scan::get_static_transform(
+            &state_info.all_fields,
+            false,
+            None,
+        )));
+        // both files have `value` stats in [0, 9]; they are partitioned by 2017-12-11 (row 1) and
+        // 2017-12-10 (row 3)
+        let selected_rows = |predicate: Expr| {
+            let PhysicalPredicate::Some(predicate, referenced_schema) =
+                PhysicalPredicate::try_new(&predicate, &schema).unwrap()
+            else {
+                panic!("expected a physical predicate");
+            };
+            let iter = scan_action_iter(
+                &SyncEngine::new(),
+                std::iter::once(Ok((add_batch_with_partition_col() as _, true))),
+                schema.clone(),
+                static_transform.clone(),
+                Some((predicate, referenced_schema)),
+                None,
+            );
+            iter.flat_map(|res| res.unwrap().scan_files.selection_vector)
+                .positions(|selected| selected)
+                .collect_vec()
+        };
+        let date_eq = |date: &str| {
+            let date = PrimitiveType::Date.parse_scalar(date).unwrap();
+            Expr::eq(column_expr!("date"), Expr::literal(date))
+        };
+        let value_gt = |v: i32| Expr::gt(column_expr!("value"), Expr::literal(v));
+        assert_eq!(selected_rows(date_eq("2017-12-10")), vec![3]);
+        assert_eq!(selected_rows(value_gt(5)), vec![1, 3]);
+        assert_eq!(
+            selected_rows(Expr::and(date_eq("2017-12-10"), value_gt(5))),
+            vec![3]
+        );
+        assert_eq!(
+            selected_rows(Expr::and(date_eq("2017-12-10"), value_gt(100))),
+            Vec::<usize>::new()
+        );
+        assert_eq!(
+            selected_rows(Expr::and(date_eq("2017-12-12"), value_gt(5))),
+            Vec::<usize>::new()
+        );
+    }
 }
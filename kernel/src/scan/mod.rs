@@ -11,33 +11,62 @@ use url::Url;
 use crate::actions::deletion_vector::{
     deletion_treemap_to_bools, split_vector, DeletionVectorDescriptor,
 };
-use crate::actions::{get_log_schema, ADD_NAME, REMOVE_NAME, SIDECAR_NAME};
+use crate::actions::{get_log_schema, Add, ADD_NAME, REMOVE_NAME, SIDECAR_NAME};
 use crate::engine_data::FilteredEngineData;
 use crate::expressions::transforms::ExpressionTransform;
-use crate::expressions::{ColumnName, Expression, ExpressionRef, Scalar};
+use crate::expressions::{
+    ColumnName, Expression, ExpressionRef, JunctionExpression, JunctionOperator, Scalar,
+};
 use crate::kernel_predicates::{DefaultKernelPredicateEvaluator, EmptyColumnResolver};
 use crate::log_replay::HasSelectionVector;
 use crate::scan::state::{DvInfo, Stats};
 use crate::schema::{
-    ArrayType, DataType, MapType, PrimitiveType, Schema, SchemaRef, SchemaTransform, StructField,
-    StructType,
+    ArrayType, ColumnMetadataKey, DataType, MapType, MetadataValue, PrimitiveType, Schema,
+    SchemaRef, SchemaTransform, StructField, StructType,
 };
 use crate::snapshot::Snapshot;
-use crate::table_features::ColumnMappingMode;
-use crate::{DeltaResult, Engine, EngineData, Error, FileMeta};
+use crate::table_features::{ColumnMappingMode, WriterFeature};
+use crate::{
+    DeltaResult, Engine, EngineData, Error, FileDataReadResultIterator, FileMeta,
+    ROW_INDEX_COLUMN_NAME, STRING_VIEW_METADATA_KEY,
+};
 
 use self::log_replay::scan_action_iter;
+use self::rechunk::rechunk;
 use self::state::GlobalScanState;
 
 pub(crate) mod data_skipping;
 pub mod log_replay;
+mod rechunk;
 pub mod state;
 
+/// Name of the column holding each row's row id in a scan built with
+/// [`ScanBuilder::with_row_tracking`].
+pub const ROW_ID_COLUMN_NAME: &str = "_row_id";
+
+/// Name of the column holding each row's row commit version in a scan built with
+/// [`ScanBuilder::with_row_tracking`].
+pub const ROW_COMMIT_VERSION_COLUMN_NAME: &str = "_row_commit_version";
+
+/// Name of the struct column holding the metadata of each row's data file in a scan built with
+/// [`ScanBuilder::with_file_path_column`].
+pub const METADATA_COLUMN_NAME: &str = "_metadata";
+
+/// Name of the field of the [`METADATA_COLUMN_NAME`] column holding the URL of each row's data
+/// file.
+pub const FILE_PATH_FIELD_NAME: &str = "file_path";
+
 /// Builder to scan a snapshot of a table.
 pub struct ScanBuilder {
     snapshot: Arc<Snapshot>,
     schema: Option<SchemaRef>,
     predicate: Option<ExpressionRef>,
+    row_tracking: bool,
+    file_path_column: bool,
+    limit: Option<usize>,
+    file_filter: Option<HashSet<String>>,
+    prefer_string_view: bool,
+    batch_size: Option<usize>,
 }
 
 impl std::fmt::Debug for ScanBuilder {
@@ -45,6 +74,12 @@ impl std::fmt::Debug for ScanBuilder {
         f.debug_struct("ScanBuilder")
             .field("schema", &self.schema)
             .field("predicate", &self.predicate)
+            .field("row_tracking", &self.row_tracking)
+            .field("file_path_column", &self.file_path_column)
+            .field("limit", &self.limit)
+            .field("file_filter", &self.file_filter)
+            .field("prefer_string_view", &self.prefer_string_view)
+            .field("batch_size", &self.batch_size)
             .finish()
     }
 }
@@ -56,6 +91,12 @@ impl ScanBuilder {
             snapshot: snapshot.into(),
             schema: None,
             predicate: None,
+            row_tracking: false,
+            file_path_column: false,
+            limit: None,
+            file_filter: None,
+            prefer_string_view: false,
+            batch_size: None,
         }
     }
 
@@ -91,6 +132,86 @@ impl ScanBuilder {
         self
     }
 
+    /// Include the row tracking metadata of each row in the scan. Two non-nullable `long` columns
+    /// are appended to the scan schema: [`ROW_ID_COLUMN_NAME`], holding the row's id (the file's
+    /// `baseRowId` plus the index of the row within the file), and
+    /// [`ROW_COMMIT_VERSION_COLUMN_NAME`], holding the file's `defaultRowCommitVersion`.
+    ///
+    /// Building the scan fails if the table does not support row tracking.
+    ///
+    /// NOTE: Row ids and commit versions materialized into the data files (by operations that
+    /// rewrite rows while preserving their ids) are not yet read, so rows in such files report
+    /// their default values.
+    pub fn with_row_tracking(mut self) -> Self {
+        self.row_tracking = true;
+        self
+    }
+
+    /// Include the URL of each row's data file in the scan, e.g. for engines implementing
+    /// `input_file_name()`. A non-nullable struct column [`METADATA_COLUMN_NAME`] is appended to
+    /// the scan schema (after the row tracking columns, if any), with a single `string` field
+    /// [`FILE_PATH_FIELD_NAME`] holding the file's path resolved against the table root, i.e.
+    /// `_metadata.file_path`.
+    ///
+    /// Building the scan fails if the scan schema already has a column named
+    /// [`METADATA_COLUMN_NAME`].
+    pub fn with_file_path_column(mut self) -> Self {
+        self.file_path_column = true;
+        self
+    }
+
+    /// Stop the scan once `limit` rows have been produced. Only live rows count toward the limit:
+    /// rows removed by a deletion vector are not counted. The last [`ScanResult`] returned by
+    /// [`Scan::execute`] has its mask narrowed so that exactly `limit` rows are selected, and no
+    /// further files are read after that.
+    ///
+    /// NOTE: The limit is only enforced by [`Scan::execute`]. Engines reading the files from
+    /// [`Scan::scan_metadata`] themselves must apply [`Scan::limit`] on their own.
+    pub fn with_limit(mut self, limit: usize) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    /// Restrict the scan to the data files with the given `paths`, e.g. because the engine already
+    /// pruned the files of the table using its own metadata. The paths are compared against the
+    /// `path` of the table's add actions, as reported by [`Scan::scan_metadata`]. Paths that are
+    /// not part of the snapshot are ignored.
+    ///
+    /// All other scan semantics are kept: the files are still subject to the scan's predicate, and
+    /// their deletion vectors and partition values still apply.
+    pub fn with_file_filter(mut self, paths: HashSet<String>) -> Self {
+        self.file_filter = Some(paths);
+        self
+    }
+
+    /// Whether [`Scan::execute`] should return string and binary columns as arrow's `Utf8View` and
+    /// `BinaryView` arrays instead of `Utf8` and `Binary` ones, for engines that process views
+    /// more efficiently. Defaults to `false`.
+    ///
+    /// The preference is passed on to the engine by marking the fields of the physical schema
+    /// data files are read with, and of the logical schema they are transformed to, with
+    /// [`STRING_VIEW_METADATA_KEY`]. Engines without view types return data as is.
+    pub fn with_prefer_string_view(mut self, prefer_string_view: bool) -> Self {
+        self.prefer_string_view = prefer_string_view;
+        self
+    }
+
+    /// Re-chunk the data returned by [`Scan::execute`] into batches of exactly `batch_size` rows,
+    /// regardless of the sizes of the data files and their row groups. Only the last batch may be
+    /// smaller. Deleted rows are dropped before re-chunking, so the returned [`ScanResult`]s have
+    /// no mask. [`Scan::execute_with_file_meta`] never merges rows of different files into one
+    /// batch, so there the last batch of each file may be smaller. Building the scan fails if
+    /// `batch_size` is zero.
+    ///
+    /// NOTE: This only applies to data returned as [`ArrowEngineData`]; other engine data is
+    /// returned as is.
+    ///
+    /// [`ArrowEngineData`]: crate::engine::arrow_data::ArrowEngineData
+    pub fn with_batch_size(mut self, batch_size: usize) -> Self {
+        self.batch_size = Some(batch_size);
+        self
+    }
+
     /// Build the [`Scan`].
     ///
     /// This does not scan the table at this point, but does do some work to ensure that the
@@ -99,17 +220,83 @@ impl ScanBuilder {
     /// perform actual data reads.
     pub fn build(self) -> DeltaResult<Scan> {
         // if no schema is provided, use snapshot's entire schema (e.g. SELECT *)
+        if self.batch_size == Some(0) {
+            return Err(Error::generic(
+                "The batch size of a scan must be greater than zero",
+            ));
+        }
         let logical_schema = self.schema.unwrap_or_else(|| self.snapshot.schema());
-        let state_info = get_state_info(
+        let file_format = FileFormat::try_from_provider(&self.snapshot.metadata().format.provider)?;
+        let mut state_info = get_state_info(
             logical_schema.as_ref(),
             &self.snapshot.metadata().partition_columns,
+            self.snapshot.column_mapping_mode(),
         )?;
 
         let physical_predicate = match self.predicate {
-            Some(predicate) => PhysicalPredicate::try_new(&predicate, &logical_schema)?,
+            Some(predicate) => PhysicalPredicate::try_new(&predicate, &logical_schema)?
+                .with_stats_columns(&self.snapshot.table_configuration().stats_columns_schema()),
             None => PhysicalPredicate::None,
         };
 
+        let logical_schema = if self.row_tracking {
+            if !self
+                .snapshot
+                .protocol()
+                .has_writer_feature(&WriterFeature::RowTracking)
+            {
+                return Err(Error::unsupported(
+                    "Cannot read row tracking columns: the table does not support row tracking",
+                ));
+            }
+            let tracking_fields = [ROW_ID_COLUMN_NAME, ROW_COMMIT_VERSION_COLUMN_NAME];
+            if file_format != FileFormat::Parquet {
+                return Err(Error::unsupported(format!(
+                    "Cannot read row tracking columns from {file_format:?} data files"
+                )));
+            }
+            if let Some(name) = tracking_fields
+                .iter()
+                .find(|name| logical_schema.contains(name))
+            {
+                return Err(Error::generic(format!(
+                    "Cannot read row tracking columns: the scan schema already has a column named {name}"
+                )));
+            }
+            // the row ids are computed from each row's index in its file, which the parquet
+            // reader fills in for this column
+            state_info
+                .read_fields
+                .push(StructField::not_null(ROW_INDEX_COLUMN_NAME, DataType::LONG));
+            let fields = logical_schema.fields().cloned().chain(
+                tracking_fields
+                    .iter()
+                    .map(|name| StructField::not_null(*name, DataType::LONG)),
+            );
+            Arc::new(StructType::new(fields))
+        } else {
+            logical_schema
+        };
+
+        let logical_schema = if self.file_path_column {
+            if logical_schema.contains(METADATA_COLUMN_NAME) {
+                return Err(Error::generic(format!(
+                    "Cannot read the file path column: the scan schema already has a column named {METADATA_COLUMN_NAME}"
+                )));
+            }
+            let metadata_field = StructField::not_null(
+                METADATA_COLUMN_NAME,
+                StructType::new([StructField::not_null(
+                    FILE_PATH_FIELD_NAME,
+                    DataType::STRING,
+                )]),
+            );
+            let fields = logical_schema.fields().cloned().chain([metadata_field]);
+            Arc::new(StructType::new(fields))
+        } else {
+            logical_schema
+        };
+
         Ok(Scan {
             snapshot: self.snapshot,
             logical_schema,
@@ -117,10 +304,77 @@ impl ScanBuilder {
             physical_predicate,
             all_fields: Arc::new(state_info.all_fields),
             have_partition_cols: state_info.have_partition_cols,
+            row_tracking: self.row_tracking,
+            file_path_column: self.file_path_column,
+            file_format,
+            limit: self.limit,
+            file_filter: self.file_filter.map(Arc::new),
+            prefer_string_view: self.prefer_string_view,
+            batch_size: self.batch_size,
         })
     }
 }
 
+// Marks every field of `schema` to be read and returned as string and binary views
+fn with_string_views(schema: &StructType) -> SchemaRef {
+    let fields = schema.fields().map(|field| {
+        let mut field = field.clone();
+        field
+            .metadata
+            .insert(STRING_VIEW_METADATA_KEY.to_string(), true.into());
+        field
+    });
+    Arc::new(StructType::new(fields))
+}
+
+/// The format of a table's data files, as named by the `provider` of the table metadata's
+/// `format`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FileFormat {
+    /// Parquet files, read with the engine's [`ParquetHandler`]. Almost every table uses this.
+    ///
+    /// [`ParquetHandler`]: crate::ParquetHandler
+    Parquet,
+    /// Newline-delimited JSON files holding one record per line, read with the engine's
+    /// [`JsonHandler`].
+    ///
+    /// [`JsonHandler`]: crate::JsonHandler
+    Json,
+}
+
+impl FileFormat {
+    fn try_from_provider(provider: &str) -> DeltaResult<Self> {
+        match provider.to_ascii_lowercase().as_str() {
+            "parquet" => Ok(Self::Parquet),
+            "json" => Ok(Self::Json),
+            _ => Err(Error::unsupported(format!(
+                "Unsupported data file format: {provider}"
+            ))),
+        }
+    }
+
+    /// Read data `files` of this format with the matching handler of the `engine`. Only the
+    /// columns of `physical_schema` are read.
+    pub fn read_files(
+        &self,
+        engine: &dyn Engine,
+        files: &[FileMeta],
+        physical_schema: SchemaRef,
+        predicate: Option<ExpressionRef>,
+    ) -> DeltaResult<FileDataReadResultIterator> {
+        match self {
+            Self::Parquet => {
+                engine
+                    .parquet_handler()
+                    .read_parquet_files(files, physical_schema, predicate)
+            }
+            Self::Json => engine
+                .json_handler()
+                .read_json_files(files, physical_schema, predicate),
+        }
+    }
+}
+
 #[derive(Clone, Debug, PartialEq)]
 pub(crate) enum PhysicalPredicate {
     Some(ExpressionRef, SchemaRef),
@@ -132,7 +386,8 @@ impl PhysicalPredicate {
     /// If we have a predicate, verify the columns it references and apply column mapping. First, get
     /// the set of references; use that to filter the schema to only the columns of interest (and
     /// verify that all referenced columns exist); then use the resulting logical/physical mappings
-    /// to rewrite the expression with physical column names.
+    /// to rewrite the expression with physical column names. The predicate is simplified first (see
+    /// [`Expression::simplify`]).
     ///
     /// NOTE: It is possible the predicate resolves to FALSE even ignoring column references,
     /// e.g. `col > 10 AND FALSE`. Such predicates can statically skip the whole query.
@@ -140,6 +395,7 @@ impl PhysicalPredicate {
         predicate: &Expression,
         logical_schema: &Schema,
     ) -> DeltaResult<PhysicalPredicate> {
+        let predicate = &predicate.clone().simplify();
         if can_statically_skip_all_files(predicate) {
             return Ok(PhysicalPredicate::StaticSkipAll);
         }
@@ -179,6 +435,82 @@ impl PhysicalPredicate {
             Ok(PhysicalPredicate::None)
         }
     }
+
+    /// Restrict the columns data skipping may use to `stats_columns`, the (physical) columns the
+    /// table collects statistics for. Stats that writers recorded for other columns, e.g. before
+    /// the table's stats columns changed, are ignored.
+    pub(crate) fn with_stats_columns(self, stats_columns: &StructType) -> Self {
+        // Keep the fields of `schema` that are (or, for structs, contain) stats columns
+        fn retain_stats_columns(schema: &StructType, stats_columns: &StructType) -> StructType {
+            StructType::new(schema.fields().filter_map(|field| {
+                let stats_field = stats_columns.field(field.name())?;
+                match (field.data_type(), stats_field.data_type()) {
+                    (DataType::Struct(inner), DataType::Struct(stats_inner)) => {
+                        let inner = retain_stats_columns(inner, stats_inner);
+                        (inner.fields_len() > 0).then(|| StructField {
+                            data_type: inner.into(),
+                            ..field.clone()
+                        })
+                    }
+                    (DataType::Struct(_), _) | (_, DataType::Struct(_)) => None,
+                    _ => Some(field.clone()),
+                }
+            }))
+        }
+        match self {
+            PhysicalPredicate::Some(predicate, schema) => {
+                let schema = retain_stats_columns(&schema, stats_columns);
+                PhysicalPredicate::Some(predicate, Arc::new(schema))
+            }
+            other => other,
+        }
+    }
+}
+
+/// A (physical) scan predicate split into its partition-only conjuncts, which are evaluated against
+/// an Add's `partitionValues`, and the remaining data conjuncts, which are evaluated against its
+/// stats. Conjuncts that reference both partition and data columns end up in `data`, since
+/// partition values alone can never prune them.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct PredicateSplit {
+    pub(crate) partition: Option<ExpressionRef>,
+    pub(crate) data: Option<ExpressionRef>,
+}
+
+impl PredicateSplit {
+    /// Split the top-level `AND` conjuncts of `predicate` according to whether every column they
+    /// reference is one of the (physical) `partition_columns`.
+    pub(crate) fn new(predicate: &Expression, partition_columns: &HashSet<ColumnName>) -> Self {
+        fn collect_conjuncts<'a>(predicate: &'a Expression, conjuncts: &mut Vec<&'a Expression>) {
+            match predicate {
+                Expression::Junction(JunctionExpression {
+                    op: JunctionOperator::And,
+                    exprs,
+                }) => exprs
+                    .iter()
+                    .for_each(|expr| collect_conjuncts(expr, conjuncts)),
+                _ => conjuncts.push(predicate),
+            }
+        }
+        let mut conjuncts = vec![];
+        collect_conjuncts(predicate, &mut conjuncts);
+
+        let (partition, data): (Vec<_>, Vec<_>) = conjuncts.into_iter().partition(|conjunct| {
+            let references = conjunct.references();
+            !references.is_empty() && references.iter().all(|c| partition_columns.contains(*c))
+        });
+        let combine = |conjuncts: Vec<&Expression>| match conjuncts[..] {
+            [] => None,
+            [conjunct] => Some(Arc::new(conjunct.clone())),
+            _ => Some(Arc::new(Expression::and_from(
+                conjuncts.into_iter().cloned(),
+            ))),
+        };
+        Self {
+            partition: combine(partition),
+            data: combine(data),
+        }
+    }
 }
 
 // Evaluates a static data skipping predicate, ignoring any column references, and returns true if
@@ -289,6 +621,41 @@ impl ScanResult {
         mask.resize(self.raw_data.as_ref().ok()?.len(), true);
         Some(mask)
     }
+
+    /// Narrow the mask so that at most `remaining` rows are selected, and subtract the number of
+    /// selected rows from `remaining`. Results whose data failed to read are left untouched.
+    fn apply_limit(&mut self, remaining: &mut usize) {
+        let Ok(data) = self.raw_data.as_ref() else {
+            return;
+        };
+        let mut mask = self.full_mask().unwrap_or_else(|| vec![true; data.len()]);
+        let selected = mask.iter().filter(|&&keep| keep).count();
+        if selected <= *remaining {
+            *remaining -= selected;
+            return;
+        }
+        // keep the first `remaining` selected rows and deselect everything after them
+        mask.iter_mut()
+            .filter(|keep| **keep)
+            .skip(*remaining)
+            .for_each(|keep| *keep = false);
+        self.raw_mask = Some(mask);
+        *remaining = 0;
+    }
+}
+
+/// Describes the data file a [`ScanResult`] returned by [`Scan::execute_with_file_meta`] was read
+/// from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScanFileMeta {
+    /// The fully-qualified location of the data file
+    pub location: Url,
+    /// The raw partition values of the file, keyed by physical partition column name. Null
+    /// partition values are omitted.
+    pub partition_values: HashMap<String, String>,
+    /// Whether the file has a deletion vector, i.e. whether the mask of the [`ScanResult`] may
+    /// drop rows that were deleted from the file
+    pub deletion_vector_applied: bool,
 }
 
 /// Scan uses this to set up what kinds of top-level columns it is scanning. For `Selected` we just
@@ -322,6 +689,12 @@ pub fn get_transform_for_row(
 pub(crate) enum TransformExpr {
     Static(Expression),
     Partition(usize),
+    // The row id column, computed from the file's base row id
+    RowId,
+    // The row commit version column, filled in from the file's default row commit version
+    RowCommitVersion,
+    // The `_metadata` column, holding the file's path resolved against this table root
+    FilePath(Url),
 }
 
 /// [`ScanMetadata`] contains (1) a batch of [`FilteredEngineData`] specifying data files to be scanned
@@ -376,6 +749,13 @@ pub struct Scan {
     physical_predicate: PhysicalPredicate,
     all_fields: Arc<Vec<ColumnType>>,
     have_partition_cols: bool,
+    row_tracking: bool,
+    file_path_column: bool,
+    file_format: FileFormat,
+    limit: Option<usize>,
+    file_filter: Option<Arc<HashSet<String>>>,
+    prefer_string_view: bool,
+    batch_size: Option<usize>,
 }
 
 impl std::fmt::Debug for Scan {
@@ -395,6 +775,39 @@ impl Scan {
         &self.logical_schema
     }
 
+    /// Get the logical schema of the data this scan produces, after projection.
+    ///
+    /// Fields appear in the order they were requested with [`ScanBuilder::with_schema`] (or in
+    /// table order if no projection was given). Partition columns are materialized as regular
+    /// columns and appear at their position in this schema, and a projection that selects only
+    /// some subfields of a struct column yields the correspondingly narrowed struct type.
+    pub fn logical_schema(&self) -> &StructType {
+        &self.logical_schema
+    }
+
+    /// Get the [`FileFormat`] of the data files this scan reads.
+    pub fn file_format(&self) -> FileFormat {
+        self.file_format
+    }
+
+    /// Get the maximum number of rows this scan produces, if a limit was set with
+    /// [`ScanBuilder::with_limit`].
+    pub fn limit(&self) -> Option<usize> {
+        self.limit
+    }
+
+    /// Whether this scan returns string and binary columns as views, as set with
+    /// [`ScanBuilder::with_prefer_string_view`].
+    pub fn prefer_string_view(&self) -> bool {
+        self.prefer_string_view
+    }
+
+    /// Get the number of rows of the batches this scan returns, if set with
+    /// [`ScanBuilder::with_batch_size`].
+    pub fn batch_size(&self) -> Option<usize> {
+        self.batch_size
+    }
+
     /// Get the predicate [`Expression`] of the scan.
     pub fn physical_predicate(&self) -> Option<ExpressionRef> {
         if let PhysicalPredicate::Some(ref predicate, _) = self.physical_predicate {
@@ -404,11 +817,39 @@ impl Scan {
         }
     }
 
+    /// Get the (physical) schema to read the data file of `add` with: the columns of this scan's
+    /// physical schema that the file actually contains. Because of schema evolution, a file may
+    /// have been written before some columns were added to the table, and reading it with the
+    /// scan's full physical schema fills those absent columns with nulls.
+    ///
+    /// The columns of the file are taken from its own schema, read from its footer with
+    /// [`ParquetHandler::read_parquet_schema`], and resolved the way the parquet reader resolves
+    /// them: by field id for columns that carry a column mapping id (column mapping in id mode),
+    /// and by physical name otherwise. With column mapping, both are unique to each column, so a
+    /// dropped and re-added column is not mistaken for its predecessor.
+    ///
+    /// [`ParquetHandler::read_parquet_schema`]: crate::ParquetHandler::read_parquet_schema
+    pub fn read_schema_for_file(&self, engine: &dyn Engine, add: &Add) -> DeltaResult<StructType> {
+        let file = FileMeta {
+            location: state::resolve_file_path(self.snapshot.table_root(), &add.path)?,
+            last_modified: add.modification_time,
+            size: add.size.try_into().map_err(|_| {
+                Error::generic(format!("Invalid size {} of file {}", add.size, add.path))
+            })?,
+        };
+        let file_schema = engine.parquet_handler().read_parquet_schema(&file)?;
+        Ok(retain_file_columns(&self.physical_schema, &file_schema))
+    }
+
     /// Convert the parts of the transform that can be computed statically into `Expression`s. For
     /// parts that cannot be computed statically, include enough metadata so lower levels of
     /// processing can create and fill in an expression.
-    fn get_static_transform(all_fields: &[ColumnType]) -> Transform {
-        all_fields
+    fn get_static_transform(
+        all_fields: &[ColumnType],
+        row_tracking: bool,
+        file_path_root: Option<&Url>,
+    ) -> Transform {
+        let mut transform: Transform = all_fields
             .iter()
             .map(|field| match field {
                 ColumnType::Selected(col_name) => {
@@ -416,7 +857,14 @@ impl Scan {
                 }
                 ColumnType::Partition(idx) => TransformExpr::Partition(*idx),
             })
-            .collect()
+            .collect();
+        if row_tracking {
+            transform.extend([TransformExpr::RowId, TransformExpr::RowCommitVersion]);
+        }
+        if let Some(table_root) = file_path_root {
+            transform.push(TransformExpr::FilePath(table_root.clone()));
+        }
+        transform
     }
 
     /// Get an iterator of [`ScanMetadata`]s that should be used to facilitate a scan. This handles
@@ -443,11 +891,20 @@ impl Scan {
         engine: &dyn Engine,
     ) -> DeltaResult<impl Iterator<Item = DeltaResult<ScanMetadata>>> {
         // Compute the static part of the transformation. This is `None` if no transformation is
-        // needed (currently just means no partition cols AND no column mapping but will be extended
-        // for other transforms as we support them)
+        // needed (currently just means no partition cols, no column mapping, no row tracking AND no
+        // file path column but will be extended for other transforms as we support them)
         let static_transform = (self.have_partition_cols
+            || self.row_tracking
+            || self.file_path_column
             || self.snapshot.column_mapping_mode() != ColumnMappingMode::None)
-            .then(|| Arc::new(Scan::get_static_transform(&self.all_fields)));
+            .then(|| {
+                let file_path_root = self.file_path_column.then(|| self.snapshot.table_root());
+                Arc::new(Scan::get_static_transform(
+                    &self.all_fields,
+                    self.row_tracking,
+                    file_path_root,
+                ))
+            });
         let physical_predicate = match self.physical_predicate.clone() {
             PhysicalPredicate::StaticSkipAll => return Ok(None.into_iter().flatten()),
             PhysicalPredicate::Some(predicate, schema) => Some((predicate, schema)),
@@ -459,6 +916,7 @@ impl Scan {
             self.logical_schema.clone(),
             static_transform,
             physical_predicate,
+            self.file_filter.clone(),
         );
         Ok(Some(it).into_iter().flatten())
     }
@@ -504,11 +962,33 @@ impl Scan {
         &self,
         engine: Arc<dyn Engine>,
     ) -> DeltaResult<impl Iterator<Item = DeltaResult<ScanResult>>> {
+        let results = self
+            .read_files(engine)?
+            .map_ok(|(scan_result, _)| (scan_result, ()));
+        Ok(rechunk(results, self.batch_size).map_ok(|(scan_result, _)| scan_result))
+    }
+
+    /// Like [`Scan::execute`], but pairs each [`ScanResult`] with the [`ScanFileMeta`] of the data
+    /// file it was read from, e.g. to audit or cache the results of a scan per file.
+    pub fn execute_with_file_meta(
+        &self,
+        engine: Arc<dyn Engine>,
+    ) -> DeltaResult<impl Iterator<Item = DeltaResult<(ScanResult, ScanFileMeta)>>> {
+        Ok(rechunk(self.read_files(engine)?, self.batch_size))
+    }
+
+    /// Read the data files of the scan, pairing each [`ScanResult`] with the [`ScanFileMeta`] of
+    /// the file it was read from, and stop once the limit of the scan is reached.
+    fn read_files(
+        &self,
+        engine: Arc<dyn Engine>,
+    ) -> DeltaResult<impl Iterator<Item = DeltaResult<(ScanResult, ScanFileMeta)>>> {
         struct ScanFile {
             path: String,
             size: i64,
             dv_info: DvInfo,
             transform: Option<ExpressionRef>,
+            partition_values: HashMap<String, String>,
         }
         fn scan_metadata_callback(
             batches: &mut Vec<ScanFile>,
@@ -517,13 +997,14 @@ impl Scan {
             _: Option<Stats>,
             dv_info: DvInfo,
             transform: Option<ExpressionRef>,
-            _: HashMap<String, String>,
+            partition_values: HashMap<String, String>,
         ) {
             batches.push(ScanFile {
                 path: path.to_string(),
                 size,
                 dv_info,
                 transform,
+                partition_values,
             });
         }
 
@@ -532,8 +1013,14 @@ impl Scan {
             self.logical_schema, self.physical_schema
         );
 
-        let global_state = Arc::new(self.global_scan_state());
+        let mut global_state = self.global_scan_state();
+        if self.prefer_string_view {
+            global_state.physical_schema = with_string_views(&global_state.physical_schema);
+            global_state.logical_schema = with_string_views(&global_state.logical_schema);
+        }
+        let global_state = Arc::new(global_state);
         let table_root = self.snapshot.table_root().clone();
+        let file_format = self.file_format;
 
         let scan_metadata_iter = self.scan_metadata(engine.as_ref())?;
         let scan_files_iter = scan_metadata_iter
@@ -548,10 +1035,15 @@ impl Scan {
         let result = scan_files_iter
             .map(move |scan_file| -> DeltaResult<_> {
                 let scan_file = scan_file?;
-                let file_path = table_root.join(&scan_file.path)?;
+                let file_path = state::resolve_file_path(&table_root, &scan_file.path)?;
                 let mut selection_vector = scan_file
                     .dv_info
                     .get_selection_vector(engine.as_ref(), &table_root)?;
+                let file_meta = ScanFileMeta {
+                    location: file_path.clone(),
+                    partition_values: scan_file.partition_values,
+                    deletion_vector_applied: scan_file.dv_info.has_vector(),
+                };
                 let meta = FileMeta {
                     last_modified: 0,
                     size: scan_file.size.try_into().map_err(|_| {
@@ -566,7 +1058,8 @@ impl Scan {
                 // https://github.com/delta-io/delta-kernel-rs/issues/434 for more details.
                 //
                 // TODO(#860): we disable predicate pushdown until we support row indexes.
-                let read_result_iter = engine.parquet_handler().read_parquet_files(
+                let read_result_iter = file_format.read_files(
+                    engine.as_ref(),
                     &[meta],
                     global_state.physical_schema.clone(),
                     None,
@@ -597,14 +1090,31 @@ impl Scan {
                         raw_mask: sv,
                     };
                     selection_vector = rest;
-                    Ok(result)
+                    Ok((result, file_meta.clone()))
                 }))
             })
-            // Iterator<DeltaResult<Iterator<DeltaResult<ScanResult>>>> to Iterator<DeltaResult<DeltaResult<ScanResult>>>
+            // Iterator<DeltaResult<Iterator<DeltaResult<(ScanResult, ScanFileMeta)>>>> to
+            // Iterator<DeltaResult<DeltaResult<(ScanResult, ScanFileMeta)>>>
             .flatten_ok()
-            // Iterator<DeltaResult<DeltaResult<ScanResult>>> to Iterator<DeltaResult<ScanResult>>
+            // to Iterator<DeltaResult<(ScanResult, ScanFileMeta)>>
             .map(|x| x?);
-        Ok(result)
+
+        // Stop pulling from `result` as soon as the limit is reached, so that no further files are
+        // read.
+        let mut result = result;
+        let mut remaining = self.limit;
+        Ok(std::iter::from_fn(move || {
+            if remaining == Some(0) {
+                return None;
+            }
+            let mut scan_result = result.next()?;
+            if let (Ok((scan_result, _)), Some(remaining)) =
+                (scan_result.as_mut(), remaining.as_mut())
+            {
+                scan_result.apply_limit(remaining);
+            }
+            Some(scan_result)
+        }))
     }
 }
 
@@ -633,10 +1143,53 @@ pub fn scan_row_schema() -> Schema {
     log_replay::SCAN_ROW_SCHEMA.as_ref().clone()
 }
 
-pub(crate) fn parse_partition_value(
-    raw: Option<&String>,
-    data_type: &DataType,
-) -> DeltaResult<Scalar> {
+// Keep the fields of the physical `schema` that the file with schema `file_schema` contains, with
+// just the nested fields the file contains for struct fields. Like the parquet reader, a field is
+// resolved by field id if both it and the file field carry one, and by name otherwise.
+fn retain_file_columns(schema: &StructType, file_schema: &StructType) -> StructType {
+    let field_id =
+        |field: &StructField| match field.get_config_value(&ColumnMetadataKey::ColumnMappingId) {
+            Some(MetadataValue::Number(id)) => Some(*id),
+            _ => None,
+        };
+    StructType::new(schema.fields().filter_map(|field| {
+        // the parquet reader fills in the row index column, it is never in the file
+        if field.name() == ROW_INDEX_COLUMN_NAME {
+            return Some(field.clone());
+        }
+        let file_field = file_schema.fields().find(|file_field| {
+            match (field_id(field), field_id(file_field)) {
+                (Some(id), Some(file_id)) => id == file_id,
+                _ => file_field.name() == field.name(),
+            }
+        })?;
+        match (field.data_type(), file_field.data_type()) {
+            (DataType::Struct(inner), DataType::Struct(file_inner)) => {
+                let inner = retain_file_columns(inner, file_inner);
+                (inner.fields_len() > 0).then(|| StructField {
+                    data_type: inner.into(),
+                    ..field.clone()
+                })
+            }
+            _ => Some(field.clone()),
+        }
+    }))
+}
+
+/// Partition value that hive-style writers use to represent a null partition
+const HIVE_DEFAULT_PARTITION: &str = "__HIVE_DEFAULT_PARTITION__";
+
+/// Parse a raw partition value, as found in an `Add` action's `partitionValues` map, into a
+/// [`Scalar`] of the given partition column type.
+///
+/// A missing value (`None`), an empty string, and the `__HIVE_DEFAULT_PARTITION__` sentinel all
+/// produce a null of `data_type`. Otherwise the value is parsed according to the
+/// [partition value serialization] rules of the Delta protocol.
+///
+/// [partition value serialization]: https://github.com/delta-io/delta/blob/master/PROTOCOL.md#partition-value-serialization
+pub fn parse_partition_value(raw: Option<&str>, data_type: &DataType) -> DeltaResult<Scalar> {
+    // Hive-style writers record a null partition value as a sentinel string instead of null
+    let raw = raw.filter(|v| *v != HIVE_DEFAULT_PARTITION);
     match (raw, data_type.as_primitive_opt()) {
         (Some(v), Some(primitive)) => primitive.parse_scalar(v),
         (Some(_), None) => Err(Error::generic(format!(
@@ -657,7 +1210,11 @@ struct StateInfo {
 }
 
 /// Get the state needed to process a scan, see [`StateInfo`] for details.
-fn get_state_info(logical_schema: &Schema, partition_columns: &[String]) -> DeltaResult<StateInfo> {
+fn get_state_info(
+    logical_schema: &Schema,
+    partition_columns: &[String],
+    column_mapping_mode: ColumnMappingMode,
+) -> DeltaResult<StateInfo> {
     let mut have_partition_cols = false;
     let mut read_fields = Vec::with_capacity(logical_schema.fields.len());
     // Loop over all selected fields and note if they are columns that will be read from the
@@ -676,7 +1233,7 @@ fn get_state_info(logical_schema: &Schema, partition_columns: &[String]) -> Delt
             } else {
                 // Add to read schema, store field so we can build a `Column` expression later
                 // if needed (i.e. if we have partition columns)
-                let physical_field = logical_field.make_physical();
+                let physical_field = logical_field.make_physical_with_mode(column_mapping_mode);
                 debug!("\n\n{logical_field:#?}\nAfter mapping: {physical_field:#?}\n\n");
                 let physical_name = physical_field.name.clone();
                 read_fields.push(physical_field);
@@ -820,6 +1377,7 @@ pub(crate) mod test_utils {
             logical_schema,
             transform,
             None,
+            None,
         );
         let mut batch_count = 0;
         for res in iter {
@@ -871,6 +1429,106 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_file_format_from_provider() {
+        for (provider, format) in [
+            ("parquet", FileFormat::Parquet),
+            ("Parquet", FileFormat::Parquet),
+            ("json", FileFormat::Json),
+        ] {
+            assert_eq!(FileFormat::try_from_provider(provider).unwrap(), format);
+        }
+        assert!(matches!(
+            FileFormat::try_from_provider("orc"),
+            Err(Error::Unsupported(_))
+        ));
+    }
+
+    #[test]
+    fn test_logical_schema() {
+        let table_schema = StructType::new([
+            StructField::nullable("id", DataType::LONG),
+            StructField::nullable("name", DataType::STRING),
+            StructField::nullable(
+                "info",
+                StructType::new([
+                    StructField::nullable("a", DataType::INTEGER),
+                    StructField::nullable("b", DataType::STRING),
+                ]),
+            ),
+            StructField::nullable("value", DataType::DOUBLE),
+            StructField::nullable("part", DataType::DATE),
+        ]);
+        let schema_string = serde_json::to_string(&table_schema).unwrap();
+        let commit = [
+            serde_json::json!({"protocol": {"minReaderVersion": 1, "minWriterVersion": 2}}),
+            serde_json::json!({"metaData": {
+                "id": "test",
+                "format": {"provider": "parquet", "options": {}},
+                "schemaString": schema_string,
+                "partitionColumns": ["part"],
+                "configuration": {},
+                "createdTime": 1677811175819u64,
+            }}),
+        ]
+        .map(|action| action.to_string())
+        .join("\n");
+        let dir = tempfile::tempdir().unwrap();
+        let log_dir = dir.path().join("_delta_log");
+        std::fs::create_dir(&log_dir).unwrap();
+        std::fs::write(log_dir.join("00000000000000000000.json"), commit).unwrap();
+
+        let url = url::Url::from_directory_path(dir.path()).unwrap();
+        let engine = SyncEngine::new();
+        let snapshot = Arc::new(Table::new(url).snapshot(&engine, None).unwrap());
+
+        // no projection: the full table schema, partition column included
+        let scan = snapshot.clone().scan_builder().build().unwrap();
+        assert_eq!(scan.logical_schema(), &table_schema);
+
+        // two of five columns, requested out of table order, one of them a partition column
+        let projection = Arc::new(StructType::new([
+            StructField::nullable("part", DataType::DATE),
+            StructField::nullable("value", DataType::DOUBLE),
+        ]));
+        let scan = snapshot
+            .clone()
+            .scan_builder()
+            .with_schema(projection.clone())
+            .build()
+            .unwrap();
+        let schema = scan.logical_schema();
+        assert_eq!(schema, projection.as_ref());
+        let fields: Vec<_> = schema
+            .fields()
+            .map(|f| (f.name().as_str(), f.data_type()))
+            .collect();
+        assert_eq!(
+            fields,
+            [("part", &DataType::DATE), ("value", &DataType::DOUBLE)]
+        );
+
+        // selecting a nested subfield narrows the struct type
+        let projection = Arc::new(StructType::new([
+            StructField::nullable("id", DataType::LONG),
+            StructField::nullable(
+                "info",
+                StructType::new([StructField::nullable("b", DataType::STRING)]),
+            ),
+        ]));
+        let scan = snapshot
+            .scan_builder()
+            .with_schema(projection.clone())
+            .build()
+            .unwrap();
+        let info = scan.logical_schema().field("info").unwrap();
+        let DataType::Struct(info) = info.data_type() else {
+            panic!("expected a struct, got {info:?}");
+        };
+        assert_eq!(info.fields().map(|f| f.name()).collect_vec(), ["b"]);
+        assert_eq!(scan.logical_schema(), projection.as_ref());
+    }
+
     #[test]
     fn test_physical_predicate() {
         let logical_schema = StructType::new(vec![
@@ -980,7 +1638,7 @@ mod tests {
             (
                 Expr::and(column_expr!("mapped.n"), Expr::literal(true)),
                 Some(PhysicalPredicate::Some(
-                    Expr::and(column_expr!("phys_mapped.phys_n"), Expr::literal(true)).into(),
+                    column_expr!("phys_mapped.phys_n").into(),
                     StructType::new(vec![StructField::nullable(
                         "phys_mapped",
                         StructType::new(vec![StructField::nullable("phys_n", DataType::LONG)
@@ -1084,6 +1742,13 @@ mod tests {
             ("1.1", PrimitiveType::Float, Scalar::Float(1.1)),
             ("10.10", PrimitiveType::Double, Scalar::Double(10.1)),
             ("true", PrimitiveType::Boolean, Scalar::Boolean(true)),
+            ("FALSE", PrimitiveType::Boolean, Scalar::Boolean(false)),
+            (
+                "123.45",
+                PrimitiveType::decimal(5, 2).unwrap(),
+                Scalar::decimal(12345, 5, 2).unwrap(),
+            ),
+            ("2021-01-01", PrimitiveType::Date, Scalar::Date(18628)),
             ("2024-01-01", PrimitiveType::Date, Scalar::Date(19723)),
             ("1970-01-01", PrimitiveType::Date, Scalar::Date(0)),
             (
@@ -1101,16 +1766,33 @@ mod tests {
                 PrimitiveType::Timestamp,
                 Scalar::Timestamp(123456),
             ),
+            (
+                "2021-01-01T00:00:01.5Z",
+                PrimitiveType::Timestamp,
+                Scalar::Timestamp(1_609_459_201_500_000),
+            ),
         ];
 
         for (raw, data_type, expected) in &cases {
-            let value = parse_partition_value(
-                Some(&raw.to_string()),
-                &DataType::Primitive(data_type.clone()),
-            )
-            .unwrap();
+            let value =
+                parse_partition_value(Some(raw), &DataType::Primitive(data_type.clone())).unwrap();
             assert_eq!(value, *expected);
         }
+
+        let decimal = DataType::decimal(5, 2).unwrap();
+        for data_type in [
+            DataType::INTEGER,
+            DataType::STRING,
+            DataType::DATE,
+            DataType::TIMESTAMP,
+            DataType::BOOLEAN,
+            decimal,
+        ] {
+            let value = parse_partition_value(Some(HIVE_DEFAULT_PARTITION), &data_type).unwrap();
+            assert!(matches!(value, Scalar::Null(ref t) if *t == data_type));
+            let value = parse_partition_value(None, &data_type).unwrap();
+            assert!(matches!(value, Scalar::Null(ref t) if *t == data_type));
+        }
     }
 
     #[test]
@@ -1239,4 +1921,120 @@ mod tests {
         );
         Ok(())
     }
+
+    #[test]
+    fn test_read_schema_for_file() -> DeltaResult<()> {
+        use crate::arrow::array::{AsArray, RecordBatch};
+        use crate::arrow::datatypes::Int64Type;
+        use crate::engine::arrow_data::ArrowEngineData;
+
+        let path = std::fs::canonicalize(PathBuf::from("./tests/data/table-with-added-column/"))?;
+        let url = url::Url::from_directory_path(path).unwrap();
+        let engine = Arc::new(SyncEngine::new());
+
+        // commit0: table with a `value` column, add a file with values 0..10
+        // commit1: add a nullable `name` column
+        // commit2: add a file with values 10..13 and names ["a", null, "c"]
+        let table = Table::new(url);
+        let snapshot = table.snapshot(engine.as_ref(), None)?;
+        let scan = snapshot.into_scan_builder().build()?;
+
+        let add = |path: &str, size| Add {
+            path: path.to_string(),
+            size,
+            ..Default::default()
+        };
+        let old_file = add(
+            "part-00000-517f5d32-9c95-48e8-82b4-0229cc194867-c000.snappy.parquet",
+            548,
+        );
+        let new_file = add(
+            "part-00000-8e4d2b6a-6f0c-4b8e-9a57-1c3f0d5e2a71-c000.snappy.parquet",
+            801,
+        );
+        let value = StructField::nullable("value", DataType::LONG);
+        let name = StructField::nullable("name", DataType::STRING);
+        assert_eq!(
+            scan.read_schema_for_file(engine.as_ref(), &old_file)?,
+            StructType::new([value.clone()])
+        );
+        assert_eq!(
+            scan.read_schema_for_file(engine.as_ref(), &new_file)?,
+            StructType::new([value, name])
+        );
+
+        // the column the older file predates reads as null
+        let mut rows = vec![];
+        for result in scan.execute(engine)? {
+            let data = ArrowEngineData::try_from_engine_data(result?.raw_data?)?;
+            let batch: RecordBatch = data.into();
+            let values = batch.column(0).as_primitive::<Int64Type>();
+            let names = batch.column(1).as_string::<i32>();
+            rows.extend(
+                values
+                    .iter()
+                    .zip(names.iter())
+                    .map(|(value, name)| (value.unwrap(), name.map(str::to_string))),
+            );
+        }
+        rows.sort();
+        let expected: Vec<_> = (0..10)
+            .map(|value| (value, None))
+            .chain([
+                (10, Some("a".to_string())),
+                (11, None),
+                (12, Some("c".to_string())),
+            ])
+            .collect();
+        assert_eq!(rows, expected);
+        Ok(())
+    }
+
+    #[test]
+    fn test_retain_file_columns_by_field_id() {
+        let with_id = |field: StructField, id: i64| {
+            field.with_metadata([(ColumnMetadataKey::ColumnMappingId.as_ref(), id)])
+        };
+        // `b` was dropped and re-added (with a new id) after the file was written, and `c.x` was
+        // added to the struct `c`
+        let schema = StructType::new([
+            with_id(StructField::nullable("col-a", DataType::LONG), 1),
+            with_id(StructField::nullable("col-b2", DataType::STRING), 4),
+            with_id(
+                StructField::nullable(
+                    "col-c",
+                    StructType::new([
+                        with_id(StructField::nullable("col-y", DataType::LONG), 3),
+                        with_id(StructField::nullable("col-x", DataType::LONG), 5),
+                    ]),
+                ),
+                2,
+            ),
+            StructField::not_null(ROW_INDEX_COLUMN_NAME, DataType::LONG),
+        ]);
+        // field ids win over names, as the file may use other names for the same columns
+        let file_schema = StructType::new([
+            with_id(StructField::nullable("a", DataType::LONG), 1),
+            with_id(
+                StructField::nullable(
+                    "c",
+                    StructType::new([with_id(StructField::nullable("y", DataType::LONG), 3)]),
+                ),
+                2,
+            ),
+            with_id(StructField::nullable("col-b2", DataType::STRING), 6),
+        ]);
+        let expected = StructType::new([
+            schema.field("col-a").unwrap().clone(),
+            with_id(
+                StructField::nullable(
+                    "col-c",
+                    StructType::new([with_id(StructField::nullable("col-y", DataType::LONG), 3)]),
+                ),
+                2,
+            ),
+            StructField::not_null(ROW_INDEX_COLUMN_NAME, DataType::LONG),
+        ]);
+        assert_eq!(retain_file_columns(&schema, &file_schema), expected);
+    }
 }
@@ -0,0 +1,163 @@
+//! Re-chunking of scan results into batches of a fixed number of rows, for scans built with
+//! [`ScanBuilder::with_batch_size`].
+//!
+//! [`ScanBuilder::with_batch_size`]: super::ScanBuilder::with_batch_size
+
+use crate::DeltaResult;
+
+use super::ScanResult;
+
+/// Re-chunks `results` into [`ScanResult`]s of exactly `batch_size` rows, except for the last one
+/// of each group. The mask of each result is applied before re-chunking, so the returned results
+/// have no mask. Consecutive results of the same group are merged, but results of different groups
+/// never are. If `batch_size` is `None`, `results` are returned as is.
+///
+/// Only [`ArrowEngineData`] can be re-chunked. Other engine data, and results whose data failed to
+/// read, are returned as is.
+///
+/// [`ArrowEngineData`]: crate::engine::arrow_data::ArrowEngineData
+#[cfg(any(feature = "default-engine-base", feature = "sync-engine"))]
+pub(crate) fn rechunk<G: Clone + PartialEq>(
+    results: impl Iterator<Item = DeltaResult<(ScanResult, G)>>,
+    batch_size: Option<usize>,
+) -> impl Iterator<Item = DeltaResult<(ScanResult, G)>> {
+    use std::collections::VecDeque;
+
+    let mut results = results.fuse();
+    let mut chunker = batch_size.map(arrow::Chunker::new);
+    let mut ready = VecDeque::new();
+    std::iter::from_fn(move || loop {
+        if let Some(item) = ready.pop_front() {
+            return Some(item);
+        }
+        let Some(chunker) = chunker.as_mut() else {
+            return results.next();
+        };
+        let pushed = match results.next() {
+            Some(Ok((result, group))) => chunker.push(result, group, &mut ready),
+            Some(Err(err)) => return Some(Err(err)),
+            None if chunker.is_empty() => return None,
+            None => chunker.flush(&mut ready),
+        };
+        if let Err(err) = pushed {
+            return Some(Err(err));
+        }
+    })
+}
+
+/// Without arrow support there is no engine data that can be re-chunked, so `results` are returned
+/// as is.
+#[cfg(not(any(feature = "default-engine-base", feature = "sync-engine")))]
+pub(crate) fn rechunk<G: Clone + PartialEq>(
+    results: impl Iterator<Item = DeltaResult<(ScanResult, G)>>,
+    _batch_size: Option<usize>,
+) -> impl Iterator<Item = DeltaResult<(ScanResult, G)>> {
+    results
+}
+
+#[cfg(any(feature = "default-engine-base", feature = "sync-engine"))]
+mod arrow {
+    use std::collections::VecDeque;
+
+    use crate::arrow::array::{BooleanArray, RecordBatch};
+    use crate::arrow::compute::{concat_batches, filter_record_batch};
+    use crate::engine::arrow_data::ArrowEngineData;
+    use crate::scan::ScanResult;
+    use crate::DeltaResult;
+
+    type Ready<G> = VecDeque<DeltaResult<(ScanResult, G)>>;
+
+    /// Accumulates the live rows of the results of one group until a full batch is available.
+    pub(super) struct Chunker<G> {
+        batch_size: usize,
+        group: Option<G>,
+        pending: Vec<RecordBatch>,
+        pending_rows: usize,
+    }
+
+    impl<G: Clone + PartialEq> Chunker<G> {
+        pub(super) fn new(batch_size: usize) -> Self {
+            Self {
+                batch_size,
+                group: None,
+                pending: vec![],
+                pending_rows: 0,
+            }
+        }
+
+        /// Whether there are no pending rows left.
+        pub(super) fn is_empty(&self) -> bool {
+            self.group.is_none()
+        }
+
+        /// Add the live rows of `result` to the pending rows, and move the full batches of pending
+        /// rows to `ready`. A result of a different group first flushes the pending rows.
+        pub(super) fn push(
+            &mut self,
+            result: ScanResult,
+            group: G,
+            ready: &mut Ready<G>,
+        ) -> DeltaResult<()> {
+            let is_arrow = result
+                .raw_data
+                .as_ref()
+                .is_ok_and(|data| data.any_ref().is::<ArrowEngineData>());
+            if !is_arrow {
+                ready.push_back(Ok((result, group)));
+                return Ok(());
+            }
+            if self.group.as_ref() != Some(&group) {
+                self.flush(ready)?;
+                self.group = Some(group);
+            }
+            let mask = result.full_mask();
+            let batch: RecordBatch =
+                ArrowEngineData::try_from_engine_data(result.raw_data?)?.into();
+            let batch = match mask {
+                Some(mask) => filter_record_batch(&batch, &BooleanArray::from(mask))?,
+                None => batch,
+            };
+            self.pending_rows += batch.num_rows();
+            self.pending.push(batch);
+            self.emit(ready, false)
+        }
+
+        /// Move all pending rows to `ready`, the last batch possibly being smaller than the batch
+        /// size.
+        pub(super) fn flush(&mut self, ready: &mut Ready<G>) -> DeltaResult<()> {
+            self.emit(ready, true)?;
+            self.group = None;
+            Ok(())
+        }
+
+        fn emit(&mut self, ready: &mut Ready<G>, flush: bool) -> DeltaResult<()> {
+            let Some(group) = self.group.as_ref() else {
+                return Ok(());
+            };
+            if self.pending_rows < self.batch_size && !(flush && self.pending_rows > 0) {
+                if flush {
+                    self.pending.clear();
+                }
+                return Ok(());
+            }
+            let batch = concat_batches(&self.pending[0].schema(), &self.pending)?;
+            self.pending.clear();
+            let mut offset = 0;
+            while offset < batch.num_rows() {
+                let len = self.batch_size.min(batch.num_rows() - offset);
+                if len < self.batch_size && !flush {
+                    self.pending.push(batch.slice(offset, len));
+                    break;
+                }
+                let result = ScanResult {
+                    raw_data: Ok(Box::new(ArrowEngineData::new(batch.slice(offset, len)))),
+                    raw_mask: None,
+                };
+                ready.push_back(Ok((result, group.clone())));
+                offset += len;
+            }
+            self.pending_rows = batch.num_rows() - offset;
+            Ok(())
+        }
+    }
+}
@@ -4,8 +4,10 @@ use std::collections::HashMap;
 use std::sync::LazyLock;
 
 use crate::actions::deletion_vector::deletion_treemap_to_bools;
+use crate::expressions::Scalar;
 use crate::scan::get_transform_for_row;
-use crate::schema::Schema;
+use crate::schema::{PrimitiveType, Schema, StructType};
+use crate::table_properties::{DataSkippingNumIndexedCols, TableProperties};
 use crate::utils::require;
 use crate::ExpressionRef;
 use crate::{
@@ -17,6 +19,7 @@ use crate::{
 use roaring::RoaringTreemap;
 use serde::{Deserialize, Serialize};
 use tracing::warn;
+use url::Url;
 
 use super::log_replay::SCAN_ROW_SCHEMA;
 use super::ScanMetadata;
@@ -54,6 +57,171 @@ pub struct Stats {
     pub num_records: u64,
 }
 
+/// The column statistics of an `add.stats` JSON string, as parsed by [`FileStats::try_new`]
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ColumnStats {
+    num_records: u64,
+    #[serde(default)]
+    min_values: Option<serde_json::Value>,
+    #[serde(default)]
+    max_values: Option<serde_json::Value>,
+    #[serde(default)]
+    null_count: Option<serde_json::Value>,
+}
+
+/// Typed per-file column statistics, keyed by the logical path of each (leaf) column. Only columns
+/// that have statistics in the file's `stats` are present.
+///
+/// Note that writers truncate long string values: a string minimum is a prefix of the true minimum
+/// and a string maximum may carry a tie-breaker suffix. They remain valid lower and upper bounds.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FileStats {
+    /// The number of records in the file, see [`Stats::num_records`].
+    pub num_records: u64,
+    /// Minimum value of each column, typed according to the column's data type.
+    pub min_values: HashMap<ColumnName, Scalar>,
+    /// Maximum value of each column, typed according to the column's data type.
+    pub max_values: HashMap<ColumnName, Scalar>,
+    /// Number of null values of each column.
+    pub null_counts: HashMap<ColumnName, i64>,
+}
+
+impl FileStats {
+    /// Parse the `stats` JSON string of a file (the `stats` column of the scan metadata) into
+    /// [`FileStats`]. `table_schema` is the logical schema of the table (e.g. [`Snapshot::schema`])
+    /// and `table_properties` its properties (e.g. [`Snapshot::table_properties`]); statistics of
+    /// columns not named by `delta.dataSkippingStatsColumns` (or, if that is not set, beyond
+    /// `delta.dataSkippingNumIndexedCols`) are ignored.
+    ///
+    /// [`Snapshot::schema`]: crate::snapshot::Snapshot::schema
+    /// [`Snapshot::table_properties`]: crate::snapshot::Snapshot::table_properties
+    pub fn try_new(
+        stats: &str,
+        table_schema: &Schema,
+        table_properties: &TableProperties,
+    ) -> DeltaResult<Self> {
+        let stats: ColumnStats = serde_json::from_str(stats)?;
+        let mut leaves = vec![];
+        collect_stats_leaves(table_schema, &[], &mut vec![], &mut leaves);
+        if let Some(stats_columns) = &table_properties.data_skipping_stats_columns {
+            // naming a struct column indexes all of its leaves
+            leaves.retain(|(logical_path, _, _)| {
+                stats_columns
+                    .iter()
+                    .any(|column| logical_path.path().starts_with(column.path()))
+            });
+        } else if let DataSkippingNumIndexedCols::NumColumns(n) =
+            table_properties.data_skipping_num_indexed_cols()
+        {
+            leaves.truncate(n.try_into().unwrap_or(usize::MAX));
+        }
+
+        let mut file_stats = FileStats {
+            num_records: stats.num_records,
+            min_values: HashMap::new(),
+            max_values: HashMap::new(),
+            null_counts: HashMap::new(),
+        };
+        for (logical_path, physical_path, data_type) in leaves {
+            let lookup = |values| lookup_stats_value(values, &physical_path);
+            if let DataType::Primitive(ptype) = data_type {
+                if let Some(min) = lookup(&stats.min_values) {
+                    let min = parse_stats_value(ptype, min)?;
+                    file_stats.min_values.insert(logical_path.clone(), min);
+                }
+                if let Some(max) = lookup(&stats.max_values) {
+                    let max = parse_stats_value(ptype, max)?;
+                    file_stats.max_values.insert(logical_path.clone(), max);
+                }
+            }
+            if let Some(null_count) = lookup(&stats.null_count) {
+                let null_count = null_count.as_i64().ok_or_else(|| {
+                    Error::generic(format!(
+                        "Invalid null count for {logical_path}: {null_count}"
+                    ))
+                })?;
+                file_stats.null_counts.insert(logical_path, null_count);
+            }
+        }
+        Ok(file_stats)
+    }
+}
+
+// Collect the (logical path, physical path, type) of every non-struct column in schema order,
+// which is the order `delta.dataSkippingNumIndexedCols` counts columns in.
+fn collect_stats_leaves<'a>(
+    schema: &'a StructType,
+    logical_prefix: &[String],
+    physical_prefix: &mut Vec<String>,
+    leaves: &mut Vec<(ColumnName, Vec<String>, &'a DataType)>,
+) {
+    for field in schema.fields() {
+        let logical_path = [logical_prefix, &[field.name().clone()]].concat();
+        physical_prefix.push(field.physical_name().to_string());
+        match field.data_type() {
+            DataType::Struct(inner) => {
+                collect_stats_leaves(inner, &logical_path, physical_prefix, leaves)
+            }
+            data_type => leaves.push((
+                ColumnName::new(logical_path),
+                physical_prefix.clone(),
+                data_type,
+            )),
+        }
+        physical_prefix.pop();
+    }
+}
+
+// Append zeros to the fractional digits of a (possibly exponential) decimal string until it has at
+// least `scale` digits after the decimal point. Too many digits are left for the parser to reject.
+fn pad_decimal_scale(raw: &str, scale: u8) -> String {
+    let (base, exp) = raw.split_at(raw.find(['e', 'E']).unwrap_or(raw.len()));
+    let exp_value: i64 = exp.get(1..).and_then(|e| e.parse().ok()).unwrap_or(0);
+    let frac_digits = base.find('.').map_or(0, |pos| base.len() - pos - 1);
+    let missing = i64::from(scale) - (frac_digits as i64 - exp_value);
+    if missing <= 0 {
+        return raw.to_string();
+    }
+    let separator = if base.contains('.') { "" } else { "." };
+    let zeros = "0".repeat(missing as usize);
+    format!("{base}{separator}{zeros}{exp}")
+}
+
+// Get the non-null value at `path` of the (nested) json stats object, if any.
+fn lookup_stats_value<'a>(
+    values: &'a Option<serde_json::Value>,
+    path: &[String],
+) -> Option<&'a serde_json::Value> {
+    path.iter()
+        .try_fold(values.as_ref()?, |value, name| value.get(name))
+        .filter(|value| !value.is_null())
+}
+
+// Convert a json stats value to a scalar of the column's type. Numbers (including decimals) are
+// parsed from their textual form so that e.g. decimals are scaled correctly, and strings use the
+// partition value parsing which understands dates and timestamps.
+fn parse_stats_value(ptype: &PrimitiveType, value: &serde_json::Value) -> DeltaResult<Scalar> {
+    match (ptype, value) {
+        // an empty string is a valid string bound, not a null
+        (PrimitiveType::String, serde_json::Value::String(s)) => Ok(Scalar::String(s.clone())),
+        (PrimitiveType::Boolean, serde_json::Value::Bool(b)) => Ok(Scalar::Boolean(*b)),
+        // stats serialize timestamp_ntz as `2021-01-01T00:00:00.000`
+        (PrimitiveType::TimestampNtz, serde_json::Value::String(s)) => {
+            ptype.parse_scalar(&s.replacen('T', " ", 1))
+        }
+        // json drops trailing zeros (e.g. `1.50` becomes `1.5`), so pad to the column's scale
+        (PrimitiveType::Decimal(dtype), serde_json::Value::Number(n)) => {
+            ptype.parse_scalar(&pad_decimal_scale(&n.to_string(), dtype.scale()))
+        }
+        (_, serde_json::Value::String(s)) => ptype.parse_scalar(s),
+        (_, serde_json::Value::Number(n)) => ptype.parse_scalar(&n.to_string()),
+        _ => Err(Error::generic(format!(
+            "Invalid stats value for type {ptype}: {value}"
+        ))),
+    }
+}
+
 impl DvInfo {
     /// Check if this DvInfo contains a Deletion Vector. This is mostly used to know if the
     /// associated [`Stats`] struct has fully accurate information or not.
@@ -100,6 +268,22 @@ impl DvInfo {
     }
 }
 
+/// Resolve the `path` of a data file, as found in an add action, to the file's location.
+///
+/// Paths are usually relative to the table root, but may also be fully-qualified URLs (e.g.
+/// `s3://bucket/dir/file.parquet`), as written by shallow clones for the files they reference in
+/// another table. Such URLs are used as is rather than joined onto the table root. Either way the
+/// path is a URI, so its special characters are percent-encoded and stay that way.
+pub fn resolve_file_path(table_root: &Url, path: &str) -> DeltaResult<Url> {
+    match Url::parse(path) {
+        Ok(url) if !url.cannot_be_a_base() => Ok(url),
+        // Something like `a:b/file.parquet` parses as an opaque URL, but is really a relative path
+        // whose first segment contains a colon
+        Ok(_) => Ok(table_root.join(&format!("./{path}"))?),
+        Err(_) => Ok(table_root.join(path)?),
+    }
+}
+
 /// utility function for applying a transform expression to convert data from physical to logical
 /// format
 pub fn transform_to_logical(
@@ -238,11 +422,17 @@ impl<T> RowVisitor for ScanFileVisitor<'_, T> {
 mod tests {
     use std::collections::HashMap;
 
+    use url::Url;
+
     use crate::actions::get_log_schema;
     use crate::scan::test_utils::{add_batch_simple, run_with_validate_callback};
     use crate::ExpressionRef;
 
-    use super::{DvInfo, Stats};
+    use crate::expressions::{column_name, Scalar};
+    use crate::schema::{DataType, DecimalType, StructField, StructType};
+    use crate::table_properties::TableProperties;
+
+    use super::{resolve_file_path, DvInfo, FileStats, Stats};
 
     #[derive(Clone)]
     struct TestContext {
@@ -286,4 +476,158 @@ mod tests {
             validate_visit,
         );
     }
+
+    #[test]
+    fn test_resolve_file_path() {
+        let table_root = Url::parse("s3://bucket/table/").unwrap();
+        let resolve = |path| resolve_file_path(&table_root, path).unwrap().to_string();
+        assert_eq!(
+            resolve("part=a%20b/file%3A1.parquet"),
+            "s3://bucket/table/part=a%20b/file%3A1.parquet"
+        );
+        assert_eq!(
+            resolve("s3://other/table/part=a%20b/file.parquet"),
+            "s3://other/table/part=a%20b/file.parquet"
+        );
+        assert_eq!(
+            resolve("file:///tmp/table/file.parquet"),
+            "file:///tmp/table/file.parquet"
+        );
+        // a colon in the first segment of a relative path does not make it a URL
+        assert_eq!(
+            resolve("a:b/file.parquet"),
+            "s3://bucket/table/a:b/file.parquet"
+        );
+    }
+
+    fn stats_json(json: serde_json::Value) -> String {
+        json.to_string()
+    }
+
+    #[test]
+    fn test_file_stats_int_column() {
+        let schema = StructType::new([
+            StructField::nullable("id", DataType::INTEGER),
+            StructField::nullable("date", DataType::DATE),
+            StructField::nullable("price", DecimalType::try_new(5, 2).unwrap()),
+        ]);
+        let stats = stats_json(serde_json::json!({
+            "numRecords": 3,
+            "minValues": { "id": 1, "date": "2021-01-01", "price": 1.5 },
+            "maxValues": { "id": 7, "date": "2021-01-31", "price": 123.45 },
+            "nullCount": { "id": 0, "date": 1, "price": 2 },
+        }));
+        let file_stats = FileStats::try_new(&stats, &schema, &TableProperties::default()).unwrap();
+
+        assert_eq!(file_stats.num_records, 3);
+        assert_eq!(
+            file_stats.min_values[&column_name!("id")],
+            Scalar::Integer(1)
+        );
+        assert_eq!(
+            file_stats.max_values[&column_name!("id")],
+            Scalar::Integer(7)
+        );
+        assert_eq!(
+            file_stats.min_values[&column_name!("date")],
+            Scalar::Date(18628)
+        );
+        assert_eq!(
+            file_stats.max_values[&column_name!("date")],
+            Scalar::Date(18658)
+        );
+        assert_eq!(
+            file_stats.min_values[&column_name!("price")],
+            Scalar::decimal(150, 5, 2).unwrap()
+        );
+        assert_eq!(
+            file_stats.max_values[&column_name!("price")],
+            Scalar::decimal(12345, 5, 2).unwrap()
+        );
+        assert_eq!(file_stats.null_counts[&column_name!("id")], 0);
+        assert_eq!(file_stats.null_counts[&column_name!("date")], 1);
+        assert_eq!(file_stats.null_counts[&column_name!("price")], 2);
+    }
+
+    #[test]
+    fn test_file_stats_truncated_string_column() {
+        let schema = StructType::new([StructField::nullable("name", DataType::STRING)]);
+        // writers truncate long strings: the max carries a tie-breaker suffix
+        let stats = stats_json(serde_json::json!({
+            "numRecords": 2,
+            "minValues": { "name": "" },
+            "maxValues": { "name": "abcdefghijklmnopqrstuvwxyzabcdef\u{10FFFF}" },
+            "nullCount": { "name": 0 },
+        }));
+        let file_stats = FileStats::try_new(&stats, &schema, &TableProperties::default()).unwrap();
+
+        assert_eq!(
+            file_stats.min_values[&column_name!("name")],
+            Scalar::String("".into())
+        );
+        assert_eq!(
+            file_stats.max_values[&column_name!("name")],
+            Scalar::String("abcdefghijklmnopqrstuvwxyzabcdef\u{10FFFF}".into())
+        );
+        assert_eq!(file_stats.null_counts[&column_name!("name")], 0);
+    }
+
+    #[test]
+    fn test_file_stats_nested_column() {
+        let schema = StructType::new([
+            StructField::nullable("a", DataType::LONG),
+            StructField::nullable(
+                "nested",
+                StructType::new([
+                    StructField::nullable("b", DataType::STRING),
+                    StructField::nullable("c", DataType::LONG),
+                ]),
+            ),
+        ]);
+        let stats = stats_json(serde_json::json!({
+            "numRecords": 4,
+            "minValues": { "a": 1, "nested": { "b": "x", "c": 10 } },
+            "maxValues": { "a": 4, "nested": { "b": "z", "c": 40 } },
+            "nullCount": { "a": 0, "nested": { "b": 1, "c": 0 } },
+        }));
+        let file_stats = FileStats::try_new(&stats, &schema, &TableProperties::default()).unwrap();
+        assert_eq!(
+            file_stats.min_values[&column_name!("nested.b")],
+            Scalar::String("x".into())
+        );
+        assert_eq!(
+            file_stats.max_values[&column_name!("nested.c")],
+            Scalar::Long(40)
+        );
+        assert_eq!(file_stats.null_counts[&column_name!("nested.b")], 1);
+        assert_eq!(file_stats.min_values.len(), 3);
+
+        // only the first two leaf columns are indexed
+        let properties = TableProperties::from([("delta.dataSkippingNumIndexedCols", "2")]);
+        let file_stats = FileStats::try_new(&stats, &schema, &properties).unwrap();
+        assert!(file_stats
+            .min_values
+            .contains_key(&column_name!("nested.b")));
+        assert!(!file_stats
+            .min_values
+            .contains_key(&column_name!("nested.c")));
+        assert!(!file_stats
+            .null_counts
+            .contains_key(&column_name!("nested.c")));
+
+        // an explicit list of stats columns takes precedence
+        let properties = TableProperties::from([
+            ("delta.dataSkippingNumIndexedCols", "1"),
+            ("delta.dataSkippingStatsColumns", "nested.c"),
+        ]);
+        let file_stats = FileStats::try_new(&stats, &schema, &properties).unwrap();
+        assert_eq!(
+            file_stats.min_values.keys().collect::<Vec<_>>(),
+            [&column_name!("nested.c")]
+        );
+        assert_eq!(
+            file_stats.null_counts.keys().collect::<Vec<_>>(),
+            [&column_name!("nested.c")]
+        );
+    }
 }
@@ -0,0 +1,569 @@
+//! Provides a check that an engine's arrow data can be written into a Delta table. The api is
+//! [`check_write_compatible`], which walks the table schema and the arrow schema of the data being
+//! written side by side and reports every field that cannot be written.
+//!
+//! A batch is write-compatible with a table when:
+//!     1. Every non-nullable table column is present in the batch, and every batch column exists
+//!        in the table.
+//!     2. No batch column is nullable where the table column is not.
+//!     3. Every batch column has the table column's type, or a type that can be safely widened to
+//!        it (e.g. `int` to `long`, `float` to `double`, or a decimal with room for its digits).
+//!
+//! A nullable batch column can still be written into a non-nullable table column if it holds no
+//! nulls, which [`check_write_nullability`] verifies against the data of a batch.
+use std::fmt::{Display, Formatter};
+
+use itertools::Itertools;
+
+use super::{ColumnName, DataType, PrimitiveType};
+
+/// A single reason a batch cannot be written into a table, along with the path of the field.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Incompatibility {
+    /// A non-nullable table column is missing from the batch.
+    MissingColumn(ColumnName),
+    /// A batch column does not exist in the table.
+    UnknownColumn(ColumnName),
+    /// The batch column's type cannot be written into the table column's type.
+    TypeMismatch {
+        path: ColumnName,
+        table_type: DataType,
+        batch_type: String,
+    },
+    /// The batch column is nullable but the table column is not.
+    NullabilityNarrowing(ColumnName),
+    /// The batch column holds a null in the given row, but the table column is not nullable.
+    NullValue { path: ColumnName, row: usize },
+}
+
+impl Incompatibility {
+    /// The path of the field that is incompatible.
+    pub fn path(&self) -> &ColumnName {
+        match self {
+            Self::MissingColumn(path)
+            | Self::UnknownColumn(path)
+            | Self::TypeMismatch { path, .. }
+            | Self::NullabilityNarrowing(path)
+            | Self::NullValue { path, .. } => path,
+        }
+    }
+}
+
+impl Display for Incompatibility {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::MissingColumn(path) => write!(f, "missing non-nullable column '{path}'"),
+            Self::UnknownColumn(path) => write!(f, "column '{path}' is not in the table schema"),
+            Self::TypeMismatch {
+                path,
+                table_type,
+                batch_type,
+            } => write!(
+                f,
+                "column '{path}' has type {batch_type} which cannot be written as {table_type}"
+            ),
+            Self::NullabilityNarrowing(path) => {
+                write!(f, "column '{path}' is nullable but the table column is not")
+            }
+            Self::NullValue { path, row } => {
+                write!(f, "non-nullable column '{path}' has a null in row {row}")
+            }
+        }
+    }
+}
+
+/// The error returned when a batch is not write-compatible with a table. Lists every
+/// incompatible field that was found.
+#[derive(Debug, thiserror::Error)]
+#[error(
+    "Data is not write-compatible with the table schema: {}",
+    incompatibilities.iter().join("; ")
+)]
+pub struct Error {
+    incompatibilities: Vec<Incompatibility>,
+}
+
+impl Error {
+    /// All the incompatibilities that were found, in schema order.
+    pub fn incompatibilities(&self) -> &[Incompatibility] {
+        &self.incompatibilities
+    }
+}
+
+/// The number of decimal digits needed to represent every value of an integer type, or `None`
+/// for non-integer types.
+fn integer_digits(t: &PrimitiveType) -> Option<u8> {
+    match t {
+        PrimitiveType::Byte | PrimitiveType::UByte => Some(3),
+        PrimitiveType::Short | PrimitiveType::UShort => Some(5),
+        PrimitiveType::Integer | PrimitiveType::UInteger => Some(10),
+        PrimitiveType::Long | PrimitiveType::ULong => Some(20),
+        _ => None,
+    }
+}
+
+/// Returns true if values of type `from` can be written into a column of type `to` without
+/// loss, following Delta's type widening rules.
+pub(crate) fn can_widen(from: &PrimitiveType, to: &PrimitiveType) -> bool {
+    use PrimitiveType::*;
+    if from == to {
+        return true;
+    }
+    match (from, to) {
+        (Byte, Short | Integer | Long | Double) => true,
+        (Short, Integer | Long | Double) => true,
+        (Integer, Long | Double) => true,
+        (UByte, UShort | UInteger | ULong | Short | Integer | Long | Double) => true,
+        (UShort, UInteger | ULong | Integer | Long | Double) => true,
+        (UInteger, ULong | Long | Double) => true,
+        (Float, Double) => true,
+        (Date, TimestampNtz) => true,
+        (Decimal(from), Decimal(to)) => {
+            to.scale() >= from.scale()
+                && to.precision() - to.scale() >= from.precision() - from.scale()
+        }
+        (from, Decimal(to)) => {
+            integer_digits(from).is_some_and(|digits| to.precision() - to.scale() >= digits)
+        }
+        _ => false,
+    }
+}
+
+#[cfg(feature = "arrow-conversion")]
+pub use check::{check_write_compatible, check_write_nullability};
+
+#[cfg(feature = "arrow-conversion")]
+mod check {
+    use itertools::Itertools;
+
+    use crate::arrow::array::{Array, AsArray, RecordBatch};
+    use crate::arrow::datatypes::{
+        DataType as ArrowDataType, Field as ArrowField, Fields as ArrowFields,
+        Schema as ArrowSchema,
+    };
+    use crate::schema::{ColumnName, DataType, StructType};
+    use crate::DeltaResult;
+
+    use super::{can_widen, Error, Incompatibility};
+
+    /// Check that data with the arrow schema `batch` can be written into a table with schema
+    /// `table`. Returns an error listing every incompatible field otherwise.
+    pub fn check_write_compatible(table: &StructType, batch: &ArrowSchema) -> DeltaResult<()> {
+        let mut incompatibilities = vec![];
+        check_struct(table, batch.fields(), &[], &mut incompatibilities);
+        if incompatibilities.is_empty() {
+            Ok(())
+        } else {
+            Err(Error { incompatibilities }.into())
+        }
+    }
+
+    /// Check that `batch` holds no nulls in the nullable columns that are written into
+    /// non-nullable columns of a table with schema `table`. Returns an error naming the path of
+    /// each such column that holds a null, along with the first row holding one. Nested fields are
+    /// only null where their parent is not, e.g. a non-nullable field of a null struct is fine.
+    ///
+    /// Other incompatibilities between `table` and the schema of `batch` are not reported, use
+    /// [`check_write_compatible`] for those.
+    pub fn check_write_nullability(table: &StructType, batch: &RecordBatch) -> DeltaResult<()> {
+        let mut narrowed = vec![];
+        check_struct(table, batch.schema_ref().fields(), &[], &mut narrowed);
+        let incompatibilities: Vec<_> = narrowed
+            .into_iter()
+            .filter_map(|incompatibility| match incompatibility {
+                Incompatibility::NullabilityNarrowing(path) => {
+                    let (column, nested) = path.split_first()?;
+                    let row = null_rows(batch.column_by_name(column)?, nested)
+                        .into_iter()
+                        .next()?;
+                    Some(Incompatibility::NullValue { path, row })
+                }
+                _ => None,
+            })
+            .collect();
+        if incompatibilities.is_empty() {
+            Ok(())
+        } else {
+            Err(Error { incompatibilities }.into())
+        }
+    }
+
+    /// The rows of `array`, in ascending order, in which the field at `path` is null while
+    /// everything containing it is not. `path` names struct fields, and the `element` of lists and
+    /// the `value` of maps, as reported by [`check_write_compatible`].
+    fn null_rows(array: &dyn Array, path: &[String]) -> Vec<usize> {
+        let Some((name, nested)) = path.split_first() else {
+            return (0..array.len()).filter(|&row| array.is_null(row)).collect();
+        };
+        // the rows of `array` holding the null child rows, given the child offsets of each row
+        let parent_rows = |child_rows: Vec<usize>, offsets: &[usize]| -> Vec<usize> {
+            let rows = child_rows
+                .into_iter()
+                .map(|child_row| offsets.partition_point(|&offset| offset <= child_row) - 1);
+            rows.dedup().filter(|&row| array.is_valid(row)).collect()
+        };
+        match array.data_type() {
+            ArrowDataType::Struct(_) => match array.as_struct().column_by_name(name) {
+                Some(child) => null_rows(child, nested)
+                    .into_iter()
+                    .filter(|&row| array.is_valid(row))
+                    .collect(),
+                None => vec![],
+            },
+            ArrowDataType::List(_) => {
+                let list = array.as_list::<i32>();
+                let offsets: Vec<_> = list.offsets().iter().map(|&o| o as usize).collect();
+                parent_rows(null_rows(list.values(), nested), &offsets)
+            }
+            ArrowDataType::LargeList(_) => {
+                let list = array.as_list::<i64>();
+                let offsets: Vec<_> = list.offsets().iter().map(|&o| o as usize).collect();
+                parent_rows(null_rows(list.values(), nested), &offsets)
+            }
+            ArrowDataType::FixedSizeList(_, size) => {
+                let list = array.as_fixed_size_list();
+                let offsets: Vec<_> = (0..=list.len()).map(|row| row * *size as usize).collect();
+                parent_rows(null_rows(list.values(), nested), &offsets)
+            }
+            ArrowDataType::Map(_, _) if name == "value" => {
+                let map = array.as_map();
+                let offsets: Vec<_> = map.offsets().iter().map(|&o| o as usize).collect();
+                parent_rows(null_rows(map.values(), nested), &offsets)
+            }
+            _ => vec![],
+        }
+    }
+
+    fn child_path(path: &[String], name: &str) -> Vec<String> {
+        path.iter().cloned().chain([name.to_string()]).collect()
+    }
+
+    fn check_struct(
+        table: &StructType,
+        batch: &ArrowFields,
+        path: &[String],
+        out: &mut Vec<Incompatibility>,
+    ) {
+        for table_field in table.fields() {
+            let field_path = child_path(path, table_field.name());
+            match batch.find(table_field.name()) {
+                Some((_, batch_field)) => {
+                    check_nullability(table_field.is_nullable(), batch_field, &field_path, out);
+                    check_type(
+                        table_field.data_type(),
+                        batch_field.data_type(),
+                        &field_path,
+                        out,
+                    );
+                }
+                None if table_field.is_nullable() => {}
+                None => out.push(Incompatibility::MissingColumn(ColumnName::new(field_path))),
+            }
+        }
+        for batch_field in batch.iter() {
+            if table.field(batch_field.name()).is_none() {
+                let field_path = child_path(path, batch_field.name());
+                out.push(Incompatibility::UnknownColumn(ColumnName::new(field_path)));
+            }
+        }
+    }
+
+    fn check_nullability(
+        table_nullable: bool,
+        batch_field: &ArrowField,
+        path: &[String],
+        out: &mut Vec<Incompatibility>,
+    ) {
+        if !table_nullable && batch_field.is_nullable() {
+            out.push(Incompatibility::NullabilityNarrowing(ColumnName::new(path)));
+        }
+    }
+
+    fn check_type(
+        table: &DataType,
+        batch: &ArrowDataType,
+        path: &[String],
+        out: &mut Vec<Incompatibility>,
+    ) {
+        match (table, batch) {
+            (DataType::Struct(table_struct), ArrowDataType::Struct(batch_fields)) => {
+                check_struct(table_struct, batch_fields, path, out)
+            }
+            (
+                DataType::Array(table_array),
+                ArrowDataType::List(element)
+                | ArrowDataType::LargeList(element)
+                | ArrowDataType::ListView(element)
+                | ArrowDataType::LargeListView(element)
+                | ArrowDataType::FixedSizeList(element, _),
+            ) => {
+                let element_path = child_path(path, "element");
+                check_nullability(table_array.contains_null(), element, &element_path, out);
+                check_type(
+                    table_array.element_type(),
+                    element.data_type(),
+                    &element_path,
+                    out,
+                );
+            }
+            (DataType::Map(table_map), ArrowDataType::Map(entries, _)) => {
+                let ArrowDataType::Struct(entry_fields) = entries.data_type() else {
+                    return out.push(type_mismatch(table, batch, path));
+                };
+                let [key, value] = &entry_fields.iter().collect::<Vec<_>>()[..] else {
+                    return out.push(type_mismatch(table, batch, path));
+                };
+                check_type(
+                    table_map.key_type(),
+                    key.data_type(),
+                    &child_path(path, "key"),
+                    out,
+                );
+                let value_path = child_path(path, "value");
+                check_nullability(table_map.value_contains_null(), value, &value_path, out);
+                check_type(table_map.value_type(), value.data_type(), &value_path, out);
+            }
+            (DataType::Primitive(table_primitive), _) => {
+                // dictionary encoding is transparent to the table, only the values matter
+                let batch_values = match batch {
+                    ArrowDataType::Dictionary(_, values) => values.as_ref(),
+                    _ => batch,
+                };
+                match DataType::try_from(batch_values) {
+                    Ok(DataType::Primitive(batch_primitive))
+                        if can_widen(&batch_primitive, table_primitive) => {}
+                    _ => out.push(type_mismatch(table, batch, path)),
+                }
+            }
+            _ => match DataType::try_from(batch) {
+                Ok(batch_type) if &batch_type == table => {}
+                _ => out.push(type_mismatch(table, batch, path)),
+            },
+        }
+    }
+
+    fn type_mismatch(table: &DataType, batch: &ArrowDataType, path: &[String]) -> Incompatibility {
+        Incompatibility::TypeMismatch {
+            path: ColumnName::new(path),
+            table_type: table.clone(),
+            batch_type: batch.to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use crate::arrow::array::{
+        ArrayRef, Int32Array, Int64Array, ListArray, RecordBatch, StringArray, StructArray,
+    };
+    use crate::arrow::buffer::{NullBuffer, OffsetBuffer};
+    use crate::arrow::datatypes::{
+        DataType as ArrowDataType, Field as ArrowField, Fields as ArrowFields,
+        Schema as ArrowSchema,
+    };
+    use crate::schema::compatibility::{
+        check_write_compatible, check_write_nullability, Incompatibility,
+    };
+    use crate::schema::{column_name, ArrayType, DataType, DecimalType, StructField, StructType};
+    use crate::Error;
+
+    fn incompatibilities(table: &StructType, batch: &ArrowSchema) -> Vec<Incompatibility> {
+        match check_write_compatible(table, batch) {
+            Err(Error::IncompatibleWriteSchema(err)) => err.incompatibilities().to_vec(),
+            res => panic!("expected an incompatible write schema error, got {res:?}"),
+        }
+    }
+
+    #[test]
+    fn compatible_schema() {
+        let table = StructType::new([
+            StructField::not_null("id", DataType::LONG),
+            StructField::nullable("score", DataType::DOUBLE),
+            StructField::nullable("amount", DecimalType::try_new(12, 2).unwrap()),
+            StructField::nullable("tags", ArrayType::new(DataType::STRING, true)),
+            StructField::nullable("comment", DataType::STRING),
+        ]);
+        // widened types, widened nullability and an omitted nullable column are all fine
+        let batch = ArrowSchema::new(vec![
+            ArrowField::new("id", ArrowDataType::Int32, false),
+            ArrowField::new("score", ArrowDataType::Float32, false),
+            ArrowField::new("amount", ArrowDataType::Decimal128(8, 2), true),
+            ArrowField::new(
+                "tags",
+                ArrowDataType::List(Arc::new(ArrowField::new(
+                    "element",
+                    ArrowDataType::Utf8,
+                    false,
+                ))),
+                true,
+            ),
+        ]);
+        check_write_compatible(&table, &batch).unwrap();
+    }
+
+    #[test]
+    fn nullability_narrowing_rejected() {
+        let table = StructType::new([StructField::not_null("id", DataType::LONG)]);
+        let batch = ArrowSchema::new(vec![ArrowField::new("id", ArrowDataType::Int64, true)]);
+        assert_eq!(
+            incompatibilities(&table, &batch),
+            vec![Incompatibility::NullabilityNarrowing(column_name!("id"))]
+        );
+    }
+
+    fn null_values(table: &StructType, batch: &RecordBatch) -> Vec<Incompatibility> {
+        match check_write_nullability(table, batch) {
+            Err(Error::IncompatibleWriteSchema(err)) => err.incompatibilities().to_vec(),
+            res => panic!("expected an incompatible write schema error, got {res:?}"),
+        }
+    }
+
+    #[test]
+    fn nullability_narrowing_checks_data() {
+        let table = StructType::new([
+            StructField::not_null("id", DataType::LONG),
+            StructField::not_null("name", DataType::STRING),
+            StructField::nullable("comment", DataType::STRING),
+        ]);
+        let schema = Arc::new(ArrowSchema::new(vec![
+            ArrowField::new("id", ArrowDataType::Int64, true),
+            ArrowField::new("name", ArrowDataType::Utf8, true),
+            ArrowField::new("comment", ArrowDataType::Utf8, true),
+        ]));
+        let batch = |ids: Vec<Option<i64>>, names: Vec<Option<&str>>| {
+            let comments = StringArray::new_null(ids.len());
+            let columns: Vec<ArrayRef> = vec![
+                Arc::new(Int64Array::from(ids)),
+                Arc::new(StringArray::from(names)),
+                Arc::new(comments),
+            ];
+            RecordBatch::try_new(schema.clone(), columns).unwrap()
+        };
+
+        // the nullable columns hold no nulls, so they can be written into non-nullable columns
+        let clean = batch(vec![Some(1), Some(2)], vec![Some("a"), Some("b")]);
+        check_write_nullability(&table, &clean).unwrap();
+        // the schema alone does not prove that
+        assert!(check_write_compatible(&table, &schema).is_err());
+
+        let with_nulls = batch(
+            vec![Some(1), Some(2), None, None],
+            vec![Some("a"), None, Some("c"), None],
+        );
+        assert_eq!(
+            null_values(&table, &with_nulls),
+            vec![
+                Incompatibility::NullValue {
+                    path: column_name!("id"),
+                    row: 2
+                },
+                Incompatibility::NullValue {
+                    path: column_name!("name"),
+                    row: 1
+                },
+            ]
+        );
+        let err = check_write_nullability(&table, &with_nulls).unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("non-nullable column 'id' has a null in row 2"),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn nested_nullability_narrowing_checks_data() {
+        let table = StructType::new([
+            StructField::nullable(
+                "s",
+                StructType::new([StructField::not_null("x", DataType::INTEGER)]),
+            ),
+            StructField::nullable("l", ArrayType::new(DataType::INTEGER, false)),
+        ]);
+        let x_field = Arc::new(ArrowField::new("x", ArrowDataType::Int32, true));
+        let element_field = Arc::new(ArrowField::new("element", ArrowDataType::Int32, true));
+        let schema = Arc::new(ArrowSchema::new(vec![
+            ArrowField::new(
+                "s",
+                ArrowDataType::Struct(ArrowFields::from(vec![x_field.clone()])),
+                true,
+            ),
+            ArrowField::new("l", ArrowDataType::List(element_field.clone()), true),
+        ]));
+        // rows: 0 and 1 hold a null x/element under a null parent, which is fine; row 2 holds a
+        // null x, and row 3 a null element, under valid parents
+        let s = StructArray::new(
+            ArrowFields::from(vec![x_field]),
+            vec![Arc::new(Int32Array::from(vec![
+                None,
+                Some(1),
+                None,
+                Some(3),
+            ]))],
+            Some(NullBuffer::from(vec![false, true, true, true])),
+        );
+        let l = ListArray::new(
+            element_field,
+            OffsetBuffer::from_lengths([1, 1, 0, 2]),
+            Arc::new(Int32Array::from(vec![Some(0), None, Some(3), None])),
+            Some(NullBuffer::from(vec![true, false, true, true])),
+        );
+        let batch = RecordBatch::try_new(schema, vec![Arc::new(s), Arc::new(l)]).unwrap();
+        assert_eq!(
+            null_values(&table, &batch),
+            vec![
+                Incompatibility::NullValue {
+                    path: column_name!("s.x"),
+                    row: 2
+                },
+                Incompatibility::NullValue {
+                    path: column_name!("l.element"),
+                    row: 3
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn nested_struct_mismatch() {
+        let table = StructType::new([StructField::nullable(
+            "a",
+            StructType::new([StructField::nullable(
+                "b",
+                StructType::new([
+                    StructField::nullable("c", DataType::INTEGER),
+                    StructField::not_null("d", DataType::STRING),
+                ]),
+            )]),
+        )]);
+        let batch = ArrowSchema::new(vec![ArrowField::new_struct(
+            "a",
+            vec![ArrowField::new_struct(
+                "b",
+                vec![
+                    ArrowField::new("c", ArrowDataType::Int64, true),
+                    ArrowField::new("e", ArrowDataType::Utf8, true),
+                ],
+                true,
+            )],
+            true,
+        )]);
+        let found = incompatibilities(&table, &batch);
+        assert_eq!(
+            found,
+            vec![
+                Incompatibility::TypeMismatch {
+                    path: column_name!("a.b.c"),
+                    table_type: DataType::INTEGER,
+                    batch_type: "Int64".to_string(),
+                },
+                Incompatibility::MissingColumn(column_name!("a.b.d")),
+                Incompatibility::UnknownColumn(column_name!("a.b.e")),
+            ]
+        );
+        let err = check_write_compatible(&table, &batch).unwrap_err();
+        assert!(err.to_string().contains("'a.b.c'"), "{err}");
+    }
+}
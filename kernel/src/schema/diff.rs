@@ -0,0 +1,273 @@
+//! Provides a structured diff between two versions of a schema, e.g. for migration or DDL
+//! generation tools. The api is [`StructType::diff`], which lists the [`SchemaChange`]s that turn
+//! one schema into another.
+//!
+//! Fields of the two schemas are matched by column mapping id when both carry one, and by name
+//! otherwise. This means a renamed column is only reported as a [`SchemaChange::RenameColumn`]
+//! when column mapping is enabled; without it, a rename shows up as a drop of the old column and
+//! an add of the new one.
+use super::{ColumnMetadataKey, ColumnName, DataType, MetadataValue, StructField, StructType};
+
+/// A single change between two versions of a schema. Paths of dropped columns refer to the old
+/// schema, all other paths to the new one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SchemaChange {
+    /// A column of the given type was added.
+    AddColumn(ColumnName, DataType),
+    /// A column was dropped.
+    DropColumn(ColumnName),
+    /// A column was renamed from the first path to the second. Only detected via column mapping
+    /// ids.
+    RenameColumn(ColumnName, ColumnName),
+    /// The type of a column changed from the first type to the second. Structs are diffed field by
+    /// field instead, so this is only reported for other types (including arrays and maps).
+    ChangeType(ColumnName, DataType, DataType),
+    /// The nullability of a column changed from the first value to the second.
+    ChangeNullability(ColumnName, bool, bool),
+}
+
+impl StructType {
+    /// Compute the changes that turn this schema into `new`. Changes are listed in the order of
+    /// this schema's fields, with nested changes following their parent's, and added columns last
+    /// in each struct. Changes to field metadata are not reported.
+    pub fn diff(&self, new: &StructType) -> Vec<SchemaChange> {
+        let mut changes = vec![];
+        diff_structs(self, new, &[], &[], &mut changes);
+        changes
+    }
+}
+
+fn column_mapping_id(field: &StructField) -> Option<i64> {
+    match field.get_config_value(&ColumnMetadataKey::ColumnMappingId) {
+        Some(MetadataValue::Number(id)) => Some(*id),
+        _ => None,
+    }
+}
+
+// Find the field of `new` that `old` was turned into: the field with the same column mapping id,
+// or, if either of them has none, the field with the same name. Fields already matched are skipped.
+fn find_match(old: &StructField, new: &StructType, matched: &[bool]) -> Option<usize> {
+    let old_id = column_mapping_id(old);
+    new.fields()
+        .enumerate()
+        .filter(|(i, _)| !matched[*i])
+        .find(|(_, field)| match (old_id, column_mapping_id(field)) {
+            (Some(old_id), Some(new_id)) => old_id == new_id,
+            _ => old.name() == field.name(),
+        })
+        .map(|(i, _)| i)
+}
+
+fn diff_structs(
+    old: &StructType,
+    new: &StructType,
+    old_prefix: &[String],
+    new_prefix: &[String],
+    changes: &mut Vec<SchemaChange>,
+) {
+    let path = |prefix: &[String], field: &StructField| {
+        ColumnName::new(prefix.iter().chain([field.name()]))
+    };
+    let mut matched = vec![false; new.fields_len()];
+    for old_field in old.fields() {
+        let old_path = path(old_prefix, old_field);
+        let Some(i) = find_match(old_field, new, &matched) else {
+            changes.push(SchemaChange::DropColumn(old_path));
+            continue;
+        };
+        matched[i] = true;
+        // NOTE: unwrap is safe because `i` is the index of a field of `new`
+        let new_field = new.fields().nth(i).unwrap();
+        let new_path = path(new_prefix, new_field);
+        if old_field.name() != new_field.name() {
+            changes.push(SchemaChange::RenameColumn(
+                old_path.clone(),
+                new_path.clone(),
+            ));
+        }
+        if old_field.is_nullable() != new_field.is_nullable() {
+            changes.push(SchemaChange::ChangeNullability(
+                new_path.clone(),
+                old_field.is_nullable(),
+                new_field.is_nullable(),
+            ));
+        }
+        match (old_field.data_type(), new_field.data_type()) {
+            (DataType::Struct(old_inner), DataType::Struct(new_inner)) => {
+                diff_structs(
+                    old_inner,
+                    new_inner,
+                    old_path.path(),
+                    new_path.path(),
+                    changes,
+                );
+            }
+            (old_type, new_type) if old_type != new_type => {
+                changes.push(SchemaChange::ChangeType(
+                    new_path,
+                    old_type.clone(),
+                    new_type.clone(),
+                ));
+            }
+            _ => {}
+        }
+    }
+    let added = new.fields().zip(matched).filter(|(_, matched)| !matched);
+    for (field, _) in added {
+        changes.push(SchemaChange::AddColumn(
+            path(new_prefix, field),
+            field.data_type().clone(),
+        ));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SchemaChange::*;
+    use crate::schema::{
+        column_name, ArrayType, ColumnMetadataKey, DataType, StructField, StructType,
+    };
+
+    #[test]
+    fn identical_schemas_have_no_changes() {
+        let schema = StructType::new([
+            StructField::not_null("id", DataType::LONG),
+            StructField::nullable(
+                "nested",
+                StructType::new([StructField::nullable("x", DataType::STRING)]),
+            ),
+        ]);
+        assert_eq!(schema.diff(&schema), vec![]);
+    }
+
+    #[test]
+    fn add_and_drop_columns() {
+        let old = StructType::new([
+            StructField::not_null("id", DataType::LONG),
+            StructField::nullable("gone", DataType::STRING),
+            StructField::nullable(
+                "nested",
+                StructType::new([StructField::nullable("x", DataType::STRING)]),
+            ),
+        ]);
+        let new = StructType::new([
+            StructField::not_null("id", DataType::LONG),
+            StructField::nullable(
+                "nested",
+                StructType::new([
+                    StructField::nullable("x", DataType::STRING),
+                    StructField::nullable("y", DataType::DATE),
+                ]),
+            ),
+            StructField::nullable("added", DataType::INTEGER),
+        ]);
+        assert_eq!(
+            old.diff(&new),
+            vec![
+                DropColumn(column_name!("gone")),
+                AddColumn(column_name!("nested.y"), DataType::DATE),
+                AddColumn(column_name!("added"), DataType::INTEGER),
+            ]
+        );
+        assert_eq!(
+            new.diff(&old),
+            vec![
+                DropColumn(column_name!("nested.y")),
+                DropColumn(column_name!("added")),
+                AddColumn(column_name!("gone"), DataType::STRING),
+            ]
+        );
+    }
+
+    #[test]
+    fn change_type_and_nullability() {
+        let old = StructType::new([
+            StructField::not_null("id", DataType::INTEGER),
+            StructField::nullable("tags", ArrayType::new(DataType::INTEGER, false)),
+            StructField::nullable("name", DataType::STRING),
+        ]);
+        let new = StructType::new([
+            StructField::not_null("id", DataType::LONG),
+            StructField::nullable("tags", ArrayType::new(DataType::LONG, false)),
+            StructField::not_null("name", DataType::STRING),
+        ]);
+        assert_eq!(
+            old.diff(&new),
+            vec![
+                ChangeType(column_name!("id"), DataType::INTEGER, DataType::LONG),
+                ChangeType(
+                    column_name!("tags"),
+                    ArrayType::new(DataType::INTEGER, false).into(),
+                    ArrayType::new(DataType::LONG, false).into(),
+                ),
+                ChangeNullability(column_name!("name"), true, false),
+            ]
+        );
+
+        // a struct replaced by a primitive is a type change, not a diff of its fields
+        let old = StructType::new([StructField::nullable(
+            "s",
+            StructType::new([StructField::nullable("x", DataType::STRING)]),
+        )]);
+        let new = StructType::new([StructField::nullable("s", DataType::STRING)]);
+        assert_eq!(
+            old.diff(&new),
+            vec![ChangeType(
+                column_name!("s"),
+                StructType::new([StructField::nullable("x", DataType::STRING)]).into(),
+                DataType::STRING,
+            )]
+        );
+    }
+
+    #[test]
+    fn renames_are_detected_by_column_mapping_id() {
+        let field = |name: &str, id: i64, data_type: DataType| {
+            StructField::nullable(name, data_type)
+                .with_metadata([(ColumnMetadataKey::ColumnMappingId.as_ref(), id)])
+        };
+        let old = StructType::new([
+            field("a", 1, DataType::LONG),
+            field(
+                "s",
+                2,
+                StructType::new([field("x", 3, DataType::INTEGER)]).into(),
+            ),
+            // dropped, and a new column with the same name but a different id added
+            field("c", 4, DataType::STRING),
+        ]);
+        let new = StructType::new([
+            field("b", 1, DataType::LONG),
+            field(
+                "t",
+                2,
+                StructType::new([field("y", 3, DataType::LONG)]).into(),
+            ),
+            field("c", 5, DataType::STRING),
+        ]);
+        assert_eq!(
+            old.diff(&new),
+            vec![
+                RenameColumn(column_name!("a"), column_name!("b")),
+                RenameColumn(column_name!("s"), column_name!("t")),
+                RenameColumn(column_name!("s.x"), column_name!("t.y")),
+                ChangeType(column_name!("t.y"), DataType::INTEGER, DataType::LONG),
+                DropColumn(column_name!("c")),
+                AddColumn(column_name!("c"), DataType::STRING),
+            ]
+        );
+    }
+
+    #[test]
+    fn renames_without_column_mapping_are_drop_and_add() {
+        let old = StructType::new([StructField::nullable("a", DataType::LONG)]);
+        let new = StructType::new([StructField::nullable("b", DataType::LONG)]);
+        assert_eq!(
+            old.diff(&new),
+            vec![
+                DropColumn(column_name!("a")),
+                AddColumn(column_name!("b"), DataType::LONG),
+            ]
+        );
+    }
+}
@@ -0,0 +1,84 @@
+//! Formatting of Delta date and timestamp values to ISO-8601 strings, e.g. for engines rendering
+//! query results. Values are in their physical representation: days since the epoch for `DATE`,
+//! and microseconds since the epoch for `TIMESTAMP` and `TIMESTAMP_NTZ` (see
+//! [`PrimitiveType`]).
+//!
+//! [`PrimitiveType`]: super::PrimitiveType
+use chrono::{DateTime, TimeDelta};
+
+/// Format a timestamp given as microseconds since the epoch. A `TIMESTAMP` (`ntz == false`) is a
+/// UTC instant and is formatted with a `Z` suffix, e.g. `2024-01-02T03:04:05.123456Z`, while a
+/// `TIMESTAMP_NTZ` (`ntz == true`) has no time zone and is formatted without one, e.g.
+/// `2024-01-02T03:04:05.123456`. Fractional seconds are written with three or six digits as
+/// needed, and omitted when zero. Values outside the range chrono can represent are formatted as
+/// the plain number.
+pub fn format_timestamp_micros(micros: i64, ntz: bool) -> String {
+    let Some(timestamp) = DateTime::from_timestamp_micros(micros) else {
+        return micros.to_string();
+    };
+    let suffix = if ntz { "" } else { "Z" };
+    format!("{}{suffix}", timestamp.format("%Y-%m-%dT%H:%M:%S%.f"))
+}
+
+/// Format a date given as days since the epoch, e.g. `2024-01-02`. Values outside the range
+/// chrono can represent are formatted as the plain number.
+pub fn format_date_days(days: i32) -> String {
+    let date = DateTime::UNIX_EPOCH
+        .date_naive()
+        .checked_add_signed(TimeDelta::days(days.into()));
+    match date {
+        Some(date) => date.format("%Y-%m-%d").to_string(),
+        None => days.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_epoch() {
+        assert_eq!(format_timestamp_micros(0, false), "1970-01-01T00:00:00Z");
+        assert_eq!(format_timestamp_micros(0, true), "1970-01-01T00:00:00");
+        assert_eq!(format_date_days(0), "1970-01-01");
+    }
+
+    #[test]
+    fn test_format_negative_values() {
+        assert_eq!(format_date_days(-1), "1969-12-31");
+        assert_eq!(format_date_days(-719162), "0001-01-01");
+        assert_eq!(
+            format_timestamp_micros(-1, false),
+            "1969-12-31T23:59:59.999999Z"
+        );
+        // 1900-01-01T00:00:00
+        assert_eq!(
+            format_timestamp_micros(-2_208_988_800_000_000, true),
+            "1900-01-01T00:00:00"
+        );
+    }
+
+    #[test]
+    fn test_format_fractional_seconds() {
+        // 2024-01-02T03:04:05
+        let micros = 1_704_164_645_000_000;
+        assert_eq!(
+            format_timestamp_micros(micros + 123_456, false),
+            "2024-01-02T03:04:05.123456Z"
+        );
+        assert_eq!(
+            format_timestamp_micros(micros + 123_000, true),
+            "2024-01-02T03:04:05.123"
+        );
+        assert_eq!(format_date_days(19724), "2024-01-02");
+    }
+
+    #[test]
+    fn test_format_out_of_range() {
+        assert_eq!(
+            format_timestamp_micros(i64::MAX, false),
+            i64::MAX.to_string()
+        );
+        assert_eq!(format_date_days(i32::MIN), i32::MIN.to_string());
+    }
+}
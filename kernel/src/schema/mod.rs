@@ -10,12 +10,20 @@ use itertools::Itertools;
 use serde::{Deserialize, Serialize};
 
 // re-export because many call sites that use schemas do not necessarily use expressions
-pub(crate) use crate::expressions::{column_name, ColumnName};
+pub(crate) use crate::expressions::column_name;
+pub use crate::expressions::ColumnName;
+use crate::table_features::ColumnMappingMode;
 use crate::utils::require;
 use crate::{DeltaResult, Error};
 use delta_kernel_derive::internal_api;
 
 pub(crate) mod compare;
+pub mod compatibility;
+pub mod diff;
+pub mod format;
+
+/// Metadata key arrow uses to name the extension type of a field
+pub(crate) const ARROW_EXTENSION_NAME_KEY: &str = "ARROW:extension:name";
 
 pub type Schema = StructType;
 pub type SchemaRef = Arc<StructType>;
@@ -150,6 +158,24 @@ impl StructField {
         self.metadata.get(key.as_ref())
     }
 
+    /// Get the name of the arrow extension type (e.g. `arrow.uuid`) this field was annotated with,
+    /// if any.
+    pub fn arrow_extension_name(&self) -> Option<&str> {
+        match self.metadata.get(ARROW_EXTENSION_NAME_KEY) {
+            Some(MetadataValue::String(name)) => Some(name),
+            _ => None,
+        }
+    }
+
+    /// Get the SQL expression this column is generated by (`delta.generationExpression`), if it is
+    /// a generated column. The expression is returned as-is, without parsing it.
+    pub fn generation_expression(&self) -> Option<&str> {
+        match self.get_config_value(&ColumnMetadataKey::GenerationExpression) {
+            Some(MetadataValue::String(expression)) => Some(expression),
+            _ => None,
+        }
+    }
+
     /// Get the physical name for this field as it should be read from parquet.
     ///
     /// NOTE: Caller affirms that the schema was already validated by
@@ -211,18 +237,40 @@ impl StructField {
     /// [`crate::table_features::validate_schema_column_mapping`], to ensure that annotations are
     /// always and only present when column mapping mode is enabled.
     pub fn make_physical(&self) -> Self {
-        struct MakePhysical;
+        self.make_physical_impl(true)
+    }
+
+    /// Applies physical name mappings to this field for a table in `column_mapping_mode`. In
+    /// [`ColumnMappingMode::Id`] each field keeps its [`ColumnMetadataKey::ColumnMappingId`], which
+    /// is the parquet field id the parquet reader resolves the column by; in other modes the id is
+    /// dropped so columns resolve by name.
+    ///
+    /// NOTE: The same validation requirements as for [`Self::make_physical`] apply.
+    pub fn make_physical_with_mode(&self, column_mapping_mode: ColumnMappingMode) -> Self {
+        self.make_physical_impl(column_mapping_mode == ColumnMappingMode::Id)
+    }
+
+    fn make_physical_impl(&self, keep_field_ids: bool) -> Self {
+        struct MakePhysical {
+            keep_field_ids: bool,
+        }
         impl<'a> SchemaTransform<'a> for MakePhysical {
             fn transform_struct_field(
                 &mut self,
                 field: &'a StructField,
             ) -> Option<Cow<'a, StructField>> {
                 let field = self.recurse_into_struct_field(field)?;
-                Some(Cow::Owned(field.with_name(field.physical_name())))
+                let mut physical_field = field.with_name(field.physical_name());
+                if !self.keep_field_ids {
+                    physical_field
+                        .metadata
+                        .remove(ColumnMetadataKey::ColumnMappingId.as_ref());
+                }
+                Some(Cow::Owned(physical_field))
             }
         }
         // NOTE: unwrap is safe because the transformer is incapable of returning None
-        MakePhysical
+        MakePhysical { keep_field_ids }
             .transform_struct_field(self)
             .unwrap()
             .into_owned()
@@ -259,6 +307,20 @@ impl StructType {
         Ok(Self::new(fields))
     }
 
+    /// Parse a schema from its Delta JSON encoding, i.e. the `schemaString` of a `metaData`
+    /// action. See [Schema Serialization Format].
+    ///
+    /// [Schema Serialization Format]: https://github.com/delta-io/delta/blob/master/PROTOCOL.md#schema-serialization-format
+    pub fn from_delta_json(json: &str) -> DeltaResult<Self> {
+        Ok(serde_json::from_str(json)?)
+    }
+
+    /// Serialize this schema to its Delta JSON encoding, as used by the `schemaString` of a
+    /// `metaData` action. This is the inverse of [`StructType::from_delta_json`].
+    pub fn to_delta_json(&self) -> DeltaResult<String> {
+        Ok(serde_json::to_string(self)?)
+    }
+
     /// Get a [`StructType`] containing [`StructField`]s of the given names. The order of fields in
     /// the returned schema will match the order passed to this function, which can be different
     /// from this order in this schema. Returns an Err if a specified field doesn't exist.
@@ -280,6 +342,116 @@ impl StructType {
         Ok(Arc::new(struct_type))
     }
 
+    /// Get a [`StructType`] containing only the (possibly nested) columns at the given paths. Unlike
+    /// [`StructType::project`], fields keep the order they have in this schema, and a nested path
+    /// such as `a.b` keeps only `b` (and not its siblings) inside the parent struct `a`. A path to
+    /// a struct keeps the whole struct. Returns an Err if a specified column doesn't exist.
+    pub fn project_columns(&self, columns: &[ColumnName]) -> DeltaResult<StructType> {
+        let paths: Vec<_> = columns.iter().map(|column| column.path()).collect();
+        self.project_paths(&paths, &[])
+    }
+
+    // Keep the fields named by the first element of `paths`, recursing into structs for longer
+    // paths. `prefix` is the path to this struct, for error messages.
+    fn project_paths(&self, paths: &[&[String]], prefix: &[String]) -> DeltaResult<StructType> {
+        let full_name = |path: &[String]| ColumnName::new(prefix.iter().chain(path));
+        for path in paths {
+            match path.first() {
+                Some(name) if self.fields.contains_key(name) => {}
+                Some(_) => return Err(Error::missing_column(full_name(path))),
+                None => {
+                    return Err(Error::generic(format!(
+                        "Cannot project an empty path in struct {}",
+                        ColumnName::new(prefix)
+                    )))
+                }
+            }
+        }
+        let fields = self.fields().filter_map(|field| {
+            let children: Vec<&[String]> = paths
+                .iter()
+                .filter(|path| path[0] == field.name)
+                .map(|path| &path[1..])
+                .collect();
+            if children.is_empty() {
+                return None;
+            }
+            if children.iter().any(|child| child.is_empty()) {
+                return Some(Ok(field.clone()));
+            }
+            let field_path = [field.name.clone()];
+            let DataType::Struct(struct_type) = field.data_type() else {
+                return Some(Err(Error::generic(format!(
+                    "Cannot project nested columns of non-struct column {}",
+                    full_name(&field_path)
+                ))));
+            };
+            let prefix: Vec<_> = prefix.iter().chain(&field_path).cloned().collect();
+            let projected = struct_type.project_paths(&children, &prefix);
+            Some(projected.map(|projected| StructField {
+                data_type: DataType::Struct(Box::new(projected)),
+                ..field.clone()
+            }))
+        });
+        Self::try_new(fields)
+    }
+
+    /// Merge the schema of data being appended into this table schema, following Delta's schema
+    /// evolution rules, and return the schema to commit:
+    /// - Columns of this schema keep their position, nullability and metadata, and columns missing
+    ///   from `incoming` are kept as they are.
+    /// - New columns of `incoming` are added at the end of their struct, and must be nullable.
+    /// - When `allow_type_widening` is set, a column may change to a wider type (e.g. `int` to
+    ///   `long`). Any other type change, including narrowing, is an error naming the column.
+    pub fn merge(
+        &self,
+        incoming: &StructType,
+        allow_type_widening: bool,
+    ) -> DeltaResult<StructType> {
+        self.merge_at(incoming, allow_type_widening, &[])
+    }
+
+    // `prefix` is the path to this struct, for error messages.
+    fn merge_at(
+        &self,
+        incoming: &StructType,
+        allow_type_widening: bool,
+        prefix: &[String],
+    ) -> DeltaResult<StructType> {
+        let field_path = |name: &str| -> Vec<String> {
+            prefix.iter().cloned().chain([name.to_string()]).collect()
+        };
+        let merged = self.fields().map(|field| {
+            let Some(incoming_field) = incoming.field(&field.name) else {
+                return Ok(field.clone());
+            };
+            let data_type = merge_data_types(
+                field.data_type(),
+                incoming_field.data_type(),
+                allow_type_widening,
+                &field_path(&field.name),
+            )?;
+            Ok(StructField {
+                data_type,
+                ..field.clone()
+            })
+        });
+        let added = incoming
+            .fields()
+            .filter(|field| !self.fields.contains_key(&field.name))
+            .map(|field| {
+                require!(
+                    field.is_nullable(),
+                    Error::generic(format!(
+                        "Cannot add non-nullable column {} to the table schema",
+                        ColumnName::new(field_path(&field.name))
+                    ))
+                );
+                Ok(field.clone())
+            });
+        Self::try_new(merged.chain(added))
+    }
+
     pub fn field(&self, name: impl AsRef<str>) -> Option<&StructField> {
         self.fields.get(name.as_ref())
     }
@@ -292,6 +464,112 @@ impl StructType {
         self.fields.values()
     }
 
+    /// Resolve the (possibly nested) field at `path`, descending through struct fields. Returns
+    /// `None` if the path does not lead to a field.
+    ///
+    /// A path crosses into an array through an `element` name part, and into a map through a `key`
+    /// or `value` name part, matching the paths of [`Self::walk_leaves`]. For example,
+    /// `points.element.x` is the `x` field of the structs stored in the `points` array. Array
+    /// elements and map keys and values are not fields themselves, so a path ending at one (like
+    /// `points.element`) resolves to `None`. Dictionaries are crossed transparently.
+    pub fn field_by_path(&self, path: &ColumnName) -> Option<&StructField> {
+        self.field_by_name_parts(path.path())
+    }
+
+    fn field_by_name_parts(&self, parts: &[String]) -> Option<&StructField> {
+        let (name, mut rest) = parts.split_first()?;
+        let field = self.field(name)?;
+        if rest.is_empty() {
+            return Some(field);
+        }
+        // Once `rest` runs out inside an array or map, no arm below matches a field any more
+        let mut data_type = field.data_type();
+        loop {
+            data_type = match (data_type, rest) {
+                (DataType::Struct(inner), _) => return inner.field_by_name_parts(rest),
+                (DataType::Dictionary(dict), _) => &dict.value_type,
+                (DataType::Array(array), [part, tail @ ..]) if part == "element" => {
+                    rest = tail;
+                    array.element_type()
+                }
+                (DataType::Map(map), [part, tail @ ..]) if part == "key" => {
+                    rest = tail;
+                    map.key_type()
+                }
+                (DataType::Map(map), [part, tail @ ..]) if part == "value" => {
+                    rest = tail;
+                    map.value_type()
+                }
+                _ => return None,
+            };
+        }
+    }
+
+    /// Collect the paths and generation expressions of all generated columns in this schema,
+    /// including ones nested in structs, in schema order. See
+    /// [`StructField::generation_expression`].
+    pub fn generated_columns(&self) -> Vec<(ColumnName, &str)> {
+        fn collect<'a>(
+            schema: &'a StructType,
+            path: &mut Vec<String>,
+            generated: &mut Vec<(ColumnName, &'a str)>,
+        ) {
+            for field in schema.fields() {
+                path.push(field.name.clone());
+                if let Some(expression) = field.generation_expression() {
+                    generated.push((ColumnName::new(path.iter()), expression));
+                }
+                if let DataType::Struct(inner) = field.data_type() {
+                    collect(inner, path, generated);
+                }
+                path.pop();
+            }
+        }
+        let mut generated = vec![];
+        collect(self, &mut vec![], &mut generated);
+        generated
+    }
+
+    /// The number of leaf (primitive) columns in this schema, see [`Self::walk_leaves`].
+    pub fn leaf_count(&self) -> usize {
+        let mut count = 0;
+        self.walk_leaves(|_, _| count += 1);
+        count
+    }
+
+    /// Call `f` with the path and type of every leaf (primitive) column in this schema, in schema
+    /// order. Unlike [`Self::leaves`], this also descends into arrays and maps: the path of an
+    /// array's element gets an `element` name part, and a map's key and value get `key` and
+    /// `value` name parts. Dictionaries are walked as their value type.
+    pub fn walk_leaves(&self, f: impl FnMut(&ColumnName, &PrimitiveType)) {
+        let mut walker = WalkSchemaLeaves { path: vec![], f };
+        let _ = walker.transform_struct(self);
+    }
+
+    /// Compare two struct types by field names, types and nullability, recursively, ignoring
+    /// field metadata (comments, column mapping annotations, etc). As with `==`, fields are
+    /// matched by name. See [`DataType::structurally_equals`].
+    pub fn structurally_equals(&self, other: &StructType) -> bool {
+        self.structurally_eq(other, true)
+    }
+
+    /// Like [`Self::structurally_equals`], but also ignoring nullability.
+    pub fn structurally_equals_ignoring_nullability(&self, other: &StructType) -> bool {
+        self.structurally_eq(other, false)
+    }
+
+    fn structurally_eq(&self, other: &StructType, check_nullability: bool) -> bool {
+        self.fields.len() == other.fields.len()
+            && self.fields().all(|field| {
+                other.fields.get(&field.name).is_some_and(|other_field| {
+                    (!check_nullability || field.nullable == other_field.nullable)
+                        && field
+                            .data_type
+                            .structurally_eq(&other_field.data_type, check_nullability)
+                })
+            })
+    }
+
     pub(crate) fn fields_len(&self) -> usize {
         // O(1) for indexmap
         self.fields.len()
@@ -443,6 +721,10 @@ pub struct DictionaryType {
     /// Denoting whether this dictionary can contain one or more null values
     #[serde(default = "default_true")]
     pub value_contains_null: bool,
+    /// Denoting whether the values of this dictionary are sorted, so that comparing indices is
+    /// equivalent to comparing values
+    #[serde(default)]
+    ordered: bool,
 }
 
 impl DictionaryType {
@@ -456,9 +738,16 @@ impl DictionaryType {
             index_type: index_type.into(),
             value_type: value_type.into(),
             value_contains_null,
+            ordered: false,
         }
     }
 
+    /// Set whether the values of this dictionary are ordered
+    pub fn with_ordered(mut self, ordered: bool) -> Self {
+        self.ordered = ordered;
+        self
+    }
+
     #[inline]
     pub const fn key_type(&self) -> &DataType {
         &self.index_type
@@ -473,6 +762,11 @@ impl DictionaryType {
     pub const fn value_contains_null(&self) -> bool {
         self.value_contains_null
     }
+
+    #[inline]
+    pub const fn is_ordered(&self) -> bool {
+        self.ordered
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Eq)]
@@ -775,12 +1069,114 @@ impl DataType {
         Ok(StructType::try_new(fields)?.into())
     }
 
+    /// True for types that contain other types: structs, arrays and maps (and dictionaries of
+    /// those).
+    pub fn is_nested(&self) -> bool {
+        match self {
+            DataType::Primitive(_) => false,
+            DataType::Array(_) | DataType::Struct(_) | DataType::Map(_) => true,
+            DataType::Dictionary(dtype) => dtype.value_type.is_nested(),
+        }
+    }
+
     pub fn as_primitive_opt(&self) -> Option<&PrimitiveType> {
         match self {
             DataType::Primitive(ptype) => Some(ptype),
             _ => None,
         }
     }
+
+    /// Compare two data types by field names, types and nullability, recursively, ignoring the
+    /// metadata of any nested struct fields. Unlike `==`, this treats two schemas that only differ
+    /// in e.g. column comments or column mapping ids as equal.
+    pub fn structurally_equals(&self, other: &DataType) -> bool {
+        self.structurally_eq(other, true)
+    }
+
+    /// Like [`Self::structurally_equals`], but also ignoring the nullability of struct fields,
+    /// array elements, map values and dictionary values.
+    pub fn structurally_equals_ignoring_nullability(&self, other: &DataType) -> bool {
+        self.structurally_eq(other, false)
+    }
+
+    fn structurally_eq(&self, other: &DataType, check_nullability: bool) -> bool {
+        let nullability_eq = |a: bool, b: bool| !check_nullability || a == b;
+        match (self, other) {
+            (DataType::Primitive(a), DataType::Primitive(b)) => a == b,
+            (DataType::Struct(a), DataType::Struct(b)) => a.structurally_eq(b, check_nullability),
+            (DataType::Array(a), DataType::Array(b)) => {
+                nullability_eq(a.contains_null, b.contains_null)
+                    && a.element_type
+                        .structurally_eq(&b.element_type, check_nullability)
+            }
+            (DataType::Map(a), DataType::Map(b)) => {
+                nullability_eq(a.value_contains_null, b.value_contains_null)
+                    && a.key_type.structurally_eq(&b.key_type, check_nullability)
+                    && a.value_type
+                        .structurally_eq(&b.value_type, check_nullability)
+            }
+            (DataType::Dictionary(a), DataType::Dictionary(b)) => {
+                nullability_eq(a.value_contains_null, b.value_contains_null)
+                    && a.ordered == b.ordered
+                    && a.index_type
+                        .structurally_eq(&b.index_type, check_nullability)
+                    && a.value_type
+                        .structurally_eq(&b.value_type, check_nullability)
+            }
+            _ => false,
+        }
+    }
+}
+
+// Merge the type of an incoming column into the type of a table column at `path`, see
+// [`StructType::merge`]. Nested nullability (array elements and map values) is kept from the table.
+fn merge_data_types(
+    current: &DataType,
+    incoming: &DataType,
+    allow_type_widening: bool,
+    path: &[String],
+) -> DeltaResult<DataType> {
+    let child_path =
+        |name: &str| -> Vec<String> { path.iter().cloned().chain([name.to_string()]).collect() };
+    match (current, incoming) {
+        (DataType::Struct(current), DataType::Struct(incoming)) => Ok(current
+            .merge_at(incoming, allow_type_widening, path)?
+            .into()),
+        (DataType::Array(current), DataType::Array(incoming)) => {
+            let element_type = merge_data_types(
+                current.element_type(),
+                incoming.element_type(),
+                allow_type_widening,
+                &child_path("element"),
+            )?;
+            Ok(ArrayType::new(element_type, current.contains_null()).into())
+        }
+        (DataType::Map(current), DataType::Map(incoming)) => {
+            let key_type = merge_data_types(
+                current.key_type(),
+                incoming.key_type(),
+                allow_type_widening,
+                &child_path("key"),
+            )?;
+            let value_type = merge_data_types(
+                current.value_type(),
+                incoming.value_type(),
+                allow_type_widening,
+                &child_path("value"),
+            )?;
+            Ok(MapType::new(key_type, value_type, current.value_contains_null()).into())
+        }
+        (DataType::Primitive(from), DataType::Primitive(to))
+            if allow_type_widening && compatibility::can_widen(from, to) =>
+        {
+            Ok(incoming.clone())
+        }
+        _ if current == incoming => Ok(current.clone()),
+        _ => Err(Error::generic(format!(
+            "Cannot merge column {} of type {current} with incoming type {incoming}",
+            ColumnName::new(path)
+        ))),
+    }
 }
 
 impl Display for DataType {
@@ -885,6 +1281,7 @@ pub trait SchemaTransform<'a> {
                     index_type: key_type.into_owned(),
                     value_type: value_type.into_owned(),
                     value_contains_null: dtype.value_contains_null,
+                    ordered: dtype.ordered,
                 })
             })
     }
@@ -1023,6 +1420,56 @@ impl<'a> SchemaTransform<'a> for GetSchemaLeaves {
     }
 }
 
+/// Visits every leaf of a schema, including the ones nested in arrays and maps, see
+/// [`StructType::walk_leaves`].
+struct WalkSchemaLeaves<F> {
+    path: Vec<String>,
+    f: F,
+}
+
+impl<F> WalkSchemaLeaves<F> {
+    fn with_name_part<'a, T>(
+        &mut self,
+        name: &str,
+        walk: impl FnOnce(&mut Self) -> Option<Cow<'a, T>>,
+    ) -> Option<Cow<'a, T>>
+    where
+        T: Clone,
+    {
+        self.path.push(name.to_string());
+        let result = walk(self);
+        self.path.pop();
+        result
+    }
+}
+
+impl<'a, F: FnMut(&ColumnName, &PrimitiveType)> SchemaTransform<'a> for WalkSchemaLeaves<F> {
+    fn transform_primitive(&mut self, ptype: &'a PrimitiveType) -> Option<Cow<'a, PrimitiveType>> {
+        (self.f)(&ColumnName::new(&self.path), ptype);
+        Some(Cow::Borrowed(ptype))
+    }
+    fn transform_struct_field(&mut self, field: &'a StructField) -> Option<Cow<'a, StructField>> {
+        self.with_name_part(&field.name, |this| this.recurse_into_struct_field(field))
+    }
+    fn transform_array_element(&mut self, etype: &'a DataType) -> Option<Cow<'a, DataType>> {
+        self.with_name_part("element", |this| this.transform(etype))
+    }
+    fn transform_map_key(&mut self, etype: &'a DataType) -> Option<Cow<'a, DataType>> {
+        self.with_name_part("key", |this| this.transform(etype))
+    }
+    fn transform_map_value(&mut self, etype: &'a DataType) -> Option<Cow<'a, DataType>> {
+        self.with_name_part("value", |this| this.transform(etype))
+    }
+    fn transform_dictionary(
+        &mut self,
+        dtype: &'a DictionaryType,
+    ) -> Option<Cow<'a, DictionaryType>> {
+        // the indices are an encoding detail, only the values are a leaf of the schema
+        self.transform(&dtype.value_type)?;
+        Some(Cow::Borrowed(dtype))
+    }
+}
+
 /// A schema "transform" that doesn't actually change the schema at all. Instead, it measures the
 /// maximum depth of a schema, with a depth limit to prevent stack overflow. Useful for verifying
 /// that a schema has reasonable depth before attempting to work with it.
@@ -1093,6 +1540,498 @@ mod tests {
     use super::*;
     use serde_json;
 
+    #[test]
+    fn test_structurally_equals() {
+        let schema = |comment: &str, id: i64, inner_nullable: bool| {
+            StructType::new([
+                StructField::not_null("id", DataType::LONG)
+                    .with_metadata([("comment", comment)])
+                    .with_metadata([(ColumnMetadataKey::ColumnMappingId.as_ref(), id)]),
+                StructField::nullable(
+                    "nested",
+                    StructType::new([
+                        StructField::new("x", DataType::INTEGER, inner_nullable)
+                            .with_metadata([("comment", comment)]),
+                        StructField::nullable(
+                            "tags",
+                            MapType::new(DataType::STRING, DataType::STRING, true),
+                        ),
+                    ]),
+                ),
+            ])
+        };
+
+        // identical except for metadata
+        let a = schema("first", 1, true);
+        let b = schema("second", 2, true);
+        assert_ne!(a, b);
+        assert!(a.structurally_equals(&b));
+        assert!(a.structurally_equals_ignoring_nullability(&b));
+        let (a, b) = (DataType::from(a), DataType::from(b));
+        assert!(a.structurally_equals(&b));
+
+        // a nested field differs in nullability
+        let a = schema("first", 1, true);
+        let b = schema("first", 1, false);
+        assert!(!a.structurally_equals(&b));
+        assert!(a.structurally_equals_ignoring_nullability(&b));
+
+        // different types and field names are never equal
+        let c = StructType::new([StructField::not_null("id", DataType::INTEGER)]);
+        let d = StructType::new([StructField::not_null("id", DataType::LONG)]);
+        let e = StructType::new([StructField::not_null("other", DataType::LONG)]);
+        assert!(!c.structurally_equals_ignoring_nullability(&d));
+        assert!(!d.structurally_equals_ignoring_nullability(&e));
+        assert!(!a.structurally_equals_ignoring_nullability(&d));
+    }
+
+    #[test]
+    fn test_merge() {
+        let table = StructType::new([
+            StructField::not_null("id", DataType::INTEGER)
+                .with_metadata([("comment", MetadataValue::from("key"))]),
+            StructField::nullable(
+                "a",
+                StructType::new([StructField::nullable("b", DataType::FLOAT)]),
+            ),
+            StructField::nullable("value", DataType::STRING),
+        ]);
+
+        // new nullable columns are appended to their struct, and missing columns are kept
+        let incoming = StructType::new([
+            StructField::nullable("extra", DataType::LONG),
+            StructField::nullable("id", DataType::INTEGER),
+            StructField::nullable(
+                "a",
+                StructType::new([
+                    StructField::nullable("c", DataType::STRING),
+                    StructField::nullable("b", DataType::FLOAT),
+                ]),
+            ),
+        ]);
+        let merged = table.merge(&incoming, false).unwrap();
+        let expected = StructType::new([
+            table.field("id").unwrap().clone(),
+            StructField::nullable(
+                "a",
+                StructType::new([
+                    StructField::nullable("b", DataType::FLOAT),
+                    StructField::nullable("c", DataType::STRING),
+                ]),
+            ),
+            StructField::nullable("value", DataType::STRING),
+            StructField::nullable("extra", DataType::LONG),
+        ]);
+        assert_eq!(merged, expected);
+
+        // widening is only allowed when enabled
+        let incoming = StructType::new([
+            StructField::not_null("id", DataType::LONG),
+            StructField::nullable(
+                "a",
+                StructType::new([StructField::nullable("b", DataType::DOUBLE)]),
+            ),
+        ]);
+        let merged = table.merge(&incoming, true).unwrap();
+        let expected = StructType::new([
+            StructField::not_null("id", DataType::LONG)
+                .with_metadata([("comment", MetadataValue::from("key"))]),
+            StructField::nullable(
+                "a",
+                StructType::new([StructField::nullable("b", DataType::DOUBLE)]),
+            ),
+            StructField::nullable("value", DataType::STRING),
+        ]);
+        assert_eq!(merged, expected);
+        let err = table.merge(&incoming, false).unwrap_err().to_string();
+        assert!(
+            err.contains("Cannot merge column id of type integer"),
+            "{err}"
+        );
+
+        // narrowing is never allowed, and the error names the nested column
+        let incoming = StructType::new([StructField::nullable(
+            "a",
+            StructType::new([StructField::nullable("b", DataType::SHORT)]),
+        )]);
+        let err = table.merge(&incoming, true).unwrap_err().to_string();
+        assert!(
+            err.contains("Cannot merge column a.b of type float"),
+            "{err}"
+        );
+
+        let incoming = StructType::new([StructField::nullable("value", DataType::INTEGER)]);
+        assert!(table.merge(&incoming, true).is_err());
+
+        let incoming = StructType::new([StructField::not_null("extra", DataType::LONG)]);
+        let err = table.merge(&incoming, false).unwrap_err().to_string();
+        assert!(
+            err.contains("Cannot add non-nullable column extra"),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn test_project_columns() {
+        let schema = StructType::new([
+            StructField::nullable("id", DataType::LONG),
+            StructField::nullable(
+                "a",
+                StructType::new([
+                    StructField::not_null("b", DataType::STRING)
+                        .with_metadata([("comment", MetadataValue::from("nested"))]),
+                    StructField::nullable("c", DataType::INTEGER),
+                    StructField::nullable(
+                        "d",
+                        StructType::new([StructField::nullable("e", DataType::LONG)]),
+                    ),
+                ]),
+            ),
+            StructField::not_null("value", DataType::STRING),
+        ]);
+
+        // a field whose name contains a dot is addressed by a single backtick-quoted segment
+        let dotted = StructType::new([
+            StructField::nullable("a.b", DataType::LONG),
+            StructField::nullable(
+                "a",
+                StructType::new([StructField::nullable("b", DataType::INTEGER)]),
+            ),
+        ]);
+        let projected = dotted.project_columns(&["`a.b`".parse().unwrap()]).unwrap();
+        assert_eq!(
+            projected,
+            StructType::new([StructField::nullable("a.b", DataType::LONG)])
+        );
+
+        // top-level pruning keeps schema order, not the requested order
+        let projected = schema
+            .project_columns(&[column_name!("value"), column_name!("id")])
+            .unwrap();
+        let expected = StructType::new([
+            StructField::nullable("id", DataType::LONG),
+            StructField::not_null("value", DataType::STRING),
+        ]);
+        assert_eq!(projected, expected);
+
+        // selecting a.b drops its sibling a.c, and a path to a struct keeps the whole struct
+        let projected = schema
+            .project_columns(&[column_name!("a.d"), column_name!("a.b")])
+            .unwrap();
+        let DataType::Struct(a) = schema.field("a").unwrap().data_type() else {
+            panic!("a should be a struct");
+        };
+        let expected = StructType::new([StructField::nullable(
+            "a",
+            StructType::new([a.field("b").unwrap().clone(), a.field("d").unwrap().clone()]),
+        )]);
+        assert_eq!(projected, expected);
+
+        // a column and one of its children select the whole column
+        let projected = schema
+            .project_columns(&[column_name!("a.b"), column_name!("a")])
+            .unwrap();
+        assert_eq!(
+            projected,
+            StructType::new([schema.field("a").unwrap().clone()])
+        );
+
+        // invalid paths are errors
+        let err = schema.project_columns(&[column_name!("a.x")]).unwrap_err();
+        assert!(err.to_string().contains("a.x"), "{err}");
+        let err = schema
+            .project_columns(&[column_name!("missing")])
+            .unwrap_err();
+        assert!(err.to_string().contains("missing"), "{err}");
+        assert!(schema.project_columns(&[column_name!("id.x")]).is_err());
+        assert!(schema
+            .project_columns(&[ColumnName::new::<&str>([])])
+            .is_err());
+    }
+
+    #[test]
+    fn test_generated_columns() {
+        let generated = |name: &str, data_type: DataType, expression: &str| {
+            StructField::nullable(name, data_type).with_metadata([(
+                ColumnMetadataKey::GenerationExpression.as_ref(),
+                MetadataValue::String(expression.into()),
+            )])
+        };
+        let schema = StructType::new([
+            StructField::nullable("ts", DataType::TIMESTAMP),
+            generated("date", DataType::DATE, "CAST(ts AS DATE)"),
+            StructField::nullable(
+                "nested",
+                StructType::new([
+                    StructField::nullable("a", DataType::LONG),
+                    generated("b", DataType::LONG, "nested.a * 2"),
+                ]),
+            ),
+        ]);
+
+        assert_eq!(
+            schema.field("date").unwrap().generation_expression(),
+            Some("CAST(ts AS DATE)")
+        );
+        assert_eq!(schema.field("ts").unwrap().generation_expression(), None);
+        assert_eq!(
+            schema.generated_columns(),
+            vec![
+                (column_name!("date"), "CAST(ts AS DATE)"),
+                (column_name!("nested.b"), "nested.a * 2"),
+            ]
+        );
+        assert!(
+            StructType::new([StructField::nullable("a", DataType::LONG)])
+                .generated_columns()
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_walk_leaves() {
+        let point = StructType::new([
+            StructField::nullable("x", DataType::DOUBLE),
+            StructField::nullable("y", DataType::DOUBLE),
+        ]);
+        let schema = StructType::new([
+            StructField::nullable("id", DataType::LONG),
+            StructField::nullable(
+                "shape",
+                StructType::new([
+                    StructField::nullable("name", DataType::STRING),
+                    StructField::nullable("points", ArrayType::new(point.into(), true)),
+                ]),
+            ),
+            StructField::nullable(
+                "tags",
+                MapType::new(
+                    DataType::STRING,
+                    ArrayType::new(DataType::INTEGER, false),
+                    true,
+                ),
+            ),
+        ]);
+        let mut leaves = vec![];
+        schema.walk_leaves(|path, ptype| leaves.push((path.to_string(), ptype.clone())));
+        assert_eq!(
+            leaves,
+            [
+                ("id", PrimitiveType::Long),
+                ("shape.name", PrimitiveType::String),
+                ("shape.points.element.x", PrimitiveType::Double),
+                ("shape.points.element.y", PrimitiveType::Double),
+                ("tags.key", PrimitiveType::String),
+                ("tags.value.element", PrimitiveType::Integer),
+            ]
+            .map(|(path, ptype)| (path.to_string(), ptype))
+        );
+        assert_eq!(schema.leaf_count(), 6);
+
+        assert!(!DataType::LONG.is_nested());
+        for field in schema.fields().skip(1) {
+            assert!(field.data_type().is_nested());
+        }
+    }
+
+    #[test]
+    fn test_field_by_path() {
+        let point = StructType::new([
+            StructField::nullable("x", DataType::DOUBLE),
+            StructField::nullable("y", DataType::DOUBLE),
+        ]);
+        let schema = StructType::new([
+            StructField::nullable("id", DataType::LONG),
+            StructField::nullable(
+                "a",
+                StructType::new([StructField::nullable(
+                    "b",
+                    StructType::new([StructField::not_null("c", DataType::STRING)]),
+                )]),
+            ),
+            StructField::nullable("points", ArrayType::new(point.clone().into(), true)),
+            StructField::nullable(
+                "by_name",
+                MapType::new(DataType::STRING, point.clone(), true),
+            ),
+        ]);
+        let field = |path: ColumnName| schema.field_by_path(&path);
+
+        assert_eq!(
+            field(column_name!("id")),
+            Some(&StructField::nullable("id", DataType::LONG))
+        );
+        assert_eq!(
+            field(column_name!("a.b.c")),
+            Some(&StructField::not_null("c", DataType::STRING))
+        );
+        assert_eq!(field(column_name!("a.b")).unwrap().name(), "b");
+        let x = Some(&StructField::nullable("x", DataType::DOUBLE));
+        assert_eq!(field(column_name!("points.element.x")), x);
+        assert_eq!(field(column_name!("by_name.value.x")), x);
+
+        // array elements and map keys and values are not fields
+        assert_eq!(field(column_name!("points.element")), None);
+        assert_eq!(field(column_name!("by_name.key")), None);
+        // arrays and maps are only crossed through their element, key or value
+        assert_eq!(field(column_name!("points.x")), None);
+        assert_eq!(field(column_name!("by_name.element.x")), None);
+        // nonexistent fields
+        assert_eq!(field(column_name!("a.b.d")), None);
+        assert_eq!(field(column_name!("a.b.c.d")), None);
+        assert_eq!(field(column_name!("missing")), None);
+        assert_eq!(field(ColumnName::new::<&str>([])), None);
+    }
+
+    #[test]
+    fn test_delta_json_roundtrip() {
+        let data = r#"{
+            "type": "struct",
+            "fields": [
+                {
+                    "name": "id",
+                    "type": "long",
+                    "nullable": false,
+                    "metadata": { "delta.columnMapping.id": 1, "comment": "primary key" }
+                },
+                {
+                    "name": "price",
+                    "type": "decimal(10,2)",
+                    "nullable": true,
+                    "metadata": {}
+                },
+                {
+                    "name": "tags",
+                    "type": { "type": "array", "elementType": "string", "containsNull": false },
+                    "nullable": true,
+                    "metadata": {}
+                },
+                {
+                    "name": "attrs",
+                    "type": {
+                        "type": "map",
+                        "keyType": "string",
+                        "valueType": {
+                            "type": "array",
+                            "elementType": "decimal(38,18)",
+                            "containsNull": true
+                        },
+                        "valueContainsNull": true
+                    },
+                    "nullable": true,
+                    "metadata": {}
+                },
+                {
+                    "name": "nested",
+                    "type": {
+                        "type": "struct",
+                        "fields": [
+                            { "name": "a", "type": "integer", "nullable": true, "metadata": {} },
+                            {
+                                "name": "b",
+                                "type": {
+                                    "type": "map",
+                                    "keyType": "string",
+                                    "valueType": "date",
+                                    "valueContainsNull": false
+                                },
+                                "nullable": false,
+                                "metadata": {}
+                            }
+                        ]
+                    },
+                    "nullable": true,
+                    "metadata": {}
+                }
+            ]
+        }"#;
+        let schema = StructType::from_delta_json(data).unwrap();
+
+        let expected = StructType::new([
+            StructField::not_null("id", DataType::LONG).with_metadata([
+                (
+                    ColumnMetadataKey::ColumnMappingId.as_ref(),
+                    MetadataValue::Number(1),
+                ),
+                ("comment", MetadataValue::String("primary key".into())),
+            ]),
+            StructField::nullable("price", DecimalType::try_new(10, 2).unwrap()),
+            StructField::nullable("tags", ArrayType::new(DataType::STRING, false)),
+            StructField::nullable(
+                "attrs",
+                MapType::new(
+                    DataType::STRING,
+                    ArrayType::new(DecimalType::try_new(38, 18).unwrap().into(), true),
+                    true,
+                ),
+            ),
+            StructField::nullable(
+                "nested",
+                StructType::new([
+                    StructField::nullable("a", DataType::INTEGER),
+                    StructField::not_null(
+                        "b",
+                        MapType::new(DataType::STRING, DataType::DATE, false),
+                    ),
+                ]),
+            ),
+        ]);
+        assert_eq!(schema, expected);
+
+        // serializing and parsing again must give back the same schema, and the json must use
+        // the delta encoding of nullability and metadata
+        let json = schema.to_delta_json().unwrap();
+        assert_eq!(StructType::from_delta_json(&json).unwrap(), schema);
+        let json: serde_json::Value = serde_json::from_str(&json).unwrap();
+        let original: serde_json::Value = serde_json::from_str(data).unwrap();
+        assert_eq!(json, original);
+
+        assert!(StructType::from_delta_json(r#"{"type": "struct"}"#).is_err());
+    }
+
+    #[test]
+    fn test_delta_json_nested_field_metadata() {
+        let id = |id: i64| [(ColumnMetadataKey::ColumnMappingId.as_ref(), id)];
+        // a struct in a map value in a struct in an array, with metadata at every level
+        let leaf = StructField::nullable("leaf", DataType::LONG).with_metadata(id(4));
+        let inner = StructType::new([leaf]);
+        let middle = StructField::nullable("middle", MapType::new(DataType::STRING, inner, true))
+            .with_metadata(id(3));
+        let element = StructType::new([middle]);
+        let schema =
+            StructType::new([
+                StructField::nullable("outer", ArrayType::new(element.into(), true))
+                    .with_metadata(id(2)),
+            ]);
+
+        let json = schema.to_delta_json().unwrap();
+        let parsed = StructType::from_delta_json(&json).unwrap();
+        assert_eq!(parsed, schema);
+
+        let column = ColumnName::new(["outer", "element", "middle", "value", "leaf"]);
+        let leaf = parsed.field_by_path(&column).unwrap();
+        assert_eq!(
+            leaf.get_config_value(&ColumnMetadataKey::ColumnMappingId),
+            Some(&MetadataValue::Number(4))
+        );
+        let column = ColumnName::new(["outer", "element", "middle"]);
+        let middle = parsed.field_by_path(&column).unwrap();
+        assert_eq!(
+            middle.get_config_value(&ColumnMetadataKey::ColumnMappingId),
+            Some(&MetadataValue::Number(3))
+        );
+
+        // column mapping picks up the nested ids
+        let outer = parsed.field("outer").unwrap();
+        let physical = StructType::new([outer.make_physical_with_mode(ColumnMappingMode::Id)]);
+        let column = ColumnName::new(["outer", "element", "middle", "value", "leaf"]);
+        let leaf = physical.field_by_path(&column).unwrap();
+        assert_eq!(
+            leaf.get_config_value(&ColumnMetadataKey::ColumnMappingId),
+            Some(&MetadataValue::Number(4))
+        );
+    }
+
     #[test]
     fn test_serde_data_types() {
         let data = r#"
@@ -1233,11 +2172,14 @@ mod tests {
             field.physical_name(),
             "col-5f422f40-de70-45b2-88ab-1d5c90e94db1"
         );
-        let physical_field = field.make_physical();
+        let physical_field = field.make_physical_with_mode(ColumnMappingMode::Name);
         assert_eq!(
             physical_field.name,
             "col-5f422f40-de70-45b2-88ab-1d5c90e94db1"
         );
+        assert!(physical_field
+            .get_config_value(&ColumnMetadataKey::ColumnMappingId)
+            .is_none());
         let DataType::Array(atype) = physical_field.data_type else {
             panic!("Expected an Array");
         };
@@ -1248,6 +2190,36 @@ mod tests {
             stype.fields.get_index(0).unwrap().1.name,
             "col-a7f4159c-53be-4cb0-b81a-f7e5240cfc49"
         );
+
+        // without a mode the metadata is kept as is
+        assert!(matches!(
+            field
+                .make_physical()
+                .get_config_value(&ColumnMetadataKey::ColumnMappingId),
+            Some(MetadataValue::Number(4))
+        ));
+
+        // id mode keeps the field ids, including those of nested fields
+        let physical_field = field.make_physical_with_mode(ColumnMappingMode::Id);
+        assert!(matches!(
+            physical_field.get_config_value(&ColumnMetadataKey::ColumnMappingId),
+            Some(MetadataValue::Number(4))
+        ));
+        let DataType::Array(atype) = physical_field.data_type else {
+            panic!("Expected an Array");
+        };
+        let DataType::Struct(stype) = atype.element_type else {
+            panic!("Expected a Struct");
+        };
+        assert!(matches!(
+            stype
+                .fields
+                .get_index(0)
+                .unwrap()
+                .1
+                .get_config_value(&ColumnMetadataKey::ColumnMappingId),
+            Some(MetadataValue::Number(5))
+        ));
     }
 
     #[test]
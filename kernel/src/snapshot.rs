@@ -1,33 +1,127 @@
 //! In-memory representation of snapshots of tables (snapshot is a table at given point in time, it
 //! has schema etc.)
 
-use std::sync::Arc;
+use std::collections::HashMap;
+use std::sync::{Arc, LazyLock, OnceLock};
 
+use crate::actions::domain_metadata::scan_domain_metadata;
 use crate::actions::set_transaction::SetTransactionScanner;
-use crate::actions::{Metadata, Protocol};
+use crate::actions::visitors::InCommitTimestampVisitor;
+use crate::actions::{
+    get_log_commit_info_schema, get_log_schema, Metadata, Protocol, ADD_NAME, REMOVE_NAME,
+    SIDECAR_NAME,
+};
+use crate::engine_data::{GetData, RowVisitor, TypedGetData as _};
+use crate::expressions::column_name;
 use crate::log_segment::{self, LogSegment};
+use crate::path::ParsedLogPath;
+use crate::scan::log_replay::scan_action_iter;
+use crate::scan::state::resolve_file_path;
 use crate::scan::ScanBuilder;
-use crate::schema::{Schema, SchemaRef};
+use crate::schema::{
+    ColumnName, ColumnNamesAndTypes, DataType, MapType, Schema, SchemaRef, StructType,
+};
 use crate::table_configuration::TableConfiguration;
-use crate::table_features::ColumnMappingMode;
+use crate::table_features::{ColumnMappingMode, TableFeature, WriterFeature};
 use crate::table_properties::TableProperties;
-use crate::{DeltaResult, Engine, Error, StorageHandler, Version};
+use crate::transaction::Transaction;
+use crate::utils::require;
+use crate::{DeltaResult, Engine, Error, FileMeta, StorageHandler, Version};
 use delta_kernel_derive::internal_api;
 
+use itertools::Itertools;
 use serde::{Deserialize, Serialize};
 use tracing::{debug, warn};
 use url::Url;
 
-const LAST_CHECKPOINT_FILE_NAME: &str = "_last_checkpoint";
+pub(crate) const LAST_CHECKPOINT_FILE_NAME: &str = "_last_checkpoint";
+/// The metadata domain in which liquid clustering records the clustering columns
+const CLUSTERING_DOMAIN_NAME: &str = "delta.clustering";
 // TODO expose methods for accessing the files of a table (with file pruning).
 /// In-memory representation of a specific snapshot of a Delta table. While a `DeltaTable` exists
 /// throughout time, `Snapshot`s represent a view of a table at a specific point in time; they
 /// have a defined schema (which may change over time for any given table), specific version, and
 /// frozen log segment.
-#[derive(PartialEq, Eq)]
+#[derive(Clone)]
 pub struct Snapshot {
     log_segment: LogSegment,
     table_configuration: TableConfiguration,
+    /// Read on first use by [`Snapshot::in_commit_timestamp`]
+    in_commit_timestamp: OnceLock<Option<i64>>,
+    /// Replayed on first use by [`Snapshot::domain_metadata`] and [`Snapshot::domains`]
+    domain_metadata: OnceLock<HashMap<String, String>>,
+}
+
+/// A data file that is part of a table at some [`Snapshot`]'s version, as returned by
+/// [`Snapshot::active_files`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ActiveFile {
+    /// The location, size and modification time of the file
+    pub file: FileMeta,
+    /// The raw partition values of the file, keyed by physical partition column name. Null
+    /// partition values are omitted.
+    pub partition_values: HashMap<String, String>,
+}
+
+/// Collects the [`ActiveFile`]s of the selected rows of a batch of scan files
+struct ActiveFileVisitor<'a> {
+    table_root: &'a Url,
+    selection_vector: &'a [bool],
+    files: Vec<ActiveFile>,
+}
+
+impl RowVisitor for ActiveFileVisitor<'_> {
+    fn selected_column_names_and_types(&self) -> (&'static [ColumnName], &'static [DataType]) {
+        static NAMES_AND_TYPES: LazyLock<ColumnNamesAndTypes> = LazyLock::new(|| {
+            let partition_values = MapType::new(DataType::STRING, DataType::STRING, true);
+            let types_and_names = vec![
+                (DataType::STRING, column_name!("path")),
+                (DataType::LONG, column_name!("size")),
+                (DataType::LONG, column_name!("modificationTime")),
+                (
+                    partition_values.into(),
+                    column_name!("fileConstantValues.partitionValues"),
+                ),
+            ];
+            let (types, names) = types_and_names.into_iter().unzip();
+            (names, types).into()
+        });
+        NAMES_AND_TYPES.as_ref()
+    }
+
+    fn visit<'a>(&mut self, row_count: usize, getters: &[&'a dyn GetData<'a>]) -> DeltaResult<()> {
+        require!(
+            getters.len() == 4,
+            Error::InternalError(format!(
+                "Wrong number of ActiveFileVisitor getters: {}",
+                getters.len()
+            ))
+        );
+        for i in 0..row_count {
+            if !self.selection_vector.get(i).copied().unwrap_or(true) {
+                continue;
+            }
+            // Since path column is required, use it to detect presence of an Add action
+            let Some(path): Option<String> = getters[0].get_opt(i, "scanFile.path")? else {
+                continue;
+            };
+            let size: i64 = getters[1].get(i, "scanFile.size")?;
+            let file = FileMeta {
+                location: resolve_file_path(self.table_root, &path)?,
+                last_modified: getters[2].get(i, "scanFile.modificationTime")?,
+                size: size.try_into().map_err(|_| {
+                    Error::generic(format!("Unable to convert size {size} of {path} to u64"))
+                })?,
+            };
+            let partition_values =
+                getters[3].get(i, "scanFile.fileConstantValues.partitionValues")?;
+            self.files.push(ActiveFile {
+                file,
+                partition_values,
+            });
+        }
+        Ok(())
+    }
 }
 
 impl Drop for Snapshot {
@@ -46,11 +140,23 @@ impl std::fmt::Debug for Snapshot {
     }
 }
 
+// Lazily read state is derived from the log segment, so it does not take part in equality
+impl PartialEq for Snapshot {
+    fn eq(&self, other: &Self) -> bool {
+        self.log_segment == other.log_segment
+            && self.table_configuration == other.table_configuration
+    }
+}
+
+impl Eq for Snapshot {}
+
 impl Snapshot {
     fn new(log_segment: LogSegment, table_configuration: TableConfiguration) -> Self {
         Self {
             log_segment,
             table_configuration,
+            in_commit_timestamp: OnceLock::new(),
+            domain_metadata: OnceLock::new(),
         }
     }
 
@@ -67,9 +173,19 @@ impl Snapshot {
         engine: &dyn Engine,
         version: Option<Version>,
     ) -> DeltaResult<Self> {
-        let storage = engine.storage_handler();
         let log_root = table_root.join("_delta_log/")?;
+        Self::try_new_with_log_root(table_root, log_root, engine, version)
+    }
 
+    /// Like [`Snapshot::try_new`], but reading the log from `log_root` instead of the `_delta_log`
+    /// directory under `table_root`. Data file paths still resolve against `table_root`.
+    pub(crate) fn try_new_with_log_root(
+        table_root: Url,
+        log_root: Url,
+        engine: &dyn Engine,
+        version: Option<Version>,
+    ) -> DeltaResult<Self> {
+        let storage = engine.storage_handler();
         let checkpoint_hint = read_last_checkpoint(storage.as_ref(), &log_root)?;
 
         let log_segment =
@@ -229,6 +345,19 @@ impl Snapshot {
         )))
     }
 
+    /// Advance this [`Snapshot`] to the latest version of the table. Only the log files after this
+    /// snapshot's version (or after a newer checkpoint, if one was written in the meantime) are
+    /// read; see [`Snapshot::try_new_from`] for details. If there are no new commits the snapshot
+    /// is returned unchanged.
+    ///
+    /// # Parameters
+    ///
+    /// - `engine`: Implementation of [`Engine`] apis.
+    pub fn update(self, engine: &dyn Engine) -> DeltaResult<Self> {
+        let snapshot = Self::try_new_from(Arc::new(self), engine, None)?;
+        Ok(Arc::unwrap_or_clone(snapshot))
+    }
+
     /// Create a new [`Snapshot`] instance.
     pub(crate) fn try_new_from_log_segment(
         location: Url,
@@ -238,10 +367,100 @@ impl Snapshot {
         let (metadata, protocol) = log_segment.read_metadata(engine)?;
         let table_configuration =
             TableConfiguration::try_new(metadata, protocol, location, log_segment.end_version)?;
-        Ok(Self {
-            log_segment,
-            table_configuration,
-        })
+        Ok(Self::new(log_segment, table_configuration))
+    }
+
+    /// Create a [`Snapshot`] of the latest version of the table that was committed at or before
+    /// `timestamp` (milliseconds since the epoch).
+    ///
+    /// Commits made while in-commit timestamps are enabled are ordered by the `inCommitTimestamp`
+    /// recorded in their commitInfo action. Older commits are ordered by the modification time of
+    /// their commit file, adjusted so that each commit is at least one millisecond after the one
+    /// before it (file modification times are not guaranteed to be monotonic).
+    ///
+    /// # Parameters
+    ///
+    /// - `table_root`: url pointing at the table root (where `_delta_log` folder is located)
+    /// - `engine`: Implementation of [`Engine`] apis.
+    /// - `timestamp`: point in time to travel to. A timestamp after the latest commit resolves to
+    ///   the latest version; a timestamp before the earliest available commit is an error.
+    pub fn try_new_at_timestamp(
+        table_root: Url,
+        engine: &dyn Engine,
+        timestamp: i64,
+    ) -> DeltaResult<Self> {
+        Self::try_new(table_root, engine, None)?.at_timestamp(engine, timestamp)
+    }
+
+    /// Get the snapshot of the latest version committed at or before `timestamp`, given the
+    /// snapshot of the latest version of the table. See [`Snapshot::try_new_at_timestamp`].
+    pub(crate) fn at_timestamp(self, engine: &dyn Engine, timestamp: i64) -> DeltaResult<Self> {
+        let version = self.version_at_timestamp(engine, timestamp)?;
+        if version == self.version() {
+            return Ok(self);
+        }
+        Self::try_new_with_log_root(
+            self.table_root().clone(),
+            self.log_segment.log_root.clone(),
+            engine,
+            Some(version),
+        )
+    }
+
+    /// Find the latest version (up to this snapshot's version) committed at or before `timestamp`.
+    fn version_at_timestamp(&self, engine: &dyn Engine, timestamp: i64) -> DeltaResult<Version> {
+        let storage = engine.storage_handler();
+        let commits = log_segment::list_commit_files(
+            storage.as_ref(),
+            &self.log_segment.log_root,
+            self.version(),
+        )?;
+        let enablement = self.table_configuration.in_commit_timestamp_enablement();
+        let ict_start = enablement.map_or(commits.len(), |(version, _)| {
+            commits.partition_point(|commit| commit.version < version)
+        });
+        let (file_time_commits, ict_commits) = commits.split_at(ict_start);
+
+        if let Some(first) = ict_commits.first() {
+            let read_ict = |commit: &ParsedLogPath| -> DeltaResult<i64> {
+                read_in_commit_timestamp(engine, commit.location.clone())?.ok_or_else(|| {
+                    Error::generic(format!(
+                        "Commit {} is missing its in-commit timestamp",
+                        commit.version
+                    ))
+                })
+            };
+            let enablement_timestamp = match enablement.and_then(|(_, timestamp)| timestamp) {
+                Some(enablement_timestamp) => enablement_timestamp,
+                None => read_ict(first)?,
+            };
+            if enablement_timestamp <= timestamp {
+                // in-commit timestamps increase with version: binary search for the first commit
+                // after `timestamp`, knowing that the first one is at or before it
+                let (mut low, mut high) = (1, ict_commits.len());
+                while low < high {
+                    let mid = low + (high - low) / 2;
+                    if read_ict(&ict_commits[mid])? <= timestamp {
+                        low = mid + 1;
+                    } else {
+                        high = mid;
+                    }
+                }
+                return Ok(ict_commits[low - 1].version);
+            }
+        }
+
+        let timestamps = monotonic_timestamps(
+            file_time_commits
+                .iter()
+                .map(|commit| commit.location.last_modified),
+        );
+        match timestamps.partition_point(|commit_timestamp| *commit_timestamp <= timestamp) {
+            0 => Err(Error::generic(format!(
+                "Timestamp {timestamp} is before the earliest available version of the table"
+            ))),
+            count => Ok(file_time_commits[count - 1].version),
+        }
     }
 
     /// Log segment this snapshot uses
@@ -259,11 +478,154 @@ impl Snapshot {
         self.table_configuration().version()
     }
 
-    /// Table [`type@Schema`] at this `Snapshot`s version.
+    /// The in-commit timestamp (milliseconds since the epoch) of the commit that produced this
+    /// `Snapshot`s version, or `None` if in-commit timestamps are not enabled for the table.
+    ///
+    /// The timestamp is read from the version's commit file on first use. Fails if in-commit
+    /// timestamps are enabled but the commit file (e.g. cleaned up after a checkpoint) or its
+    /// timestamp is missing.
+    pub fn in_commit_timestamp(&self, engine: &dyn Engine) -> DeltaResult<Option<i64>> {
+        if let Some(timestamp) = self.in_commit_timestamp.get() {
+            return Ok(*timestamp);
+        }
+        let timestamp = if self.table_configuration.is_in_commit_timestamps_enabled() {
+            Some(read_snapshot_in_commit_timestamp(
+                engine,
+                &self.log_segment,
+            )?)
+        } else {
+            None
+        };
+        Ok(*self.in_commit_timestamp.get_or_init(|| timestamp))
+    }
+
+    /// The schema of the per-file statistics in the `stats` of this `Snapshot`s add actions, for
+    /// parsing them into typed batches (e.g. with [`JsonHandler::parse_json`]). Statistics are
+    /// keyed by physical column name, and only cover the columns selected by the
+    /// `delta.dataSkippingStatsColumns` (or else `delta.dataSkippingNumIndexedCols`) table
+    /// property. See [`Snapshot::table_properties`].
+    ///
+    /// [`JsonHandler::parse_json`]: crate::JsonHandler::parse_json
+    pub fn stats_schema(&self) -> DeltaResult<StructType> {
+        Ok(self.table_configuration.stats_schema())
+    }
+
+    /// The time (milliseconds since the epoch) the table was created, as recorded in its metadata,
+    /// or `None` if the writer did not record it.
+    pub fn created_time(&self) -> Option<i64> {
+        self.metadata().created_time
+    }
+
+    /// The time (milliseconds since the epoch) this `Snapshot`s version was committed. This is the
+    /// version's in-commit timestamp if in-commit timestamps are enabled, and otherwise the
+    /// modification time of its commit file.
+    ///
+    /// The commit file is normally already known, but if this `Snapshot` was loaded from a
+    /// checkpoint at its version, the log has to be listed to find it.
+    pub fn modified_time(&self, engine: &dyn Engine) -> DeltaResult<i64> {
+        if let Some(timestamp) = self.in_commit_timestamp(engine)? {
+            return Ok(timestamp);
+        }
+        let version = self.version();
+        let commit = match self.log_segment.ascending_commit_files.last() {
+            Some(commit) if commit.version == version => commit.clone(),
+            _ => log_segment::find_commit_file(
+                engine.storage_handler().as_ref(),
+                &self.log_segment.log_root,
+                version,
+            )?
+            .ok_or_else(|| {
+                Error::generic(format!(
+                    "Cannot determine the commit time of version {version}: its commit file is missing"
+                ))
+            })?,
+        };
+        Ok(commit.location.last_modified)
+    }
+
+    /// Verify that the checkpoint this `Snapshot` was loaded from lists exactly the files that
+    /// replaying the commits up to the checkpoint version yields. See
+    /// [`LogSegment::verify_checkpoint`] for details.
+    ///
+    /// This replays the whole log, so it is meant for opt-in data-quality checks rather than
+    /// regular reads.
+    pub fn verify_checkpoint(&self, engine: &dyn Engine) -> DeltaResult<()> {
+        self.log_segment.verify_checkpoint(engine)
+    }
+
+    /// The configuration of the metadata `domain` at this `Snapshot`s version, or `None` if the
+    /// domain does not exist or was removed.
+    ///
+    /// The domains are replayed from the log on first use of this or [`Snapshot::domains`].
+    pub fn domain_metadata(&self, domain: &str, engine: &dyn Engine) -> DeltaResult<Option<&str>> {
+        let domains = self.domain_metadata_map(engine)?;
+        Ok(domains.get(domain).map(String::as_str))
+    }
+
+    /// All metadata domains at this `Snapshot`s version, with their configurations, in no
+    /// particular order. Removed domains are not included.
+    pub fn domains(&self, engine: &dyn Engine) -> DeltaResult<impl Iterator<Item = (&str, &str)>> {
+        let domains = self.domain_metadata_map(engine)?;
+        Ok(domains
+            .iter()
+            .map(|(domain, configuration)| (domain.as_str(), configuration.as_str())))
+    }
+
+    fn domain_metadata_map(&self, engine: &dyn Engine) -> DeltaResult<&HashMap<String, String>> {
+        if let Some(domains) = self.domain_metadata.get() {
+            return Ok(domains);
+        }
+        // Writers may only emit domain metadata actions once the feature is enabled, so other
+        // tables can skip the replay
+        let domains = if self
+            .protocol()
+            .has_writer_feature(&WriterFeature::DomainMetadata)
+        {
+            scan_domain_metadata(&self.log_segment, engine)?
+        } else {
+            HashMap::new()
+        };
+        Ok(self.domain_metadata.get_or_init(|| domains))
+    }
+
+    /// The clustering columns of a liquid clustered table, as logical column paths, or `None` if
+    /// the table is not clustered.
+    pub fn clustering_columns(&self, engine: &dyn Engine) -> DeltaResult<Option<Vec<ColumnName>>> {
+        #[derive(Deserialize)]
+        #[serde(rename_all = "camelCase")]
+        struct ClusteringDomain {
+            /// Physical column paths of the clustering columns
+            clustering_columns: Vec<Vec<String>>,
+        }
+        let Some(configuration) = self.domain_metadata(CLUSTERING_DOMAIN_NAME, engine)? else {
+            return Ok(None);
+        };
+        let domain: ClusteringDomain = serde_json::from_str(configuration)?;
+        let schema = self.schema();
+        let column_mapping_mode = self.column_mapping_mode();
+        domain
+            .clustering_columns
+            .iter()
+            .map(|path| logical_column_path(&schema, path, column_mapping_mode))
+            .collect::<DeltaResult<_>>()
+            .map(Some)
+    }
+
+    /// Table [`type@Schema`] at this `Snapshot`s version. This is the logical schema that queries
+    /// are written against; see [`Snapshot::physical_schema`] for the names used in data files.
     pub fn schema(&self) -> SchemaRef {
         self.table_configuration.schema()
     }
 
+    /// The physical [`type@Schema`] of the table at this `Snapshot`s version: the logical
+    /// [`Snapshot::schema`] with column mapping applied, so that each field carries the name of the
+    /// column in the data files (and, in id mode, its column mapping id, which is the parquet field
+    /// id). Engines reading data files themselves should request this schema. Without column
+    /// mapping this equals the logical schema.
+    pub fn physical_schema(&self) -> SchemaRef {
+        self.table_configuration.physical_schema()
+    }
+
     /// Table [`Metadata`] at this `Snapshot`s version.
     #[internal_api]
     pub(crate) fn metadata(&self) -> &Metadata {
@@ -271,12 +633,26 @@ impl Snapshot {
     }
 
     /// Table [`Protocol`] at this `Snapshot`s version.
-    #[allow(dead_code)]
-    #[internal_api]
-    pub(crate) fn protocol(&self) -> &Protocol {
+    pub fn protocol(&self) -> &Protocol {
         self.table_configuration.protocol()
     }
 
+    /// The reader and writer features enabled in this `Snapshot`s [`Protocol`].
+    ///
+    /// Tables on a legacy protocol (reader version < 3 and writer version < 7) do not list their
+    /// features explicitly, and return none here.
+    pub fn table_features(&self) -> Vec<TableFeature> {
+        let protocol = self.protocol();
+        let reader_features = protocol.reader_features().unwrap_or_default();
+        let writer_features = protocol.writer_features().unwrap_or_default();
+        reader_features
+            .iter()
+            .cloned()
+            .map(TableFeature::Reader)
+            .chain(writer_features.iter().cloned().map(TableFeature::Writer))
+            .collect()
+    }
+
     /// Get the [`TableProperties`] for this [`Snapshot`].
     pub fn table_properties(&self) -> &TableProperties {
         self.table_configuration().table_properties()
@@ -305,18 +681,95 @@ impl Snapshot {
         ScanBuilder::new(self)
     }
 
-    /// Fetch the latest version of the provided `application_id` for this snapshot.
+    /// List the active data files of the table at this `Snapshot`s version, without building a
+    /// [`Scan`]. This replays the log to reconcile adds and removes, but applies no predicate, so
+    /// every file the table currently contains is returned, in no particular order.
+    ///
+    /// [`Scan`]: crate::scan::Scan
+    pub fn active_files(
+        &self,
+        engine: &dyn Engine,
+    ) -> DeltaResult<impl Iterator<Item = DeltaResult<ActiveFile>>> {
+        let commit_read_schema = get_log_schema().project(&[ADD_NAME, REMOVE_NAME])?;
+        let checkpoint_read_schema = get_log_schema().project(&[ADD_NAME, SIDECAR_NAME])?;
+        let actions = self.log_segment.read_actions(
+            engine,
+            commit_read_schema,
+            checkpoint_read_schema,
+            None,
+        )?;
+        let table_root = self.table_root().clone();
+        let files = scan_action_iter(engine, actions, self.schema(), None, None, None)
+            .map(move |scan_metadata| -> DeltaResult<_> {
+                let scan_metadata = scan_metadata?;
+                let mut visitor = ActiveFileVisitor {
+                    table_root: &table_root,
+                    selection_vector: &scan_metadata.scan_files.selection_vector,
+                    files: vec![],
+                };
+                visitor.visit_rows_of(scan_metadata.scan_files.data.as_ref())?;
+                Ok(visitor.files)
+            })
+            .flatten_ok();
+        Ok(files)
+    }
+
+    /// Write a checkpoint of the table at this `Snapshot`s version, so later snapshots don't need
+    /// to replay the commits up to it. The checkpoint is a single parquet file at
+    /// `_delta_log/<version>.checkpoint.parquet` holding the table state: the protocol, the
+    /// metadata, the active add actions, the remove actions still within the
+    /// `delta.deletedFileRetentionDuration`, the set transactions and the metadata domains. The
+    /// `_last_checkpoint` file is then updated to point to the new checkpoint.
     ///
-    /// Note that this method performs log replay (fetches and processes metadata from storage).
-    // TODO: add a get_app_id_versions to fetch all at once using SetTransactionScanner::get_all
+    /// Tables with the `v2Checkpoint` feature are not supported yet.
+    pub fn write_checkpoint(&self, engine: &dyn Engine) -> DeltaResult<()> {
+        crate::checkpoint::write_checkpoint(self, engine)
+    }
+
+    /// Create a new write [`Transaction`] for an `Arc<Snapshot>`. The transaction commits the
+    /// version after this snapshot's version.
+    pub fn transaction(self: Arc<Self>) -> DeltaResult<Transaction> {
+        Transaction::try_new(self)
+    }
+
+    /// Fetch the latest version of the provided `application_id` for this snapshot. See
+    /// [`Snapshot::transaction_version`].
     pub fn get_app_id_version(
         self: Arc<Self>,
         application_id: &str,
         engine: &dyn Engine,
     ) -> DeltaResult<Option<i64>> {
-        let txn = SetTransactionScanner::get_one(self.log_segment(), application_id, engine)?;
+        self.transaction_version(application_id, engine)
+    }
+
+    /// Get the version of the latest `txn` action of the application `app_id` at this snapshot's
+    /// version, or `None` if the application never committed a transaction. Streaming writers use
+    /// this to skip writes that were already committed. As with all actions, the `txn` action
+    /// committed last wins.
+    ///
+    /// Note that this method performs log replay (fetches and processes metadata from storage),
+    /// which stops as soon as a transaction of `app_id` is found. To look up several applications,
+    /// use [`Snapshot::transaction_versions`] instead.
+    pub fn transaction_version(
+        &self,
+        app_id: &str,
+        engine: &dyn Engine,
+    ) -> DeltaResult<Option<i64>> {
+        let txn = SetTransactionScanner::get_one(self.log_segment(), app_id, engine)?;
         Ok(txn.map(|t| t.version))
     }
+
+    /// Get the version of the latest `txn` action of every application that committed a
+    /// transaction to the table, keyed by application id. See [`Snapshot::transaction_version`].
+    ///
+    /// Note that this method replays the whole log (fetches and processes metadata from storage).
+    pub fn transaction_versions(&self, engine: &dyn Engine) -> DeltaResult<HashMap<String, i64>> {
+        let txns = SetTransactionScanner::get_all(self.log_segment(), engine)?;
+        Ok(txns
+            .into_iter()
+            .map(|(app_id, txn)| (app_id, txn.version))
+            .collect())
+    }
 }
 
 // Note: Schema can not be derived because the checkpoint schema is only known at runtime.
@@ -366,6 +819,99 @@ fn read_last_checkpoint(
     }
 }
 
+/// Read the in-commit timestamp of the commit at the end of `log_segment`. A commit at the version
+/// of the segment's checkpoint is not part of the segment, so the log is listed to find it.
+fn read_snapshot_in_commit_timestamp(
+    engine: &dyn Engine,
+    log_segment: &LogSegment,
+) -> DeltaResult<i64> {
+    let version = log_segment.end_version;
+    let commit = match log_segment.ascending_commit_files.last() {
+        Some(commit) if commit.version == version => commit.clone(),
+        _ => log_segment::find_commit_file(
+            engine.storage_handler().as_ref(),
+            &log_segment.log_root,
+            version,
+        )?
+        .ok_or_else(|| {
+            Error::generic(format!(
+                "Cannot read the in-commit timestamp of version {version}: its commit file is missing"
+            ))
+        })?,
+    };
+    read_in_commit_timestamp(engine, commit.location)?.ok_or_else(|| {
+        Error::generic(format!(
+            "Commit {version} is missing its in-commit timestamp"
+        ))
+    })
+}
+
+/// Resolve a path of physical column names into the logical [`ColumnName`] of `schema` it refers
+/// to. Without column mapping, physical and logical names are the same.
+fn logical_column_path(
+    schema: &Schema,
+    physical_path: &[String],
+    column_mapping_mode: ColumnMappingMode,
+) -> DeltaResult<ColumnName> {
+    let mut fields = Some(schema);
+    let mut logical_path = Vec::with_capacity(physical_path.len());
+    for physical_name in physical_path {
+        let field = fields.and_then(|fields| {
+            fields.fields().find(|field| match column_mapping_mode {
+                ColumnMappingMode::None => field.name() == physical_name,
+                ColumnMappingMode::Id | ColumnMappingMode::Name => {
+                    field.physical_name() == physical_name
+                }
+            })
+        });
+        let Some(field) = field else {
+            return Err(Error::generic(format!(
+                "Column {physical_path:?} does not exist in the table schema"
+            )));
+        };
+        logical_path.push(field.name().clone());
+        fields = match field.data_type() {
+            DataType::Struct(inner) => Some(inner),
+            _ => None,
+        };
+    }
+    Ok(ColumnName::new(logical_path))
+}
+
+/// Read the `inCommitTimestamp` from the commitInfo action of a commit file.
+fn read_in_commit_timestamp(engine: &dyn Engine, commit: FileMeta) -> DeltaResult<Option<i64>> {
+    let batches = engine.json_handler().read_json_files(
+        &[commit],
+        get_log_commit_info_schema().clone(),
+        None,
+    )?;
+    let mut visitor = InCommitTimestampVisitor::default();
+    for batch in batches {
+        visitor.visit_rows_of(batch?.as_ref())?;
+        if visitor.in_commit_timestamp.is_some() {
+            break;
+        }
+    }
+    Ok(visitor.in_commit_timestamp)
+}
+
+/// Adjust commit timestamps (in version order) so that they strictly increase: a commit that is not
+/// later than its predecessor is treated as one millisecond after it.
+fn monotonic_timestamps(timestamps: impl IntoIterator<Item = i64>) -> Vec<i64> {
+    let mut previous: Option<i64> = None;
+    timestamps
+        .into_iter()
+        .map(|timestamp| {
+            let timestamp = match previous {
+                Some(previous) if timestamp <= previous => previous + 1,
+                _ => timestamp,
+            };
+            previous = Some(timestamp);
+            timestamp
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -388,7 +934,10 @@ mod tests {
     use crate::engine::default::filesystem::ObjectStoreStorageHandler;
     use crate::engine::default::DefaultEngine;
     use crate::engine::sync::SyncEngine;
+    use crate::expressions::column_name;
     use crate::path::ParsedLogPath;
+    use crate::schema::StructField;
+    use crate::table_features::{ReaderFeature, WriterFeature};
     use crate::utils::test_utils::string_array_to_engine_data;
     use test_utils::{add_commit, delta_path_for_version};
 
@@ -410,6 +959,38 @@ mod tests {
         assert_eq!(snapshot.schema(), expected);
     }
 
+    #[test]
+    fn test_snapshot_version_and_features() {
+        let engine = SyncEngine::new();
+        let snapshot_of = |table: &str| {
+            let path = std::fs::canonicalize(PathBuf::from(table)).unwrap();
+            let url = url::Url::from_directory_path(path).unwrap();
+            Snapshot::try_new(url, &engine, None).unwrap()
+        };
+
+        let snapshot = snapshot_of("./tests/data/table-with-dv-small/");
+        assert_eq!(snapshot.version(), 1);
+        assert_eq!(snapshot.protocol().min_reader_version(), 3);
+        assert_eq!(snapshot.protocol().min_writer_version(), 7);
+        assert_eq!(
+            snapshot.table_features(),
+            [
+                TableFeature::Reader(ReaderFeature::DeletionVectors),
+                TableFeature::Writer(WriterFeature::DeletionVectors),
+            ]
+        );
+        assert_eq!(
+            snapshot.table_properties().enable_deletion_vectors,
+            Some(true)
+        );
+
+        let snapshot = snapshot_of("./tests/data/basic_partitioned/");
+        assert_eq!(snapshot.version(), 1);
+        assert_eq!(snapshot.protocol().min_reader_version(), 1);
+        assert_eq!(snapshot.protocol().min_writer_version(), 2);
+        assert_eq!(snapshot.table_features(), []);
+    }
+
     #[test]
     fn test_new_snapshot() {
         let path =
@@ -633,6 +1214,747 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_snapshot_update() -> DeltaResult<()> {
+        let engine = SyncEngine::new();
+
+        // advance across one commit
+        let path =
+            std::fs::canonicalize(PathBuf::from("./tests/data/table-with-dv-small/")).unwrap();
+        let url = url::Url::from_directory_path(path).unwrap();
+        let snapshot = Snapshot::try_new(url.clone(), &engine, Some(0))?.update(&engine)?;
+        let expected = Snapshot::try_new(url, &engine, None)?;
+        assert_eq!(snapshot.version(), 1);
+        assert_eq!(snapshot, expected);
+
+        // advance across a checkpoint written after the old version
+        let path = std::fs::canonicalize(PathBuf::from(
+            "./tests/data/with_checkpoint_no_last_checkpoint/",
+        ))
+        .unwrap();
+        let url = url::Url::from_directory_path(path).unwrap();
+        let snapshot = Snapshot::try_new(url.clone(), &engine, Some(1))?.update(&engine)?;
+        let expected = Snapshot::try_new(url, &engine, None)?;
+        assert_eq!(snapshot.version(), 3);
+        assert_eq!(snapshot.log_segment().checkpoint_version, Some(2));
+        assert_eq!(snapshot, expected);
+
+        // no-op when already at the latest version
+        let updated = expected.clone().update(&engine)?;
+        assert_eq!(updated, expected);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_transaction_versions() -> DeltaResult<()> {
+        let txn = |app_id: &str, version: i64| json!({ "txn": { "appId": app_id, "version": version, "lastUpdated": 1587968586154i64 } });
+        let commits = [
+            ict_commit(None, &[ict_protocol(), ict_metadata(json!({}))]),
+            ict_commit(None, &[txn("app-a", 1), txn("app-b", 10)]),
+            ict_commit(None, &[txn("app-a", 2)]),
+            ict_commit(None, &[txn("app-a", 3)]),
+            // the txn action committed last wins, even if its version is lower
+            ict_commit(None, &[txn("app-b", 5)]),
+        ];
+        let store = Arc::new(InMemory::new());
+        for (version, commit) in commits.into_iter().enumerate() {
+            add_commit(store.as_ref(), version as Version, commit)
+                .await
+                .unwrap();
+        }
+        let url = Url::parse("memory:///")?;
+        let engine = DefaultEngine::new(store, Arc::new(TokioBackgroundExecutor::new()));
+
+        let snapshot = Snapshot::try_new(url.clone(), &engine, Some(0))?;
+        assert_eq!(snapshot.transaction_version("app-a", &engine)?, None);
+        assert!(snapshot.transaction_versions(&engine)?.is_empty());
+
+        let snapshot = Snapshot::try_new(url.clone(), &engine, Some(2))?;
+        assert_eq!(snapshot.transaction_version("app-a", &engine)?, Some(2));
+        assert_eq!(snapshot.transaction_version("app-b", &engine)?, Some(10));
+        assert_eq!(
+            snapshot.transaction_versions(&engine)?,
+            HashMap::from([("app-a".to_string(), 2), ("app-b".to_string(), 10)])
+        );
+
+        let snapshot = Snapshot::try_new(url, &engine, None)?;
+        assert_eq!(snapshot.transaction_version("app-a", &engine)?, Some(3));
+        assert_eq!(snapshot.transaction_version("app-b", &engine)?, Some(5));
+        assert_eq!(snapshot.transaction_version("app-c", &engine)?, None);
+        assert_eq!(
+            snapshot.transaction_versions(&engine)?,
+            HashMap::from([("app-a".to_string(), 3), ("app-b".to_string(), 5)])
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_domain_metadata() -> DeltaResult<()> {
+        let domain = |domain: &str, configuration: &str, removed: bool| {
+            json!({
+                "domainMetadata": {
+                    "domain": domain,
+                    "configuration": configuration,
+                    "removed": removed,
+                }
+            })
+        };
+        let protocol = json!({
+            "protocol": {
+                "minReaderVersion": 1,
+                "minWriterVersion": 7,
+                "writerFeatures": ["domainMetadata"]
+            }
+        });
+        let commits = [
+            ict_commit(
+                None,
+                &[
+                    protocol,
+                    ict_metadata(json!({})),
+                    domain("delta.rowTracking", r#"{"rowIdHighWaterMark":1}"#, false),
+                    domain("app", "v0", false),
+                ],
+            ),
+            ict_commit(
+                None,
+                &[domain(
+                    "delta.rowTracking",
+                    r#"{"rowIdHighWaterMark":7}"#,
+                    false,
+                )],
+            ),
+            ict_commit(None, &[domain("app", "v0", true)]),
+            ict_commit(None, &[domain("other", "", false)]),
+        ];
+        let store = Arc::new(InMemory::new());
+        for (version, commit) in commits.into_iter().enumerate() {
+            add_commit(store.as_ref(), version as Version, commit)
+                .await
+                .unwrap();
+        }
+        let url = Url::parse("memory:///")?;
+        let engine = DefaultEngine::new(store, Arc::new(TokioBackgroundExecutor::new()));
+
+        let snapshot = Snapshot::try_new(url.clone(), &engine, Some(1))?;
+        assert_eq!(
+            snapshot.domain_metadata("delta.rowTracking", &engine)?,
+            Some(r#"{"rowIdHighWaterMark":7}"#)
+        );
+        assert_eq!(snapshot.domain_metadata("app", &engine)?, Some("v0"));
+
+        let snapshot = Snapshot::try_new(url, &engine, None)?;
+        assert_eq!(
+            snapshot.domain_metadata("delta.rowTracking", &engine)?,
+            Some(r#"{"rowIdHighWaterMark":7}"#)
+        );
+        assert_eq!(snapshot.domain_metadata("app", &engine)?, None);
+        assert_eq!(snapshot.domain_metadata("missing", &engine)?, None);
+        let domains: HashMap<_, _> = snapshot.domains(&engine)?.collect();
+        assert_eq!(
+            domains,
+            HashMap::from([
+                ("delta.rowTracking", r#"{"rowIdHighWaterMark":7}"#),
+                ("other", ""),
+            ])
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_clustering_columns() -> DeltaResult<()> {
+        let protocol = json!({
+            "protocol": {
+                "minReaderVersion": 2,
+                "minWriterVersion": 7,
+                "writerFeatures": ["domainMetadata", "clustering", "columnMapping"]
+            }
+        });
+        let clustering = json!({
+            "domainMetadata": {
+                "domain": "delta.clustering",
+                "configuration": r#"{"clusteringColumns":[["col-2","col-3"],["col-1"]]}"#,
+                "removed": false,
+            }
+        });
+        let field = |name: &str, id: i64, data_type: serde_json::Value| {
+            json!({
+                "name": name,
+                "type": data_type,
+                "nullable": true,
+                "metadata": {
+                    "delta.columnMapping.id": id,
+                    "delta.columnMapping.physicalName": format!("col-{id}"),
+                }
+            })
+        };
+        let schema = json!({
+            "type": "struct",
+            "fields": [
+                field("id", 1, json!("integer")),
+                field("nested", 2, json!({
+                    "type": "struct",
+                    "fields": [field("x", 3, json!("long"))],
+                })),
+            ]
+        });
+        let mut metadata = ict_metadata(json!({"delta.columnMapping.mode": "name"}));
+        metadata["metaData"]["schemaString"] = schema.to_string().into();
+
+        let store = Arc::new(InMemory::new());
+        let commit0 = ict_commit(None, &[protocol.clone(), metadata, clustering]);
+        add_commit(store.as_ref(), 0, commit0).await.unwrap();
+        let url = Url::parse("memory:///")?;
+        let engine = DefaultEngine::new(store, Arc::new(TokioBackgroundExecutor::new()));
+        let snapshot = Snapshot::try_new(url, &engine, None)?;
+        assert_eq!(
+            snapshot.clustering_columns(&engine)?,
+            Some(vec![column_name!("nested.x"), column_name!("id")])
+        );
+
+        // without column mapping the paths are logical names already
+        let store = Arc::new(InMemory::new());
+        let clustering = json!({
+            "domainMetadata": {
+                "domain": "delta.clustering",
+                "configuration": r#"{"clusteringColumns":[["id"]]}"#,
+                "removed": false,
+            }
+        });
+        let commit0 = ict_commit(None, &[protocol, ict_metadata(json!({})), clustering]);
+        add_commit(store.as_ref(), 0, commit0).await.unwrap();
+        let url = Url::parse("memory:///")?;
+        let engine = DefaultEngine::new(store, Arc::new(TokioBackgroundExecutor::new()));
+        let snapshot = Snapshot::try_new(url, &engine, None)?;
+        assert_eq!(
+            snapshot.clustering_columns(&engine)?,
+            Some(vec![column_name!("id")])
+        );
+
+        // not a clustered table
+        let path =
+            std::fs::canonicalize(PathBuf::from("./tests/data/table-with-dv-small/")).unwrap();
+        let url = url::Url::from_directory_path(path).unwrap();
+        let engine = SyncEngine::new();
+        let snapshot = Snapshot::try_new(url, &engine, None)?;
+        assert_eq!(snapshot.clustering_columns(&engine)?, None);
+        Ok(())
+    }
+
+    #[test]
+    fn test_monotonic_timestamps() {
+        assert_eq!(
+            monotonic_timestamps([5, 3, 3, 10, 10, 20]),
+            [5, 6, 7, 10, 11, 20]
+        );
+        assert!(monotonic_timestamps([]).is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_physical_schema() -> DeltaResult<()> {
+        let protocol = json!({
+            "protocol": {
+                "minReaderVersion": 2,
+                "minWriterVersion": 5,
+            }
+        });
+        let field = |name: &str, id: i64, data_type: serde_json::Value| {
+            json!({
+                "name": name,
+                "type": data_type,
+                "nullable": true,
+                "metadata": {
+                    "delta.columnMapping.id": id,
+                    "delta.columnMapping.physicalName": format!("col-{id}"),
+                }
+            })
+        };
+        let schema = json!({
+            "type": "struct",
+            "fields": [
+                field("id", 1, json!("integer")),
+                field("nested", 2, json!({
+                    "type": "struct",
+                    "fields": [field("x", 3, json!("long"))],
+                })),
+            ]
+        });
+        let mut metadata = ict_metadata(json!({"delta.columnMapping.mode": "name"}));
+        metadata["metaData"]["schemaString"] = schema.to_string().into();
+
+        let store = Arc::new(InMemory::new());
+        add_commit(store.as_ref(), 0, ict_commit(None, &[protocol, metadata]))
+            .await
+            .unwrap();
+        let url = Url::parse("memory:///")?;
+        let engine = DefaultEngine::new(store, Arc::new(TokioBackgroundExecutor::new()));
+        let snapshot = Snapshot::try_new(url, &engine, None)?;
+
+        let logical = snapshot.schema();
+        let logical_names: Vec<_> = logical.fields().map(|f| f.name().as_str()).collect();
+        assert_eq!(logical_names, ["id", "nested"]);
+        let DataType::Struct(nested) = logical.field("nested").unwrap().data_type() else {
+            panic!("nested should be a struct");
+        };
+        assert!(nested.field("x").is_some());
+
+        let physical = snapshot.physical_schema();
+        let physical_names: Vec<_> = physical.fields().map(|f| f.name().as_str()).collect();
+        assert_eq!(physical_names, ["col-1", "col-2"]);
+        let DataType::Struct(nested) = physical.field("col-2").unwrap().data_type() else {
+            panic!("nested should be a struct");
+        };
+        assert!(nested.field("col-3").is_some());
+        assert!(Arc::ptr_eq(&physical, &snapshot.physical_schema()));
+
+        // without column mapping both schemas are the same
+        let path = std::fs::canonicalize(PathBuf::from("./tests/data/basic_partitioned/")).unwrap();
+        let url = url::Url::from_directory_path(path).unwrap();
+        let snapshot = Snapshot::try_new(url, &SyncEngine::new(), None)?;
+        assert_eq!(snapshot.physical_schema(), snapshot.schema());
+        Ok(())
+    }
+
+    #[test]
+    fn test_active_files() -> DeltaResult<()> {
+        let path = std::fs::canonicalize(PathBuf::from("./tests/data/basic_partitioned/")).unwrap();
+        let url = url::Url::from_directory_path(path).unwrap();
+        let engine = SyncEngine::new();
+        let snapshot = Snapshot::try_new(url.clone(), &engine, None)?;
+
+        let files: Vec<_> = snapshot.active_files(&engine)?.try_collect()?;
+        assert_eq!(files.len(), 6);
+        assert_eq!(files.iter().map(|f| f.file.size).sum::<u64>(), 4505);
+        let file = files
+            .iter()
+            .find(|f| f.file.location.path().contains("letter=e/"))
+            .unwrap();
+        assert_eq!(
+            file.file.location,
+            url.join(
+                "letter=e/part-00000-847cf2d1-1247-4aa0-89ef-2f90c68ea51e.c000.snappy.parquet"
+            )?
+        );
+        assert_eq!(file.file.size, 750);
+        assert_eq!(file.file.last_modified, 1674611429949);
+        assert_eq!(
+            file.partition_values,
+            HashMap::from([("letter".to_string(), "e".to_string())])
+        );
+        // null partition values are omitted
+        assert_eq!(
+            files
+                .iter()
+                .filter(|f| f.partition_values.is_empty())
+                .count(),
+            1
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_active_files_after_remove() -> DeltaResult<()> {
+        let add = |path: &str, size: i64| {
+            json!({
+                "add": {
+                    "path": path,
+                    "partitionValues": {},
+                    "size": size,
+                    "modificationTime": 1587968586000i64,
+                    "dataChange": true,
+                }
+            })
+        };
+        let remove = |path: &str| {
+            json!({
+                "remove": {
+                    "path": path,
+                    "deletionTimestamp": 1587968596250i64,
+                    "dataChange": true,
+                }
+            })
+        };
+        let protocol = json!({"protocol": {"minReaderVersion": 1, "minWriterVersion": 2}});
+        let commits = [
+            ict_commit(
+                None,
+                &[
+                    protocol,
+                    ict_metadata(json!({})),
+                    add("a.parquet", 10),
+                    add("b.parquet", 20),
+                ],
+            ),
+            ict_commit(None, &[remove("a.parquet"), add("c.parquet", 30)]),
+        ];
+        let store = Arc::new(InMemory::new());
+        for (version, commit) in commits.into_iter().enumerate() {
+            add_commit(store.as_ref(), version as Version, commit)
+                .await
+                .unwrap();
+        }
+        let url = Url::parse("memory:///")?;
+        let engine = DefaultEngine::new(store, Arc::new(TokioBackgroundExecutor::new()));
+
+        let active_files = |version| -> DeltaResult<Vec<(String, u64)>> {
+            let snapshot = Snapshot::try_new(url.clone(), &engine, Some(version))?;
+            let files = snapshot
+                .active_files(&engine)?
+                .map_ok(|f| (f.file.location.path().to_string(), f.file.size))
+                .try_collect::<_, Vec<_>, _>()?;
+            Ok(files.into_iter().sorted().collect())
+        };
+        assert_eq!(
+            active_files(0)?,
+            [
+                ("/a.parquet".to_string(), 10),
+                ("/b.parquet".to_string(), 20)
+            ]
+        );
+        assert_eq!(
+            active_files(1)?,
+            [
+                ("/b.parquet".to_string(), 20),
+                ("/c.parquet".to_string(), 30)
+            ]
+        );
+        Ok(())
+    }
+
+    fn ict_commit(in_commit_timestamp: Option<i64>, actions: &[serde_json::Value]) -> String {
+        let mut commit_info = json!({"timestamp": 1587968586154i64, "operation": "WRITE"});
+        if let Some(ict) = in_commit_timestamp {
+            commit_info["inCommitTimestamp"] = ict.into();
+        }
+        std::iter::once(json!({ "commitInfo": commit_info }))
+            .chain(actions.iter().cloned())
+            .map(|action| action.to_string())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    fn ict_metadata(configuration: serde_json::Value) -> serde_json::Value {
+        json!({
+            "metaData": {
+                "id": "5fba94ed-9794-4965-ba6e-6ee3c0d22af9",
+                "format": {"provider": "parquet", "options": {}},
+                "schemaString": "{\"type\":\"struct\",\"fields\":[{\"name\":\"id\",\"type\":\"integer\",\"nullable\":true,\"metadata\":{}}]}",
+                "partitionColumns": [],
+                "configuration": configuration,
+                "createdTime": 1587968585495i64
+            }
+        })
+    }
+
+    fn ict_protocol() -> serde_json::Value {
+        json!({
+            "protocol": {
+                "minReaderVersion": 1,
+                "minWriterVersion": 7,
+                "writerFeatures": ["inCommitTimestamp"]
+            }
+        })
+    }
+
+    #[tokio::test]
+    async fn test_in_commit_timestamp_time_travel() -> DeltaResult<()> {
+        let store = Arc::new(InMemory::new());
+        let enabled = ict_metadata(json!({"delta.enableInCommitTimestamps": "true"}));
+        let commits = [
+            ict_commit(Some(1000), &[ict_protocol(), enabled]),
+            ict_commit(Some(2000), &[]),
+            ict_commit(Some(3000), &[]),
+        ];
+        for (version, commit) in commits.into_iter().enumerate() {
+            add_commit(store.as_ref(), version as Version, commit)
+                .await
+                .unwrap();
+        }
+        let url = Url::parse("memory:///")?;
+        let engine = DefaultEngine::new(store, Arc::new(TokioBackgroundExecutor::new()));
+
+        assert_eq!(
+            Snapshot::try_new(url.clone(), &engine, None)?.in_commit_timestamp(&engine)?,
+            Some(3000)
+        );
+        assert_eq!(
+            Snapshot::try_new(url.clone(), &engine, Some(1))?.in_commit_timestamp(&engine)?,
+            Some(2000)
+        );
+
+        let version_at = |timestamp| {
+            Snapshot::try_new_at_timestamp(url.clone(), &engine, timestamp)
+                .map(|snapshot| snapshot.version())
+        };
+        assert_eq!(version_at(1000)?, 0);
+        assert_eq!(version_at(2500)?, 1);
+        assert_eq!(version_at(3000)?, 2);
+        assert_eq!(version_at(1_000_000)?, 2);
+        assert!(matches!(version_at(999), Err(Error::Generic(_))));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_in_commit_timestamp_enabled_later() -> DeltaResult<()> {
+        // file modification times of the in-memory store are "now", well before these timestamps
+        let enablement_timestamp = 4_000_000_000_000;
+        let store = Arc::new(InMemory::new());
+        let disabled = ict_metadata(json!({}));
+        let enabled = ict_metadata(json!({
+            "delta.enableInCommitTimestamps": "true",
+            "delta.inCommitTimestampEnablementVersion": "2",
+            "delta.inCommitTimestampEnablementTimestamp": enablement_timestamp.to_string(),
+        }));
+        let commits = [
+            ict_commit(None, &[ict_protocol(), disabled]),
+            ict_commit(None, &[]),
+            ict_commit(Some(enablement_timestamp), &[enabled]),
+            ict_commit(Some(enablement_timestamp + 1000), &[]),
+        ];
+        for (version, commit) in commits.into_iter().enumerate() {
+            add_commit(store.as_ref(), version as Version, commit)
+                .await
+                .unwrap();
+        }
+        let url = Url::parse("memory:///")?;
+        let engine = DefaultEngine::new(store, Arc::new(TokioBackgroundExecutor::new()));
+
+        assert_eq!(
+            Snapshot::try_new(url.clone(), &engine, Some(1))?.in_commit_timestamp(&engine)?,
+            None
+        );
+        assert_eq!(
+            Snapshot::try_new(url.clone(), &engine, Some(2))?.in_commit_timestamp(&engine)?,
+            Some(enablement_timestamp)
+        );
+
+        let version_at = |timestamp| {
+            Snapshot::try_new_at_timestamp(url.clone(), &engine, timestamp)
+                .map(|snapshot| snapshot.version())
+        };
+        assert_eq!(version_at(enablement_timestamp + 500)?, 2);
+        assert_eq!(version_at(enablement_timestamp + 1000)?, 3);
+        // before enablement, commits are ordered by file modification time
+        assert_eq!(version_at(enablement_timestamp - 1)?, 1);
+        assert!(matches!(version_at(0), Err(Error::Generic(_))));
+        Ok(())
+    }
+
+    #[test]
+    fn test_timestamp_without_in_commit_timestamps() {
+        let path =
+            std::fs::canonicalize(PathBuf::from("./tests/data/table-with-dv-small/")).unwrap();
+        let url = url::Url::from_directory_path(path).unwrap();
+        let engine = SyncEngine::new();
+        let snapshot = Snapshot::try_new(url, &engine, None).unwrap();
+        assert_eq!(snapshot.in_commit_timestamp(&engine).unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn test_missing_in_commit_timestamp() -> DeltaResult<()> {
+        let store = Arc::new(InMemory::new());
+        let enabled = ict_metadata(json!({"delta.enableInCommitTimestamps": "true"}));
+        let commits = [
+            ict_commit(Some(1000), &[ict_protocol(), enabled]),
+            ict_commit(None, &[]),
+        ];
+        for (version, commit) in commits.into_iter().enumerate() {
+            add_commit(store.as_ref(), version as Version, commit)
+                .await
+                .unwrap();
+        }
+        let url = Url::parse("memory:///")?;
+        let engine = DefaultEngine::new(store, Arc::new(TokioBackgroundExecutor::new()));
+
+        // the timestamp is only read when asked for, so loading the snapshot succeeds
+        let snapshot = Snapshot::try_new(url, &engine, None)?;
+        assert!(matches!(
+            snapshot.in_commit_timestamp(&engine),
+            Err(Error::Generic(_))
+        ));
+        assert!(snapshot.modified_time(&engine).is_err());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_stats_schema() -> DeltaResult<()> {
+        let schema = StructType::new([
+            StructField::nullable("a", DataType::INTEGER),
+            StructField::nullable("part", DataType::STRING),
+            StructField::nullable(
+                "b",
+                StructType::new([
+                    StructField::nullable("c", DataType::STRING),
+                    StructField::nullable("d", DataType::BOOLEAN),
+                ]),
+            ),
+            StructField::nullable("e", DataType::LONG),
+        ]);
+        let schema_string = serde_json::to_string(&schema).unwrap();
+        let stats_schema = |configuration: serde_json::Value| {
+            let schema_string = schema_string.clone();
+            async move {
+                let store = Arc::new(InMemory::new());
+                let metadata = json!({
+                    "metaData": {
+                        "id": "5fba94ed-9794-4965-ba6e-6ee3c0d22af9",
+                        "format": {"provider": "parquet", "options": {}},
+                        "schemaString": schema_string,
+                        "partitionColumns": ["part"],
+                        "configuration": configuration,
+                        "createdTime": 1587968585495i64
+                    }
+                });
+                let protocol = json!({"protocol": {"minReaderVersion": 1, "minWriterVersion": 2}});
+                add_commit(store.as_ref(), 0, format!("{protocol}\n{metadata}"))
+                    .await
+                    .unwrap();
+                let engine = DefaultEngine::new(store, Arc::new(TokioBackgroundExecutor::new()));
+                Snapshot::try_new(Url::parse("memory:///").unwrap(), &engine, None)?.stats_schema()
+            }
+        };
+        let expected = |null_count: StructType, min_max: StructType| {
+            StructType::new([
+                StructField::nullable("numRecords", DataType::LONG),
+                StructField::nullable("nullCount", null_count),
+                StructField::nullable("minValues", min_max.clone()),
+                StructField::nullable("maxValues", min_max),
+                StructField::nullable("tightBounds", DataType::BOOLEAN),
+            ])
+        };
+
+        // by default every (non-partition) column is indexed; booleans have no min/max
+        assert_eq!(
+            stats_schema(json!({})).await?,
+            expected(
+                StructType::new([
+                    StructField::nullable("a", DataType::LONG),
+                    StructField::nullable(
+                        "b",
+                        StructType::new([
+                            StructField::nullable("c", DataType::LONG),
+                            StructField::nullable("d", DataType::LONG),
+                        ]),
+                    ),
+                    StructField::nullable("e", DataType::LONG),
+                ]),
+                StructType::new([
+                    StructField::nullable("a", DataType::INTEGER),
+                    StructField::nullable(
+                        "b",
+                        StructType::new([StructField::nullable("c", DataType::STRING)]),
+                    ),
+                    StructField::nullable("e", DataType::LONG),
+                ]),
+            )
+        );
+
+        // the first two leaf columns, in schema order
+        assert_eq!(
+            stats_schema(json!({"delta.dataSkippingNumIndexedCols": "2"})).await?,
+            expected(
+                StructType::new([
+                    StructField::nullable("a", DataType::LONG),
+                    StructField::nullable(
+                        "b",
+                        StructType::new([StructField::nullable("c", DataType::LONG)]),
+                    ),
+                ]),
+                StructType::new([
+                    StructField::nullable("a", DataType::INTEGER),
+                    StructField::nullable(
+                        "b",
+                        StructType::new([StructField::nullable("c", DataType::STRING)]),
+                    ),
+                ]),
+            )
+        );
+
+        // an explicit list takes precedence over the number of indexed columns
+        let configuration = json!({
+            "delta.dataSkippingNumIndexedCols": "1",
+            "delta.dataSkippingStatsColumns": "e,b.d",
+        });
+        assert_eq!(
+            stats_schema(configuration).await?,
+            expected(
+                StructType::new([
+                    StructField::nullable(
+                        "b",
+                        StructType::new([StructField::nullable("d", DataType::LONG)]),
+                    ),
+                    StructField::nullable("e", DataType::LONG),
+                ]),
+                StructType::new([StructField::nullable("e", DataType::LONG)]),
+            )
+        );
+        Ok(())
+    }
+
+    /// Set the modification time of the file at `path` to `millis` since the epoch
+    fn set_modified_time(path: &std::path::Path, millis: u64) {
+        let time = std::time::UNIX_EPOCH + std::time::Duration::from_millis(millis);
+        let file = std::fs::File::options().write(true).open(path).unwrap();
+        file.set_modified(time).unwrap();
+    }
+
+    #[test]
+    fn test_created_and_modified_time() -> DeltaResult<()> {
+        let dir = tempfile::tempdir().unwrap();
+        let log_dir = dir.path().join("_delta_log");
+        std::fs::create_dir(&log_dir).unwrap();
+        let commits = [
+            ict_commit(None, &[ict_protocol(), ict_metadata(json!({}))]),
+            ict_commit(None, &[]),
+            ict_commit(
+                Some(4_000_000_000_000),
+                &[ict_metadata(
+                    json!({"delta.enableInCommitTimestamps": "true"}),
+                )],
+            ),
+        ];
+        // give every commit file a distinct modification time
+        for (version, commit) in commits.into_iter().enumerate() {
+            let path = log_dir.join(format!("{version:020}.json"));
+            std::fs::write(&path, commit).unwrap();
+            set_modified_time(&path, 1_000_000 * (version as u64 + 1));
+        }
+        let url = Url::from_directory_path(dir.path()).unwrap();
+        let engine = SyncEngine::new();
+
+        let snapshot = Snapshot::try_new(url.clone(), &engine, Some(1))?;
+        assert_eq!(snapshot.created_time(), Some(1587968585495));
+        // without in-commit timestamps, the commit file's modification time is used
+        assert_eq!(snapshot.modified_time(&engine)?, 2_000_000);
+
+        let snapshot = Snapshot::try_new(url, &engine, None)?;
+        assert_eq!(snapshot.modified_time(&engine)?, 4_000_000_000_000);
+        Ok(())
+    }
+
+    #[test]
+    fn test_modified_time_at_checkpoint() -> DeltaResult<()> {
+        let source = PathBuf::from("./tests/data/app-txn-checkpoint/_delta_log/");
+        let dir = tempfile::tempdir().unwrap();
+        let log_dir = dir.path().join("_delta_log");
+        std::fs::create_dir(&log_dir).unwrap();
+        for entry in std::fs::read_dir(source).unwrap() {
+            let entry = entry.unwrap();
+            std::fs::copy(entry.path(), log_dir.join(entry.file_name())).unwrap();
+            set_modified_time(&log_dir.join(entry.file_name()), 1_000_000);
+        }
+        set_modified_time(&log_dir.join("00000000000000000001.json"), 2_000_000);
+
+        // the snapshot is loaded from the checkpoint at version 1, so the commit file of version 1
+        // is not part of its log segment
+        let url = Url::from_directory_path(dir.path()).unwrap();
+        let engine = SyncEngine::new();
+        let snapshot = Snapshot::try_new(url, &engine, Some(1))?;
+        assert!(snapshot.log_segment().ascending_commit_files.is_empty());
+        assert_eq!(snapshot.modified_time(&engine)?, 2_000_000);
+        Ok(())
+    }
+
     #[test]
     fn test_read_table_with_last_checkpoint() {
         let path = std::fs::canonicalize(PathBuf::from(
@@ -2,27 +2,43 @@
 //! the different versions
 
 use std::borrow::Cow;
-use std::ops::Deref;
+use std::collections::{HashMap, HashSet};
+use std::ops::{Deref, RangeInclusive};
 use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
 
+use itertools::Itertools;
 use url::Url;
 
+use crate::actions::visitors::{ActionVisitor, RemoveVisitor};
+use crate::actions::{get_log_schema, Action, Protocol, REMOVE_NAME};
+use crate::log_segment::{list_commit_files, LogSegment};
+use crate::path::ParsedLogPath;
+use crate::schema::{DataType, StructField, StructType};
 use crate::snapshot::Snapshot;
 use crate::table_changes::TableChanges;
-use crate::transaction::Transaction;
-use crate::{DeltaResult, Engine, Error, Version};
+use crate::table_features::{
+    validate_schema_column_mapping, ColumnMappingMode, ReaderFeature, WriterFeature,
+};
+use crate::table_properties::TableProperties;
+use crate::transaction::{Transaction, KERNEL_VERSION};
+use crate::{DeltaResult, Engine, Error, EvaluationHandlerExtension as _, RowVisitor, Version};
 
 /// In-memory representation of a Delta table, which acts as an immutable root entity for reading
 /// the different versions (see [`Snapshot`]) of the table located in storage.
 #[derive(Clone)]
 pub struct Table {
     location: Url,
+    /// Location of the transaction log, if it is not the `_delta_log` directory of the table.
+    log_root: Option<Url>,
 }
 
 impl std::fmt::Debug for Table {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
         f.debug_struct("Table")
             .field("location", &self.location)
+            .field("log_root", &self.log_root)
             .finish()
     }
 }
@@ -30,7 +46,22 @@ impl std::fmt::Debug for Table {
 impl Table {
     /// Create a new Delta table with the given parameters
     pub fn new(location: Url) -> Self {
-        Self { location }
+        Self {
+            location,
+            log_root: None,
+        }
+    }
+
+    /// Read (and write) the transaction log at `log_root` instead of the `_delta_log` directory
+    /// under the table's location, e.g. for catalog-managed tables whose log lives in a separate
+    /// bucket or prefix. Relative data file paths still resolve against the table's location.
+    pub fn with_log_root(mut self, mut log_root: Url) -> Self {
+        // the log root is a directory, so that log file names are joined onto it
+        if !log_root.path().ends_with('/') {
+            log_root.set_path(&format!("{}/", log_root.path()));
+        }
+        self.log_root = Some(log_root);
+        self
     }
 
     /// Try to create a new table from a string uri. This will do it's best to handle things like
@@ -74,11 +105,33 @@ impl Table {
         &self.location
     }
 
+    /// Fully qualified location of the table's transaction log: the `_delta_log` directory of the
+    /// table unless overridden with [`Table::with_log_root`].
+    pub fn log_root(&self) -> DeltaResult<Url> {
+        match &self.log_root {
+            Some(log_root) => Ok(log_root.clone()),
+            None => Ok(self.location.join("_delta_log/")?),
+        }
+    }
+
     /// Create a [`Snapshot`] of the table corresponding to `version`.
     ///
-    /// If no version is supplied, a snapshot for the latest version will be created.
+    /// If no version is supplied, a snapshot for the latest version will be created. A snapshot as
+    /// of an older version only uses checkpoints at or before that version and the commits up to
+    /// it, and never lists or reads any later log file, however many newer commits the table has.
     pub fn snapshot(&self, engine: &dyn Engine, version: Option<Version>) -> DeltaResult<Snapshot> {
-        Snapshot::try_new(self.location.clone(), engine, version)
+        Snapshot::try_new_with_log_root(self.location.clone(), self.log_root()?, engine, version)
+    }
+
+    /// Create a [`Snapshot`] of the latest version of the table committed at or before `timestamp`
+    /// (milliseconds since the epoch). See [`Snapshot::try_new_at_timestamp`] for how commit
+    /// timestamps are determined.
+    pub fn snapshot_at_timestamp(
+        &self,
+        engine: &dyn Engine,
+        timestamp: i64,
+    ) -> DeltaResult<Snapshot> {
+        self.snapshot(engine, None)?.at_timestamp(engine, timestamp)
     }
 
     /// Create a [`TableChanges`] to get a change data feed for the table between `start_version`,
@@ -90,18 +143,329 @@ impl Table {
         start_version: Version,
         end_version: impl Into<Option<Version>>,
     ) -> DeltaResult<TableChanges> {
-        TableChanges::try_new(
+        TableChanges::try_new_with_log_root(
             self.location.clone(),
+            self.log_root()?,
             engine,
             start_version,
             end_version.into(),
         )
     }
 
+    /// Get the files removed from the table by the commits between `start_version` and
+    /// `end_version` (both inclusive), in commit order. If no `end_version` is supplied, the latest
+    /// version will be used as the `end_version`.
+    ///
+    /// Only the `remove` actions of the JSON commits in the range are read; no snapshot is
+    /// reconstructed, so files added and removed by the commits are not reconciled with each other.
+    pub fn removed_files(
+        &self,
+        engine: &dyn Engine,
+        start_version: Version,
+        end_version: impl Into<Option<Version>>,
+    ) -> DeltaResult<Vec<RemoveFileInfo>> {
+        let log_segment = LogSegment::for_table_changes(
+            engine.storage_handler().as_ref(),
+            self.log_root()?,
+            start_version,
+            end_version,
+        )?;
+        let commit_files: Vec<_> = log_segment
+            .ascending_commit_files
+            .into_iter()
+            .map(|commit| commit.location)
+            .collect();
+        let read_schema = get_log_schema().project(&[REMOVE_NAME])?;
+        let mut visitor = RemoveVisitor::default();
+        for batch in engine
+            .json_handler()
+            .read_json_files(&commit_files, read_schema, None)?
+        {
+            visitor.visit_rows_of(batch?.as_ref())?;
+        }
+        Ok(visitor
+            .removes
+            .into_iter()
+            .map(|remove| RemoveFileInfo {
+                path: remove.path,
+                deletion_timestamp: remove.deletion_timestamp,
+                data_change: remove.data_change,
+            })
+            .collect())
+    }
+
+    /// Iterate over the commits of this table with versions in `versions`, in commit order, along
+    /// with the actions each commit contains.
+    ///
+    /// Commits are read lazily, one commit file per item of the iterator; no snapshot is
+    /// reconstructed. The timestamp of a commit is its in-commit timestamp if it has one, and
+    /// otherwise the last modification time of its commit file.
+    pub fn commits<'a>(
+        &self,
+        engine: &'a dyn Engine,
+        versions: RangeInclusive<Version>,
+    ) -> DeltaResult<impl Iterator<Item = DeltaResult<Commit>> + 'a> {
+        let log_segment = LogSegment::for_table_changes(
+            engine.storage_handler().as_ref(),
+            self.log_root()?,
+            *versions.start(),
+            *versions.end(),
+        )?;
+        let commits = log_segment.ascending_commit_files.into_iter();
+        Ok(commits.map(move |commit| {
+            let mut visitor = ActionVisitor::default();
+            for batch in engine.json_handler().read_json_files(
+                std::slice::from_ref(&commit.location),
+                ActionVisitor::read_schema(),
+                None,
+            )? {
+                visitor.visit_rows_of(batch?.as_ref())?;
+            }
+            let in_commit_timestamp = visitor.actions.iter().find_map(|action| match action {
+                Action::CommitInfo(commit_info) => commit_info.in_commit_timestamp,
+                _ => None,
+            });
+            Ok(Commit {
+                version: commit.version,
+                timestamp: in_commit_timestamp.unwrap_or(commit.location.last_modified),
+                actions: visitor.actions,
+            })
+        }))
+    }
+
+    /// Get the history of this table: the `commitInfo` of each commit, like `DESCRIBE HISTORY`.
+    ///
+    /// The entries are ordered by descending version, and only the most recent `limit` commits are
+    /// read if a `limit` is given. Commits that were cleaned up from the log are not part of the
+    /// history. A commit without a `commitInfo` action produces an entry with only its version set.
+    pub fn history(
+        &self,
+        engine: &dyn Engine,
+        limit: Option<usize>,
+    ) -> DeltaResult<Vec<HistoryEntry>> {
+        let commit_files =
+            list_commit_files(engine.storage_handler().as_ref(), &self.log_root()?, None)?;
+        let (Some(first), Some(last)) = (commit_files.first(), commit_files.last()) else {
+            return Ok(vec![]);
+        };
+        let limit = limit.unwrap_or(commit_files.len());
+        if limit == 0 {
+            return Ok(vec![]);
+        }
+        let start = first
+            .version
+            .max((last.version + 1).saturating_sub(limit as u64));
+        let mut history: Vec<_> = self
+            .commits(engine, start..=last.version)?
+            .map_ok(|commit| {
+                let commit_info = commit.actions.into_iter().find_map(|action| match action {
+                    Action::CommitInfo(commit_info) => Some(commit_info),
+                    _ => None,
+                });
+                match commit_info {
+                    Some(commit_info) => HistoryEntry {
+                        version: commit.version,
+                        timestamp: commit_info.in_commit_timestamp.or(commit_info.timestamp),
+                        operation: commit_info.operation,
+                        operation_parameters: commit_info.operation_parameters,
+                        user_metadata: commit_info.user_metadata,
+                    },
+                    None => HistoryEntry {
+                        version: commit.version,
+                        ..Default::default()
+                    },
+                }
+            })
+            .try_collect()?;
+        history.reverse();
+        Ok(history)
+    }
+
     /// Create a new write transaction for this table.
     pub fn new_transaction(&self, engine: &dyn Engine) -> DeltaResult<Transaction> {
         Transaction::try_new(self.snapshot(engine, None)?)
     }
+
+    /// Create a brand-new Delta table at `table_root` by writing its initial commit (version 0),
+    /// and return a [`Snapshot`] of the new table.
+    ///
+    /// The commit contains a `commitInfo`, a `protocol` and a `metaData` action. The protocol is
+    /// the minimal one required by the features `properties` enable: column mapping bumps it to
+    /// (2, 5) and deletion vectors to table features (3, 7). When a column mapping mode is
+    /// requested, `schema` must already carry the column mapping annotations for every field.
+    ///
+    /// The commit is written with put-if-absent semantics, so if the table already exists (or a
+    /// concurrent create wins the race) this fails with [`Error::FileAlreadyExists`].
+    pub fn create(
+        engine: &dyn Engine,
+        table_root: Url,
+        schema: &StructType,
+        partition_columns: &[String],
+        properties: HashMap<String, String>,
+    ) -> DeltaResult<Snapshot> {
+        let table_properties = TableProperties::from(properties.iter());
+        let column_mapping_mode = table_properties
+            .column_mapping_mode
+            .unwrap_or(ColumnMappingMode::None);
+        validate_schema_column_mapping(schema, column_mapping_mode)?;
+        validate_partition_columns(schema, partition_columns)?;
+        let protocol = protocol_for_new_table(&table_properties)?;
+        // Make sure we can at least read back the table we are about to create.
+        protocol.ensure_read_supported()?;
+
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .ok()
+            .and_then(|d| i64::try_from(d.as_millis()).ok())
+            .ok_or_else(|| Error::generic("Failed to get current time for table creation"))?;
+        let actions = [
+            serde_json::json!({
+                "commitInfo": {
+                    "timestamp": timestamp,
+                    "operation": "CREATE TABLE",
+                    "operationParameters": {},
+                    "kernelVersion": format!("v{KERNEL_VERSION}"),
+                }
+            }),
+            serde_json::json!({ "protocol": protocol }),
+            serde_json::json!({
+                "metaData": {
+                    "id": uuid::Uuid::new_v4().to_string(),
+                    "format": { "provider": "parquet", "options": {} },
+                    "schemaString": serde_json::to_string(schema)?,
+                    "partitionColumns": partition_columns,
+                    "createdTime": timestamp,
+                    "configuration": properties,
+                }
+            }),
+        ];
+
+        // Like the add actions of a transaction, the actions are serialized to json and parsed by
+        // the engine into single-row batches with the log schema.
+        let json_schema = Arc::new(StructType::new(vec![StructField::not_null(
+            "json",
+            DataType::STRING,
+        )]));
+        let actions = actions.map(|action| {
+            let json_strings = engine
+                .evaluation_handler()
+                .create_one(json_schema.clone(), &[action.to_string().into()])?;
+            engine
+                .json_handler()
+                .parse_json(json_strings, get_log_schema().clone())
+        });
+
+        let commit_path = ParsedLogPath::new_commit(&table_root, 0)?;
+        engine.json_handler().write_json_file(
+            &commit_path.location,
+            Box::new(actions.into_iter()),
+            false,
+        )?;
+        Snapshot::try_new(table_root, engine, Some(0))
+    }
+}
+
+/// A single commit of the table and the actions it contains, as returned by [`Table::commits`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Commit {
+    /// The version of the commit.
+    pub version: Version,
+    /// The timestamp of the commit, as milliseconds since the epoch.
+    pub timestamp: i64,
+    /// The actions of the commit, in the order they appear in the commit file.
+    pub actions: Vec<Action>,
+}
+
+/// The `commitInfo` of a single commit, as returned by [`Table::history`]. All fields but the
+/// version are `None` if the commit has no `commitInfo` action or the action lacks the field.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct HistoryEntry {
+    /// The version of the commit.
+    pub version: Version,
+    /// The time of the commit as milliseconds since the epoch: its in-commit timestamp if it has
+    /// one, and otherwise the `timestamp` of its `commitInfo`.
+    pub timestamp: Option<i64>,
+    /// The name of the operation that produced the commit, e.g. `WRITE` or `OPTIMIZE`.
+    pub operation: Option<String>,
+    /// The parameters of the operation.
+    pub operation_parameters: Option<HashMap<String, String>>,
+    /// The user-defined metadata attached to the commit.
+    pub user_metadata: Option<String>,
+}
+
+/// A file removed from the table by a `remove` action, as returned by [`Table::removed_files`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RemoveFileInfo {
+    /// The path of the removed file, relative to the table root.
+    pub path: String,
+    /// When the file was removed, in milliseconds since the epoch.
+    pub deletion_timestamp: Option<i64>,
+    /// Whether the removal changed the data of the table (false for e.g. compactions).
+    pub data_change: bool,
+}
+
+/// Partition columns must be distinct, top-level, primitive columns of the table schema.
+fn validate_partition_columns(
+    schema: &StructType,
+    partition_columns: &[String],
+) -> DeltaResult<()> {
+    let mut seen = HashSet::new();
+    for column in partition_columns {
+        let field = schema.field(column).ok_or_else(|| {
+            Error::generic(format!("Partition column {column} not found in the schema"))
+        })?;
+        if !matches!(field.data_type(), DataType::Primitive(_)) {
+            return Err(Error::generic(format!(
+                "Partition column {column} must have a primitive type, found {}",
+                field.data_type()
+            )));
+        }
+        if !seen.insert(column) {
+            return Err(Error::generic(format!(
+                "Partition column {column} is specified more than once"
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// The minimal protocol that supports every feature enabled by the given table properties.
+fn protocol_for_new_table(properties: &TableProperties) -> DeltaResult<Protocol> {
+    let column_mapping = properties
+        .column_mapping_mode
+        .is_some_and(|mode| mode != ColumnMappingMode::None);
+    let deletion_vectors = properties.enable_deletion_vectors == Some(true);
+    let change_data_feed = properties.enable_change_data_feed == Some(true);
+
+    if deletion_vectors {
+        // Deletion vectors are only available as a table feature, so every other enabled feature
+        // has to be listed explicitly as well.
+        let mut reader_features = vec![ReaderFeature::DeletionVectors];
+        let mut writer_features = vec![WriterFeature::DeletionVectors];
+        if column_mapping {
+            reader_features.push(ReaderFeature::ColumnMapping);
+            writer_features.push(WriterFeature::ColumnMapping);
+        }
+        if properties.append_only == Some(true) {
+            writer_features.push(WriterFeature::AppendOnly);
+        }
+        if change_data_feed {
+            writer_features.push(WriterFeature::ChangeDataFeed);
+        }
+        return Protocol::try_new(3, 7, Some(reader_features), Some(writer_features));
+    }
+
+    let (min_reader_version, min_writer_version) = match (column_mapping, change_data_feed) {
+        (true, _) => (2, 5),
+        (false, true) => (1, 4),
+        (false, false) => (1, 2),
+    };
+    Protocol::try_new(
+        min_reader_version,
+        min_writer_version,
+        None::<Vec<String>>,
+        None::<Vec<String>>,
+    )
 }
 
 #[derive(Debug)]
@@ -144,6 +508,8 @@ fn resolve_uri_type(table_uri: impl AsRef<str>) -> DeltaResult<UriType> {
 mod tests {
     use std::path::PathBuf;
 
+    use itertools::Itertools;
+
     use super::*;
     use crate::engine::sync::SyncEngine;
 
@@ -209,4 +575,344 @@ mod tests {
             "s3://foo/__unitystorage/catalogs/cid/tables/tid/_delta_log/"
         );
     }
+
+    fn test_schema() -> StructType {
+        StructType::new([
+            StructField::nullable("id", DataType::LONG),
+            StructField::nullable("value", DataType::STRING),
+            StructField::nullable("part", DataType::INTEGER),
+        ])
+    }
+
+    /// Write each entry of `commits` as the json commit of its index into the log of `dir`
+    fn write_commits(dir: &std::path::Path, commits: &[&[serde_json::Value]]) {
+        let log_dir = dir.join("_delta_log");
+        std::fs::create_dir_all(&log_dir).unwrap();
+        for (version, actions) in commits.iter().enumerate() {
+            let commit = actions.iter().map(|action| format!("{action}\n"));
+            std::fs::write(
+                log_dir.join(format!("{version:020}.json")),
+                commit.collect::<String>(),
+            )
+            .unwrap();
+        }
+    }
+
+    /// The protocol and metaData actions of a table with [`test_schema`]
+    fn protocol_and_metadata() -> [serde_json::Value; 2] {
+        [
+            serde_json::json!({"protocol": {"minReaderVersion": 1, "minWriterVersion": 2}}),
+            serde_json::json!({"metaData": {"id": "id", "format": {"provider": "parquet",
+                "options": {}}, "schemaString": serde_json::to_string(&test_schema()).unwrap(),
+                "partitionColumns": [], "configuration": {}}}),
+        ]
+    }
+
+    fn add(path: &str) -> serde_json::Value {
+        serde_json::json!({"add": {"path": path, "partitionValues": {}, "size": 1,
+            "modificationTime": 1, "dataChange": true}})
+    }
+
+    #[test]
+    fn test_create_table() {
+        let dir = tempfile::tempdir().unwrap();
+        let table_root = Url::from_directory_path(dir.path()).unwrap();
+        let engine = SyncEngine::new();
+        let schema = test_schema();
+
+        let snapshot =
+            Table::create(&engine, table_root.clone(), &schema, &[], HashMap::new()).unwrap();
+        assert_eq!(snapshot.version(), 0);
+        assert_eq!(snapshot.schema().as_ref(), &schema);
+        assert!(snapshot.metadata().partition_columns.is_empty());
+        assert_eq!(snapshot.protocol().min_reader_version(), 1);
+        assert_eq!(snapshot.protocol().min_writer_version(), 2);
+
+        // the table can be read back independently of the create call
+        let snapshot = Table::new(table_root.clone())
+            .snapshot(&engine, None)
+            .unwrap();
+        assert_eq!(snapshot.version(), 0);
+        assert_eq!(snapshot.schema().as_ref(), &schema);
+
+        // creating the table again must not overwrite the existing commit
+        let result = Table::create(&engine, table_root, &schema, &[], HashMap::new());
+        assert!(matches!(result, Err(Error::FileAlreadyExists(_))));
+    }
+
+    #[test]
+    fn test_create_partitioned_table() {
+        let dir = tempfile::tempdir().unwrap();
+        let table_root = Url::from_directory_path(dir.path()).unwrap();
+        let engine = SyncEngine::new();
+        let schema = test_schema();
+        let properties = HashMap::from([
+            (
+                "delta.enableDeletionVectors".to_string(),
+                "true".to_string(),
+            ),
+            ("delta.appendOnly".to_string(), "true".to_string()),
+        ]);
+
+        let snapshot = Table::create(
+            &engine,
+            table_root,
+            &schema,
+            &["part".to_string()],
+            properties,
+        )
+        .unwrap();
+        assert_eq!(snapshot.version(), 0);
+        assert_eq!(snapshot.metadata().partition_columns, vec!["part"]);
+        assert_eq!(
+            snapshot.table_properties().enable_deletion_vectors,
+            Some(true)
+        );
+        let protocol = snapshot.protocol();
+        assert_eq!(protocol.min_reader_version(), 3);
+        assert_eq!(protocol.min_writer_version(), 7);
+        assert_eq!(
+            protocol.reader_features(),
+            Some(&[ReaderFeature::DeletionVectors][..])
+        );
+        assert_eq!(
+            protocol.writer_features(),
+            Some(&[WriterFeature::DeletionVectors, WriterFeature::AppendOnly][..])
+        );
+    }
+
+    #[test]
+    fn test_create_table_invalid() {
+        let dir = tempfile::tempdir().unwrap();
+        let table_root = Url::from_directory_path(dir.path()).unwrap();
+        let engine = SyncEngine::new();
+        let schema = test_schema();
+
+        // partition columns must exist in the schema
+        let result = Table::create(
+            &engine,
+            table_root.clone(),
+            &schema,
+            &["missing".to_string()],
+            HashMap::new(),
+        );
+        assert!(result.is_err());
+
+        // column mapping requires an annotated schema
+        let properties =
+            HashMap::from([("delta.columnMapping.mode".to_string(), "name".to_string())]);
+        let result = Table::create(&engine, table_root.clone(), &schema, &[], properties);
+        assert!(matches!(result, Err(Error::InvalidColumnMappingMode(_))));
+
+        // nothing was committed by the failed attempts
+        assert!(Table::new(table_root).snapshot(&engine, None).is_err());
+    }
+
+    #[test]
+    fn test_removed_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let remove = |path: &str, timestamp: i64, data_change: bool| {
+            serde_json::json!({"remove": {"path": path, "deletionTimestamp": timestamp,
+                "dataChange": data_change}})
+        };
+        let [protocol, metadata] = protocol_and_metadata();
+        write_commits(
+            dir.path(),
+            &[
+                &[protocol, metadata, add("a.parquet"), add("b.parquet")],
+                &[remove("a.parquet", 10, true), add("c.parquet")],
+                &[add("d.parquet")],
+                &[
+                    remove("b.parquet", 30, false),
+                    remove("c.parquet", 30, false),
+                    add("e.parquet"),
+                ],
+                &[remove("d.parquet", 40, true)],
+            ],
+        );
+
+        let engine = SyncEngine::new();
+        let table = Table::new(Url::from_directory_path(dir.path()).unwrap());
+        let info = |path: &str, timestamp, data_change| RemoveFileInfo {
+            path: path.to_string(),
+            deletion_timestamp: Some(timestamp),
+            data_change,
+        };
+
+        let removed = table.removed_files(&engine, 1, 3).unwrap();
+        assert_eq!(
+            removed,
+            vec![
+                info("a.parquet", 10, true),
+                info("b.parquet", 30, false),
+                info("c.parquet", 30, false),
+            ]
+        );
+
+        // no end version reads up to the latest commit
+        let removed = table.removed_files(&engine, 2, None).unwrap();
+        assert_eq!(
+            removed,
+            vec![
+                info("b.parquet", 30, false),
+                info("c.parquet", 30, false),
+                info("d.parquet", 40, true),
+            ]
+        );
+
+        assert!(table.removed_files(&engine, 0, 0).unwrap().is_empty());
+        assert!(table.removed_files(&engine, 3, 1).is_err());
+    }
+
+    #[test]
+    fn test_commits() {
+        let path = std::fs::canonicalize(PathBuf::from("./tests/data/type-widening/")).unwrap();
+        let table = Table::new(Url::from_directory_path(path).unwrap());
+        let engine = SyncEngine::new();
+        let kind = |action: &Action| match action {
+            Action::Add(_) => "add",
+            Action::Remove(_) => "remove",
+            Action::Metadata(_) => "metaData",
+            Action::Protocol(_) => "protocol",
+            Action::SetTransaction(_) => "txn",
+            Action::CommitInfo(_) => "commitInfo",
+            Action::Cdc(_) => "cdc",
+            Action::DomainMetadata(_) => "domainMetadata",
+        };
+
+        let commits: Vec<_> = table
+            .commits(&engine, 0..=2)
+            .unwrap()
+            .try_collect()
+            .unwrap();
+        let versions_and_kinds: Vec<_> = commits
+            .iter()
+            .map(|commit| {
+                let kinds: Vec<_> = commit.actions.iter().map(kind).collect();
+                (commit.version, kinds)
+            })
+            .collect();
+        assert_eq!(
+            versions_and_kinds,
+            vec![
+                (0, vec!["commitInfo", "metaData", "protocol", "add"]),
+                (1, vec!["commitInfo", "metaData", "protocol"]),
+                (2, vec!["commitInfo", "metaData", "add"]),
+            ]
+        );
+        let Action::CommitInfo(commit_info) = &commits[1].actions[0] else {
+            panic!("Expected a commitInfo action");
+        };
+        assert_eq!(commit_info.timestamp, Some(1726233754010));
+        let Action::Protocol(protocol) = &commits[1].actions[2] else {
+            panic!("Expected a protocol action");
+        };
+        assert_eq!(protocol.min_reader_version(), 3);
+
+        let commits: Vec<_> = table
+            .commits(&engine, 1..=1)
+            .unwrap()
+            .try_collect()
+            .unwrap();
+        assert_eq!(commits.len(), 1);
+        assert_eq!(commits[0].version, 1);
+
+        // the range must not extend past the latest commit
+        assert!(table.commits(&engine, 1..=3).is_err());
+    }
+
+    #[test]
+    fn test_history() {
+        let dir = tempfile::tempdir().unwrap();
+        let [protocol, metadata] = protocol_and_metadata();
+        write_commits(
+            dir.path(),
+            &[
+                &[
+                    serde_json::json!({"commitInfo": {"timestamp": 100, "operation": "WRITE",
+                        "operationParameters": {"mode": "ErrorIfExists"}}}),
+                    protocol,
+                    metadata,
+                    add("a.parquet"),
+                ],
+                &[
+                    serde_json::json!({"commitInfo": {"timestamp": 200, "operation": "WRITE",
+                        "operationParameters": {"mode": "Append"},
+                        "userMetadata": "nightly load"}}),
+                    add("b.parquet"),
+                ],
+                // a commit without commitInfo
+                &[add("c.parquet")],
+                &[
+                    add("d.parquet"),
+                    serde_json::json!({"commitInfo": {"timestamp": 400, "inCommitTimestamp": 399,
+                        "operation": "OPTIMIZE", "operationParameters": {"predicate": "[]"}}}),
+                    serde_json::json!({"remove": {"path": "a.parquet", "dataChange": false}}),
+                ],
+            ],
+        );
+
+        let engine = SyncEngine::new();
+        let table = Table::new(Url::from_directory_path(dir.path()).unwrap());
+        let history = table.history(&engine, None).unwrap();
+        let params = |key: &str, value: &str| Some(HashMap::from([(key.into(), value.into())]));
+        assert_eq!(
+            history,
+            vec![
+                HistoryEntry {
+                    version: 3,
+                    timestamp: Some(399),
+                    operation: Some("OPTIMIZE".into()),
+                    operation_parameters: params("predicate", "[]"),
+                    user_metadata: None,
+                },
+                HistoryEntry {
+                    version: 2,
+                    ..Default::default()
+                },
+                HistoryEntry {
+                    version: 1,
+                    timestamp: Some(200),
+                    operation: Some("WRITE".into()),
+                    operation_parameters: params("mode", "Append"),
+                    user_metadata: Some("nightly load".into()),
+                },
+                HistoryEntry {
+                    version: 0,
+                    timestamp: Some(100),
+                    operation: Some("WRITE".into()),
+                    operation_parameters: params("mode", "ErrorIfExists"),
+                    user_metadata: None,
+                },
+            ]
+        );
+
+        let history = table.history(&engine, Some(2)).unwrap();
+        let versions: Vec<_> = history.iter().map(|entry| entry.version).collect();
+        assert_eq!(versions, vec![3, 2]);
+        assert!(table.history(&engine, Some(0)).unwrap().is_empty());
+        assert_eq!(table.history(&engine, Some(10)).unwrap().len(), 4);
+    }
+
+    #[test]
+    fn test_protocol_for_new_table() {
+        let protocol = |props: &[(&str, &str)]| {
+            protocol_for_new_table(&TableProperties::from(props.iter().copied())).unwrap()
+        };
+        let p = protocol(&[]);
+        assert_eq!((p.min_reader_version(), p.min_writer_version()), (1, 2));
+        let p = protocol(&[("delta.columnMapping.mode", "id")]);
+        assert_eq!((p.min_reader_version(), p.min_writer_version()), (2, 5));
+        let p = protocol(&[("delta.columnMapping.mode", "none")]);
+        assert_eq!((p.min_reader_version(), p.min_writer_version()), (1, 2));
+        let p = protocol(&[("delta.enableChangeDataFeed", "true")]);
+        assert_eq!((p.min_reader_version(), p.min_writer_version()), (1, 4));
+        let p = protocol(&[
+            ("delta.columnMapping.mode", "name"),
+            ("delta.enableDeletionVectors", "true"),
+        ]);
+        assert_eq!((p.min_reader_version(), p.min_writer_version()), (3, 7));
+        assert!(p.has_reader_feature(&ReaderFeature::ColumnMapping));
+        assert!(p.has_writer_feature(&WriterFeature::DeletionVectors));
+    }
 }
@@ -138,9 +138,21 @@ impl TableChanges {
         end_version: Option<Version>,
     ) -> DeltaResult<Self> {
         let log_root = table_root.join("_delta_log/")?;
+        Self::try_new_with_log_root(table_root, log_root, engine, start_version, end_version)
+    }
+
+    /// Like [`TableChanges::try_new`], but reading the log from `log_root` instead of the
+    /// `_delta_log` directory under `table_root`.
+    pub(crate) fn try_new_with_log_root(
+        table_root: Url,
+        log_root: Url,
+        engine: &dyn Engine,
+        start_version: Version,
+        end_version: Option<Version>,
+    ) -> DeltaResult<Self> {
         let log_segment = LogSegment::for_table_changes(
             engine.storage_handler().as_ref(),
-            log_root,
+            log_root.clone(),
             start_version,
             end_version,
         )?;
@@ -148,8 +160,9 @@ impl TableChanges {
         // Both snapshots ensure that reading is supported at the start and end version using
         // `ensure_read_supported`. Note that we must still verify that reading is
         // supported for every protocol action in the CDF range.
-        let start_snapshot = Arc::new(Snapshot::try_new(
+        let start_snapshot = Arc::new(Snapshot::try_new_with_log_root(
             table_root.as_url().clone(),
+            log_root,
             engine,
             Some(start_version),
         )?);
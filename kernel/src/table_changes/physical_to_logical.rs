@@ -49,8 +49,10 @@ pub(crate) fn physical_to_logical_expr(
                     ));
                 };
                 let name = field.physical_name();
-                let value_expression =
-                    parse_partition_value(scan_file.partition_values.get(name), field.data_type())?;
+                let value_expression = parse_partition_value(
+                    scan_file.partition_values.get(name).map(String::as_str),
+                    field.data_type(),
+                )?;
                 Ok(value_expression.into())
             }
             ColumnType::Selected(field_name) => {
@@ -7,7 +7,7 @@ use tracing::debug;
 use url::Url;
 
 use crate::actions::deletion_vector::split_vector;
-use crate::scan::state::GlobalScanState;
+use crate::scan::state::{resolve_file_path, GlobalScanState};
 use crate::scan::{ColumnType, PhysicalPredicate, ScanResult};
 use crate::schema::{SchemaRef, StructType};
 use crate::{DeltaResult, Engine, ExpressionRef, FileMeta};
@@ -153,7 +153,9 @@ impl TableChangesScanBuilder {
                 } else {
                     // Add to read schema, store field so we can build a `Column` expression later
                     // if needed (i.e. if we have partition columns)
-                    let physical_field = logical_field.make_physical();
+                    let physical_field = logical_field.make_physical_with_mode(
+                        self.table_changes.end_snapshot.column_mapping_mode(),
+                    );
                     debug!("\n\n{logical_field:#?}\nAfter mapping: {physical_field:#?}\n\n");
                     let physical_name = physical_field.name.clone();
                     read_fields.push(physical_field);
@@ -162,7 +164,14 @@ impl TableChangesScanBuilder {
             })
             .try_collect()?;
         let physical_predicate = match self.predicate {
-            Some(predicate) => PhysicalPredicate::try_new(&predicate, &logical_schema)?,
+            Some(predicate) => PhysicalPredicate::try_new(&predicate, &logical_schema)?
+                .with_stats_columns(
+                    &self
+                        .table_changes
+                        .end_snapshot
+                        .table_configuration()
+                        .stats_columns_schema(),
+                ),
             None => PhysicalPredicate::None,
         };
 
@@ -296,7 +305,7 @@ fn read_scan_file(
     let is_dv_resolved_pair = scan_file.remove_dv.is_some();
 
     let table_root = Url::parse(&global_state.table_root)?;
-    let location = table_root.join(&scan_file.path)?;
+    let location = resolve_file_path(&table_root, &scan_file.path)?;
     let file = FileMeta {
         last_modified: 0,
         size: 0,
@@ -13,12 +13,15 @@ use std::sync::{Arc, LazyLock};
 use url::Url;
 
 use crate::actions::{ensure_supported_features, Metadata, Protocol};
-use crate::schema::{InvariantChecker, SchemaRef};
+use crate::expressions::ColumnName;
+use crate::schema::{
+    DataType, InvariantChecker, PrimitiveType, SchemaRef, StructField, StructType,
+};
 use crate::table_features::{
     column_mapping_mode, validate_schema_column_mapping, ColumnMappingMode, ReaderFeature,
     WriterFeature,
 };
-use crate::table_properties::TableProperties;
+use crate::table_properties::{DataSkippingNumIndexedCols, TableProperties};
 use crate::{DeltaResult, Error, Version};
 use delta_kernel_derive::internal_api;
 
@@ -38,6 +41,7 @@ pub(crate) struct TableConfiguration {
     metadata: Metadata,
     protocol: Protocol,
     schema: SchemaRef,
+    physical_schema: SchemaRef,
     table_properties: TableProperties,
     column_mapping_mode: ColumnMappingMode,
     table_root: Url,
@@ -78,8 +82,14 @@ impl TableConfiguration {
 
         // validate column mapping mode -- all schema fields should be correctly (un)annotated
         validate_schema_column_mapping(&schema, column_mapping_mode)?;
+        let physical_schema = Arc::new(StructType::new(
+            schema
+                .fields()
+                .map(|field| field.make_physical_with_mode(column_mapping_mode)),
+        ));
         Ok(Self {
             schema,
+            physical_schema,
             metadata,
             protocol,
             table_properties,
@@ -132,6 +142,13 @@ impl TableConfiguration {
         self.schema.clone()
     }
 
+    /// The physical schema ([`SchemaRef`]) of this table at this version, i.e. the logical schema
+    /// with column mapping physical names applied.
+    #[internal_api]
+    pub(crate) fn physical_schema(&self) -> SchemaRef {
+        self.physical_schema.clone()
+    }
+
     /// The [`TableProperties`] of this table at this version.
     #[internal_api]
     pub(crate) fn table_properties(&self) -> &TableProperties {
@@ -255,6 +272,92 @@ impl TableConfiguration {
         self.is_append_only_supported() && self.table_properties.append_only.unwrap_or(false)
     }
 
+    /// Returns `true` if in-commit timestamps are enabled for this table. This is the case when the
+    /// table is on writer version 7 with the [`WriterFeature::InCommitTimestamp`] writer feature,
+    /// and the `delta.enableInCommitTimestamps` table property is set to `true`.
+    ///
+    /// See: <https://github.com/delta-io/delta/blob/master/PROTOCOL.md#in-commit-timestamps>
+    pub(crate) fn is_in_commit_timestamps_enabled(&self) -> bool {
+        self.protocol.min_writer_version() == 7
+            && self
+                .protocol
+                .has_writer_feature(&WriterFeature::InCommitTimestamp)
+            && self
+                .table_properties
+                .enable_in_commit_timestamps
+                .unwrap_or(false)
+    }
+
+    /// Returns the first version whose commit carries an in-commit timestamp, together with that
+    /// timestamp if it was recorded, or `None` if in-commit timestamps are not enabled. When the
+    /// feature was enabled at table creation, no enablement properties are recorded and every
+    /// version has an in-commit timestamp.
+    pub(crate) fn in_commit_timestamp_enablement(&self) -> Option<(Version, Option<i64>)> {
+        if !self.is_in_commit_timestamps_enabled() {
+            return None;
+        }
+        let properties = &self.table_properties;
+        Some((
+            properties
+                .in_commit_timestamp_enablement_version
+                .unwrap_or(0),
+            properties.in_commit_timestamp_enablement_timestamp,
+        ))
+    }
+
+    /// The schema of the statistics writers record in the `stats` of add actions for this table:
+    ///
+    /// ```ignored
+    /// {
+    ///    numRecords: long,
+    ///    nullCount: { <indexed columns, as long> },
+    ///    minValues: { <indexed columns that support min/max statistics> },
+    ///    maxValues: { <indexed columns that support min/max statistics> },
+    ///    tightBounds: boolean,
+    /// }
+    /// ```
+    ///
+    /// See [`TableConfiguration::stats_columns_schema`] for which columns are indexed.
+    pub(crate) fn stats_schema(&self) -> StructType {
+        let indexed = self.stats_columns_schema();
+        let null_count = map_stats_leaves(&indexed, &mut |_| Some(DataType::LONG));
+        let min_max = map_stats_leaves(&indexed, &mut |data_type| match data_type {
+            DataType::Primitive(PrimitiveType::Boolean | PrimitiveType::Binary) => None,
+            DataType::Primitive(_) => Some(data_type.clone()),
+            _ => None,
+        });
+        StructType::new([
+            StructField::nullable("numRecords", DataType::LONG),
+            StructField::nullable("nullCount", null_count),
+            StructField::nullable("minValues", min_max.clone()),
+            StructField::nullable("maxValues", min_max),
+            StructField::nullable("tightBounds", DataType::BOOLEAN),
+        ])
+    }
+
+    /// The (physical) schema of the columns writers collect statistics for. These are the columns
+    /// named by `delta.dataSkippingStatsColumns` if it is set, and otherwise the first
+    /// `delta.dataSkippingNumIndexedCols` leaf columns in schema order. Partition columns are never
+    /// indexed. Nested columns keep their struct nesting, and all fields use physical names and are
+    /// nullable, since stats may be missing for any column.
+    pub(crate) fn stats_columns_schema(&self) -> StructType {
+        let num_indexed_cols = match self.table_properties.data_skipping_num_indexed_cols() {
+            DataSkippingNumIndexedCols::AllColumns => None,
+            DataSkippingNumIndexedCols::NumColumns(n) => Some(n),
+        };
+        let mut builder = StatsColumnsBuilder {
+            stats_columns: self.table_properties.data_skipping_stats_columns.as_deref(),
+            remaining: num_indexed_cols,
+            path: vec![],
+        };
+        let partition_columns = &self.metadata.partition_columns;
+        builder.build(
+            self.schema
+                .fields()
+                .filter(|field| !partition_columns.contains(field.name())),
+        )
+    }
+
     /// Returns `true` if the table supports the column invariant table feature.
     pub(crate) fn is_invariants_supported(&self) -> bool {
         let protocol = &self.protocol;
@@ -265,6 +368,77 @@ impl TableConfiguration {
     }
 }
 
+/// Builds [`TableConfiguration::stats_columns_schema`].
+struct StatsColumnsBuilder<'a> {
+    /// Explicitly indexed columns, from `delta.dataSkippingStatsColumns`
+    stats_columns: Option<&'a [ColumnName]>,
+    /// How many more leaf columns may be indexed, if there is no explicit list
+    remaining: Option<u64>,
+    /// Logical path of the current field
+    path: Vec<String>,
+}
+
+impl StatsColumnsBuilder<'_> {
+    fn build<'f>(&mut self, fields: impl Iterator<Item = &'f StructField>) -> StructType {
+        let mut indexed = vec![];
+        for field in fields {
+            self.path.push(field.name().clone());
+            let name = field.physical_name();
+            match field.data_type() {
+                DataType::Struct(inner) => {
+                    let inner = self.build(inner.fields());
+                    if inner.fields_len() > 0 {
+                        indexed.push(StructField::nullable(name, inner));
+                    }
+                }
+                data_type if self.is_indexed() => {
+                    indexed.push(StructField::nullable(name, data_type.clone()));
+                }
+                _ => {}
+            }
+            self.path.pop();
+        }
+        StructType::new(indexed)
+    }
+
+    // Whether the leaf column at `self.path` is indexed. Leaves are visited in schema order, so
+    // without an explicit list this counts down the remaining number of indexed columns.
+    fn is_indexed(&mut self) -> bool {
+        if let Some(stats_columns) = self.stats_columns {
+            // naming a struct column indexes all of its leaves
+            return stats_columns
+                .iter()
+                .any(|column| self.path.starts_with(column.path()));
+        }
+        match self.remaining.as_mut() {
+            None => true,
+            Some(0) => false,
+            Some(remaining) => {
+                *remaining -= 1;
+                true
+            }
+        }
+    }
+}
+
+// Map the (non-struct) leaves of `schema` with `f`, dropping those it maps to `None` along with any
+// structs left empty.
+fn map_stats_leaves(
+    schema: &StructType,
+    f: &mut impl FnMut(&DataType) -> Option<DataType>,
+) -> StructType {
+    StructType::new(schema.fields().filter_map(|field| {
+        let data_type = match field.data_type() {
+            DataType::Struct(inner) => {
+                let inner = map_stats_leaves(inner, f);
+                (inner.fields_len() > 0).then(|| inner.into())
+            }
+            data_type => f(data_type),
+        }?;
+        Some(StructField::nullable(field.name(), data_type))
+    }))
+}
+
 #[cfg(test)]
 mod test {
     use std::collections::HashMap;
@@ -94,6 +94,8 @@ pub enum WriterFeature {
     DeletionVectors,
     /// Row tracking on tables
     RowTracking,
+    /// Monotonic commit timestamps recorded in the commitInfo action
+    InCommitTimestamp,
     /// timestamps without timezone support
     #[strum(serialize = "timestampNtz")]
     #[serde(rename = "timestampNtz")]
@@ -119,6 +121,59 @@ pub enum WriterFeature {
     Unknown(String),
 }
 
+/// A feature enabled in a table's protocol.
+///
+/// Reader-writer features (such as deletion vectors) are listed in both the reader and the writer
+/// features of the protocol, and so appear both as a [`TableFeature::Reader`] and as a
+/// [`TableFeature::Writer`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum TableFeature {
+    /// A feature listed in the protocol's `readerFeatures`
+    Reader(ReaderFeature),
+    /// A feature listed in the protocol's `writerFeatures`
+    Writer(WriterFeature),
+}
+
+impl TableFeature {
+    /// Whether this feature is listed in the protocol's reader or writer features.
+    pub fn kind(&self) -> ReaderOrWriter {
+        match self {
+            TableFeature::Reader(_) => ReaderOrWriter::Reader,
+            TableFeature::Writer(_) => ReaderOrWriter::Writer,
+        }
+    }
+}
+
+impl std::fmt::Display for TableFeature {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TableFeature::Reader(feature) => write!(f, "{feature}"),
+            TableFeature::Writer(feature) => write!(f, "{feature}"),
+        }
+    }
+}
+
+impl From<ReaderFeature> for TableFeature {
+    fn from(feature: ReaderFeature) -> Self {
+        TableFeature::Reader(feature)
+    }
+}
+
+impl From<WriterFeature> for TableFeature {
+    fn from(feature: WriterFeature) -> Self {
+        TableFeature::Writer(feature)
+    }
+}
+
+/// Whether a [`TableFeature`] is a reader feature, required to read the table, or a writer
+/// feature, required to write to it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, StrumDisplay)]
+#[strum(serialize_all = "lowercase")]
+pub enum ReaderOrWriter {
+    Reader,
+    Writer,
+}
+
 impl ToDataType for ReaderFeature {
     fn to_data_type() -> DataType {
         DataType::STRING
@@ -246,6 +301,7 @@ mod tests {
             (WriterFeature::IdentityColumns, "identityColumns"),
             (WriterFeature::DeletionVectors, "deletionVectors"),
             (WriterFeature::RowTracking, "rowTracking"),
+            (WriterFeature::InCommitTimestamp, "inCommitTimestamp"),
             (WriterFeature::TimestampWithoutTimezone, "timestampNtz"),
             (WriterFeature::TypeWidening, "typeWidening"),
             (WriterFeature::TypeWideningPreview, "typeWidening-preview"),
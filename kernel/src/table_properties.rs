@@ -22,6 +22,7 @@ use strum::EnumString;
 
 mod deserialize;
 pub use deserialize::ParseIntervalError;
+use deserialize::{SECONDS_PER_DAY, SECONDS_PER_WEEK};
 
 /// Delta table properties. These are parsed from the 'configuration' map in the most recent
 /// 'Metadata' action of a table.
@@ -155,6 +156,121 @@ pub struct TableProperties {
     pub unknown_properties: HashMap<String, String>,
 }
 
+/// Typed accessors for the well-known properties, which fall back to the property's default (as
+/// defined by Delta) when the table doesn't set it.
+impl TableProperties {
+    /// Whether the table is append-only. Defaults to false.
+    pub fn append_only(&self) -> bool {
+        self.append_only.unwrap_or(false)
+    }
+
+    /// Whether files are automatically compacted after writes. Defaults to false.
+    pub fn auto_compact(&self) -> bool {
+        self.auto_compact.unwrap_or(false)
+    }
+
+    /// Whether writes optimize the layout of the files they write. Defaults to false.
+    pub fn optimize_write(&self) -> bool {
+        self.optimize_write.unwrap_or(false)
+    }
+
+    /// The number of commits between checkpoints. Defaults to 10.
+    pub fn checkpoint_interval(&self) -> u64 {
+        self.checkpoint_interval.map_or(10, NonZero::get)
+    }
+
+    /// Whether checkpoints store file statistics as JSON in the `stats` column. Defaults to true.
+    pub fn checkpoint_write_stats_as_json(&self) -> bool {
+        self.checkpoint_write_stats_as_json.unwrap_or(true)
+    }
+
+    /// Whether checkpoints store file statistics and partition values as structs. Defaults to
+    /// false.
+    pub fn checkpoint_write_stats_as_struct(&self) -> bool {
+        self.checkpoint_write_stats_as_struct.unwrap_or(false)
+    }
+
+    /// The column mapping mode of the table. Defaults to [`ColumnMappingMode::None`].
+    pub fn column_mapping_mode(&self) -> ColumnMappingMode {
+        self.column_mapping_mode.unwrap_or(ColumnMappingMode::None)
+    }
+
+    /// The number of leaf columns statistics are collected for. Defaults to the first 32 columns.
+    pub fn data_skipping_num_indexed_cols(&self) -> DataSkippingNumIndexedCols {
+        self.data_skipping_num_indexed_cols
+            .unwrap_or(DataSkippingNumIndexedCols::NumColumns(32))
+    }
+
+    /// How long logically deleted data files are kept before they may be physically deleted.
+    /// Defaults to one week.
+    pub fn deleted_file_retention_duration(&self) -> Duration {
+        self.deleted_file_retention_duration
+            .unwrap_or(Duration::from_secs(SECONDS_PER_WEEK))
+    }
+
+    /// Whether change data feed is enabled. Defaults to false.
+    pub fn enable_change_data_feed(&self) -> bool {
+        self.enable_change_data_feed.unwrap_or(false)
+    }
+
+    /// Whether deletion vectors are enabled. Defaults to false.
+    pub fn enable_deletion_vectors(&self) -> bool {
+        self.enable_deletion_vectors.unwrap_or(false)
+    }
+
+    /// The isolation level of transactions. Defaults to [`IsolationLevel::Serializable`].
+    pub fn isolation_level(&self) -> IsolationLevel {
+        self.isolation_level.unwrap_or_default()
+    }
+
+    /// How long the history of the table is kept. Defaults to 30 days.
+    pub fn log_retention_duration(&self) -> Duration {
+        self.log_retention_duration
+            .unwrap_or(Duration::from_secs(30 * SECONDS_PER_DAY))
+    }
+
+    /// Whether expired log files are cleaned up when checkpointing. Defaults to true.
+    pub fn enable_expired_log_cleanup(&self) -> bool {
+        self.enable_expired_log_cleanup.unwrap_or(true)
+    }
+
+    /// Whether file paths get a random prefix instead of partition directories. Defaults to false.
+    pub fn randomize_file_prefixes(&self) -> bool {
+        self.randomize_file_prefixes.unwrap_or(false)
+    }
+
+    /// The length of random file prefixes. Defaults to 2.
+    pub fn random_prefix_length(&self) -> u64 {
+        self.random_prefix_length.map_or(2, NonZero::get)
+    }
+
+    /// Whether file sizes are tuned for rewrites. Defaults to false.
+    pub fn tune_file_sizes_for_rewrites(&self) -> bool {
+        self.tune_file_sizes_for_rewrites.unwrap_or(false)
+    }
+
+    /// The kind of checkpoints to write. Defaults to [`CheckpointPolicy::Classic`].
+    pub fn checkpoint_policy(&self) -> CheckpointPolicy {
+        self.checkpoint_policy.clone().unwrap_or_default()
+    }
+
+    /// Whether row tracking is enabled. Defaults to false.
+    pub fn enable_row_tracking(&self) -> bool {
+        self.enable_row_tracking.unwrap_or(false)
+    }
+
+    /// Whether in-commit timestamps are enabled. Defaults to false.
+    pub fn enable_in_commit_timestamps(&self) -> bool {
+        self.enable_in_commit_timestamps.unwrap_or(false)
+    }
+
+    /// Properties that were not recognized, or whose value could not be parsed, as they appear in
+    /// the table's configuration.
+    pub fn unknown_properties(&self) -> &HashMap<String, String> {
+        &self.unknown_properties
+    }
+}
+
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub enum DataSkippingNumIndexedCols {
     AllColumns,
@@ -317,4 +433,87 @@ mod tests {
         };
         assert_eq!(actual, expected);
     }
+
+    #[test]
+    fn test_typed_accessors() {
+        let properties = [
+            ("delta.appendOnly", "true"),
+            ("delta.checkpointInterval", "101"),
+            ("delta.checkpoint.writeStatsAsJson", "false"),
+            ("delta.columnMapping.mode", "name"),
+            ("delta.dataSkippingNumIndexedCols", "-1"),
+            ("delta.deletedFileRetentionDuration", "interval 1 day"),
+            ("delta.enableDeletionVectors", "true"),
+            ("delta.isolationLevel", "writeSerializable"),
+            ("delta.logRetentionDuration", "interval 2 weeks"),
+            ("delta.randomPrefixLength", "5"),
+            ("delta.checkpointPolicy", "v2"),
+            ("delta.enableInCommitTimestamps", "true"),
+            ("delta.enableRowTracking", "wack"),
+            ("custom.key", "value"),
+        ];
+        let props = TableProperties::from(properties.into_iter());
+        assert!(props.append_only());
+        assert_eq!(props.checkpoint_interval(), 101);
+        assert!(!props.checkpoint_write_stats_as_json());
+        assert_eq!(props.column_mapping_mode(), ColumnMappingMode::Name);
+        assert_eq!(
+            props.data_skipping_num_indexed_cols(),
+            DataSkippingNumIndexedCols::AllColumns
+        );
+        assert_eq!(
+            props.deleted_file_retention_duration(),
+            Duration::from_secs(SECONDS_PER_DAY)
+        );
+        assert!(props.enable_deletion_vectors());
+        assert_eq!(props.isolation_level(), IsolationLevel::WriteSerializable);
+        assert_eq!(
+            props.log_retention_duration(),
+            Duration::from_secs(2 * SECONDS_PER_WEEK)
+        );
+        assert_eq!(props.random_prefix_length(), 5);
+        assert_eq!(props.checkpoint_policy(), CheckpointPolicy::V2);
+        assert!(props.enable_in_commit_timestamps());
+        // an unparsable value falls back to the default, and stays available in the raw map
+        assert!(!props.enable_row_tracking());
+        assert_eq!(
+            props.unknown_properties(),
+            &HashMap::from([
+                ("delta.enableRowTracking".to_string(), "wack".to_string()),
+                ("custom.key".to_string(), "value".to_string()),
+            ])
+        );
+
+        let props = TableProperties::default();
+        assert!(!props.append_only());
+        assert!(!props.auto_compact());
+        assert!(!props.optimize_write());
+        assert_eq!(props.checkpoint_interval(), 10);
+        assert!(props.checkpoint_write_stats_as_json());
+        assert!(!props.checkpoint_write_stats_as_struct());
+        assert_eq!(props.column_mapping_mode(), ColumnMappingMode::None);
+        assert_eq!(
+            props.data_skipping_num_indexed_cols(),
+            DataSkippingNumIndexedCols::NumColumns(32)
+        );
+        assert_eq!(
+            props.deleted_file_retention_duration(),
+            Duration::from_secs(SECONDS_PER_WEEK)
+        );
+        assert!(!props.enable_change_data_feed());
+        assert!(!props.enable_deletion_vectors());
+        assert_eq!(props.isolation_level(), IsolationLevel::Serializable);
+        assert_eq!(
+            props.log_retention_duration(),
+            Duration::from_secs(30 * SECONDS_PER_DAY)
+        );
+        assert!(props.enable_expired_log_cleanup());
+        assert!(!props.randomize_file_prefixes());
+        assert_eq!(props.random_prefix_length(), 2);
+        assert!(!props.tune_file_sizes_for_rewrites());
+        assert_eq!(props.checkpoint_policy(), CheckpointPolicy::Classic);
+        assert!(!props.enable_row_tracking());
+        assert!(!props.enable_in_commit_timestamps());
+        assert!(props.unknown_properties().is_empty());
+    }
 }
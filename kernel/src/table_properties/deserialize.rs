@@ -14,8 +14,8 @@ use tracing::warn;
 
 const SECONDS_PER_MINUTE: u64 = 60;
 const SECONDS_PER_HOUR: u64 = 60 * SECONDS_PER_MINUTE;
-const SECONDS_PER_DAY: u64 = 24 * SECONDS_PER_HOUR;
-const SECONDS_PER_WEEK: u64 = 7 * SECONDS_PER_DAY;
+pub(super) const SECONDS_PER_DAY: u64 = 24 * SECONDS_PER_HOUR;
+pub(super) const SECONDS_PER_WEEK: u64 = 7 * SECONDS_PER_DAY;
 
 impl<K, V, I> From<I> for TableProperties
 where
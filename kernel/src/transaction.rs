@@ -12,11 +12,13 @@ use crate::expressions::{column_expr, Scalar, StructData};
 use crate::path::ParsedLogPath;
 use crate::schema::{SchemaRef, StructField, StructType};
 use crate::snapshot::Snapshot;
-use crate::{DataType, DeltaResult, Engine, EngineData, Expression, Version};
+use crate::{
+    DataType, DeltaResult, Engine, EngineData, EvaluationHandlerExtension as _, Expression, Version,
+};
 
 use url::Url;
 
-const KERNEL_VERSION: &str = env!("CARGO_PKG_VERSION");
+pub(crate) const KERNEL_VERSION: &str = env!("CARGO_PKG_VERSION");
 const UNKNOWN_OPERATION: &str = "UNKNOWN";
 
 pub(crate) static WRITE_METADATA_SCHEMA: LazyLock<SchemaRef> = LazyLock::new(|| {
@@ -45,8 +47,9 @@ pub fn get_write_metadata_schema() -> &'static SchemaRef {
 /// ```rust,ignore
 /// // create a transaction
 /// let mut txn = table.new_transaction(&engine)?;
-/// // stage table changes (right now only commit info)
+/// // stage table changes: commit info and the data files written by the engine
 /// txn.commit_info(Box::new(ArrowEngineData::new(engine_commit_info)));
+/// txn.add_files([DataFileInfo::new("part-00000.parquet", 1024, modification_time)]);
 /// // commit! (consume the transaction)
 /// txn.commit(&engine)?;
 /// ```
@@ -55,6 +58,7 @@ pub struct Transaction {
     operation: Option<String>,
     commit_info: Option<Arc<dyn EngineData>>,
     write_metadata: Vec<Box<dyn EngineData>>,
+    data_files: Vec<DataFileInfo>,
     // NB: hashmap would require either duplicating the appid or splitting SetTransaction
     // key/payload. HashSet requires Borrow<&str> with matching Eq, Ord, and Hash. Plus,
     // HashSet::insert drops the to-be-inserted value without returning the existing one, which
@@ -103,6 +107,7 @@ impl Transaction {
             operation: None,
             commit_info: None,
             write_metadata: vec![],
+            data_files: vec![],
             set_transactions: vec![],
             commit_timestamp,
         })
@@ -144,15 +149,20 @@ impl Transaction {
             engine_commit_info.as_ref(),
         );
         let add_actions = generate_adds(engine, self.write_metadata.iter().map(|a| a.as_ref()));
+        let data_file_actions = self
+            .data_files
+            .iter()
+            .map(|file| file.to_engine_data(engine));
 
         let actions = iter::once(commit_info_actions)
             .chain(add_actions)
+            .chain(data_file_actions)
             .chain(set_transaction_actions);
 
         // step two: set new commit version (current_version + 1) and path to write
         let commit_version = self.read_snapshot.version() + 1;
-        let commit_path =
-            ParsedLogPath::new_commit(self.read_snapshot.table_root(), commit_version)?;
+        let log_root = &self.read_snapshot.log_segment().log_root;
+        let commit_path = ParsedLogPath::new_commit_in_log(log_root, commit_version)?;
 
         // step three: commit the actions as a json file in the log
         let json_handler = engine.json_handler();
@@ -232,6 +242,87 @@ impl Transaction {
     pub fn add_write_metadata(&mut self, write_metadata: Box<dyn EngineData>) {
         self.write_metadata.push(write_metadata);
     }
+
+    /// Add data files that the engine has already written to include in the transaction. Each
+    /// file is committed as an `add` action. This API can be called multiple times.
+    pub fn add_files(&mut self, files: impl IntoIterator<Item = DataFileInfo>) {
+        self.data_files.extend(files);
+    }
+}
+
+/// Information about a data file written by the engine, committed as an `add` action by
+/// [`Transaction::add_files`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DataFileInfo {
+    /// Path of the data file, relative to the table root (or an absolute URI).
+    pub path: String,
+    /// Partition column name to (serialized) partition value for this file.
+    pub partition_values: HashMap<String, String>,
+    /// Size of the file in bytes.
+    pub size: i64,
+    /// Time the file was created, in milliseconds since the epoch.
+    pub modification_time: i64,
+    /// Whether the file contains data changes (false for e.g. compaction rewrites).
+    pub data_change: bool,
+    /// Optional JSON-encoded file statistics, as described by the Delta protocol.
+    pub stats: Option<String>,
+}
+
+impl DataFileInfo {
+    /// Create a new unpartitioned data file with no statistics which is marked as a data change.
+    pub fn new(path: impl Into<String>, size: i64, modification_time: i64) -> Self {
+        Self {
+            path: path.into(),
+            partition_values: HashMap::new(),
+            size,
+            modification_time,
+            data_change: true,
+            stats: None,
+        }
+    }
+
+    /// Set the partition values of this file.
+    pub fn with_partition_values(mut self, partition_values: HashMap<String, String>) -> Self {
+        self.partition_values = partition_values;
+        self
+    }
+
+    /// Set the JSON-encoded statistics of this file.
+    pub fn with_stats(mut self, stats: impl Into<String>) -> Self {
+        self.stats = Some(stats.into());
+        self
+    }
+
+    /// Set whether this file contains data changes.
+    pub fn with_data_change(mut self, data_change: bool) -> Self {
+        self.data_change = data_change;
+        self
+    }
+
+    // We have no map literals, so the add action is serialized to json and parsed by the engine
+    // into a single-row batch with the log's add schema.
+    fn to_engine_data(&self, engine: &dyn Engine) -> DeltaResult<Box<dyn EngineData>> {
+        let add = serde_json::json!({
+            "add": {
+                "path": self.path,
+                "partitionValues": self.partition_values,
+                "size": self.size,
+                "modificationTime": self.modification_time,
+                "dataChange": self.data_change,
+                "stats": self.stats,
+            }
+        });
+        let json_schema = Arc::new(StructType::new(vec![StructField::not_null(
+            "json",
+            DataType::STRING,
+        )]));
+        let json_strings = engine
+            .evaluation_handler()
+            .create_one(json_schema, &[add.to_string().into()])?;
+        engine
+            .json_handler()
+            .parse_json(json_strings, get_log_add_schema().clone())
+    }
 }
 
 // convert write_metadata into add actions using an expression to transform the data in a single
@@ -366,6 +457,8 @@ fn generate_commit_info(
     commit_info_data_type
         .fields
         .shift_remove("inCommitTimestamp");
+    // Kernel has no user metadata to write
+    commit_info_data_type.fields.shift_remove("userMetadata");
     commit_info_field.data_type = DataType::Struct(commit_info_data_type);
 
     let commit_info_evaluator = engine.evaluation_handler().new_expression_evaluator(
@@ -136,6 +136,54 @@ fn basic_cdf() -> Result<(), Box<dyn error::Error>> {
     Ok(())
 }
 
+#[test]
+fn cdf_update_and_delete_after_start() -> Result<(), Box<dyn error::Error>> {
+    // the range starts after the initial insert, so only the change rows of versions 2 and 3 show up
+    let batches = read_cdf_for_table("cdf-table", 2, 3, None)?;
+    let mut expected = vec![
+        "+----+--------+------------+------------------+-----------------+",
+        "| id | name   | birthday   | _change_type     | _commit_version |",
+        "+----+--------+------------+------------------+-----------------+",
+        "| 7  | Dennis | 2023-12-24 | update_preimage  | 2               |",
+        "| 7  | Dennis | 2023-12-29 | update_postimage | 2               |",
+        "| 5  | Emily  | 2023-12-24 | update_preimage  | 2               |",
+        "| 5  | Emily  | 2023-12-29 | update_postimage | 2               |",
+        "| 6  | Carl   | 2023-12-24 | update_preimage  | 2               |",
+        "| 6  | Carl   | 2023-12-29 | update_postimage | 2               |",
+        "| 7  | Dennis | 2023-12-29 | delete           | 3               |",
+        "+----+--------+------------+------------------+-----------------+",
+    ];
+    sort_lines!(expected);
+    assert_batches_sorted_eq!(expected, &batches);
+    Ok(())
+}
+
+#[test]
+fn cdf_schema_has_change_columns() -> Result<(), Box<dyn error::Error>> {
+    let test_dir = load_test_data("tests/data", "cdf-table")?;
+    let test_path = test_dir.path().join("cdf-table");
+    let table = Table::try_from_uri(test_path.to_str().expect("table path to string"))?;
+    let engine = SyncEngine::new();
+    let table_changes = table.table_changes(&engine, 0, None)?;
+    let names = table_changes
+        .schema()
+        .fields()
+        .map(|field| field.name().as_str())
+        .collect_vec();
+    assert_eq!(
+        names,
+        [
+            "id",
+            "name",
+            "birthday",
+            "_change_type",
+            "_commit_version",
+            "_commit_timestamp"
+        ]
+    );
+    Ok(())
+}
+
 #[test]
 fn cdf_non_partitioned() -> Result<(), Box<dyn error::Error>> {
     let batches = read_cdf_for_table("cdf-table-non-partitioned", 0, None, None)?;
@@ -4,9 +4,13 @@ use delta_kernel::arrow::util::pretty::pretty_format_batches;
 use itertools::Itertools;
 
 use crate::ArrowEngineData;
+use delta_kernel::engine::memory::MemoryEngine;
 use delta_kernel::scan::Scan;
+use delta_kernel::schema::StructType;
 use delta_kernel::{DeltaResult, Engine, EngineData, Table};
+use url::Url;
 
+use std::collections::HashMap;
 use std::sync::Arc;
 
 #[macro_export]
@@ -102,3 +106,74 @@ pub(crate) fn read_scan(scan: &Scan, engine: Arc<dyn Engine>) -> DeltaResult<Vec
         })
         .try_collect()
 }
+
+/// The `protocol` action of a test table without any table features
+#[allow(unused)]
+pub(crate) const PROTOCOL: &str = r#"{"protocol":{"minReaderVersion":1,"minWriterVersion":2}}"#;
+
+/// The `metaData` action of a test table of parquet files with the given `schema`, partition columns
+/// and table properties
+#[allow(unused)]
+pub(crate) fn metadata_action(
+    schema: &StructType,
+    partition_columns: &[&str],
+    configuration: &[(&str, &str)],
+) -> String {
+    format_metadata_action("parquet", schema, partition_columns, configuration)
+}
+
+/// Like [`metadata_action`], for a table of data files in the format of `provider` (e.g. `json`)
+#[allow(unused)]
+pub(crate) fn format_metadata_action(
+    provider: &str,
+    schema: &StructType,
+    partition_columns: &[&str],
+    configuration: &[(&str, &str)],
+) -> String {
+    let configuration: HashMap<_, _> = configuration.iter().copied().collect();
+    serde_json::json!({
+        "metaData": {
+            "id": "5fba94ed-9794-4965-ba6e-6ee3c0d22af9",
+            "format": {"provider": provider, "options": {}},
+            "schemaString": serde_json::to_string(schema).unwrap(),
+            "partitionColumns": partition_columns,
+            "configuration": configuration,
+            "createdTime": 1587968585495i64,
+        }
+    })
+    .to_string()
+}
+
+/// An `add` action of the data file at `path`, with the given partition values (`None` for a null
+/// value) and `stats` json, if any
+#[allow(unused)]
+pub(crate) fn add_action(
+    path: &str,
+    partition_values: &[(&str, Option<&str>)],
+    stats: Option<&str>,
+) -> String {
+    let partition_values: HashMap<_, _> = partition_values.iter().copied().collect();
+    let mut add = serde_json::json!({
+        "path": path,
+        "partitionValues": partition_values,
+        "size": 0,
+        "modificationTime": 1587968586000i64,
+        "dataChange": true,
+    });
+    if let Some(stats) = stats {
+        add["stats"] = stats.into();
+    }
+    serde_json::json!({ "add": add }).to_string()
+}
+
+/// Create a test table at `memory:///table/` of a new [`MemoryEngine`], whose version 0 commits
+/// `actions` (one json action each). Returns the engine and the table root.
+#[allow(unused)]
+pub(crate) fn memory_table(
+    actions: impl IntoIterator<Item = String>,
+) -> DeltaResult<(Arc<MemoryEngine>, Url)> {
+    let engine = Arc::new(MemoryEngine::new());
+    let table_root = Url::parse("memory:///table/").unwrap();
+    engine.put_commit(&table_root, 0, actions.into_iter().join("\n"))?;
+    Ok((engine, table_root))
+}
@@ -57,3 +57,29 @@ fn non_dv_table() -> Result<(), Box<dyn std::error::Error>> {
     assert_eq!(total_rows, 10);
     Ok(())
 }
+
+#[test]
+fn inline_dv_table() -> Result<(), Box<dyn std::error::Error>> {
+    // the same table as table-with-dv-small, with the deletion vector stored inline in the log
+    let path = std::fs::canonicalize(PathBuf::from("./tests/data/table-with-inline-dv/"))?;
+    let url = url::Url::from_directory_path(path).unwrap();
+    let engine = Arc::new(SyncEngine::new());
+
+    let table = Table::new(url);
+    let snapshot = table.snapshot(engine.as_ref(), None)?;
+    let scan = snapshot.into_scan_builder().build()?;
+
+    let results: Vec<_> = scan.execute(engine)?.try_collect()?;
+    assert_eq!(results.len(), 1);
+    let deleted_rows: Vec<_> = results[0]
+        .raw_mask()
+        .expect("the file has a deletion vector")
+        .iter()
+        .positions(|&selected| !selected)
+        .collect();
+    assert_eq!(deleted_rows, [0, 9]);
+
+    let total_rows = count_total_scan_rows(results.into_iter().map(Ok))?;
+    assert_eq!(total_rows, 8);
+    Ok(())
+}
@@ -3,18 +3,22 @@ use std::path::PathBuf;
 use std::sync::Arc;
 
 use delta_kernel::actions::deletion_vector::split_vector;
+use delta_kernel::arrow::array::AsArray;
 use delta_kernel::arrow::compute::{concat_batches, filter_record_batch};
 use delta_kernel::arrow::datatypes::SchemaRef as ArrowSchemaRef;
 use delta_kernel::engine::arrow_data::ArrowEngineData;
 use delta_kernel::engine::default::executor::tokio::TokioBackgroundExecutor;
 use delta_kernel::engine::default::DefaultEngine;
+use delta_kernel::engine::memory::MemoryEngine;
 use delta_kernel::expressions::{column_expr, BinaryOperator, Expression as Expr, ExpressionRef};
 use delta_kernel::object_store::{memory::InMemory, path::Path, ObjectStore};
 use delta_kernel::parquet::file::properties::{EnabledStatistics, WriterProperties};
 use delta_kernel::scan::state::{transform_to_logical, DvInfo, Stats};
-use delta_kernel::scan::Scan;
-use delta_kernel::schema::{DataType, Schema};
-use delta_kernel::{Engine, FileMeta, Table};
+use delta_kernel::scan::{FileFormat, Scan};
+use delta_kernel::schema::{
+    ColumnMetadataKey, DataType, DecimalType, MetadataValue, Schema, StructField, StructType,
+};
+use delta_kernel::{Engine, Error, FileMeta, Table};
 use itertools::Itertools;
 use test_utils::{
     actions_to_string, add_commit, generate_batch, generate_simple_batch, into_record_batch,
@@ -23,7 +27,10 @@ use test_utils::{
 use url::Url;
 
 mod common;
-use common::{read_scan, to_arrow};
+use common::{
+    add_action, format_metadata_action, memory_table, metadata_action, read_scan, to_arrow,
+    PROTOCOL,
+};
 
 const PARQUET_FILE1: &str = "part-00000-a72b1fb3-f2df-41fe-a8f0-e65b746382dd-c000.snappy.parquet";
 const PARQUET_FILE2: &str = "part-00001-c506e79a-0bf8-4e2b-a42b-9731b2e490ae-c000.snappy.parquet";
@@ -1085,6 +1092,261 @@ async fn predicate_on_non_nullable_partition_column() -> Result<(), Box<dyn std:
     Ok(())
 }
 
+#[test]
+fn column_mapping_id_mode_resolves_renamed_columns() -> Result<(), Box<dyn std::error::Error>> {
+    use delta_kernel::arrow::array::{Int32Array, RecordBatch, StringArray, StructArray};
+    use delta_kernel::arrow::datatypes::{DataType as ArrowDataType, Field, Schema as ArrowSchema};
+    use delta_kernel::parquet::arrow::PARQUET_FIELD_ID_META_KEY;
+
+    let field_id =
+        |id: i64| HashMap::from([(PARQUET_FIELD_ID_META_KEY.to_string(), id.to_string())]);
+    // The file was written before the columns were renamed, under physical names that no longer
+    // match the table schema, so it can only be read by resolving the columns by field id.
+    let score = Field::new("score_old", ArrowDataType::Int32, true).with_metadata(field_id(3));
+    let file_schema = Arc::new(ArrowSchema::new(vec![
+        Field::new("name_old", ArrowDataType::Utf8, true).with_metadata(field_id(1)),
+        Field::new_struct("info_old", vec![score.clone()], true).with_metadata(field_id(2)),
+    ]));
+    let batch = RecordBatch::try_new(
+        file_schema,
+        vec![
+            Arc::new(StringArray::from(vec!["a", "b"])),
+            Arc::new(StructArray::from(vec![(
+                Arc::new(score),
+                Arc::new(Int32Array::from(vec![1, 2])) as _,
+            )])),
+        ],
+    )?;
+
+    let mapped = |name: &str, data_type: DataType, id: i64, physical_name: &str| {
+        StructField::nullable(name, data_type).with_metadata([
+            (ColumnMetadataKey::ColumnMappingId.as_ref(), id.into()),
+            (
+                ColumnMetadataKey::ColumnMappingPhysicalName.as_ref(),
+                MetadataValue::from(physical_name),
+            ),
+        ])
+    };
+    let info = StructType::new([mapped("score", DataType::INTEGER, 3, "col-score")]);
+    let schema = StructType::new([
+        mapped("name", DataType::STRING, 1, "col-name"),
+        mapped("info", info.into(), 2, "col-info"),
+    ]);
+    let configuration = [
+        ("delta.columnMapping.mode", "id"),
+        ("delta.columnMapping.maxColumnId", "3"),
+    ];
+    let (engine, table_root) = memory_table([
+        r#"{"protocol":{"minReaderVersion":2,"minWriterVersion":5}}"#.to_string(),
+        metadata_action(&schema, &[], &configuration),
+        add_action(PARQUET_FILE1, &[], None),
+    ])?;
+    engine.put_parquet(table_root.join(PARQUET_FILE1)?, &batch)?;
+
+    let table = Table::new(table_root);
+    let snapshot = Arc::new(table.snapshot(engine.as_ref(), None)?);
+    let scan = snapshot.scan_builder().build()?;
+    let batches = read_scan(&scan, engine)?;
+
+    let mut expected = vec![
+        "+------+------------+",
+        "| name | info       |",
+        "+------+------------+",
+        "| a    | {score: 1} |",
+        "| b    | {score: 2} |",
+        "+------+------------+",
+    ];
+    sort_lines!(expected);
+    assert_batches_sorted_eq!(expected, &batches);
+    Ok(())
+}
+
+fn scan_file_path_callback(
+    paths: &mut Vec<String>,
+    path: &str,
+    _: i64,
+    _: Option<Stats>,
+    _: DvInfo,
+    _: Option<ExpressionRef>,
+    _: HashMap<String, String>,
+) {
+    paths.push(path.to_string());
+}
+
+#[test]
+fn partition_pruning_with_null_partitions() -> Result<(), Box<dyn std::error::Error>> {
+    let add = |dir: &str, year: Option<&str>| {
+        add_action(
+            &format!("year={dir}/{PARQUET_FILE1}"),
+            &[("year", year)],
+            None,
+        )
+    };
+    let schema = StructType::new([
+        StructField::nullable("year", DataType::INTEGER),
+        StructField::nullable("val", DataType::STRING),
+    ]);
+    let (engine, table_root) = memory_table([
+        PROTOCOL.to_string(),
+        r#"{"commitInfo":{"timestamp":1587968586154,"operation":"WRITE","operationParameters":{"mode":"ErrorIfExists","partitionBy":"[\"year\"]"},"isBlindAppend":true}}"#.to_string(),
+        metadata_action(&schema, &["year"], &[]),
+        add("2020", Some("2020")),
+        add("2021", Some("2021")),
+        add("2022", Some("2022")),
+        add("null", None),
+        add("__HIVE_DEFAULT_PARTITION__", Some("__HIVE_DEFAULT_PARTITION__")),
+    ])?;
+    let snapshot = Arc::new(Table::new(table_root).snapshot(engine.as_ref(), None)?);
+
+    let scan_paths = |predicate: Expr| -> Result<Vec<String>, Box<dyn std::error::Error>> {
+        let scan = snapshot
+            .clone()
+            .scan_builder()
+            .with_predicate(Arc::new(predicate))
+            .build()?;
+        let mut paths = vec![];
+        for scan_metadata in scan.scan_metadata(engine.as_ref())? {
+            paths = scan_metadata?.visit_scan_files(paths, scan_file_path_callback)?;
+        }
+        paths.sort();
+        Ok(paths)
+    };
+
+    let paths = scan_paths(Expr::eq(column_expr!("year"), Expr::literal(2021)))?;
+    assert_eq!(paths, vec![format!("year=2021/{PARQUET_FILE1}")]);
+
+    // both the null value and the hive sentinel are null partitions
+    let paths = scan_paths(Expr::is_null(column_expr!("year")))?;
+    assert_eq!(
+        paths,
+        vec![
+            format!("year=__HIVE_DEFAULT_PARTITION__/{PARQUET_FILE1}"),
+            format!("year=null/{PARQUET_FILE1}"),
+        ]
+    );
+    Ok(())
+}
+
+#[test]
+fn hive_partitioned_files_without_partition_columns() -> Result<(), Box<dyn std::error::Error>> {
+    // hive-style layout: the partition columns only live in the directory names and the adds'
+    // `partitionValues`, the data files contain just `val`
+    let files = [
+        ("us", "2024", vec!["a", "b"]),
+        ("eu", "2025", vec!["c"]),
+        ("__HIVE_DEFAULT_PARTITION__", "2025", vec!["d", "e"]),
+    ];
+    let path = |region: &str, year: &str| format!("region={region}/year={year}/{PARQUET_FILE1}");
+    let schema = StructType::new([
+        StructField::nullable("region", DataType::STRING),
+        StructField::nullable("val", DataType::STRING),
+        StructField::nullable("year", DataType::INTEGER),
+    ]);
+    let mut actions = vec![
+        PROTOCOL.to_string(),
+        metadata_action(&schema, &["region", "year"], &[]),
+    ];
+    actions.extend(files.iter().map(|(region, year, _)| {
+        let partition_values = [("region", Some(*region)), ("year", Some(*year))];
+        add_action(&path(region, year), &partition_values, None)
+    }));
+    let (engine, table_root) = memory_table(actions)?;
+    for (region, year, vals) in files {
+        let batch = generate_batch(vec![("val", vals.into_array())])?;
+        engine.put_parquet(table_root.join(&path(region, year))?, &batch)?;
+    }
+
+    let snapshot = Arc::new(Table::new(table_root).snapshot(engine.as_ref(), None)?);
+    let scan = snapshot.scan_builder().build()?;
+    let physical_schema = scan.global_scan_state().physical_schema;
+    assert_eq!(
+        physical_schema.fields().map(|f| f.name()).collect_vec(),
+        ["val"]
+    );
+
+    // every file is read into its own batch, with its own partition values filled in
+    let batches = read_scan(&scan, engine)?;
+    assert_eq!(batches.len(), 3);
+    let mut expected = vec![
+        "+--------+-----+------+",
+        "| region | val | year |",
+        "+--------+-----+------+",
+        "| us     | a   | 2024 |",
+        "| us     | b   | 2024 |",
+        "| eu     | c   | 2025 |",
+        "|        | d   | 2025 |",
+        "|        | e   | 2025 |",
+        "+--------+-----+------+",
+    ];
+    sort_lines!(expected);
+    assert_batches_sorted_eq!(expected, &batches);
+    Ok(())
+}
+
+#[test]
+fn json_format_table() -> Result<(), Box<dyn std::error::Error>> {
+    let schema = StructType::new([
+        StructField::nullable("id", DataType::LONG),
+        StructField::nullable(
+            "info",
+            StructType::new([StructField::nullable("name", DataType::STRING)]),
+        ),
+    ]);
+    let (engine, table_root) = memory_table([
+        PROTOCOL.to_string(),
+        format_metadata_action("json", &schema, &[], &[]),
+        add_action("part-00000.json", &[], None),
+        add_action("part-00001.json", &[], None),
+    ])?;
+    // one record per line; keys the schema doesn't know about are ignored
+    let files = [
+        (
+            "part-00000.json",
+            r#"{"id":1,"info":{"name":"a"},"extra":true}
+{"info":{"name":"b"},"id":2}"#,
+        ),
+        ("part-00001.json", r#"{"id":3,"info":{"name":null}}"#),
+    ];
+    for (path, data) in files {
+        engine.put_file(table_root.join(path)?, data)?;
+    }
+
+    let snapshot = Arc::new(Table::new(table_root).snapshot(engine.as_ref(), None)?);
+
+    let scan = snapshot.clone().scan_builder().build()?;
+    assert_eq!(scan.file_format(), FileFormat::Json);
+    let batches = read_scan(&scan, engine.clone())?;
+    let mut expected = vec![
+        "+----+-----------+",
+        "| id | info      |",
+        "+----+-----------+",
+        "| 1  | {name: a} |",
+        "| 2  | {name: b} |",
+        "| 3  | {name: }  |",
+        "+----+-----------+",
+    ];
+    sort_lines!(expected);
+    assert_batches_sorted_eq!(expected, &batches);
+
+    // only the projected columns are read
+    let schema = snapshot.schema().project(&["info"])?;
+    let scan = snapshot.scan_builder().with_schema(schema).build()?;
+    let batches = read_scan(&scan, engine)?;
+    let mut expected = vec![
+        "+-----------+",
+        "| info      |",
+        "+-----------+",
+        "| {name: a} |",
+        "| {name: b} |",
+        "| {name: }  |",
+        "+-----------+",
+    ];
+    sort_lines!(expected);
+    assert_batches_sorted_eq!(expected, &batches);
+    assert!(batches.iter().all(|batch| batch.num_columns() == 1));
+    Ok(())
+}
+
 #[tokio::test]
 async fn predicate_on_non_nullable_column_missing_stats() -> Result<(), Box<dyn std::error::Error>>
 {
@@ -1330,3 +1592,1057 @@ fn timestamp_partitioned_table() -> Result<(), Box<dyn std::error::Error>> {
     let test_path = test_dir.path().join(test_name);
     read_table_data_str(test_path.to_str().unwrap(), None, None, expected)
 }
+
+/// Legacy Spark writers store logical `timestamp` columns as parquet int96 (which arrow reads as
+/// `Timestamp(Nanosecond, None)`) or as `Timestamp(Microsecond, None)`, without the UTC annotation.
+/// Those values are already UTC, so reading them must not shift them: they should come back as
+/// the same instants, in the logical `Timestamp(Microsecond, "UTC")` type.
+#[test]
+fn timestamp_read_from_ntz_physical_columns() -> Result<(), Box<dyn std::error::Error>> {
+    use delta_kernel::arrow::array::{
+        Array, RecordBatch, TimestampMicrosecondArray, TimestampNanosecondArray,
+    };
+    use delta_kernel::arrow::datatypes::{
+        DataType as ArrowDataType, Field, Schema as ArrowSchema, TimeUnit,
+    };
+
+    // 2021-01-01T00:00:01.5Z and 1970-01-01T00:00:00Z
+    let micros = [1_609_459_201_500_000i64, 0];
+    let micros_file = RecordBatch::try_new(
+        Arc::new(ArrowSchema::new(vec![Field::new(
+            "ts",
+            ArrowDataType::Timestamp(TimeUnit::Microsecond, None),
+            true,
+        )])),
+        vec![Arc::new(TimestampMicrosecondArray::from(micros.to_vec()))],
+    )?;
+    let nanos_file = RecordBatch::try_new(
+        Arc::new(ArrowSchema::new(vec![Field::new(
+            "ts",
+            ArrowDataType::Timestamp(TimeUnit::Nanosecond, None),
+            true,
+        )])),
+        vec![Arc::new(TimestampNanosecondArray::from(
+            micros.map(|us| us * 1000).to_vec(),
+        ))],
+    )?;
+
+    let schema = StructType::new([StructField::nullable("ts", DataType::TIMESTAMP)]);
+    let (engine, table_root) = memory_table([
+        PROTOCOL.to_string(),
+        metadata_action(&schema, &[], &[]),
+        add_action(PARQUET_FILE1, &[], None),
+        add_action(PARQUET_FILE2, &[], None),
+    ])?;
+    for (path, batch) in [(PARQUET_FILE1, &micros_file), (PARQUET_FILE2, &nanos_file)] {
+        engine.put_parquet(table_root.join(path)?, batch)?;
+    }
+
+    let snapshot = Table::new(table_root).snapshot(engine.as_ref(), None)?;
+    let scan = snapshot.into_scan_builder().build()?;
+    let batches = read_scan(&scan, engine)?;
+    assert_eq!(batches.len(), 2);
+    for batch in batches {
+        let ts = batch.column(0);
+        assert_eq!(
+            ts.data_type(),
+            &ArrowDataType::Timestamp(TimeUnit::Microsecond, Some("UTC".into()))
+        );
+        let ts = ts
+            .as_any()
+            .downcast_ref::<TimestampMicrosecondArray>()
+            .unwrap();
+        assert_eq!(ts.values().as_ref(), micros);
+    }
+    Ok(())
+}
+
+/// Arrow `Date64` counts milliseconds since the epoch while Delta dates are days since the epoch,
+/// so a physical `Date64` column must be rescaled (not reinterpreted) when read as a `date`.
+#[test]
+fn date_read_from_date64_physical_column() -> Result<(), Box<dyn std::error::Error>> {
+    use delta_kernel::arrow::array::{Array, Date32Array, Date64Array, RecordBatch};
+    use delta_kernel::arrow::datatypes::{DataType as ArrowDataType, Field, Schema as ArrowSchema};
+
+    // 2021-01-01, 1970-01-01 and 1969-12-31
+    let days = [18_628, 0, -1];
+    let millis_file = RecordBatch::try_new(
+        Arc::new(ArrowSchema::new(vec![Field::new(
+            "d",
+            ArrowDataType::Date64,
+            true,
+        )])),
+        vec![Arc::new(Date64Array::from(
+            days.map(|d| i64::from(d) * 86_400_000).to_vec(),
+        ))],
+    )?;
+
+    let schema = StructType::new([StructField::nullable("d", DataType::DATE)]);
+    let (engine, table_root) = memory_table([
+        PROTOCOL.to_string(),
+        metadata_action(&schema, &[], &[]),
+        add_action(PARQUET_FILE1, &[], None),
+    ])?;
+    engine.put_parquet(table_root.join(PARQUET_FILE1)?, &millis_file)?;
+
+    let snapshot = Table::new(table_root).snapshot(engine.as_ref(), None)?;
+    let scan = snapshot.into_scan_builder().build()?;
+    let batches = read_scan(&scan, engine)?;
+    assert_eq!(batches.len(), 1);
+    let dates = batches[0].column(0);
+    assert_eq!(dates.data_type(), &ArrowDataType::Date32);
+    let dates = dates.as_any().downcast_ref::<Date32Array>().unwrap();
+    assert_eq!(dates.values().as_ref(), days);
+    Ok(())
+}
+
+#[test]
+fn row_tracking_row_ids_across_files() -> Result<(), Box<dyn std::error::Error>> {
+    use delta_kernel::arrow::array::{Array, Int32Array, Int64Array, RecordBatch};
+    use delta_kernel::arrow::datatypes::{DataType as ArrowDataType, Field, Schema as ArrowSchema};
+    use delta_kernel::scan::{ROW_COMMIT_VERSION_COLUMN_NAME, ROW_ID_COLUMN_NAME};
+
+    let file_batch = |values: Vec<i32>| {
+        RecordBatch::try_new(
+            Arc::new(ArrowSchema::new(vec![Field::new(
+                "x",
+                ArrowDataType::Int32,
+                true,
+            )])),
+            vec![Arc::new(Int32Array::from(values))],
+        )
+    };
+    let add = |path: &str, base_row_id: i64, version: i64| {
+        let mut add: serde_json::Value =
+            serde_json::from_str(&add_action(path, &[], None)).unwrap();
+        add["add"]["baseRowId"] = base_row_id.into();
+        add["add"]["defaultRowCommitVersion"] = version.into();
+        add.to_string()
+    };
+
+    let schema = StructType::new([StructField::nullable("x", DataType::INTEGER)]);
+    let (engine, table_root) = memory_table([
+        r#"{"protocol":{"minReaderVersion":1,"minWriterVersion":7,"writerFeatures":["rowTracking","domainMetadata"]}}"#.to_string(),
+        metadata_action(&schema, &[], &[("delta.enableRowTracking", "true")]),
+        add(PARQUET_FILE1, 0, 0),
+    ])?;
+    engine.put_commit(&table_root, 1, add(PARQUET_FILE2, 3, 1))?;
+    for (path, values) in [
+        (PARQUET_FILE1, vec![10, 11, 12]),
+        (PARQUET_FILE2, vec![20, 21]),
+    ] {
+        engine.put_parquet(table_root.join(path)?, &file_batch(values)?)?;
+    }
+
+    let snapshot = Table::new(table_root).snapshot(engine.as_ref(), None)?;
+    let scan = snapshot.into_scan_builder().with_row_tracking().build()?;
+    let field_names: Vec<_> = scan.schema().fields().map(|f| f.name().as_str()).collect();
+    assert_eq!(
+        field_names,
+        ["x", ROW_ID_COLUMN_NAME, ROW_COMMIT_VERSION_COLUMN_NAME]
+    );
+
+    let mut rows = vec![];
+    for batch in read_scan(&scan, engine)? {
+        let column = |idx: usize| {
+            batch
+                .column(idx)
+                .as_any()
+                .downcast_ref::<Int64Array>()
+                .cloned()
+        };
+        let values = batch
+            .column(0)
+            .as_any()
+            .downcast_ref::<Int32Array>()
+            .unwrap();
+        let row_ids = column(1).unwrap();
+        let versions = column(2).unwrap();
+        assert_eq!(row_ids.null_count(), 0);
+        for i in 0..batch.num_rows() {
+            rows.push((row_ids.value(i), values.value(i), versions.value(i)));
+        }
+    }
+    rows.sort();
+    assert_eq!(
+        rows,
+        [(0, 10, 0), (1, 11, 0), (2, 12, 0), (3, 20, 1), (4, 21, 1)]
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn row_tracking_requires_table_support() -> Result<(), Box<dyn std::error::Error>> {
+    let path = std::fs::canonicalize(PathBuf::from("./tests/data/basic_partitioned/"))?;
+    let url = Url::from_directory_path(path).unwrap();
+    let engine = DefaultEngine::new(
+        Arc::new(delta_kernel::object_store::local::LocalFileSystem::new()),
+        Arc::new(TokioBackgroundExecutor::new()),
+    );
+    let snapshot = Table::new(url).snapshot(&engine, None)?;
+    let result = snapshot.into_scan_builder().with_row_tracking().build();
+    assert!(result
+        .unwrap_err()
+        .to_string()
+        .contains("does not support row tracking"));
+    Ok(())
+}
+
+/// Delta timestamps are microseconds, so physical timestamp columns in a coarser unit must be
+/// upcast when read.
+#[test]
+fn timestamp_read_from_millisecond_physical_column() -> Result<(), Box<dyn std::error::Error>> {
+    use delta_kernel::arrow::array::{
+        Array, RecordBatch, TimestampMicrosecondArray, TimestampMillisecondArray,
+        TimestampSecondArray,
+    };
+    use delta_kernel::arrow::datatypes::{
+        DataType as ArrowDataType, Field, Schema as ArrowSchema, TimeUnit,
+    };
+
+    // 2021-01-01T00:00:00.123Z, the epoch and one millisecond before it
+    let millis = [1_609_459_200_123, 0, -1];
+    let seconds = [1_609_459_200, 0, -1];
+    let file = RecordBatch::try_new(
+        Arc::new(ArrowSchema::new(vec![
+            Field::new(
+                "ms",
+                ArrowDataType::Timestamp(TimeUnit::Millisecond, Some("UTC".into())),
+                true,
+            ),
+            Field::new(
+                "s",
+                ArrowDataType::Timestamp(TimeUnit::Second, Some("UTC".into())),
+                true,
+            ),
+        ])),
+        vec![
+            Arc::new(TimestampMillisecondArray::from(millis.to_vec()).with_timezone("UTC")),
+            Arc::new(TimestampSecondArray::from(seconds.to_vec()).with_timezone("UTC")),
+        ],
+    )?;
+
+    let schema = StructType::new([
+        StructField::nullable("ms", DataType::TIMESTAMP),
+        StructField::nullable("s", DataType::TIMESTAMP),
+    ]);
+    let (engine, table_root) = memory_table([
+        PROTOCOL.to_string(),
+        metadata_action(&schema, &[], &[]),
+        add_action(PARQUET_FILE1, &[], None),
+    ])?;
+    engine.put_parquet(table_root.join(PARQUET_FILE1)?, &file)?;
+
+    let snapshot = Table::new(table_root).snapshot(engine.as_ref(), None)?;
+    let scan = snapshot.into_scan_builder().build()?;
+    let batches = read_scan(&scan, engine)?;
+    assert_eq!(batches.len(), 1);
+    let expected_type = ArrowDataType::Timestamp(TimeUnit::Microsecond, Some("UTC".into()));
+    let micros = |idx: usize| {
+        let column = batches[0].column(idx);
+        assert_eq!(column.data_type(), &expected_type);
+        column
+            .as_any()
+            .downcast_ref::<TimestampMicrosecondArray>()
+            .unwrap()
+            .values()
+            .to_vec()
+    };
+    assert_eq!(micros(0), millis.map(|ms| ms * 1_000));
+    assert_eq!(micros(1), seconds.map(|s| s * 1_000_000));
+    Ok(())
+}
+
+#[test]
+fn data_skipping_on_nested_column() -> Result<(), Box<dyn std::error::Error>> {
+    let add = |path: &str, stats: &str| add_action(path, &[], Some(stats));
+    let schema = StructType::new([
+        StructField::nullable("id", DataType::LONG),
+        StructField::nullable(
+            "address",
+            StructType::new([
+                StructField::nullable("street", DataType::STRING),
+                StructField::nullable("zip", DataType::INTEGER),
+            ]),
+        ),
+    ]);
+    let (engine, table_root) = memory_table([
+        PROTOCOL.to_string(),
+        metadata_action(&schema, &[], &[]),
+        add(
+            "low.parquet",
+            r#"{"numRecords":3,"nullCount":{"id":0,"address":{"street":0,"zip":0}},"minValues":{"id":1,"address":{"street":"a","zip":10000}},"maxValues":{"id":3,"address":{"street":"c","zip":50000}}}"#,
+        ),
+        add(
+            "high.parquet",
+            r#"{"numRecords":3,"nullCount":{"id":0,"address":{"street":0,"zip":0}},"minValues":{"id":4,"address":{"street":"d","zip":90001}},"maxValues":{"id":6,"address":{"street":"f","zip":99999}}}"#,
+        ),
+        // the nested column is not among the indexed columns of this file
+        add(
+            "unindexed.parquet",
+            r#"{"numRecords":3,"nullCount":{"id":0},"minValues":{"id":7},"maxValues":{"id":9}}"#,
+        ),
+    ])?;
+    let snapshot = Arc::new(Table::new(table_root).snapshot(engine.as_ref(), None)?);
+    let scan_paths = |predicate: Expr| -> Result<Vec<String>, Box<dyn std::error::Error>> {
+        let scan = snapshot
+            .clone()
+            .scan_builder()
+            .with_predicate(Arc::new(predicate))
+            .build()?;
+        let mut paths = vec![];
+        for scan_metadata in scan.scan_metadata(engine.as_ref())? {
+            paths = scan_metadata?.visit_scan_files(paths, scan_file_path_callback)?;
+        }
+        paths.sort();
+        Ok(paths)
+    };
+
+    let paths = scan_paths(Expr::gt(column_expr!("address.zip"), Expr::literal(90000)))?;
+    assert_eq!(paths, ["high.parquet", "unindexed.parquet"]);
+
+    let paths = scan_paths(Expr::lt(column_expr!("address.zip"), Expr::literal(20000)))?;
+    assert_eq!(paths, ["low.parquet", "unindexed.parquet"]);
+
+    let paths = scan_paths(Expr::and(
+        Expr::gt(column_expr!("address.zip"), Expr::literal(90000)),
+        Expr::lt(column_expr!("id"), Expr::literal(5i64)),
+    ))?;
+    assert_eq!(paths, ["high.parquet"]);
+    Ok(())
+}
+
+/// Checkpoints may be written with any parquet compression codec, which the reader must pick up
+/// from the file metadata.
+#[tokio::test]
+async fn read_checkpoint_with_any_compression() -> Result<(), Box<dyn std::error::Error>> {
+    use delta_kernel::parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+    use delta_kernel::parquet::arrow::ArrowWriter;
+    use delta_kernel::parquet::basic::{Compression, GzipLevel, ZstdLevel};
+    use delta_kernel::snapshot::Snapshot;
+
+    const CHECKPOINT: &str = "00000000000000000002.checkpoint.parquet";
+    let log_dir = std::fs::canonicalize(PathBuf::from(
+        "./tests/data/with_checkpoint_no_last_checkpoint/_delta_log/",
+    ))?;
+    let reader =
+        ParquetRecordBatchReaderBuilder::try_new(std::fs::File::open(log_dir.join(CHECKPOINT))?)?
+            .build()?;
+    let checkpoint_batches: Vec<_> = reader.try_collect()?;
+    let checkpoint_batches = &checkpoint_batches;
+
+    // Only the checkpoint is stored, so the snapshot can only be read through it
+    let read_files = |compression: Compression| async move {
+        let props = WriterProperties::builder()
+            .set_compression(compression)
+            .build();
+        let mut data = vec![];
+        let mut writer =
+            ArrowWriter::try_new(&mut data, checkpoint_batches[0].schema(), Some(props))?;
+        for batch in checkpoint_batches {
+            writer.write(batch)?;
+        }
+        let metadata = writer.close()?;
+        assert_eq!(
+            metadata.row_groups[0].columns[0]
+                .meta_data
+                .as_ref()
+                .unwrap()
+                .codec,
+            compression.into()
+        );
+
+        let storage = Arc::new(InMemory::new());
+        storage
+            .put(&Path::from(format!("_delta_log/{CHECKPOINT}")), data.into())
+            .await?;
+        let engine = DefaultEngine::new(storage, Arc::new(TokioBackgroundExecutor::new()));
+        let snapshot = Snapshot::try_new(Url::parse("memory:///")?, &engine, Some(2))?;
+        let files: Vec<_> = snapshot
+            .active_files(&engine)?
+            .map_ok(|file| (file.file.location.to_string(), file.file.size))
+            .try_collect()?;
+        Ok::<_, Box<dyn std::error::Error>>((
+            snapshot.schema(),
+            files.into_iter().sorted().collect::<Vec<_>>(),
+        ))
+    };
+
+    let expected = read_files(Compression::UNCOMPRESSED).await?;
+    assert!(!expected.1.is_empty());
+    for compression in [
+        Compression::SNAPPY,
+        Compression::ZSTD(ZstdLevel::default()),
+        Compression::LZ4_RAW,
+        Compression::GZIP(GzipLevel::default()),
+    ] {
+        assert_eq!(read_files(compression).await?, expected, "{compression}");
+    }
+    Ok(())
+}
+
+#[test]
+fn memory_engine_snapshot_and_scan() -> Result<(), Box<dyn std::error::Error>> {
+    let batch = generate_simple_batch()?;
+    let table_root = Url::parse("memory:///table/")?;
+    let engine = Arc::new(MemoryEngine::new());
+    engine.put_commit(
+        &table_root,
+        0,
+        actions_to_string(vec![
+            TestAction::Metadata,
+            TestAction::Add(PARQUET_FILE1.to_string()),
+            TestAction::Add(PARQUET_FILE2.to_string()),
+        ]),
+    )?;
+    engine.put_parquet(table_root.join(PARQUET_FILE1)?, &batch)?;
+    engine.put_parquet(table_root.join(PARQUET_FILE2)?, &batch)?;
+    assert!(matches!(
+        engine.put_commit(&table_root, 0, METADATA),
+        Err(Error::FileAlreadyExists(_))
+    ));
+
+    let table = Table::new(table_root);
+    let snapshot = table.snapshot(engine.as_ref(), None)?;
+    assert_eq!(snapshot.version(), 0);
+    let scan = snapshot.into_scan_builder().build()?;
+    assert_eq!(
+        read_scan(&scan, engine.clone())?,
+        vec![batch.clone(), batch]
+    );
+
+    // writing a commit that already exists is a conflict, even through the json handler
+    let commit = table
+        .location()
+        .join("_delta_log/00000000000000000000.json")?;
+    let result =
+        engine
+            .json_handler()
+            .write_json_file(&commit, Box::new(std::iter::empty()), false);
+    assert!(matches!(result, Err(Error::FileAlreadyExists(_))));
+    Ok(())
+}
+
+#[test]
+fn split_log_and_data_roots() -> Result<(), Box<dyn std::error::Error>> {
+    let batch = generate_simple_batch()?;
+    let data_root = Url::parse("memory:///data/table/")?;
+    let log_root = Url::parse("memory:///catalog/logs/table")?;
+    let engine = Arc::new(MemoryEngine::new());
+    engine.put_file(
+        Url::parse("memory:///catalog/logs/table/00000000000000000000.json")?,
+        actions_to_string(vec![
+            TestAction::Metadata,
+            TestAction::Add(PARQUET_FILE1.to_string()),
+        ]),
+    )?;
+    engine.put_parquet(data_root.join(PARQUET_FILE1)?, &batch)?;
+
+    let table = Table::new(data_root.clone()).with_log_root(log_root);
+    assert_eq!(table.log_root()?.as_str(), "memory:///catalog/logs/table/");
+    // the table root has no log of its own
+    assert!(Table::new(data_root.clone())
+        .snapshot(engine.as_ref(), None)
+        .is_err());
+
+    // add paths resolve against the data root, not the log root
+    let snapshot = Arc::new(table.snapshot(engine.as_ref(), None)?);
+    assert_eq!(snapshot.version(), 0);
+    assert_eq!(snapshot.table_root(), &data_root);
+    let scan = snapshot.clone().scan_builder().build()?;
+    assert_eq!(read_scan(&scan, engine)?, vec![batch]);
+    Ok(())
+}
+
+#[test]
+fn scan_with_limit() -> Result<(), Box<dyn std::error::Error>> {
+    let schema = StructType::new([StructField::nullable("val", DataType::STRING)]);
+    let (engine, table_root) = memory_table([
+        PROTOCOL.to_string(),
+        metadata_action(&schema, &[], &[]),
+        add_action("a.parquet", &[], None),
+        add_action("b.parquet", &[], None),
+        // the last file doesn't exist, so the scan fails if it is ever read
+        add_action("missing.parquet", &[], None),
+    ])?;
+    let files = [("a.parquet", vec!["a", "b"]), ("b.parquet", vec!["c", "d"])];
+    for (path, vals) in files {
+        let batch = generate_batch(vec![("val", vals.into_array())])?;
+        engine.put_parquet(table_root.join(path)?, &batch)?;
+    }
+    let snapshot = Arc::new(Table::new(table_root).snapshot(engine.as_ref(), None)?);
+
+    let read_vals = |limit| -> Result<Vec<String>, Box<dyn std::error::Error>> {
+        let scan = snapshot.clone().scan_builder().with_limit(limit).build()?;
+        assert_eq!(scan.limit(), Some(limit));
+        let batches = read_scan(&scan, engine.clone())?;
+        let vals = batches
+            .iter()
+            .flat_map(|batch| {
+                let vals = batch.column(0).as_string::<i32>();
+                vals.iter().map(|v| v.unwrap().to_string()).collect_vec()
+            })
+            .collect();
+        Ok(vals)
+    };
+    assert_eq!(read_vals(0)?, Vec::<String>::new());
+    // the second file is truncated
+    assert_eq!(read_vals(3)?, ["a", "b", "c"]);
+    // the limit is reached exactly at the end of the second file
+    assert_eq!(read_vals(4)?, ["a", "b", "c", "d"]);
+    // without a satisfying set of files, the missing file is read
+    assert!(read_vals(5).is_err());
+    assert!(read_scan(&snapshot.scan_builder().build()?, engine).is_err());
+    Ok(())
+}
+
+#[test]
+fn scan_with_limit_skips_deleted_rows() -> Result<(), Box<dyn std::error::Error>> {
+    let path = std::fs::canonicalize(PathBuf::from("./tests/data/table-with-dv-small/"))?;
+    let url = url::Url::from_directory_path(path).unwrap();
+    let engine = Arc::new(delta_kernel::engine::sync::SyncEngine::new());
+    let snapshot = Table::new(url).snapshot(engine.as_ref(), None)?;
+    // rows 0 and 9 are deleted, so they don't count toward the limit
+    let scan = snapshot.into_scan_builder().with_limit(3).build()?;
+    let batches = read_scan(&scan, engine)?;
+    let expected = vec![
+        "+-------+",
+        "| value |",
+        "+-------+",
+        "| 1     |",
+        "| 2     |",
+        "| 3     |",
+        "+-------+",
+    ];
+    assert_batches_sorted_eq!(expected, &batches);
+    Ok(())
+}
+
+#[test]
+fn scan_with_batch_size() -> Result<(), Box<dyn std::error::Error>> {
+    let engine = Arc::new(delta_kernel::engine::sync::SyncEngine::new());
+    let batch_sizes = |results: &[delta_kernel::scan::ScanResult]| -> Vec<usize> {
+        results
+            .iter()
+            .map(|result| {
+                assert!(result.raw_mask().is_none());
+                result.raw_data.as_ref().unwrap().len()
+            })
+            .collect()
+    };
+
+    // the deleted rows 0 and 9 are dropped before re-chunking
+    let path = std::fs::canonicalize(PathBuf::from("./tests/data/table-with-dv-small/"))?;
+    let url = url::Url::from_directory_path(path).unwrap();
+    let snapshot = Arc::new(Table::new(url).snapshot(engine.as_ref(), None)?);
+    let scan = snapshot.clone().scan_builder().with_batch_size(3).build()?;
+    assert_eq!(scan.batch_size(), Some(3));
+    let results: Vec<_> = scan.execute(engine.clone())?.try_collect()?;
+    assert_eq!(batch_sizes(&results), [3, 3, 2]);
+    let batches: Vec<_> = results
+        .into_iter()
+        .map(|result| to_arrow(result.raw_data?))
+        .try_collect()?;
+    let expected = vec![
+        "+-------+",
+        "| value |",
+        "+-------+",
+        "| 1     |",
+        "| 2     |",
+        "| 3     |",
+        "| 4     |",
+        "| 5     |",
+        "| 6     |",
+        "| 7     |",
+        "| 8     |",
+        "+-------+",
+    ];
+    assert_batches_sorted_eq!(expected, &batches);
+
+    let err = snapshot
+        .scan_builder()
+        .with_batch_size(0)
+        .build()
+        .unwrap_err();
+    assert!(matches!(err, Error::Generic(_)), "{err}");
+
+    // rows are merged across files, and only the last batch is smaller
+    let path = std::fs::canonicalize(PathBuf::from("./tests/data/basic_partitioned/"))?;
+    let url = url::Url::from_directory_path(path).unwrap();
+    let snapshot = Arc::new(Table::new(url).snapshot(engine.as_ref(), None)?);
+    let scan = snapshot.clone().scan_builder().build()?;
+    let expected_batches = read_scan(&scan, engine.clone())?;
+    let total_rows: usize = expected_batches.iter().map(|batch| batch.num_rows()).sum();
+    let file_rows: HashMap<_, _> = scan
+        .execute_with_file_meta(engine.clone())?
+        .map_ok(|(result, file_meta)| (file_meta.location, result.raw_data.unwrap().len()))
+        .try_collect()?;
+    assert!(file_rows.values().any(|rows| rows % 4 != 0));
+
+    let scan = snapshot.scan_builder().with_batch_size(4).build()?;
+    let results: Vec<_> = scan.execute(engine.clone())?.try_collect()?;
+    let sizes = batch_sizes(&results);
+    let (last, full) = sizes.split_last().unwrap();
+    assert!(full.iter().all(|&size| size == 4), "{sizes:?}");
+    assert!((1..=4).contains(last));
+    assert_eq!(sizes.iter().sum::<usize>(), total_rows);
+    let batches: Vec<_> = results
+        .into_iter()
+        .map(|result| to_arrow(result.raw_data?))
+        .try_collect()?;
+    // re-chunking preserves the order of the rows
+    assert_eq!(
+        concat_batches(&batches[0].schema(), &batches)?,
+        concat_batches(&expected_batches[0].schema(), &expected_batches)?
+    );
+
+    // with file meta, rows of different files are never merged
+    let results: Vec<_> = scan.execute_with_file_meta(engine)?.try_collect()?;
+    let mut per_file: Vec<(Url, Vec<usize>)> = vec![];
+    for (result, file_meta) in &results {
+        let rows = batch_sizes(std::slice::from_ref(result))[0];
+        match per_file.last_mut() {
+            Some((location, sizes)) if *location == file_meta.location => sizes.push(rows),
+            _ => per_file.push((file_meta.location.clone(), vec![rows])),
+        }
+    }
+    assert_eq!(per_file.len(), file_rows.len());
+    for (location, sizes) in per_file {
+        let (last, full) = sizes.split_last().unwrap();
+        assert!(full.iter().all(|&size| size == 4), "{sizes:?}");
+        assert!((1..=4).contains(last));
+        assert_eq!(sizes.iter().sum::<usize>(), file_rows[&location]);
+    }
+    Ok(())
+}
+
+#[test]
+fn execute_with_file_meta() -> Result<(), Box<dyn std::error::Error>> {
+    let path = std::fs::canonicalize(PathBuf::from("./tests/data/basic_partitioned/"))?;
+    let url = url::Url::from_directory_path(path).unwrap();
+    let engine = Arc::new(delta_kernel::engine::sync::SyncEngine::new());
+    let snapshot = Table::new(url).snapshot(engine.as_ref(), None)?;
+    let scan = snapshot.into_scan_builder().build()?;
+    let physical_schema = scan.global_scan_state().physical_schema;
+
+    let mut locations = vec![];
+    for result in scan.execute_with_file_meta(engine.clone())? {
+        let (scan_result, file_meta) = result?;
+        assert!(!file_meta.deletion_vector_applied);
+        let letter = file_meta.partition_values.get("letter");
+        let dir = format!(
+            "/letter={}/",
+            letter.map_or("__HIVE_DEFAULT_PARTITION__", String::as_str)
+        );
+        assert!(file_meta.location.path().contains(&dir), "{file_meta:?}");
+
+        // the batch holds the rows of the file it names, with its partition value
+        let batch = to_arrow(scan_result.raw_data?)?;
+        let file = FileMeta::new(file_meta.location.clone(), 0, 0);
+        let file_batches: Vec<_> = engine
+            .parquet_handler()
+            .read_parquet_files(&[file], physical_schema.clone(), None)?
+            .map(|data| to_arrow(data?))
+            .try_collect()?;
+        let file_batch = concat_batches(&file_batches[0].schema(), &file_batches)?;
+        assert_eq!(
+            batch.column_by_name("number"),
+            file_batch.column_by_name("number")
+        );
+        let letters = batch.column_by_name("letter").unwrap().as_string::<i32>();
+        assert!(letters
+            .iter()
+            .all(|value| value == letter.map(String::as_str)));
+        locations.push(file_meta.location);
+    }
+    assert_eq!(locations.len(), 6);
+    assert!(locations.iter().all_unique());
+
+    let path = std::fs::canonicalize(PathBuf::from("./tests/data/table-with-dv-small/"))?;
+    let url = url::Url::from_directory_path(path).unwrap();
+    let snapshot = Table::new(url).snapshot(engine.as_ref(), None)?;
+    let scan = snapshot.into_scan_builder().build()?;
+    let results: Vec<_> = scan.execute_with_file_meta(engine)?.try_collect()?;
+    assert_eq!(results.len(), 1);
+    let (scan_result, file_meta) = &results[0];
+    assert!(file_meta.deletion_vector_applied);
+    assert!(file_meta.partition_values.is_empty());
+    assert!(scan_result.raw_mask().is_some());
+    Ok(())
+}
+
+#[test]
+fn scan_prefer_string_view() -> Result<(), Box<dyn std::error::Error>> {
+    use delta_kernel::arrow::array::{ArrayRef, BinaryArray, RecordBatch, StringArray};
+    use delta_kernel::arrow::datatypes::DataType as ArrowDataType;
+
+    let schema = StructType::new([
+        StructField::nullable("val", DataType::STRING),
+        StructField::nullable("bin", DataType::BINARY),
+        StructField::nullable("part", DataType::STRING),
+    ]);
+    let (engine, table_root) = memory_table([
+        PROTOCOL.to_string(),
+        metadata_action(&schema, &["part"], &[]),
+        add_action("part=x/a.parquet", &[("part", Some("x"))], None),
+    ])?;
+    let batch = RecordBatch::try_from_iter([
+        (
+            "val",
+            Arc::new(StringArray::from(vec![Some("a"), None])) as ArrayRef,
+        ),
+        (
+            "bin",
+            Arc::new(BinaryArray::from(vec![Some(&b"b"[..]), None])) as ArrayRef,
+        ),
+    ])?;
+    engine.put_parquet(table_root.join("part=x/a.parquet")?, &batch)?;
+
+    let snapshot = Arc::new(Table::new(table_root).snapshot(engine.as_ref(), None)?);
+    for (prefer_string_view, string_type, binary_type) in [
+        (false, ArrowDataType::Utf8, ArrowDataType::Binary),
+        (true, ArrowDataType::Utf8View, ArrowDataType::BinaryView),
+    ] {
+        let scan = snapshot
+            .clone()
+            .scan_builder()
+            .with_prefer_string_view(prefer_string_view)
+            .build()?;
+        assert_eq!(scan.prefer_string_view(), prefer_string_view);
+        let batches = read_scan(&scan, engine.clone())?;
+        assert_eq!(batches.len(), 1);
+        let schema = batches[0].schema();
+        let types: Vec<_> = schema.fields().iter().map(|f| f.data_type()).collect();
+        assert_eq!(types, [&string_type, &binary_type, &string_type]);
+        let expected = vec![
+            "+-----+-----+------+",
+            "| val | bin | part |",
+            "+-----+-----+------+",
+            "|     |     | x    |",
+            "| a   | 62  | x    |",
+            "+-----+-----+------+",
+        ];
+        assert_batches_sorted_eq!(expected, &batches);
+    }
+    Ok(())
+}
+
+#[test]
+fn scan_with_file_filter() -> Result<(), Box<dyn std::error::Error>> {
+    let path = std::fs::canonicalize(PathBuf::from("./tests/data/with-short-dv/"))?;
+    let url = url::Url::from_directory_path(path).unwrap();
+    let engine = Arc::new(delta_kernel::engine::sync::SyncEngine::new());
+    let snapshot = Arc::new(Table::new(url).snapshot(engine.as_ref(), None)?);
+    let schema = snapshot.schema().project(&["id"])?;
+    let read_ids = |paths: &[&str]| {
+        let paths = paths.iter().map(|path| path.to_string()).collect();
+        let scan = snapshot
+            .clone()
+            .scan_builder()
+            .with_schema(schema.clone())
+            .with_file_filter(paths)
+            .build()?;
+        read_scan(&scan, engine.clone())
+    };
+
+    // ids 0-2 of the first file are removed by its deletion vector
+    let batches =
+        read_ids(&["part-00000-8029f411-746c-41c1-a0c1-c5eb867c5d05-c000.snappy.parquet"])?;
+    let expected = vec!["+----+", "| id |", "+----+", "| 3  |", "| 4  |", "+----+"];
+    assert_batches_sorted_eq!(expected, &batches);
+
+    // paths that are not part of the table are ignored
+    let batches = read_ids(&[
+        "part-00001-24db34ab-bdfe-4814-aba8-c1f34d6d8923-c000.snappy.parquet",
+        "missing.parquet",
+    ])?;
+    let expected = vec![
+        "+----+", "| id |", "+----+", "| 5  |", "| 6  |", "| 7  |", "| 8  |", "| 9  |", "+----+",
+    ];
+    assert_batches_sorted_eq!(expected, &batches);
+
+    assert!(read_ids(&[])?.is_empty());
+    Ok(())
+}
+
+#[test]
+fn null_partition_values() -> Result<(), Box<dyn std::error::Error>> {
+    use delta_kernel::arrow::datatypes::DataType as ArrowDataType;
+
+    let schema = StructType::new([
+        StructField::nullable("val", DataType::STRING),
+        StructField::nullable("s", DataType::STRING),
+        StructField::nullable("i", DataType::INTEGER),
+        StructField::nullable("d", DataType::DATE),
+        StructField::nullable("dec", DecimalType::try_new(10, 2)?),
+    ]);
+    // JSON nulls, the hive sentinel, and empty strings are all null partition values
+    let files = [
+        ("a.parquet", None),
+        ("b.parquet", Some("__HIVE_DEFAULT_PARTITION__")),
+        ("c.parquet", Some("")),
+    ];
+    let mut actions = vec![
+        PROTOCOL.to_string(),
+        metadata_action(&schema, &["s", "i", "d", "dec"], &[]),
+    ];
+    actions.extend(files.map(|(path, value)| {
+        let partition_values = ["s", "i", "d", "dec"].map(|column| (column, value));
+        add_action(path, &partition_values, None)
+    }));
+    let (engine, table_root) = memory_table(actions)?;
+    for (path, _) in files {
+        let batch = generate_batch(vec![("val", vec!["x", "y"].into_array())])?;
+        engine.put_parquet(table_root.join(path)?, &batch)?;
+    }
+
+    let snapshot = Arc::new(Table::new(table_root).snapshot(engine.as_ref(), None)?);
+    let scan = snapshot.scan_builder().build()?;
+    let batches = read_scan(&scan, engine)?;
+    assert_eq!(batches.len(), files.len());
+    let expected_types = [
+        ("s", ArrowDataType::Utf8),
+        ("i", ArrowDataType::Int32),
+        ("d", ArrowDataType::Date32),
+        ("dec", ArrowDataType::Decimal128(10, 2)),
+    ];
+    for batch in &batches {
+        for (name, data_type) in &expected_types {
+            let column = batch.column_by_name(name).unwrap();
+            assert_eq!(column.data_type(), data_type);
+            assert_eq!(column.null_count(), batch.num_rows());
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn absolute_add_paths() -> Result<(), Box<dyn std::error::Error>> {
+    let add = |path: &str| add_action(path, &[], None);
+    let schema = StructType::new([StructField::nullable("val", DataType::STRING)]);
+    // one file relative to the table root and one referenced by its URL, like a shallow clone does
+    let (engine, table_root) = memory_table([
+        PROTOCOL.to_string(),
+        metadata_action(&schema, &[], &[]),
+        add("a%20b.parquet"),
+        add("memory:///source/c%20d.parquet"),
+    ])?;
+    for (location, value) in [
+        ("memory:///table/a%20b.parquet", "relative"),
+        ("memory:///source/c%20d.parquet", "absolute"),
+    ] {
+        let batch = generate_batch(vec![("val", vec![value].into_array())])?;
+        engine.put_parquet(Url::parse(location)?, &batch)?;
+    }
+
+    let snapshot = Arc::new(Table::new(table_root.clone()).snapshot(engine.as_ref(), None)?);
+    let batches = read_scan(&snapshot.scan_builder().build()?, engine.clone())?;
+    let expected = vec![
+        "+----------+",
+        "| val      |",
+        "+----------+",
+        "| absolute |",
+        "| relative |",
+        "+----------+",
+    ];
+    assert_batches_sorted_eq!(expected, &batches);
+
+    // an s3 file can't be read here, but resolves to its own URL rather than one under the root
+    engine.put_commit(&table_root, 1, add("s3://bucket/source/e.parquet"))?;
+    let snapshot = Table::new(table_root).snapshot(engine.as_ref(), None)?;
+    let locations: Vec<_> = snapshot
+        .active_files(engine.as_ref())?
+        .map_ok(|file| file.file.location.to_string())
+        .try_collect()?;
+    assert_eq!(
+        locations.into_iter().sorted().collect_vec(),
+        [
+            "memory:///source/c%20d.parquet",
+            "memory:///table/a%20b.parquet",
+            "s3://bucket/source/e.parquet",
+        ]
+    );
+    Ok(())
+}
+
+#[test]
+fn decimal_rescaled_on_read() -> Result<(), Box<dyn std::error::Error>> {
+    use delta_kernel::arrow::array::{ArrayRef, Decimal128Array};
+
+    // each table stores a Decimal(10, 2) column, but its schema declares the column differently
+    let read_table = |precision: u8, scale: u8, values: Vec<Option<i128>>| {
+        let schema = StructType::new([StructField::nullable(
+            "dec",
+            DecimalType::try_new(precision, scale)?,
+        )]);
+        let (engine, table_root) = memory_table([
+            PROTOCOL.to_string(),
+            metadata_action(&schema, &[], &[]),
+            add_action("a.parquet", &[], None),
+        ])?;
+        let values = Decimal128Array::from(values).with_precision_and_scale(10, 2)?;
+        let batch = generate_batch(vec![("dec", Arc::new(values) as ArrayRef)])?;
+        engine.put_parquet(table_root.join("a.parquet")?, &batch)?;
+        let snapshot = Arc::new(Table::new(table_root).snapshot(engine.as_ref(), None)?);
+        read_scan(&snapshot.scan_builder().build()?, engine)
+    };
+
+    let values = vec![Some(12345), Some(-1), None, Some(9999999999)];
+    let batches = read_table(12, 4, values)?;
+    let expected = vec![
+        "+---------------+",
+        "| dec           |",
+        "+---------------+",
+        "|               |",
+        "| -0.0100       |",
+        "| 123.4500      |",
+        "| 99999999.9900 |",
+        "+---------------+",
+    ];
+    assert_batches_sorted_eq!(expected, &batches);
+
+    // a smaller scale is fine as long as no digits are lost
+    let batches = read_table(10, 1, vec![Some(12340), None])?;
+    let expected = vec![
+        "+-------+",
+        "| dec   |",
+        "+-------+",
+        "|       |",
+        "| 123.4 |",
+        "+-------+",
+    ];
+    assert_batches_sorted_eq!(expected, &batches);
+
+    // 123.45 has too many fractional digits for a scale of 1
+    let result = read_table(10, 1, vec![Some(12340), Some(12345)]);
+    assert!(result.unwrap_err().to_string().contains("loses precision"));
+
+    // 99999999.99 has too many digits for a precision of 10 at a scale of 4
+    let result = read_table(10, 4, vec![Some(9999999999)]);
+    assert!(result.is_err());
+    Ok(())
+}
+
+#[test]
+fn data_skipping_with_stats_columns() -> Result<(), Box<dyn std::error::Error>> {
+    let add = |path: &str, value: i32| {
+        // every column has (possibly stale) stats, but only the stats columns may be used
+        let stats = format!(
+            r#"{{"numRecords":3,"nullCount":{{"a":0,"b":0,"c":0,"d":0,"e":0}},"minValues":{{"a":{value},"b":{value},"c":{value},"d":{value},"e":{value}}},"maxValues":{{"a":{value},"b":{value},"c":{value},"d":{value},"e":{value}}}}}"#
+        );
+        add_action(path, &[], Some(&stats))
+    };
+    let schema = StructType::new(
+        ["a", "b", "c", "d", "e"].map(|name| StructField::nullable(name, DataType::INTEGER)),
+    );
+    let (engine, table_root) = memory_table([
+        PROTOCOL.to_string(),
+        metadata_action(&schema, &[], &[("delta.dataSkippingStatsColumns", "c,e")]),
+        add("low.parquet", 1),
+        add("high.parquet", 10),
+    ])?;
+    let snapshot = Arc::new(Table::new(table_root).snapshot(engine.as_ref(), None)?);
+    let scan_paths = |predicate: Expr| -> Result<Vec<String>, Box<dyn std::error::Error>> {
+        let scan = snapshot
+            .clone()
+            .scan_builder()
+            .with_predicate(Arc::new(predicate))
+            .build()?;
+        let mut paths = vec![];
+        for scan_metadata in scan.scan_metadata(engine.as_ref())? {
+            paths = scan_metadata?.visit_scan_files(paths, scan_file_path_callback)?;
+        }
+        paths.sort();
+        Ok(paths)
+    };
+
+    for column in ["c", "e"] {
+        let predicate = Expr::gt(Expr::column([column]), Expr::literal(5));
+        assert_eq!(scan_paths(predicate)?, ["high.parquet"]);
+        let predicate = Expr::lt(Expr::column([column]), Expr::literal(5));
+        assert_eq!(scan_paths(predicate)?, ["low.parquet"]);
+    }
+    for column in ["a", "b", "d"] {
+        let predicate = Expr::gt(Expr::column([column]), Expr::literal(5));
+        assert_eq!(scan_paths(predicate)?, ["high.parquet", "low.parquet"]);
+    }
+    // a conjunction can still skip files based on its indexed column
+    let predicate = Expr::and(
+        Expr::gt(column_expr!("a"), Expr::literal(5)),
+        Expr::lt(column_expr!("e"), Expr::literal(5)),
+    );
+    assert_eq!(scan_paths(predicate)?, ["low.parquet"]);
+    // but a disjunction with an unindexed column can't skip anything
+    let predicate = Expr::or(
+        Expr::gt(column_expr!("a"), Expr::literal(5)),
+        Expr::lt(column_expr!("e"), Expr::literal(5)),
+    );
+    assert_eq!(scan_paths(predicate)?, ["high.parquet", "low.parquet"]);
+    Ok(())
+}
+
+#[test]
+fn scan_with_file_path_column() -> Result<(), Box<dyn std::error::Error>> {
+    use delta_kernel::scan::{FILE_PATH_FIELD_NAME, METADATA_COLUMN_NAME};
+    use delta_kernel::schema::{StructField, StructType};
+
+    let engine = Arc::new(delta_kernel::engine::sync::SyncEngine::new());
+    let path = std::fs::canonicalize(PathBuf::from("./tests/data/basic_partitioned/"))?;
+    let url = url::Url::from_directory_path(path).unwrap();
+    let snapshot = Arc::new(Table::new(url).snapshot(engine.as_ref(), None)?);
+
+    // project a data column and the partition column `letter`, out of table order
+    let schema = Arc::new(StructType::new([
+        StructField::nullable("number", DataType::LONG),
+        StructField::nullable("letter", DataType::STRING),
+    ]));
+    let scan = snapshot
+        .clone()
+        .scan_builder()
+        .with_schema(schema)
+        .with_file_path_column()
+        .build()?;
+    let metadata_field = StructField::not_null(
+        METADATA_COLUMN_NAME,
+        StructType::new([StructField::not_null(
+            FILE_PATH_FIELD_NAME,
+            DataType::STRING,
+        )]),
+    );
+    assert_eq!(
+        scan.logical_schema().field(METADATA_COLUMN_NAME),
+        Some(&metadata_field)
+    );
+
+    let mut rows = 0;
+    for res in scan.execute_with_file_meta(engine.clone())? {
+        let (result, file_meta) = res?;
+        let batch = to_arrow(result.raw_data?)?;
+        assert_eq!(batch.num_columns(), 3);
+        // the partition value of the file is materialized next to its path
+        let letter = file_meta.partition_values.get("letter").map(String::as_str);
+        let letters = batch.column(1).as_string::<i32>();
+        let file_paths = batch.column(2).as_struct().column(0).as_string::<i32>();
+        for (file_path, row_letter) in file_paths.iter().zip(letters) {
+            assert_eq!(file_path, Some(file_meta.location.as_str()));
+            assert_eq!(row_letter, letter);
+        }
+        assert!(file_meta.location.path().ends_with(".parquet"));
+        rows += batch.num_rows();
+    }
+    assert_eq!(rows, 6);
+
+    // the virtual column cannot shadow a column of the scan schema
+    let schema = Arc::new(StructType::new([
+        StructField::nullable("number", DataType::LONG),
+        StructField::nullable(METADATA_COLUMN_NAME, DataType::STRING),
+    ]));
+    let err = snapshot
+        .scan_builder()
+        .with_schema(schema)
+        .with_file_path_column()
+        .build()
+        .unwrap_err();
+    assert!(matches!(err, Error::Generic(_)), "{err}");
+    Ok(())
+}
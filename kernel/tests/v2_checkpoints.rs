@@ -1,6 +1,8 @@
+use std::path::Path;
 use std::sync::Arc;
 
 use delta_kernel::arrow::array::RecordBatch;
+use delta_kernel::arrow::util::pretty::pretty_format_batches;
 use delta_kernel::engine::sync::SyncEngine;
 
 use delta_kernel::engine::arrow_data::ArrowEngineData;
@@ -13,14 +15,15 @@ use itertools::Itertools;
 fn read_v2_checkpoint_table(test_name: impl AsRef<str>) -> DeltaResult<Vec<RecordBatch>> {
     let test_dir = load_test_data("tests/data", test_name.as_ref()).unwrap();
     let test_path = test_dir.path().join(test_name.as_ref());
+    scan_table_at(&test_path)
+}
 
-    let table = Table::try_from_uri(test_path.to_str().expect("table path to string")).unwrap();
+fn scan_table_at(table_path: &Path) -> DeltaResult<Vec<RecordBatch>> {
+    let table = Table::try_from_uri(table_path.to_str().expect("table path to string"))?;
     let engine = Arc::new(SyncEngine::new());
     let snapshot = table.snapshot(engine.as_ref(), None)?;
     let scan = snapshot.into_scan_builder().build()?;
-    let batches = read_scan(&scan, engine)?;
-
-    Ok(batches)
+    read_scan(&scan, engine)
 }
 
 fn test_v2_checkpoint_with_table(
@@ -222,3 +225,56 @@ fn v2_checkpoints_parquet_with_last_checkpoint() -> DeltaResult<()> {
         get_simple_id_table(),
     )
 }
+
+// Delete every checkpoint (and the _last_checkpoint hint) of the table so that reading it has to
+// replay the json commits only.
+fn remove_checkpoints(table_path: &Path) {
+    let log_path = table_path.join("_delta_log");
+    for entry in std::fs::read_dir(&log_path).unwrap() {
+        let path = entry.unwrap().path();
+        let name = path.file_name().unwrap().to_str().unwrap();
+        if name.contains(".checkpoint.") || name == "_last_checkpoint" {
+            std::fs::remove_file(&path).unwrap();
+        }
+    }
+}
+
+fn format_sorted(batches: &[RecordBatch]) -> Vec<String> {
+    let formatted = pretty_format_batches(batches).unwrap().to_string();
+    let mut lines = formatted.trim().lines().map(String::from).collect_vec();
+    sort_lines!(lines);
+    lines
+}
+
+/// The file set reconstructed from a V2 checkpoint (with the add actions in its sidecars) must
+/// match the one reconstructed by replaying the json commits only.
+#[test]
+fn v2_checkpoints_with_sidecars_match_json_replay() -> DeltaResult<()> {
+    for test_name in [
+        "v2-checkpoints-json-with-sidecars",
+        "v2-checkpoints-parquet-with-sidecars",
+    ] {
+        let test_dir = load_test_data("tests/data", test_name).unwrap();
+        let test_path = test_dir.path().join(test_name);
+
+        let with_checkpoint = format_sorted(&scan_table_at(&test_path)?);
+        remove_checkpoints(&test_path);
+        let json_only = format_sorted(&scan_table_at(&test_path)?);
+
+        assert_eq!(with_checkpoint, json_only, "{test_name}");
+        let mut expected = generate_sidecar_expected_data();
+        sort_lines!(expected);
+        assert_eq!(json_only, expected, "{test_name}");
+    }
+    Ok(())
+}
+
+#[test]
+fn v2_checkpoints_with_missing_sidecar_fails() {
+    let test_name = "v2-checkpoints-parquet-with-sidecars";
+    let test_dir = load_test_data("tests/data", test_name).unwrap();
+    let test_path = test_dir.path().join(test_name);
+    std::fs::remove_dir_all(test_path.join("_delta_log").join("_sidecars")).unwrap();
+
+    assert!(scan_table_at(&test_path).is_err());
+}
@@ -7,6 +7,7 @@ use delta_kernel::arrow::array::{
 use delta_kernel::arrow::datatypes::{DataType as ArrowDataType, Field, Schema as ArrowSchema};
 use delta_kernel::arrow::error::ArrowError;
 use delta_kernel::arrow::record_batch::RecordBatch;
+use delta_kernel::arrow::util::pretty::pretty_format_batches;
 
 use delta_kernel::object_store::local::LocalFileSystem;
 use delta_kernel::object_store::memory::InMemory;
@@ -20,12 +21,18 @@ use url::Url;
 use delta_kernel::engine::arrow_data::ArrowEngineData;
 use delta_kernel::engine::default::executor::tokio::TokioBackgroundExecutor;
 use delta_kernel::engine::default::DefaultEngine;
+use delta_kernel::engine::memory::MemoryEngine;
+use delta_kernel::expressions::ColumnName;
+use delta_kernel::parquet::arrow::ArrowWriter;
+use delta_kernel::schema::compatibility::Incompatibility;
 use delta_kernel::schema::{DataType, SchemaRef, StructField, StructType};
+use delta_kernel::snapshot::Snapshot;
+use delta_kernel::transaction::{CommitResult, DataFileInfo};
 use delta_kernel::Error as KernelError;
 use delta_kernel::{DeltaResult, Table};
 
 mod common;
-use common::test_read;
+use common::{read_scan, test_read};
 
 // setup default engine with in-memory (=true) or local fs (=false) object store.
 fn setup(
@@ -111,6 +118,35 @@ async fn create_table(
     Ok(Table::new(table_path))
 }
 
+// like `create_table`, but in a `MemoryEngine` and with the log stored under `log_root` instead of
+// the `_delta_log` directory of `data_root`. The table has a single integer column `number`.
+fn create_table_with_log_root(
+    engine: &MemoryEngine,
+    data_root: &Url,
+    log_root: &Url,
+) -> Result<Table, Box<dyn std::error::Error>> {
+    let schema = serde_json::to_string(&StructType::new(vec![StructField::nullable(
+        "number",
+        DataType::INTEGER,
+    )]))?;
+    let actions = [
+        json!({"protocol": {"minReaderVersion": 1, "minWriterVersion": 2}}),
+        json!({"metaData": {
+            "id": "test_id",
+            "format": {"provider": "parquet", "options": {}},
+            "schemaString": schema,
+            "partitionColumns": [],
+            "configuration": {},
+            "createdTime": 1677811175819u64,
+        }}),
+    ];
+    engine.put_file(
+        log_root.join("00000000000000000000.json")?,
+        actions.map(|action| action.to_string()).join("\n"),
+    )?;
+    Ok(Table::new(data_root.clone()).with_log_root(log_root.clone()))
+}
+
 // create commit info in arrow of the form {engineInfo: "default engine"}
 fn new_commit_info() -> DeltaResult<Box<ArrowEngineData>> {
     // create commit info of the form {engineCommitInfo: Map { "engineInfo": "default engine" } }
@@ -706,6 +742,63 @@ async fn test_append_invalid_schema() -> Result<(), Box<dyn std::error::Error>>
     Ok(())
 }
 
+#[tokio::test]
+async fn test_append_nulls_into_non_nullable_column() -> Result<(), Box<dyn std::error::Error>> {
+    // setup tracing
+    let _ = tracing_subscriber::fmt::try_init();
+    // create a simple table: one non-nullable int column named 'number'
+    let table_schema = Arc::new(StructType::new(vec![StructField::not_null(
+        "number",
+        DataType::INTEGER,
+    )]));
+    // the data has a nullable 'number' column
+    let data_schema = Arc::new(ArrowSchema::new(vec![Field::new(
+        "number",
+        ArrowDataType::Int32,
+        true,
+    )]));
+
+    for (table, engine, _store, _table_name) in setup_tables(table_schema, &[]).await? {
+        let txn = table
+            .new_transaction(&engine)?
+            .with_commit_info(new_commit_info()?);
+        let write_context = txn.get_write_context();
+        let write = |data: Vec<Option<i32>>| {
+            let data =
+                RecordBatch::try_new(data_schema.clone(), vec![Arc::new(Int32Array::from(data))])
+                    .unwrap();
+            let data = ArrowEngineData::new(data);
+            let write_context = &write_context;
+            let engine = &engine;
+            async move {
+                engine
+                    .write_parquet(&data, write_context, HashMap::new(), true)
+                    .await
+            }
+        };
+
+        // no nulls: the batch can be written even though its column is nullable
+        write(vec![Some(1), Some(2), Some(3)]).await?;
+
+        let err = match write(vec![Some(1), Some(2), None]).await {
+            Err(KernelError::Backtraced { source, .. }) => *source,
+            Err(err) => err,
+            Ok(_) => panic!("expected the write of a null to fail"),
+        };
+        let KernelError::IncompatibleWriteSchema(err) = err else {
+            panic!("expected an incompatible write schema error, got {err:?}");
+        };
+        assert_eq!(
+            err.incompatibilities(),
+            [Incompatibility::NullValue {
+                path: ColumnName::new(["number"]),
+                row: 2
+            }]
+        );
+    }
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_write_txn_actions() -> Result<(), Box<dyn std::error::Error>> {
     // setup tracing
@@ -835,3 +928,338 @@ async fn test_write_txn_actions() -> Result<(), Box<dyn std::error::Error>> {
     }
     Ok(())
 }
+
+// write `values` as a parquet file named `file_name` in the table directory, returning its size
+async fn put_parquet_file(
+    store: &Arc<dyn ObjectStore>,
+    table_name: &str,
+    file_name: &str,
+    schema: &SchemaRef,
+    values: Vec<i32>,
+) -> Result<i64, Box<dyn std::error::Error>> {
+    let batch = RecordBatch::try_new(
+        Arc::new(schema.as_ref().try_into()?),
+        vec![Arc::new(Int32Array::from(values))],
+    )?;
+    let mut buffer = vec![];
+    let mut writer = ArrowWriter::try_new(&mut buffer, batch.schema(), None)?;
+    writer.write(&batch)?;
+    writer.close()?;
+    let size = buffer.len().try_into()?;
+    store
+        .put(
+            &Path::from(format!("/{table_name}/{file_name}")),
+            buffer.into(),
+        )
+        .await?;
+    Ok(size)
+}
+
+#[tokio::test]
+async fn test_append_files() -> Result<(), Box<dyn std::error::Error>> {
+    // setup tracing
+    let _ = tracing_subscriber::fmt::try_init();
+    // create a simple table: one int column named 'number'
+    let schema = Arc::new(StructType::new(vec![StructField::nullable(
+        "number",
+        DataType::INTEGER,
+    )]));
+
+    for (table, engine, store, table_name) in setup_tables(schema.clone(), &[]).await? {
+        // two separate appends to the existing table, committing versions 1 and 2
+        for (version, values) in [(1, vec![1, 2, 3]), (2, vec![4, 5])] {
+            let file_name = format!("part-{version}.parquet");
+            let size = put_parquet_file(&store, table_name, &file_name, &schema, values).await?;
+            let stats = json!({
+                "numRecords": if version == 1 { 3 } else { 2 },
+            })
+            .to_string();
+
+            let snapshot = Arc::new(table.snapshot(&engine, None)?);
+            let mut txn = snapshot
+                .transaction()?
+                .with_commit_info(new_commit_info()?)
+                .with_operation("WRITE".to_string());
+            txn.add_files([DataFileInfo::new(&file_name, size, 1000).with_stats(stats.clone())]);
+            assert!(matches!(
+                txn.commit(&engine)?,
+                CommitResult::Committed(v) if v == version
+            ));
+
+            let commit = store
+                .get(&Path::from(format!(
+                    "/{table_name}/_delta_log/{version:020}.json"
+                )))
+                .await?;
+            let mut parsed_commits: Vec<_> = Deserializer::from_slice(&commit.bytes().await?)
+                .into_iter::<serde_json::Value>()
+                .try_collect()?;
+            set_value(&mut parsed_commits[0], "commitInfo.timestamp", json!(0))?;
+
+            let expected_commit = vec![
+                json!({
+                    "commitInfo": {
+                        "timestamp": 0,
+                        "operation": "WRITE",
+                        "kernelVersion": format!("v{}", env!("CARGO_PKG_VERSION")),
+                        "operationParameters": {},
+                        "engineCommitInfo": {
+                            "engineInfo": "default engine"
+                        }
+                    }
+                }),
+                json!({
+                    "add": {
+                        "path": file_name,
+                        "partitionValues": {},
+                        "size": size,
+                        "modificationTime": 1000,
+                        "dataChange": true,
+                        "stats": stats
+                    }
+                }),
+            ];
+            assert_eq!(parsed_commits, expected_commit);
+        }
+
+        test_read(
+            &ArrowEngineData::new(RecordBatch::try_new(
+                Arc::new(schema.as_ref().try_into()?),
+                vec![Arc::new(Int32Array::from(vec![4, 5, 1, 2, 3]))],
+            )?),
+            &table,
+            Arc::new(engine),
+        )?;
+    }
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_append_files_conflict() -> Result<(), Box<dyn std::error::Error>> {
+    // setup tracing
+    let _ = tracing_subscriber::fmt::try_init();
+    // create a simple table: one int column named 'number'
+    let schema = Arc::new(StructType::new(vec![StructField::nullable(
+        "number",
+        DataType::INTEGER,
+    )]));
+
+    for (table, engine, store, table_name) in setup_tables(schema.clone(), &[]).await? {
+        let snapshot = Arc::new(table.snapshot(&engine, None)?);
+
+        // two writers start from the same snapshot; the first to commit wins version 1
+        let mut txn = snapshot
+            .clone()
+            .transaction()?
+            .with_commit_info(new_commit_info()?);
+        let mut concurrent_txn = snapshot.transaction()?.with_commit_info(new_commit_info()?);
+
+        let size = put_parquet_file(&store, table_name, "a.parquet", &schema, vec![1]).await?;
+        concurrent_txn.add_files([DataFileInfo::new("a.parquet", size, 1000)]);
+        assert!(matches!(
+            concurrent_txn.commit(&engine)?,
+            CommitResult::Committed(1)
+        ));
+
+        let size = put_parquet_file(&store, table_name, "b.parquet", &schema, vec![2]).await?;
+        txn.add_files([DataFileInfo::new("b.parquet", size, 1000)]);
+        assert!(matches!(txn.commit(&engine)?, CommitResult::Conflict(_, 1)));
+
+        // the losing transaction must not have touched the table
+        test_read(
+            &ArrowEngineData::new(RecordBatch::try_new(
+                Arc::new(schema.as_ref().try_into()?),
+                vec![Arc::new(Int32Array::from(vec![1]))],
+            )?),
+            &table,
+            Arc::new(engine),
+        )?;
+    }
+    Ok(())
+}
+
+#[test]
+fn write_checkpoint_to_separate_log_root() -> Result<(), Box<dyn std::error::Error>> {
+    let data_root = Url::parse("memory:///data/table/")?;
+    let log_root = Url::parse("memory:///catalog/logs/table/")?;
+    let engine = MemoryEngine::new();
+    let table = create_table_with_log_root(&engine, &data_root, &log_root)?;
+    table.snapshot(&engine, None)?.write_checkpoint(&engine)?;
+
+    // the checkpoint went to the log root, and nothing was written under the data root
+    engine.get_file(&log_root.join("00000000000000000000.checkpoint.parquet")?)?;
+    engine.get_file(&log_root.join("_last_checkpoint")?)?;
+    assert!(engine
+        .get_file(&data_root.join("_delta_log/00000000000000000000.checkpoint.parquet")?)
+        .is_err());
+
+    // and is picked up when the commit is no longer readable
+    engine.put_file(log_root.join("00000000000000000000.json")?, "not json")?;
+    let snapshot = table.snapshot(&engine, None)?;
+    assert_eq!(snapshot.version(), 0);
+    Ok(())
+}
+
+#[test]
+fn commit_to_separate_log_root() -> Result<(), Box<dyn std::error::Error>> {
+    let data_root = Url::parse("memory:///data/table/")?;
+    let log_root = Url::parse("memory:///catalog/logs/table/")?;
+    let engine = MemoryEngine::new();
+    let table = create_table_with_log_root(&engine, &data_root, &log_root)?;
+    let txn = table
+        .new_transaction(&engine)?
+        .with_commit_info(new_commit_info()?);
+    assert!(matches!(txn.commit(&engine)?, CommitResult::Committed(1)));
+
+    // the commit went to the log root, and nothing was written under the data root
+    engine.get_file(&log_root.join("00000000000000000001.json")?)?;
+    assert!(engine
+        .get_file(&data_root.join("_delta_log/00000000000000000001.json")?)
+        .is_err());
+    assert_eq!(table.snapshot(&engine, None)?.version(), 1);
+    Ok(())
+}
+
+#[test]
+fn test_write_checkpoint() -> Result<(), Box<dyn std::error::Error>> {
+    let engine = Arc::new(MemoryEngine::new());
+    let table_root = Url::parse("memory:///table/")?;
+    let schema = serde_json::to_string(&StructType::new(vec![
+        StructField::nullable("val", DataType::STRING),
+        StructField::nullable("part", DataType::STRING),
+    ]))?;
+    let now: i64 = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)?
+        .as_millis()
+        .try_into()?;
+    let add = |path: &str, part: &str| {
+        json!({"add": {
+            "path": path,
+            "partitionValues": {"part": part},
+            "size": 0,
+            "modificationTime": 1677811175819u64,
+            "dataChange": true,
+        }})
+    };
+    let remove = |path: &str, part: &str, deletion_timestamp: i64| {
+        json!({"remove": {
+            "path": path,
+            "partitionValues": {"part": part},
+            "deletionTimestamp": deletion_timestamp,
+            "dataChange": true,
+        }})
+    };
+    let txn = |app_id: &str, version: i64| json!({"txn": {"appId": app_id, "version": version, "lastUpdated": now}});
+    let domain = |domain: &str, configuration: &str, removed: bool| {
+        json!({"domainMetadata": {
+            "domain": domain,
+            "configuration": configuration,
+            "removed": removed,
+        }})
+    };
+    let commits = [
+        vec![
+            json!({"protocol": {
+                "minReaderVersion": 1,
+                "minWriterVersion": 7,
+                "writerFeatures": ["domainMetadata"],
+            }}),
+            json!({"metaData": {
+                "id": "test_id",
+                "format": {"provider": "parquet", "options": {}},
+                "schemaString": schema,
+                "partitionColumns": ["part"],
+                "configuration": {},
+                "createdTime": 1677811175819u64,
+            }}),
+            add("a.parquet", "x"),
+            add("b.parquet", "y"),
+            add("expired.parquet", "y"),
+        ],
+        vec![
+            remove("a.parquet", "x", now),
+            remove("expired.parquet", "y", 1),
+            add("c.parquet", "x"),
+            txn("app1", 1),
+            domain("kept", r#"{"v":1}"#, false),
+            domain("dropped", "{}", false),
+        ],
+        vec![
+            txn("app1", 2),
+            txn("app2", 7),
+            domain("kept", r#"{"v":2}"#, false),
+            domain("dropped", "{}", true),
+        ],
+    ];
+    for (version, actions) in commits.iter().enumerate() {
+        let commit = actions.iter().map(|action| action.to_string()).join("\n");
+        engine.put_commit(&table_root, version.try_into()?, commit)?;
+    }
+    for (file, val) in [("a", "a"), ("b", "b"), ("c", "c"), ("expired", "expired")] {
+        let batch = RecordBatch::try_new(
+            Arc::new(ArrowSchema::new(vec![Field::new(
+                "val",
+                ArrowDataType::Utf8,
+                true,
+            )])),
+            vec![Arc::new(StringArray::from(vec![val]))],
+        )?;
+        engine.put_parquet(table_root.join(&format!("{file}.parquet"))?, &batch)?;
+    }
+
+    // everything a snapshot knows about the table, to compare json and checkpoint replay
+    let table = Table::new(table_root.clone());
+    let table_state = |snapshot: Arc<Snapshot>| -> DeltaResult<_> {
+        let batches = read_scan(&snapshot.clone().scan_builder().build()?, engine.clone())?;
+        let rows = pretty_format_batches(&batches)?
+            .to_string()
+            .lines()
+            .map(str::to_string)
+            .sorted()
+            .collect_vec();
+        let files: Vec<_> = snapshot
+            .active_files(engine.as_ref())?
+            .map_ok(|file| file.file.location.to_string())
+            .try_collect()?;
+        let domains = snapshot
+            .domains(engine.as_ref())?
+            .map(|(domain, configuration)| (domain.to_string(), configuration.to_string()))
+            .collect::<HashMap<_, _>>();
+        let txns = ["app1", "app2"]
+            .map(|app_id| snapshot.clone().get_app_id_version(app_id, engine.as_ref()));
+        Ok((
+            snapshot.schema(),
+            snapshot.protocol().clone(),
+            rows,
+            files.into_iter().sorted().collect_vec(),
+            domains,
+            txns.into_iter().try_collect::<_, Vec<_>, _>()?,
+        ))
+    };
+    let snapshot = Arc::new(table.snapshot(engine.as_ref(), None)?);
+    let json_state = table_state(snapshot.clone())?;
+    assert_eq!(json_state.3.len(), 2);
+    assert_eq!(json_state.5, [Some(2), Some(7)]);
+
+    snapshot.write_checkpoint(engine.as_ref())?;
+
+    // protocol, metadata, the adds of b and c, the unexpired remove of a, two txns and one domain
+    let last_checkpoint = engine.get_file(&table_root.join("_delta_log/_last_checkpoint")?)?;
+    let last_checkpoint: serde_json::Value = serde_json::from_slice(&last_checkpoint)?;
+    assert_eq!(
+        last_checkpoint,
+        json!({"version": 2, "size": 8, "numOfAddFiles": 2})
+    );
+
+    // make the commits unreadable, so only the checkpoint can be replayed
+    for version in 0..commits.len() {
+        engine.put_file(
+            table_root.join(&format!("_delta_log/{version:020}.json"))?,
+            "not json",
+        )?;
+    }
+    let snapshot = Arc::new(table.snapshot(engine.as_ref(), None)?);
+    assert_eq!(snapshot.version(), 2);
+    assert_eq!(table_state(snapshot)?, json_state);
+    Ok(())
+}